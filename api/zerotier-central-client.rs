@@ -0,0 +1,528 @@
+#[derive(Clone)]
+pub struct Client {
+    baseurl: String,
+    client: reqwest::Client,
+}
+
+impl Client {
+    pub fn new(baseurl: &str) -> Self {
+        let dur = std::time::Duration::from_secs(15);
+        let client = reqwest::ClientBuilder::new()
+            .connect_timeout(dur)
+            .timeout(dur)
+            .build()
+            .unwrap();
+        Self::new_with_client(baseurl, client)
+    }
+
+    pub fn new_with_client(baseurl: &str, client: reqwest::Client) -> Self {
+        Self {
+            baseurl: baseurl.to_string(),
+            client,
+        }
+    }
+
+    pub fn baseurl(&self) -> &String {
+        &self.baseurl
+    }
+
+    pub fn client(&self) -> &reqwest::Client {
+        &self.client
+    }
+
+    #[doc = "Obtain the overall status of the account tied to the API token in use\n\nSends a `GET` request to `/status`"]
+    pub async fn get_status<'a>(&'a self) -> Result<ResponseValue<types::Status>, Error<()>> {
+        let url = format!("{}/status", self.baseurl,);
+        let request = self.client.get(url).build()?;
+        let result = self.client.execute(request).await;
+        let response = result?;
+        match response.status().as_u16() {
+            200u16 => ResponseValue::from_response(response).await,
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+
+    #[doc = "Returns a list of Networks you have access to\n\nSends a `GET` request to `/network`"]
+    pub async fn get_network_list<'a>(
+        &'a self,
+    ) -> Result<ResponseValue<Vec<types::Network>>, Error<()>> {
+        let url = format!("{}/network", self.baseurl,);
+        let request = self.client.get(url).build()?;
+        let result = self.client.execute(request).await;
+        let response = result?;
+        match response.status().as_u16() {
+            200u16 => ResponseValue::from_response(response).await,
+            403u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+
+    #[doc = "Create a new network\n\nSends a `POST` request to `/network`\n\nArguments:\n- `body`: empty JSON object\n"]
+    pub async fn new_network<'a>(
+        &'a self,
+        body: &'a std::collections::HashMap<String, serde_json::Value>,
+    ) -> Result<ResponseValue<types::Network>, Error<()>> {
+        let url = format!("{}/network", self.baseurl,);
+        let request = self.client.post(url).json(body).build()?;
+        let result = self.client.execute(request).await;
+        let response = result?;
+        match response.status().as_u16() {
+            200u16 => ResponseValue::from_response(response).await,
+            403u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+
+    #[doc = "Get network by ID\n\nReturns a single network\n\nSends a `GET` request to `/network/{networkID}`\n\nArguments:\n- `network_id`: ID of the network to return\n"]
+    pub async fn get_network_by_id<'a>(
+        &'a self,
+        network_id: &'a str,
+    ) -> Result<ResponseValue<types::Network>, Error<()>> {
+        let url = format!(
+            "{}/network/{}",
+            self.baseurl,
+            progenitor_client::encode_path(&network_id.to_string()),
+        );
+        let request = self.client.get(url).build()?;
+        let result = self.client.execute(request).await;
+        let response = result?;
+        match response.status().as_u16() {
+            200u16 => ResponseValue::from_response(response).await,
+            401u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            403u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            404u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+
+    #[doc = "update network configuration\n\nSends a `POST` request to `/network/{networkID}`\n\nArguments:\n- `network_id`: ID of the network to change\n- `body`: Network object JSON\n"]
+    pub async fn update_network<'a>(
+        &'a self,
+        network_id: &'a str,
+        body: &'a types::Network,
+    ) -> Result<ResponseValue<types::Network>, Error<()>> {
+        let url = format!(
+            "{}/network/{}",
+            self.baseurl,
+            progenitor_client::encode_path(&network_id.to_string()),
+        );
+        let request = self.client.post(url).json(body).build()?;
+        let result = self.client.execute(request).await;
+        let response = result?;
+        match response.status().as_u16() {
+            200u16 => ResponseValue::from_response(response).await,
+            401u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            403u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            404u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+
+    #[doc = "delete network\n\nSends a `DELETE` request to `/network/{networkID}`\n\nArguments:\n- `network_id`: ID of the network\n"]
+    pub async fn delete_network<'a>(
+        &'a self,
+        network_id: &'a str,
+    ) -> Result<ResponseValue<()>, Error<()>> {
+        let url = format!(
+            "{}/network/{}",
+            self.baseurl,
+            progenitor_client::encode_path(&network_id.to_string()),
+        );
+        let request = self.client.delete(url).build()?;
+        let result = self.client.execute(request).await;
+        let response = result?;
+        match response.status().as_u16() {
+            200u16 => Ok(ResponseValue::empty(response)),
+            401u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            403u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            404u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+
+    #[doc = "Returns a list of Members on the network\n\nSends a `GET` request to `/network/{networkID}/member`\n\nArguments:\n- `network_id`: ID of the network to return\n"]
+    pub async fn get_network_member_list<'a>(
+        &'a self,
+        network_id: &'a str,
+    ) -> Result<ResponseValue<Vec<types::Member>>, Error<()>> {
+        let url = format!(
+            "{}/network/{}/member",
+            self.baseurl,
+            progenitor_client::encode_path(&network_id.to_string()),
+        );
+        let request = self.client.get(url).build()?;
+        let result = self.client.execute(request).await;
+        let response = result?;
+        match response.status().as_u16() {
+            200u16 => ResponseValue::from_response(response).await,
+            401u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            403u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            404u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+
+    #[doc = "Return an individual member on a network\n\nSends a `GET` request to `/network/{networkID}/member/{memberID}`\n\nArguments:\n- `network_id`: ID of the network\n- `member_id`: ID of the member\n"]
+    pub async fn get_network_member<'a>(
+        &'a self,
+        network_id: &'a str,
+        member_id: &'a str,
+    ) -> Result<ResponseValue<types::Member>, Error<()>> {
+        let url = format!(
+            "{}/network/{}/member/{}",
+            self.baseurl,
+            progenitor_client::encode_path(&network_id.to_string()),
+            progenitor_client::encode_path(&member_id.to_string()),
+        );
+        let request = self.client.get(url).build()?;
+        let result = self.client.execute(request).await;
+        let response = result?;
+        match response.status().as_u16() {
+            200u16 => ResponseValue::from_response(response).await,
+            401u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            403u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            404u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+
+    #[doc = "Modify a network member\n\nSends a `POST` request to `/network/{networkID}/member/{memberID}`\n\nArguments:\n- `network_id`: ID of the network\n- `member_id`: ID of the member\n- `body`: Member object JSON\n"]
+    pub async fn update_network_member<'a>(
+        &'a self,
+        network_id: &'a str,
+        member_id: &'a str,
+        body: &'a types::Member,
+    ) -> Result<ResponseValue<types::Member>, Error<()>> {
+        let url = format!(
+            "{}/network/{}/member/{}",
+            self.baseurl,
+            progenitor_client::encode_path(&network_id.to_string()),
+            progenitor_client::encode_path(&member_id.to_string()),
+        );
+        let request = self.client.post(url).json(body).build()?;
+        let result = self.client.execute(request).await;
+        let response = result?;
+        match response.status().as_u16() {
+            200u16 => ResponseValue::from_response(response).await,
+            401u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            403u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            404u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+
+    #[doc = "Delete a network member\n\nSends a `DELETE` request to `/network/{networkID}/member/{memberID}`\n\nArguments:\n- `network_id`: ID of the network\n- `member_id`: ID of the member\n"]
+    pub async fn delete_network_member<'a>(
+        &'a self,
+        network_id: &'a str,
+        member_id: &'a str,
+    ) -> Result<ResponseValue<()>, Error<()>> {
+        let url = format!(
+            "{}/network/{}/member/{}",
+            self.baseurl,
+            progenitor_client::encode_path(&network_id.to_string()),
+            progenitor_client::encode_path(&member_id.to_string()),
+        );
+        let request = self.client.delete(url).build()?;
+        let result = self.client.execute(request).await;
+        let response = result?;
+        match response.status().as_u16() {
+            200u16 => Ok(ResponseValue::empty(response)),
+            401u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            403u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            404u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+
+    #[doc = "Get user record\n\nSends a `GET` request to `/user/{userID}`\n\nArguments:\n- `user_id`: User ID\n"]
+    pub async fn get_user_by_id<'a>(
+        &'a self,
+        user_id: &'a str,
+    ) -> Result<ResponseValue<types::User>, Error<()>> {
+        let url = format!(
+            "{}/user/{}",
+            self.baseurl,
+            progenitor_client::encode_path(&user_id.to_string()),
+        );
+        let request = self.client.get(url).build()?;
+        let result = self.client.execute(request).await;
+        let response = result?;
+        match response.status().as_u16() {
+            200u16 => ResponseValue::from_response(response).await,
+            401u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            403u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            404u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+
+    #[doc = "Update user record (SMS number or Display Name only)\n\nSends a `POST` request to `/user/{userID}`\n\nArguments:\n- `user_id`: User ID\n- `body`: User object JSON\n"]
+    pub async fn update_user_by_id<'a>(
+        &'a self,
+        user_id: &'a str,
+        body: &'a types::User,
+    ) -> Result<ResponseValue<types::User>, Error<()>> {
+        let url = format!(
+            "{}/user/{}",
+            self.baseurl,
+            progenitor_client::encode_path(&user_id.to_string()),
+        );
+        let request = self.client.post(url).json(body).build()?;
+        let result = self.client.execute(request).await;
+        let response = result?;
+        match response.status().as_u16() {
+            200u16 => ResponseValue::from_response(response).await,
+            401u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            403u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            404u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+
+    #[doc = "Delete user\n\nDeletes the user and all associated networks.  This is not reversible. Delete at your own risk.\n\nSends a `DELETE` request to `/user/{userID}`\n\nArguments:\n- `user_id`: User ID\n"]
+    pub async fn delete_user_by_id<'a>(
+        &'a self,
+        user_id: &'a str,
+    ) -> Result<ResponseValue<()>, Error<()>> {
+        let url = format!(
+            "{}/user/{}",
+            self.baseurl,
+            progenitor_client::encode_path(&user_id.to_string()),
+        );
+        let request = self.client.delete(url).build()?;
+        let result = self.client.execute(request).await;
+        let response = result?;
+        match response.status().as_u16() {
+            200u16 => Ok(ResponseValue::empty(response)),
+            401u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            403u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            404u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+
+    #[doc = "Add an API token\n\nSends a `POST` request to `/user/{userID}/token`\n\nArguments:\n- `user_id`: User ID\n- `body`: APIToken JSON object\n"]
+    pub async fn add_api_token<'a>(
+        &'a self,
+        user_id: &'a str,
+        body: &'a types::ApiToken,
+    ) -> Result<ResponseValue<types::ApiToken>, Error<()>> {
+        let url = format!(
+            "{}/user/{}/token",
+            self.baseurl,
+            progenitor_client::encode_path(&user_id.to_string()),
+        );
+        let request = self.client.post(url).json(body).build()?;
+        let result = self.client.execute(request).await;
+        let response = result?;
+        match response.status().as_u16() {
+            200u16 => ResponseValue::from_response(response).await,
+            400u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            401u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            403u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            404u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+
+    #[doc = "Delete API Token\n\nSends a `DELETE` request to `/user/{userID}/token/{tokenName}`\n\nArguments:\n- `user_id`: User ID\n- `token_name`: Token Name\n"]
+    pub async fn delete_api_token<'a>(
+        &'a self,
+        user_id: &'a str,
+        token_name: &'a str,
+    ) -> Result<ResponseValue<()>, Error<()>> {
+        let url = format!(
+            "{}/user/{}/token/{}",
+            self.baseurl,
+            progenitor_client::encode_path(&user_id.to_string()),
+            progenitor_client::encode_path(&token_name.to_string()),
+        );
+        let request = self.client.delete(url).build()?;
+        let result = self.client.execute(request).await;
+        let response = result?;
+        match response.status().as_u16() {
+            200u16 => Ok(ResponseValue::empty(response)),
+            401u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            403u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            404u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+
+    #[doc = "Get the current user's organization\n\nSends a `GET` request to `/org`"]
+    pub async fn get_organization<'a>(
+        &'a self,
+    ) -> Result<ResponseValue<types::Organization>, Error<()>> {
+        let url = format!("{}/org", self.baseurl,);
+        let request = self.client.get(url).build()?;
+        let result = self.client.execute(request).await;
+        let response = result?;
+        match response.status().as_u16() {
+            200u16 => ResponseValue::from_response(response).await,
+            401u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            403u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            404u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+
+    #[doc = "Get organization by ID\n\nSends a `GET` request to `/org/{orgID}`\n\nArguments:\n- `org_id`: Organization ID\n"]
+    pub async fn get_organization_by_id<'a>(
+        &'a self,
+        org_id: &'a str,
+    ) -> Result<ResponseValue<types::Organization>, Error<()>> {
+        let url = format!(
+            "{}/org/{}",
+            self.baseurl,
+            progenitor_client::encode_path(&org_id.to_string()),
+        );
+        let request = self.client.get(url).build()?;
+        let result = self.client.execute(request).await;
+        let response = result?;
+        match response.status().as_u16() {
+            200u16 => ResponseValue::from_response(response).await,
+            401u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            403u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            404u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+
+    #[doc = "Get list of organization members\n\nSends a `GET` request to `/org/{orgID}/user`\n\nArguments:\n- `org_id`: Organization ID\n"]
+    pub async fn get_organization_members<'a>(
+        &'a self,
+        org_id: &'a str,
+    ) -> Result<ResponseValue<types::OrganizationMember>, Error<()>> {
+        let url = format!(
+            "{}/org/{}/user",
+            self.baseurl,
+            progenitor_client::encode_path(&org_id.to_string()),
+        );
+        let request = self.client.get(url).build()?;
+        let result = self.client.execute(request).await;
+        let response = result?;
+        match response.status().as_u16() {
+            200u16 => ResponseValue::from_response(response).await,
+            401u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            403u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            404u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+
+    #[doc = "Get list of organization invitations\n\nSends a `GET` request to `/org-invitation`"]
+    pub async fn get_organization_invitation_list<'a>(
+        &'a self,
+    ) -> Result<ResponseValue<Vec<types::OrganizationInvitation>>, Error<()>> {
+        let url = format!("{}/org-invitation", self.baseurl,);
+        let request = self.client.get(url).build()?;
+        let result = self.client.execute(request).await;
+        let response = result?;
+        match response.status().as_u16() {
+            200u16 => ResponseValue::from_response(response).await,
+            401u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            403u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            404u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+
+    #[doc = "Invite a user to your organization by email\n\nSends a `POST` request to `/org-invitation`\n\nArguments:\n- `body`: Organization Invitation JSON object\n"]
+    pub async fn invite_user_by_email<'a>(
+        &'a self,
+        body: &'a types::OrganizationInvitation,
+    ) -> Result<ResponseValue<types::OrganizationInvitation>, Error<()>> {
+        let url = format!("{}/org-invitation", self.baseurl,);
+        let request = self.client.post(url).json(body).build()?;
+        let result = self.client.execute(request).await;
+        let response = result?;
+        match response.status().as_u16() {
+            200u16 => ResponseValue::from_response(response).await,
+            401u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            403u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            404u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+
+    #[doc = "Get organization invitation\n\nSends a `GET` request to `/org-invitation/{inviteID}`\n\nArguments:\n- `invite_id`: Invitation ID\n"]
+    pub async fn get_invitation_by_id<'a>(
+        &'a self,
+        invite_id: &'a str,
+    ) -> Result<ResponseValue<types::OrganizationInvitation>, Error<()>> {
+        let url = format!(
+            "{}/org-invitation/{}",
+            self.baseurl,
+            progenitor_client::encode_path(&invite_id.to_string()),
+        );
+        let request = self.client.get(url).build()?;
+        let result = self.client.execute(request).await;
+        let response = result?;
+        match response.status().as_u16() {
+            200u16 => ResponseValue::from_response(response).await,
+            401u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            403u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            404u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+
+    #[doc = "Accept organization invitation\n\nSends a `POST` request to `/org-invitation/{inviteID}`\n\nArguments:\n- `invite_id`: Invitation ID\n"]
+    pub async fn accept_invitation<'a>(
+        &'a self,
+        invite_id: &'a str,
+    ) -> Result<ResponseValue<types::OrganizationInvitation>, Error<()>> {
+        let url = format!(
+            "{}/org-invitation/{}",
+            self.baseurl,
+            progenitor_client::encode_path(&invite_id.to_string()),
+        );
+        let request = self.client.post(url).build()?;
+        let result = self.client.execute(request).await;
+        let response = result?;
+        match response.status().as_u16() {
+            200u16 => ResponseValue::from_response(response).await,
+            401u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            403u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            404u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+
+    #[doc = "Decline organization invitation\n\nSends a `DELETE` request to `/org-invitation/{inviteID}`\n\nArguments:\n- `invite_id`: Invitation ID\n"]
+    pub async fn decline_invitation<'a>(
+        &'a self,
+        invite_id: &'a str,
+    ) -> Result<ResponseValue<()>, Error<()>> {
+        let url = format!(
+            "{}/org-invitation/{}",
+            self.baseurl,
+            progenitor_client::encode_path(&invite_id.to_string()),
+        );
+        let request = self.client.delete(url).build()?;
+        let result = self.client.execute(request).await;
+        let response = result?;
+        match response.status().as_u16() {
+            200u16 => Ok(ResponseValue::empty(response)),
+            401u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            403u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            404u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+
+    #[doc = "Get a random 32 character token\n\nGet a random 32 character.  Used by the web UI to generate API keys\n\nSends a `GET` request to `/randomToken`"]
+    pub async fn get_random_token<'a>(
+        &'a self,
+    ) -> Result<ResponseValue<types::RandomToken>, Error<()>> {
+        let url = format!("{}/randomToken", self.baseurl,);
+        let request = self.client.get(url).build()?;
+        let result = self.client.execute(request).await;
+        let response = result?;
+        match response.status().as_u16() {
+            200u16 => ResponseValue::from_response(response).await,
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+}
@@ -0,0 +1,577 @@
+pub use progenitor_client::{ByteStream, Error, ResponseValue};
+pub mod types {
+    use serde::{Deserialize, Serialize};
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    pub struct ApiToken {
+        #[doc = "API Token.  Minimum 32 characters. This token is encrypted in the database and can not be retrieved once set"]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub token: Option<String>,
+        #[doc = "user specified token name"]
+        #[serde(rename = "tokenName", default, skip_serializing_if = "Option::is_none")]
+        pub token_name: Option<String>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    pub struct AuthMethods {
+        #[doc = "Google OIDC ID"]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub google: Option<String>,
+        #[doc = "email address for built-in authentication"]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub local: Option<String>,
+        #[doc = "Generic OIDC ID"]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub oidc: Option<String>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    pub struct Dns {
+        #[doc = "Search domain to use for DNS records"]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub domain: Option<String>,
+        #[doc = "IP address of unicast DNS service"]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub servers: Option<Vec<String>>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialOrd, Ord, PartialEq, Eq, Hash)]
+    pub enum InviteStatus {
+        #[serde(rename = "pending")]
+        Pending,
+        #[serde(rename = "accepted")]
+        Accepted,
+        #[serde(rename = "canceled")]
+        Canceled,
+    }
+
+    impl ToString for InviteStatus {
+        fn to_string(&self) -> String {
+            match *self {
+                InviteStatus::Pending => "pending".to_string(),
+                InviteStatus::Accepted => "accepted".to_string(),
+                InviteStatus::Canceled => "canceled".to_string(),
+            }
+        }
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    pub struct IpRange {
+        #[serde(
+            rename = "ipRangeEnd",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub ip_range_end: Option<String>,
+        #[serde(
+            rename = "ipRangeStart",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub ip_range_start: Option<String>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    pub struct Ipv4AssignMode {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub zt: Option<bool>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    pub struct Ipv6AssignMode {
+        #[serde(rename = "6plane", default, skip_serializing_if = "Option::is_none")]
+        pub _6plane: Option<bool>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub rfc4193: Option<bool>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub zt: Option<bool>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    pub struct Member {
+        #[doc = "ZeroTier version the member is running"]
+        #[serde(
+            rename = "clientVersion",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub client_version: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub clock: Option<i64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub config: Option<MemberConfig>,
+        #[serde(
+            rename = "controllerId",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub controller_id: Option<String>,
+        #[doc = "User defined description of the member"]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub description: Option<String>,
+        #[doc = "Whether or not the member is hidden in the UI"]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub hidden: Option<bool>,
+        #[doc = "concatenation of network ID and member ID"]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub id: Option<String>,
+        #[doc = "Last seen time of the member.  Note: This data is considered ephemeral and may be reset to 0 at any time without warning."]
+        #[serde(
+            rename = "lastOnline",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub last_online: Option<i64>,
+        #[doc = "User defined name of the member"]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub name: Option<String>,
+        #[serde(rename = "networkId", default, skip_serializing_if = "Option::is_none")]
+        pub network_id: Option<String>,
+        #[doc = "ZeroTier ID of the member"]
+        #[serde(rename = "nodeId", default, skip_serializing_if = "Option::is_none")]
+        pub node_id: Option<String>,
+        #[doc = "IP address the member last spoke to the controller via.  Note: This data is considered ephemeral and may be reset to 0 at any time without warning."]
+        #[serde(
+            rename = "physicalAddress",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub physical_address: Option<String>,
+        #[doc = "ZeroTier protocol version"]
+        #[serde(
+            rename = "protocolVersion",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub protocol_version: Option<i64>,
+        #[doc = "Whether or not the client version is new enough to support the rules engine (1.4.0+)"]
+        #[serde(
+            rename = "supportsRulesEngine",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub supports_rules_engine: Option<bool>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    pub struct MemberConfig {
+        #[doc = "Allow the member to be a bridge on the network"]
+        #[serde(
+            rename = "activeBridge",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub active_bridge: Option<bool>,
+        #[doc = "Is the member authorized on the network"]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub authorized: Option<bool>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub capabilities: Option<Vec<i64>>,
+        #[doc = "Time the member was created or first tried to join the network"]
+        #[serde(
+            rename = "creationTime",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub creation_time: Option<i64>,
+        #[doc = "ID of the member node.  This is the 10 digit identifier that identifies a ZeroTier node."]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub id: Option<String>,
+        #[doc = "Public Key of the member's Identity"]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub identity: Option<String>,
+        #[doc = "List of assigned IP addresses"]
+        #[serde(
+            rename = "ipAssignments",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub ip_assignments: Option<Vec<String>>,
+        #[doc = "Time the member was authorized on the network"]
+        #[serde(
+            rename = "lastAuthorizedTime",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub last_authorized_time: Option<i64>,
+        #[doc = "Time the member was deauthorized on the network"]
+        #[serde(
+            rename = "lastDeauthorizedTime",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub last_deauthorized_time: Option<i64>,
+        #[doc = "Exempt this member from the IP auto assignment pool on a Network"]
+        #[serde(
+            rename = "noAutoAssignIps",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub no_auto_assign_ips: Option<bool>,
+        #[doc = "Member record revision count"]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub revision: Option<i64>,
+        #[doc = "Array of 2 member tuples of tag [ID, tag value]"]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub tags: Option<Vec<Vec<MemberConfigTagsItemItem>>>,
+        #[doc = "Major version of the client"]
+        #[serde(rename = "vMajor", default, skip_serializing_if = "Option::is_none")]
+        pub v_major: Option<i64>,
+        #[doc = "Minor version of the client"]
+        #[serde(rename = "vMinor", default, skip_serializing_if = "Option::is_none")]
+        pub v_minor: Option<i64>,
+        #[doc = "Protocol version of the client"]
+        #[serde(rename = "vProto", default, skip_serializing_if = "Option::is_none")]
+        pub v_proto: Option<i64>,
+        #[doc = "Revision number of the client"]
+        #[serde(rename = "vRev", default, skip_serializing_if = "Option::is_none")]
+        pub v_rev: Option<i64>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    #[serde(untagged)]
+    pub enum MemberConfigTagsItemItem {
+        Variant0(i64),
+        Variant1(bool),
+    }
+
+    #[doc = "Network object"]
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    pub struct Network {
+        #[serde(
+            rename = "authorizedMemberCount",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub authorized_member_count: Option<i64>,
+        #[serde(
+            rename = "capabilitiesByName",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub capabilities_by_name: Option<std::collections::HashMap<String, serde_json::Value>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub clock: Option<i64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub config: Option<NetworkConfig>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub description: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub id: Option<String>,
+        #[doc = "Note: May be 0 on endpoints returning lists of Networks"]
+        #[serde(
+            rename = "onlineMemberCount",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub online_member_count: Option<i64>,
+        #[serde(rename = "ownerId", default, skip_serializing_if = "Option::is_none")]
+        pub owner_id: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub permissions: Option<PermissionsMap>,
+        #[serde(
+            rename = "rulesSource",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub rules_source: Option<String>,
+        #[serde(
+            rename = "tagsByName",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub tags_by_name: Option<std::collections::HashMap<String, serde_json::Value>>,
+        #[serde(
+            rename = "totalMemberCount",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub total_member_count: Option<i64>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    pub struct NetworkConfig {
+        #[doc = "Array of network capabilities"]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub capabilities: Option<Vec<std::collections::HashMap<String, serde_json::Value>>>,
+        #[doc = "Time the network was created"]
+        #[serde(
+            rename = "creationTime",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub creation_time: Option<i64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub dns: Option<Dns>,
+        #[doc = "Enable broadcast packets on the network"]
+        #[serde(
+            rename = "enableBroadcast",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub enable_broadcast: Option<bool>,
+        #[doc = "Network ID"]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub id: Option<String>,
+        #[doc = "Range of IP addresses for the auto assign pool"]
+        #[serde(
+            rename = "ipAssignmentPools",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub ip_assignment_pools: Option<Vec<IpRange>>,
+        #[doc = "Time the network was last modified"]
+        #[serde(
+            rename = "lastModified",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub last_modified: Option<i64>,
+        #[doc = "MTU to set on the client virtual network adapter"]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub mtu: Option<i64>,
+        #[doc = "Maximum number of recipients per multicast or broadcast. Warning - Setting this to 0 will disable IPv4 communication on your network!"]
+        #[serde(
+            rename = "multicastLimit",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub multicast_limit: Option<i64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub name: Option<String>,
+        #[doc = "Whether or not the network is private.  If false, members will *NOT* need to be authorized to join."]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub private: Option<bool>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub routes: Option<Vec<Route>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub rules: Option<Vec<std::collections::HashMap<String, serde_json::Value>>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub tags: Option<Vec<std::collections::HashMap<String, serde_json::Value>>>,
+        #[serde(
+            rename = "v4AssignMode",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub v4_assign_mode: Option<Ipv4AssignMode>,
+        #[serde(
+            rename = "v6AssignMode",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub v6_assign_mode: Option<Ipv6AssignMode>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    pub struct Organization {
+        #[doc = "Organization ID"]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub id: Option<String>,
+        #[doc = "List of organization members"]
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub members: Vec<OrganizationMember>,
+        #[doc = "Organization owner's email address"]
+        #[serde(
+            rename = "ownerEmail",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub owner_email: Option<String>,
+        #[doc = "User ID of the organization owner"]
+        #[serde(rename = "ownerId", default, skip_serializing_if = "Option::is_none")]
+        pub owner_id: Option<String>,
+        #[serde(rename = "ssoConfig", default, skip_serializing_if = "Option::is_none")]
+        pub sso_config: Option<SsoConfig>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    pub struct OrganizationInvitation {
+        #[doc = "Creation time of the invite"]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub creation_time: Option<i64>,
+        #[doc = "Email address of invitee"]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub email: Option<String>,
+        #[doc = "Invitation ID"]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub id: Option<String>,
+        #[doc = "Organization ID"]
+        #[serde(rename = "orgId", default, skip_serializing_if = "Option::is_none")]
+        pub org_id: Option<String>,
+        #[doc = "Organization owner email address"]
+        #[serde(
+            rename = "ownerEmail",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub owner_email: Option<String>,
+        #[doc = "Invitation status"]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub status: Option<InviteStatus>,
+        #[doc = "Last updated time of the invitation"]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub update_time: Option<i64>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    pub struct OrganizationMember {
+        #[doc = "Organization member email address"]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub email: Option<String>,
+        #[doc = "Organization member display name"]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub name: Option<String>,
+        #[doc = "Organization ID"]
+        #[serde(rename = "orgId", default, skip_serializing_if = "Option::is_none")]
+        pub org_id: Option<String>,
+        #[doc = "User ID"]
+        #[serde(rename = "userId", default, skip_serializing_if = "Option::is_none")]
+        pub user_id: Option<String>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    pub struct Permissions {
+        #[doc = "Authorize permission"]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub a: Option<bool>,
+        #[doc = "Delete permission"]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub d: Option<bool>,
+        #[doc = "Modify network settings permission"]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub m: Option<bool>,
+        #[doc = "Read network settings permission"]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub r: Option<bool>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    pub struct PermissionsMap(pub std::collections::HashMap<String, Permissions>);
+    impl std::ops::Deref for PermissionsMap {
+        type Target = std::collections::HashMap<String, Permissions>;
+        fn deref(&self) -> &Self::Target {
+            &self.0
+        }
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    pub struct RandomToken {
+        #[doc = "Current time on server"]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub clock: Option<i64>,
+        #[doc = "hex encoded random bytes of the token"]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub hex: Option<String>,
+        #[doc = "Random 32 character token"]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub token: Option<String>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    pub struct Route {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub target: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub via: Option<String>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    pub struct SsoConfig {
+        #[doc = "OIDC Client ID"]
+        #[serde(rename = "clientId", default, skip_serializing_if = "Option::is_none")]
+        pub client_id: Option<String>,
+        #[doc = "Enabled flag for SSO"]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub enabled: Option<bool>,
+        #[doc = "OIDC Issuer URL"]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub issuer: Option<String>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    pub struct Status {
+        #[serde(
+            rename = "apiVersion",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub api_version: Option<String>,
+        #[doc = "Current time on server"]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub clock: Option<i64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub id: Option<String>,
+        #[serde(
+            rename = "loginMethods",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub login_methods: Option<StatusLoginMethods>,
+        #[serde(
+            rename = "readOnlyMode",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub read_only_mode: Option<bool>,
+        #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+        pub type_: Option<String>,
+        #[doc = "Uptime on server"]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub uptime: Option<i64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub user: Option<User>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub version: Option<String>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    pub struct StatusLoginMethods {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub facebook: Option<bool>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub github: Option<bool>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub google: Option<bool>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub local: Option<bool>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub oidc: Option<bool>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub saml: Option<bool>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub twitter: Option<bool>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    pub struct User {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub auth: Option<AuthMethods>,
+        #[doc = "Display Name"]
+        #[serde(
+            rename = "displayName",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub display_name: Option<String>,
+        #[doc = "User email address"]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub email: Option<String>,
+        #[serde(
+            rename = "globalPermissions",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub global_permissions: Option<Permissions>,
+        #[doc = "User ID"]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub id: Option<String>,
+        #[doc = "Organization ID"]
+        #[serde(rename = "orgId", default, skip_serializing_if = "Option::is_none")]
+        pub org_id: Option<String>,
+        #[doc = "SMS number"]
+        #[serde(rename = "smsNumber", default, skip_serializing_if = "Option::is_none")]
+        pub sms_number: Option<String>,
+        #[doc = "List of API token names."]
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub tokens: Vec<String>,
+    }
+}
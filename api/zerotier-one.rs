@@ -0,0 +1,749 @@
+pub use progenitor_client::{ByteStream, Error, ResponseValue};
+pub mod types {
+    use serde::{Deserialize, Serialize};
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    pub struct ControllerNetwork {
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub capabilities: Vec<std::collections::HashMap<String, serde_json::Value>>,
+        #[serde(
+            rename = "creationTime",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub creation_time: Option<f64>,
+        #[serde(
+            rename = "enableBroadcast",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub enable_broadcast: Option<bool>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub id: Option<String>,
+        #[serde(
+            rename = "ipAssignmentPools",
+            default,
+            skip_serializing_if = "Vec::is_empty"
+        )]
+        pub ip_assignment_pools: Vec<ControllerNetworkIpAssignmentPoolsItem>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub mtu: Option<i64>,
+        #[serde(
+            rename = "multicastLimit",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub multicast_limit: Option<i64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub name: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub nwid: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub objtype: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub private: Option<bool>,
+        #[serde(
+            rename = "remoteTraceLevel",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub remote_trace_level: Option<i64>,
+        #[serde(
+            rename = "remoteTraceTarget",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub remote_trace_target: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub revision: Option<i64>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub routes: Vec<ControllerNetworkRoutesItem>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub rules: Vec<std::collections::HashMap<String, serde_json::Value>>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub tags: Vec<std::collections::HashMap<String, serde_json::Value>>,
+        #[serde(
+            rename = "v4AssignMode",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub v4_assign_mode: Option<ControllerNetworkV4AssignMode>,
+        #[serde(
+            rename = "v6AssignMode",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub v6_assign_mode: Option<ControllerNetworkV6AssignMode>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    pub struct ControllerNetworkIpAssignmentPoolsItem {
+        #[serde(
+            rename = "ipRangeEnd",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub ip_range_end: Option<String>,
+        #[serde(
+            rename = "ipRangeStart",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub ip_range_start: Option<String>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    pub struct ControllerNetworkMember {
+        #[serde(
+            rename = "activeBridge",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub active_bridge: Option<bool>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub address: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub authorized: Option<bool>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub id: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub identity: Option<String>,
+        #[serde(
+            rename = "ipAssignments",
+            default,
+            skip_serializing_if = "Vec::is_empty"
+        )]
+        pub ip_assignments: Vec<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub nwid: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub revision: Option<i64>,
+        #[serde(rename = "vMajor", default, skip_serializing_if = "Option::is_none")]
+        pub v_major: Option<i64>,
+        #[serde(rename = "vMinor", default, skip_serializing_if = "Option::is_none")]
+        pub v_minor: Option<i64>,
+        #[serde(rename = "vProto", default, skip_serializing_if = "Option::is_none")]
+        pub v_proto: Option<i64>,
+        #[serde(rename = "vRev", default, skip_serializing_if = "Option::is_none")]
+        pub v_rev: Option<i64>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    pub struct ControllerNetworkRoutesItem {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub target: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub via: Option<String>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    pub struct ControllerNetworkV4AssignMode {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub zt: Option<bool>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    pub struct ControllerNetworkV6AssignMode {
+        #[serde(rename = "6plane", default, skip_serializing_if = "Option::is_none")]
+        pub _6plane: Option<bool>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub rfc4193: Option<bool>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub zt: Option<bool>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    pub struct ControllerStatus {
+        #[serde(
+            rename = "apiVersion",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub api_version: Option<i64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub clock: Option<i64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub controller: Option<bool>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    pub struct Network {
+        #[serde(flatten)]
+        pub subtype_0: NetworkSubtype0,
+        #[serde(flatten)]
+        pub subtype_1: NetworkSubtype1,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    pub struct NetworkSubtype0 {
+        #[doc = "Let ZeroTier modify the system's default route."]
+        #[serde(
+            rename = "allowDefault",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub allow_default: Option<bool>,
+        #[doc = "Let ZeroTier modify the system's DNS settings."]
+        #[serde(rename = "allowDNS", default, skip_serializing_if = "Option::is_none")]
+        pub allow_dns: Option<bool>,
+        #[doc = "Let ZeroTier manage IP addresses and route assignments that aren't in private ranges (rfc1918)."]
+        #[serde(
+            rename = "allowGlobal",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub allow_global: Option<bool>,
+        #[doc = "Let ZeroTier manage IP addresses and Route assignments."]
+        #[serde(
+            rename = "allowManaged",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub allow_managed: Option<bool>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    pub struct NetworkSubtype1 {
+        #[doc = "Let ZeroTier modify the system's default route."]
+        #[serde(
+            rename = "allowDefault",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub allow_default: Option<bool>,
+        #[doc = "Let ZeroTier modify the system's DNS settings."]
+        #[serde(rename = "allowDNS", default, skip_serializing_if = "Option::is_none")]
+        pub allow_dns: Option<bool>,
+        #[doc = "Let ZeroTier manage IP addresses and Route assignments that aren't in private ranges (rfc1918)."]
+        #[serde(
+            rename = "allowGlobal",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub allow_global: Option<bool>,
+        #[doc = "Let ZeroTier to manage IP addresses and Route assignments."]
+        #[serde(
+            rename = "allowManaged",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub allow_managed: Option<bool>,
+        #[serde(
+            rename = "assignedAddresses",
+            default,
+            skip_serializing_if = "Vec::is_empty"
+        )]
+        pub assigned_addresses: Vec<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub bridge: Option<bool>,
+        #[serde(
+            rename = "broadcastEnabled",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub broadcast_enabled: Option<bool>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub dns: Option<NetworkSubtype1Dns>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub id: Option<String>,
+        #[doc = "MAC address for this network's interface."]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub mac: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub mtu: Option<i64>,
+        #[serde(
+            rename = "multicastSubscriptions",
+            default,
+            skip_serializing_if = "Vec::is_empty"
+        )]
+        pub multicast_subscriptions: Vec<NetworkSubtype1MulticastSubscriptionsItem>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub name: Option<String>,
+        #[serde(
+            rename = "netconfRevision",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub netconf_revision: Option<i64>,
+        #[serde(
+            rename = "portDeviceName",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub port_device_name: Option<String>,
+        #[serde(rename = "portError", default, skip_serializing_if = "Option::is_none")]
+        pub port_error: Option<i64>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub routes: Vec<NetworkSubtype1RoutesItem>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub status: Option<String>,
+        #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+        pub type_: Option<String>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    pub struct NetworkSubtype1Dns {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub domain: Option<String>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub servers: Vec<String>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    pub struct NetworkSubtype1MulticastSubscriptionsItem {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub adi: Option<i64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub mac: Option<String>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    pub struct NetworkSubtype1RoutesItem {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub flags: Option<i64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub metric: Option<i64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub target: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub via: Option<String>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    pub struct Peer {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub address: Option<String>,
+        #[serde(rename = "isBonded", default, skip_serializing_if = "Option::is_none")]
+        pub is_bonded: Option<bool>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub latency: Option<i64>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub paths: Vec<PeerPathsItem>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub role: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub version: Option<String>,
+        #[serde(
+            rename = "versionMajor",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub version_major: Option<i64>,
+        #[serde(
+            rename = "versionMinor",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub version_minor: Option<i64>,
+        #[serde(
+            rename = "versionRev",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub version_rev: Option<i64>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    pub struct PeerPathsItem {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub active: Option<bool>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub address: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub expired: Option<bool>,
+        #[serde(
+            rename = "lastReceive",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub last_receive: Option<i64>,
+        #[serde(rename = "lastSend", default, skip_serializing_if = "Option::is_none")]
+        pub last_send: Option<i64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub preferred: Option<bool>,
+        #[serde(
+            rename = "trustedPathId",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub trusted_path_id: Option<i64>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    pub struct Status {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub address: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub clock: Option<i64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub config: Option<StatusConfig>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub online: Option<bool>,
+        #[serde(
+            rename = "planetWorldId",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub planet_world_id: Option<i64>,
+        #[serde(
+            rename = "planetWorldTimestamp",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub planet_world_timestamp: Option<i64>,
+        #[serde(
+            rename = "publicIdentity",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub public_identity: Option<String>,
+        #[serde(
+            rename = "tcpFallbackActive",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub tcp_fallback_active: Option<bool>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub version: Option<String>,
+        #[serde(
+            rename = "versionBuild",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub version_build: Option<i64>,
+        #[serde(
+            rename = "versionMajor",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub version_major: Option<i64>,
+        #[serde(
+            rename = "versionMinor",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub version_minor: Option<i64>,
+        #[serde(
+            rename = "versionRev",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub version_rev: Option<i64>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    pub struct StatusConfig {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub settings: Option<StatusConfigSettings>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    pub struct StatusConfigSettings {
+        #[serde(
+            rename = "allowTcpFallbackRelay",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub allow_tcp_fallback_relay: Option<bool>,
+        #[serde(
+            rename = "portMappingEnabled",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub port_mapping_enabled: Option<bool>,
+        #[serde(
+            rename = "primaryPort",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub primary_port: Option<i64>,
+    }
+}
+
+#[derive(Clone)]
+pub struct Client {
+    baseurl: String,
+    client: reqwest::Client,
+}
+
+impl Client {
+    pub fn new(baseurl: &str) -> Self {
+        let dur = std::time::Duration::from_secs(15);
+        let client = reqwest::ClientBuilder::new()
+            .connect_timeout(dur)
+            .timeout(dur)
+            .build()
+            .unwrap();
+        Self::new_with_client(baseurl, client)
+    }
+
+    pub fn new_with_client(baseurl: &str, client: reqwest::Client) -> Self {
+        Self {
+            baseurl: baseurl.to_string(),
+            client,
+        }
+    }
+
+    pub fn baseurl(&self) -> &String {
+        &self.baseurl
+    }
+
+    pub fn client(&self) -> &reqwest::Client {
+        &self.client
+    }
+
+    #[doc = "Get Controller Status\n\nCheck for controller function and return controller status.\n\nSends a `GET` request to `/controller`"]
+    pub async fn get_controller_status<'a>(
+        &'a self,
+    ) -> Result<ResponseValue<types::ControllerStatus>, Error<()>> {
+        let url = format!("{}/controller", self.baseurl,);
+        let request = self.client.get(url).build()?;
+        let result = self.client.execute(request).await;
+        let response = result?;
+        match response.status().as_u16() {
+            200u16 => ResponseValue::from_response(response).await,
+            401u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+
+    #[doc = "List Networks\n\nList IDs of all networks hosted by this controller.\n\nSends a `GET` request to `/controller/network`"]
+    pub async fn get_controller_networks<'a>(
+        &'a self,
+    ) -> Result<ResponseValue<Vec<String>>, Error<()>> {
+        let url = format!("{}/controller/network", self.baseurl,);
+        let request = self.client.get(url).build()?;
+        let result = self.client.execute(request).await;
+        let response = result?;
+        match response.status().as_u16() {
+            200u16 => ResponseValue::from_response(response).await,
+            401u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+
+    #[doc = "Get Network by ID\n\nGet details of a network by it's ID.\n\nSends a `GET` request to `/controller/network/{networkID}`\n\nArguments:\n- `network_id`: ID of the network.\n"]
+    pub async fn get_controller_network<'a>(
+        &'a self,
+        network_id: &'a str,
+    ) -> Result<ResponseValue<types::ControllerNetwork>, Error<()>> {
+        let url = format!(
+            "{}/controller/network/{}",
+            self.baseurl,
+            progenitor_client::encode_path(&network_id.to_string()),
+        );
+        let request = self.client.get(url).build()?;
+        let result = self.client.execute(request).await;
+        let response = result?;
+        match response.status().as_u16() {
+            200u16 => ResponseValue::from_response(response).await,
+            401u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+
+    #[doc = "Create or Update a Network\n\nSends a `POST` request to `/controller/network/{networkID}`\n\nArguments:\n- `network_id`: ID of the network.\n- `body`: Network object JSON.\n"]
+    pub async fn set_controller_network<'a>(
+        &'a self,
+        network_id: &'a str,
+        body: &'a types::ControllerNetwork,
+    ) -> Result<ResponseValue<types::ControllerNetwork>, Error<()>> {
+        let url = format!(
+            "{}/controller/network/{}",
+            self.baseurl,
+            progenitor_client::encode_path(&network_id.to_string()),
+        );
+        let request = self.client.post(url).json(body).build()?;
+        let result = self.client.execute(request).await;
+        let response = result?;
+        match response.status().as_u16() {
+            200u16 => ResponseValue::from_response(response).await,
+            401u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+
+    #[doc = "Generate Random Network ID\n\nCreate a new network with a random ID.\n\nSends a `POST` request to `/controller/network/{controllerID}`\n\nArguments:\n- `controller_id`: Node ID of the controller.\n- `body`: Network object JSON.\n"]
+    pub async fn generate_controller_network<'a>(
+        &'a self,
+        controller_id: &'a str,
+        body: &'a types::ControllerNetwork,
+    ) -> Result<ResponseValue<types::ControllerNetwork>, Error<()>> {
+        let url = format!(
+            "{}/controller/network/{}",
+            self.baseurl,
+            progenitor_client::encode_path(&controller_id.to_string()),
+        );
+        let request = self.client.post(url).json(body).build()?;
+        let result = self.client.execute(request).await;
+        let response = result?;
+        match response.status().as_u16() {
+            200u16 => ResponseValue::from_response(response).await,
+            401u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+
+    #[doc = "List Network Members\n\nJSON object containing all member IDs as keys and their memberRevisionCounter values as values.\n\nSends a `GET` request to `/controller/network/{networkID}/member`\n\nArguments:\n- `network_id`: ID of the network.\n"]
+    pub async fn get_controller_network_members<'a>(
+        &'a self,
+        network_id: &'a str,
+    ) -> Result<ResponseValue<std::collections::HashMap<String, i64>>, Error<()>> {
+        let url = format!(
+            "{}/controller/network/{}/member",
+            self.baseurl,
+            progenitor_client::encode_path(&network_id.to_string()),
+        );
+        let request = self.client.get(url).build()?;
+        let result = self.client.execute(request).await;
+        let response = result?;
+        match response.status().as_u16() {
+            200u16 => ResponseValue::from_response(response).await,
+            401u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+
+    #[doc = "Get Network Member Details by ID\n\nSends a `GET` request to `/controller/network/{networkID}/member/{nodeID}`\n\nArguments:\n- `network_id`: ID of the network.\n- `node_id`: ID of the member node.\n"]
+    pub async fn get_controller_network_member<'a>(
+        &'a self,
+        network_id: &'a str,
+        node_id: &'a str,
+    ) -> Result<ResponseValue<types::ControllerNetworkMember>, Error<()>> {
+        let url = format!(
+            "{}/controller/network/{}/member/{}",
+            self.baseurl,
+            progenitor_client::encode_path(&network_id.to_string()),
+            progenitor_client::encode_path(&node_id.to_string()),
+        );
+        let request = self.client.get(url).build()?;
+        let result = self.client.execute(request).await;
+        let response = result?;
+        match response.status().as_u16() {
+            200u16 => ResponseValue::from_response(response).await,
+            401u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+
+    #[doc = "Node status and addressing info\n\nSends a `GET` request to `/status`"]
+    pub async fn get_status<'a>(&'a self) -> Result<ResponseValue<types::Status>, Error<()>> {
+        let url = format!("{}/status", self.baseurl,);
+        let request = self.client.get(url).build()?;
+        let result = self.client.execute(request).await;
+        let response = result?;
+        match response.status().as_u16() {
+            200u16 => ResponseValue::from_response(response).await,
+            401u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+
+    #[doc = "Get all network memberships\n\nSends a `GET` request to `/network`"]
+    pub async fn get_networks<'a>(
+        &'a self,
+    ) -> Result<ResponseValue<Vec<types::Network>>, Error<()>> {
+        let url = format!("{}/network", self.baseurl,);
+        let request = self.client.get(url).build()?;
+        let result = self.client.execute(request).await;
+        let response = result?;
+        match response.status().as_u16() {
+            200u16 => ResponseValue::from_response(response).await,
+            401u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+
+    #[doc = "Get a joined Network membership configuration by Network ID\n\nSends a `GET` request to `/network/{networkID}`\n\nArguments:\n- `network_id`: ID of the network to get.\n"]
+    pub async fn get_network<'a>(
+        &'a self,
+        network_id: &'a str,
+    ) -> Result<ResponseValue<types::Network>, Error<()>> {
+        let url = format!(
+            "{}/network/{}",
+            self.baseurl,
+            progenitor_client::encode_path(&network_id.to_string()),
+        );
+        let request = self.client.get(url).build()?;
+        let result = self.client.execute(request).await;
+        let response = result?;
+        match response.status().as_u16() {
+            200u16 => ResponseValue::from_response(response).await,
+            401u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+
+    #[doc = "Join a network or update it's configuration by Network ID\n\nSends a `POST` request to `/network/{networkID}`\n\nArguments:\n- `network_id`: ID of the network to change.\n- `body`: Network membership object\n"]
+    pub async fn update_network<'a>(
+        &'a self,
+        network_id: &'a str,
+        body: &'a types::Network,
+    ) -> Result<ResponseValue<types::Network>, Error<()>> {
+        let url = format!(
+            "{}/network/{}",
+            self.baseurl,
+            progenitor_client::encode_path(&network_id.to_string()),
+        );
+        let request = self.client.post(url).json(body).build()?;
+        let result = self.client.execute(request).await;
+        let response = result?;
+        match response.status().as_u16() {
+            200u16 => ResponseValue::from_response(response).await,
+            401u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+
+    #[doc = "Leave a network\n\nSends a `DELETE` request to `/network/{networkID}`\n\nArguments:\n- `network_id`: ID of the network\n"]
+    pub async fn delete_network<'a>(
+        &'a self,
+        network_id: &'a str,
+    ) -> Result<ResponseValue<()>, Error<()>> {
+        let url = format!(
+            "{}/network/{}",
+            self.baseurl,
+            progenitor_client::encode_path(&network_id.to_string()),
+        );
+        let request = self.client.delete(url).build()?;
+        let result = self.client.execute(request).await;
+        let response = result?;
+        match response.status().as_u16() {
+            200u16 => Ok(ResponseValue::empty(response)),
+            401u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+
+    #[doc = "Get all peers\n\nSends a `GET` request to `/peer`"]
+    pub async fn get_peers<'a>(&'a self) -> Result<ResponseValue<Vec<types::Peer>>, Error<()>> {
+        let url = format!("{}/peer", self.baseurl,);
+        let request = self.client.get(url).build()?;
+        let result = self.client.execute(request).await;
+        let response = result?;
+        match response.status().as_u16() {
+            200u16 => ResponseValue::from_response(response).await,
+            401u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+
+    #[doc = "Get information about a specific peer by Node ID\n\nSends a `GET` request to `/peer/{address}`\n\nArguments:\n- `address`: ZeroTier address of the peer to get\n"]
+    pub async fn get_peer<'a>(
+        &'a self,
+        address: &'a str,
+    ) -> Result<ResponseValue<types::Peer>, Error<()>> {
+        let url = format!(
+            "{}/peer/{}",
+            self.baseurl,
+            progenitor_client::encode_path(&address.to_string()),
+        );
+        let request = self.client.get(url).build()?;
+        let result = self.client.execute(request).await;
+        let response = result?;
+        match response.status().as_u16() {
+            200u16 => ResponseValue::from_response(response).await,
+            401u16 => Err(Error::ErrorResponse(ResponseValue::empty(response))),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+}
@@ -0,0 +1,30 @@
+// with the codegen feature, generates the API clients from the specs in api/ rather than using
+// the checked-in ones; see src/api.rs
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    #[cfg(feature = "codegen")]
+    for name in ["zerotier-one", "zerotier-central"] {
+        let spec = format!("api/{}.json", name);
+        println!("cargo:rerun-if-changed={}", spec);
+
+        let file = std::fs::File::open(&spec).unwrap();
+        let spec = serde_json::from_reader(file).unwrap();
+        let content = progenitor::Generator::default()
+            .generate_text(&spec)
+            .unwrap();
+
+        let out = std::path::PathBuf::from(std::env::var("OUT_DIR").unwrap());
+        // Central's client goes in a file of its own, left out without the central feature
+        let (types, client) = match name {
+            "zerotier-central" => {
+                content.split_at(content.find("#[derive(Clone)]\npub struct Client").unwrap())
+            }
+            _ => (content.as_str(), ""),
+        };
+        std::fs::write(out.join(format!("{}.rs", name)), types).unwrap();
+        if !client.is_empty() {
+            std::fs::write(out.join(format!("{}-client.rs", name)), client).unwrap();
+        }
+    }
+}
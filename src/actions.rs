@@ -0,0 +1,116 @@
+use std::{
+    io::Write,
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
+
+use crate::config::{data_path, write_atomic, MAX_DATA_FILE};
+
+// how many actions are kept around for the log dialog; the file has the rest
+const KEEP: usize = 200;
+
+static DRY_RUN: AtomicBool = AtomicBool::new(false);
+
+lazy_static::lazy_static! {
+    static ref LOG: Mutex<Vec<Action>> = Mutex::new(Vec::new());
+}
+
+pub fn set_dry_run(dry_run: bool) {
+    DRY_RUN.store(dry_run, Ordering::SeqCst)
+}
+
+pub fn dry_run() -> bool {
+    DRY_RUN.load(Ordering::SeqCst)
+}
+
+// a change ztui made, or under dry-run would have made, to central or the local node
+#[derive(Debug, Clone)]
+pub struct Action {
+    pub at: u64,
+    pub dry_run: bool,
+    pub target: &'static str,
+    pub method: &'static str,
+    pub endpoint: String,
+    pub payload: Option<serde_json::Value>,
+}
+
+impl std::fmt::Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}{} {} {}",
+            if self.dry_run { "(dry run) " } else { "" },
+            self.target,
+            self.method,
+            self.endpoint,
+        )?;
+
+        if let Some(payload) = &self.payload {
+            write!(f, " {}", payload)?;
+        }
+
+        Ok(())
+    }
+}
+
+// once the file grows past the limit the other data files have, its oldest lines go. it's cut to
+// three quarters of that, so it isn't rewritten for every change after.
+fn prune(path: &Path) -> Result<(), anyhow::Error> {
+    if std::fs::metadata(path)?.len() as usize <= MAX_DATA_FILE {
+        return Ok(());
+    }
+
+    let log = std::fs::read(path)?;
+    let cut = log.len() - MAX_DATA_FILE * 3 / 4;
+    // from the start of the next whole line
+    let start = log[cut..]
+        .iter()
+        .position(|&b| b == b'\n')
+        .map_or(log.len(), |n| cut + n + 1);
+    // swapped in whole, so a crash partway through leaves the log as it was
+    write_atomic(path, &String::from_utf8_lossy(&log[start..]))
+}
+
+// every mutating client call goes through here first. returns whether the call should actually
+// be made.
+pub fn record(
+    target: &'static str,
+    method: &'static str,
+    endpoint: String,
+    payload: Option<serde_json::Value>,
+) -> bool {
+    let action = Action {
+        at: crate::history::now(),
+        dry_run: dry_run(),
+        target,
+        method,
+        endpoint,
+        payload,
+    };
+
+    // the log is a convenience; failing to write it shouldn't stop anything
+    let path = data_path().join("actions.log");
+    if let Ok(mut f) = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+    {
+        let _ = writeln!(f, "{} {}", action.at, action);
+        let _ = prune(&path);
+    }
+
+    let mut log = LOG.lock().unwrap();
+    log.push(action);
+    if log.len() > KEEP {
+        log.remove(0);
+    }
+
+    !dry_run()
+}
+
+pub fn recent() -> Vec<Action> {
+    LOG.lock().unwrap().clone()
+}
@@ -0,0 +1,150 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    net::IpAddr,
+};
+
+#[cfg(feature = "central")]
+use crate::api::central::types::IpRange;
+use crate::api::central::types::Member;
+use crate::{api::one::types::Network, routes::parse_cidr};
+
+// addresses assigned to more than one member of a network, with the node ids of each. Central
+// doesn't stop two members being given the same one by hand, and only one of them will work.
+pub fn duplicates(members: &[Member]) -> BTreeMap<String, Vec<String>> {
+    let mut seen: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+    for member in members {
+        let node_id = member.node_id.clone().unwrap_or_default();
+        let addresses = member
+            .config
+            .as_ref()
+            .and_then(|c| c.ip_assignments.clone())
+            .unwrap_or_default();
+
+        for address in addresses {
+            seen.entry(address).or_default().push(node_id.clone());
+        }
+    }
+
+    seen.retain(|_, ids| ids.len() > 1);
+    seen
+}
+
+// a range addresses are handed out from, both ends included
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pool {
+    pub start: IpAddr,
+    pub end: IpAddr,
+}
+
+// pools past this share used are called out as nearly full
+pub const NEARLY_FULL: f64 = 0.9;
+
+fn number(ip: IpAddr) -> u128 {
+    match ip {
+        IpAddr::V4(ip) => u32::from(ip) as u128,
+        IpAddr::V6(ip) => u128::from(ip),
+    }
+}
+
+impl Pool {
+    #[cfg(feature = "central")]
+    pub fn parse(range: &IpRange) -> Option<Self> {
+        let start = range.ip_range_start.as_ref()?.parse().ok()?;
+        let end = range.ip_range_end.as_ref()?.parse().ok()?;
+        Some(Self { start, end })
+    }
+
+    fn size(&self) -> u128 {
+        (number(self.end).saturating_sub(number(self.start))).saturating_add(1)
+    }
+
+    fn contains(&self, ip: IpAddr) -> bool {
+        ip.is_ipv4() == self.start.is_ipv4()
+            && (number(self.start)..=number(self.end)).contains(&number(ip))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Utilization {
+    pub used: u128,
+    pub size: u128,
+}
+
+impl Utilization {
+    pub fn nearly_full(&self) -> bool {
+        self.size > 0 && self.used as f64 / self.size as f64 >= NEARLY_FULL
+    }
+}
+
+// how many of the addresses in a network's IPv4 pools are given to members. IPv6 pools are far
+// too big to run out, so they're left out.
+pub fn utilization(pools: &[Pool], members: &[Member]) -> Option<Utilization> {
+    let pools = pools
+        .iter()
+        .filter(|p| p.start.is_ipv4())
+        .collect::<Vec<&Pool>>();
+    if pools.is_empty() {
+        return None;
+    }
+
+    let used = members
+        .iter()
+        .filter_map(|m| m.config.as_ref()?.ip_assignments.clone())
+        .flatten()
+        .filter_map(|a| a.parse::<IpAddr>().ok())
+        .filter(|ip| pools.iter().any(|p| p.contains(*ip)))
+        .collect::<BTreeSet<IpAddr>>()
+        .len() as u128;
+
+    Some(Utilization {
+        used,
+        size: pools.iter().map(|p| p.size()).sum(),
+    })
+}
+
+// the first address of one family, without a prefix length, or nothing if there isn't one
+pub fn first(addresses: &[String], v6: bool) -> String {
+    addresses
+        .iter()
+        .map(|a| a.split('/').next().unwrap_or_default())
+        .find(|a| a.parse::<IpAddr>().is_ok_and(|ip| ip.is_ipv6() == v6))
+        .unwrap_or_default()
+        .to_string()
+}
+
+// the network's own route that covers `address`, or its first one without a gateway
+pub fn subnet(network: &Network, address: Option<&str>) -> String {
+    let address = address
+        .and_then(|a| a.split('/').next())
+        .and_then(|a| a.parse::<IpAddr>().ok());
+    let local = network
+        .subtype_1
+        .routes
+        .iter()
+        .filter(|r| r.via.is_none())
+        .filter_map(|r| r.target.clone())
+        .collect::<Vec<String>>();
+
+    local
+        .iter()
+        .find(|target| {
+            let (Some((net, prefix)), Some(ip)) = (parse_cidr(target), address) else {
+                return false;
+            };
+            parse_cidr(&format!("{}/{}", ip, prefix)) == Some((net, prefix))
+        })
+        .or(local.first())
+        .cloned()
+        .unwrap_or_default()
+}
+
+// the first gateway the network routes through
+pub fn gateway(network: &Network) -> String {
+    network
+        .subtype_1
+        .routes
+        .iter()
+        .find_map(|r| r.via.clone())
+        .unwrap_or_default()
+}
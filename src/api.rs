@@ -0,0 +1,37 @@
+// the clients for ZeroTier's local service and for Central. they're generated by progenitor from
+// the OpenAPI specs in api/, and the generated code is checked in beside them so building ztui
+// doesn't need a progenitor that agrees with the toolchain. building with `--features codegen`
+// generates them from the specs instead, for when the specs change; copy the results from
+// OUT_DIR over the checked-in ones once they build. only some of each API is used, and the
+// generated code isn't held to this crate's lints. Central's client is kept apart from its types,
+// which the cached members are kept in, so building without the central feature leaves it out.
+
+#[cfg(not(feature = "codegen"))]
+#[allow(dead_code, unused_imports, clippy::all)]
+pub mod one {
+    include!("../api/zerotier-one.rs");
+}
+
+#[cfg(not(feature = "codegen"))]
+#[allow(dead_code, unused_imports, clippy::all)]
+pub mod central {
+    include!("../api/zerotier-central.rs");
+
+    #[cfg(feature = "central")]
+    include!("../api/zerotier-central-client.rs");
+}
+
+#[cfg(feature = "codegen")]
+#[allow(dead_code, unused_imports, clippy::all)]
+pub mod one {
+    include!(concat!(env!("OUT_DIR"), "/zerotier-one.rs"));
+}
+
+#[cfg(feature = "codegen")]
+#[allow(dead_code, unused_imports, clippy::all)]
+pub mod central {
+    include!(concat!(env!("OUT_DIR"), "/zerotier-central.rs"));
+
+    #[cfg(feature = "central")]
+    include!(concat!(env!("OUT_DIR"), "/zerotier-central-client.rs"));
+}
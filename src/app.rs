@@ -1,48 +1,66 @@
+#[cfg(feature = "central")]
+use std::io::Write;
 use std::{
-    collections::HashMap,
-    io::{Read, Write},
+    collections::{HashMap, HashSet},
     process::Stdio,
     sync::{Arc, Mutex},
-    time::{Duration, Instant},
+    time::{Instant, SystemTime, UNIX_EPOCH},
 };
 
 use bat::{Input, PrettyPrinter};
 use crossterm::{
-    event::{self, Event, KeyCode, KeyEvent},
+    event::{Event, KeyCode, KeyEvent, KeyModifiers},
     execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    terminal::{EnterAlternateScreen, LeaveAlternateScreen},
 };
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "central")]
 use tempfile::NamedTempFile;
 use tokio::sync::mpsc;
 use tui::{
-    backend::{Backend, CrosstermBackend},
+    backend::Backend,
     layout::Rect,
     style::{Color, Modifier, Style},
     text::{Span, Spans},
     widgets::{Clear, Paragraph, TableState},
     Frame, Terminal,
 };
+use unicode_width::UnicodeWidthChar;
 
+use crate::api::central::types::Member;
+use crate::api::one::types::Network;
 use crate::{
-    client::{self, central_client},
-    config::Settings,
+    batch::{Batch, Outcome},
+    capture::Capture,
+    client,
+    config::{config_path, data_path, template_rename, Launch, Settings, Tmux},
+    display::MemberCache,
+    doctor::Check,
+    form::{self, Field, Form},
+    grouping::{Grouping, MemberRow},
+    help::{Help, Topic},
+    i18n::{t, tf},
+    identity::IdentityReport,
+    picker::{Item, Picker, Purpose},
+    ping::Pinger,
+    registry::{self, Action, Binding},
+    routes::RouteReport,
+    rules::RuleNames,
+    snippets::Snippet,
+    terminal::Console,
 };
+#[cfg(feature = "central")]
+use crate::{pending::Op, rules::RulesEdit};
 
 pub const STATUS_DISCONNECTED: &str = "DISCONNECTED";
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum EditingMode {
-    Command,
-    Editing,
-}
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ListFilter {
     None,
     Connected,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum NetworkFlag {
     AllowDNS,
     AllowManaged,
@@ -50,527 +68,2629 @@ pub enum NetworkFlag {
     AllowDefault,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl NetworkFlag {
+    pub const ALL: [NetworkFlag; 4] = [
+        NetworkFlag::AllowDNS,
+        NetworkFlag::AllowManaged,
+        NetworkFlag::AllowGlobal,
+        NetworkFlag::AllowDefault,
+    ];
+
+    // as the local service calls it
+    pub fn name(&self) -> &'static str {
+        match self {
+            NetworkFlag::AllowDNS => "allowDNS",
+            NetworkFlag::AllowManaged => "allowManaged",
+            NetworkFlag::AllowGlobal => "allowGlobal",
+            NetworkFlag::AllowDefault => "allowDefault",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|flag| flag.name() == name)
+    }
+
+    pub fn get(&self, network: &Network) -> bool {
+        match self {
+            NetworkFlag::AllowDNS => network.subtype_0.allow_dns,
+            NetworkFlag::AllowManaged => network.subtype_0.allow_managed,
+            NetworkFlag::AllowGlobal => network.subtype_0.allow_global,
+            NetworkFlag::AllowDefault => network.subtype_0.allow_default,
+        }
+        .unwrap_or_default()
+    }
+}
+
+pub fn on_off(on: bool) -> &'static str {
+    if on {
+        "on"
+    } else {
+        "off"
+    }
+}
+
+// what an open dialog has built up or has running. it's dropped with the dialog, and doesn't make
+// two dialogs open on the same thing any different.
+#[derive(Debug, Clone)]
+pub struct DialogState<T>(pub T);
+
+impl<T> PartialEq for DialogState<T> {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl<T> Eq for DialogState<T> {}
+
+impl<T> std::ops::Deref for DialogState<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> std::ops::DerefMut for DialogState<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Dialog {
     None,
-    Join,
-    Config,
-    Help,
-    APIKey(String),
+    Join(DialogState<Form>),
+    // settings json, shown in a pager
+    Config(String),
+    // and why the key last entered was refused
+    APIKey(String, Option<String>),
     RenameMember(String, String),
-    AddMember(String),
+    BatchRename(String),
+    Legend(String),
+    Changelog(String),
+    Snippets(String),
+    #[cfg(feature = "central")]
+    RulesDiff(String, DialogState<RulesEdit>),
+    Actions,
+    Pending,
+    RecentlyLeft,
+    MacroName,
+    RunMacro,
+    Menu,
+    AddMember(String, DialogState<Form>),
+    // the network's SSO settings on Central, and a form to change them
+    #[cfg(feature = "central")]
+    Sso(String, DialogState<client::Sso>),
+    #[cfg(feature = "central")]
+    SsoEdit(String, DialogState<Form>),
+    NewNetwork(DialogState<Form>),
+    Pick(Purpose, DialogState<Picker>),
+    Batch(DialogState<Batch>),
     NetworkFlags(String),
+    // asks before turning on allowDefault over ssh
+    FullTunnel(String),
+    // what setting a flag on every connected network would change, before it's changed
+    FlagAll(NetworkFlag, bool),
+    // the networks of a join set, by its name, and what applying it would do to each
+    JoinSet(String),
+    Ping(String, DialogState<Pinger>),
+    Capture(String, DialogState<Capture>),
+    Routes(String, DialogState<RouteReport>),
+    History(String, String),
+    Traffic(String),
+    Identity(DialogState<IdentityReport>),
+    IdentityBackup(DialogState<IdentityReport>),
+    // asked before quitting when confirm_quit is set in the config
+    ConfirmQuit,
+    // settings couldn't be written on the way out
+    SaveFailed(String),
+    // another ztui has the settings, with its PID; this one carries on without saving them
+    AlreadyRunning(Option<u32>),
+    // Central turned away the network's API key, and what it said
+    KeyRejected(String, String),
+    // asks for a node ID to find among the members of every network with a key
+    GoToMember,
+    Doctor(DialogState<Vec<Check>>),
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl Dialog {
+    // dialogs that are typed into. every key goes to the input until they close.
+    pub fn takes_input(&self) -> bool {
+        match self {
+            Dialog::Join(_)
+            | Dialog::APIKey(..)
+            | Dialog::RenameMember(_, _)
+            | Dialog::BatchRename(_)
+            | Dialog::MacroName
+            | Dialog::RunMacro
+            | Dialog::AddMember(..)
+            | Dialog::NewNetwork(_)
+            | Dialog::Pick(..)
+            | Dialog::IdentityBackup(_)
+            | Dialog::GoToMember => true,
+            #[cfg(feature = "central")]
+            Dialog::SsoEdit(..) => true,
+            _ => false,
+        }
+    }
+
+    // the action that opens the dialog, whose key also closes it
+    fn opened_by(&self) -> Option<Action> {
+        match self {
+            Dialog::Legend(_) => Some(Action::Legend),
+            Dialog::Changelog(_) => Some(Action::Changelog),
+            Dialog::Snippets(_) => Some(Action::EditRulesFromSnippet),
+            Dialog::Actions => Some(Action::Log),
+            Dialog::Pending => Some(Action::PendingChanges),
+            #[cfg(feature = "central")]
+            Dialog::Sso(..) => Some(Action::Sso),
+            Dialog::RecentlyLeft => Some(Action::RecentlyLeft),
+            Dialog::NetworkFlags(_) => Some(Action::Flags),
+            Dialog::Ping(..) => Some(Action::Ping),
+            Dialog::Capture(..) => Some(Action::Capture),
+            Dialog::Routes(..) => Some(Action::Routes),
+            Dialog::History(_, _) => Some(Action::History),
+            Dialog::Traffic(_) => Some(Action::Traffic),
+            Dialog::Identity(_) => Some(Action::Identity),
+            Dialog::Doctor(_) => Some(Action::Doctor),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub enum Page {
+    #[default]
     Networks,
     Network(String),
 }
 
-impl Default for Page {
-    fn default() -> Self {
-        Page::Networks
-    }
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Location {
+    page: Page,
+    dialog: Dialog,
+}
+
+// dialogs we can come back to. the rest hold running state (a ping, a capture, a pending edit)
+// that moving back or forward can't bring back, so they're remembered as the page underneath.
+fn restorable(dialog: &Dialog) -> bool {
+    matches!(
+        dialog,
+        Dialog::None
+            | Dialog::NetworkFlags(_)
+            | Dialog::History(_, _)
+            | Dialog::Traffic(_)
+            | Dialog::Legend(_)
+            | Dialog::Changelog(_)
+            | Dialog::Actions
+            | Dialog::Pending
+    )
 }
 
 #[derive(Debug, Clone)]
 pub struct App {
-    pub editing_mode: EditingMode,
     pub dialog: Dialog,
     pub inputbuffer: String,
-    pub last_usage: HashMap<String, Vec<(u128, u128, Instant)>>,
+    pub cursor: usize,
     pub member_count: usize,
+    pub member_rows: Vec<MemberRow>,
+    pub grouping: Grouping,
+    pub collapsed: HashSet<String>,
+    // node ids marked for batch operations
+    pub marked: HashSet<String>,
+    // outcome of the last batch rename, by node id
+    pub batch_errors: HashMap<String, String>,
+    // one-off message shown until the next key press
+    pub notice: Option<String>,
+    // set when the last key pressed wasn't bound to anything, so there's nothing to redraw
+    unbound: bool,
+    // drawn over everything else while it's open
+    pub help: Option<Help>,
+    // the keys of a chord pressed so far, or the prefix key. what can follow is shown until the
+    // chord is finished.
+    pub chord: Vec<String>,
+    // keys pressed since recording started, and a finished recording waiting for its name
+    pub recording: Option<Vec<String>>,
+    pub recorded: Vec<String>,
+    // a macro to replay once the dialog that asked for it is out of the way
+    queued_macro: Option<(String, usize)>,
+    pub menu_state: TableState,
+    // the key for whatever was picked from the action menu, run once the menu has closed
+    menu_key: Option<KeyEvent>,
+    pub snippets: Vec<Snippet>,
+    pub snippet_state: TableState,
+    pub pending_state: TableState,
+    pub left_state: TableState,
+    // a change to make to several members, started once the key handlers are done
+    #[cfg(feature = "central")]
+    queued_batch: Option<(String, Op, Vec<String>)>,
     pub member_state: TableState,
+    // the text of the members page's rows, and of the split view's
+    pub member_cache: MemberCache,
+    pub split_cache: MemberCache,
+    back: Vec<Location>,
+    forward: Vec<Location>,
+    // what the terminal's title was last set to
+    terminal_title: String,
+    // an image to draw over the screen this frame, and the one drawn last
+    pub image: Option<(Rect, crate::image::Image)>,
+    shown_image: Option<(Rect, crate::image::Image)>,
 }
 
 impl Default for App {
     fn default() -> Self {
         Self {
             dialog: Dialog::None,
-            editing_mode: EditingMode::Command,
             inputbuffer: String::new(),
-            last_usage: HashMap::new(),
+            cursor: 0,
             member_count: 0,
+            member_rows: Vec::new(),
+            grouping: Grouping::None,
+            collapsed: HashSet::new(),
+            marked: HashSet::new(),
+            batch_errors: HashMap::new(),
+            notice: None,
+            unbound: false,
+            help: None,
+            chord: Vec::new(),
+            recording: None,
+            recorded: Vec::new(),
+            queued_macro: None,
+            menu_state: TableState::default(),
+            menu_key: None,
+            snippets: Vec::new(),
+            snippet_state: TableState::default(),
+            pending_state: TableState::default(),
+            left_state: TableState::default(),
+            #[cfg(feature = "central")]
+            queued_batch: None,
             member_state: TableState::default(),
+            member_cache: MemberCache::default(),
+            split_cache: MemberCache::default(),
+            back: Vec::new(),
+            forward: Vec::new(),
+            terminal_title: String::new(),
+            image: None,
+            shown_image: None,
+        }
+    }
+}
+
+#[cfg(feature = "central")]
+fn queue_change(
+    settings: &mut Settings,
+    network: &str,
+    member: &str,
+    op: Op,
+) -> Result<(), anyhow::Error> {
+    settings
+        .pending
+        .push(network.to_string(), member.to_string(), op);
+    settings.pending.to_file(config_path())
+}
+
+// the local service's networks, read again now. the networks page has them refreshed every few
+// seconds; the members page doesn't, so its flags dialog would show them as they were.
+fn refresh_networks(settings: &mut Settings) -> Result<(), anyhow::Error> {
+    if settings.update_networks(crate::client::sync_get_networks()?)? {
+        settings.network_state = TableState::default();
+    }
+    Ok(())
+}
+
+#[cfg(feature = "central")]
+// a change Central refused because the key may only read the network marks the network read-only,
+// so its other changes are greyed out rather than tried one by one
+fn refused(settings: &mut Settings, network: &str, e: anyhow::Error) -> anyhow::Error {
+    if !client::is_forbidden(&e) {
+        return e;
+    }
+
+    settings.mark_read_only(network);
+    anyhow::anyhow!(tf("toast.read_only", &[&network]))
+}
+
+// the first network with a key that has a member whose node ID starts with `query`, that member's
+// ID, and the other networks it's in. member lists already loaded are searched before Central is
+// asked for the rest.
+fn find_member(
+    settings: &mut Settings,
+    query: &str,
+) -> Result<(String, String, Vec<String>), anyhow::Error> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() || query.len() > 10 || !query.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(anyhow::anyhow!(tf("toast.not_a_node_id", &[&query])));
+    }
+
+    let networks = settings.networks_with_keys();
+    let search = |settings: &Settings| {
+        let mut found = Vec::new();
+        for network in &networks {
+            for member in settings.members.get(network).into_iter().flatten() {
+                if let Some(node_id) = member.node_id.as_ref().filter(|n| n.starts_with(&query)) {
+                    found.push((network.clone(), node_id.clone()));
+                }
+            }
+        }
+        found
+    };
+
+    #[cfg(feature = "central")]
+    if search(settings).is_empty() {
+        for network in &networks {
+            if settings.members.contains_key(network) {
+                continue;
+            }
+            // a network that can't be listed just isn't searched
+            if let Ok(members) = settings
+                .central_client_for_id(network.clone())
+                .and_then(|client| client::sync_get_members(client, network.clone()))
+            {
+                settings.set_members(network.clone(), members);
+            }
+        }
+    }
+
+    let mut found = search(settings);
+
+    let mut nodes = found.iter().map(|(_, n)| n.clone()).collect::<Vec<_>>();
+    nodes.sort();
+    nodes.dedup();
+    match nodes.len() {
+        0 => Err(anyhow::anyhow!(tf("toast.no_such_member", &[&query]))),
+        1 => {
+            let (network, node_id) = found.remove(0);
+            let also = found.into_iter().map(|(n, _)| n).collect();
+            Ok((network, node_id, also))
+        }
+        _ => Err(anyhow::anyhow!(tf(
+            "toast.member_ambiguous",
+            &[&query, &nodes.join(", ")]
+        ))),
+    }
+}
+
+// every network the saved API keys can see on their controllers, for picking from
+#[cfg(feature = "central")]
+fn central_networks(settings: &Settings) -> Result<Vec<Item>, anyhow::Error> {
+    let mut seen = HashSet::new();
+    let mut items = Vec::new();
+
+    for id in settings.networks_with_keys() {
+        let key = settings
+            .api_key_for_id(id.clone())
+            .cloned()
+            .unwrap_or_default();
+        if !seen.insert((key, settings.central_url_for_id(id.clone()))) {
+            continue;
         }
+
+        let client = settings.central_client_for_id(id)?;
+        for network in client::sync_get_network_list(client)? {
+            let id = match network.id {
+                Some(id) if !items.iter().any(|i: &Item| i.value == id) => id,
+                _ => continue,
+            };
+            let name = network.config.and_then(|c| c.name).unwrap_or_default();
+            let joined = if settings.get(&id).is_some() {
+                "  (joined)"
+            } else {
+                ""
+            };
+
+            items.push(Item {
+                label: format!("{}  {}{}", id, name, joined),
+                value: id,
+            });
+        }
+    }
+
+    if items.is_empty() {
+        return Err(anyhow::anyhow!(
+            "no networks found; add an API key with s on a network first"
+        ));
     }
+
+    Ok(items)
 }
 
 impl App {
-    pub fn run<W: Write>(
+    pub fn run<B: Console>(
         &mut self,
-        terminal: &mut Terminal<CrosstermBackend<W>>,
+        terminal: &mut Terminal<B>,
         settings: Arc<Mutex<Settings>>,
     ) -> Result<(), anyhow::Error> {
         terminal.clear()?;
+        let events = crate::events::start()?;
+        let mut animated = false;
 
         loop {
-            if let Dialog::Config = self.dialog {
+            if let Dialog::Config(json) = &self.dialog {
+                crate::events::pause();
                 crate::temp_mute_terminal!(terminal, {
                     PrettyPrinter::new()
-                        .input(Input::from_bytes(self.inputbuffer.as_bytes()).name("settings.json"))
+                        .input(Input::from_bytes(json.as_bytes()).name("settings.json"))
                         .paging_mode(bat::PagingMode::Always)
                         .print()
                         .expect("could not print");
                 });
-                self.dialog = Dialog::None;
-                self.inputbuffer = String::new();
+                crate::events::resume();
+                self.go_back(&mut settings.lock().unwrap());
+                crate::display::mark_dirty();
+            }
+
+            if let Some(reason) = crate::watchdog::check() {
+                self.notice = Some(reason);
+                crate::display::mark_dirty();
+            }
+            self.ask_for_key(&mut settings.lock().unwrap());
+
+            let moving = self.animating(&settings.lock().unwrap());
+            // one more frame once it stops, so the last of it isn't left on screen
+            if crate::display::take_dirty() || moving || animated {
+                let s = settings.clone();
+                terminal.draw(|f| {
+                    self.draw(f, s).unwrap();
+                })?;
+                self.update_terminal(terminal, &mut settings.lock().unwrap())?;
+            }
+            if moving != animated {
+                crate::events::animate(moving);
+            }
+            animated = moving;
+
+            let input = match events.recv()? {
+                crate::events::Event::Input(input) => input,
+                crate::events::Event::Data | crate::events::Event::Tick => continue,
+                crate::events::Event::Hangup if !crate::terminal::gone() => {
+                    self.reload(&mut settings.lock().unwrap());
+                    continue;
+                }
+                // there's no one left to ask, or ztui was told to go: it saves and goes
+                crate::events::Event::Hangup | crate::events::Event::Terminate => {
+                    return self.stop(terminal, &settings);
+                }
+            };
+            if self.input(terminal, settings.clone(), input)? && self.quit(terminal, &settings)? {
+                return Ok(());
+            }
+        }
+    }
+
+    // whether something on screen changes without anything being said: a spinner, a batch's
+    // progress, or pings and captures coming in
+    fn animating(&self, settings: &Settings) -> bool {
+        matches!(&self.dialog, Dialog::Batch(batch) if !batch.finished())
+            || settings.is_loading()
+            || matches!(self.dialog, Dialog::Ping(..) | Dialog::Capture(..))
+    }
+
+    // true once it's time to go: settings are saved, or the user has chosen to go without saving
+    // them. otherwise asks first, or says why they couldn't be saved.
+    fn quit<B: Console>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        settings: &Arc<Mutex<Settings>>,
+    ) -> Result<bool, anyhow::Error> {
+        {
+            let mut lock = settings.lock().unwrap();
+            match self.dialog {
+                Dialog::SaveFailed(_) => {
+                    drop(lock);
+                    self.finish(terminal, settings)?;
+                    return Ok(true);
+                }
+                Dialog::ConfirmQuit => {}
+                _ if lock.user_config().confirm_quit() => {
+                    let page = lock.page.clone();
+                    self.navigate(&mut lock, page, Dialog::ConfirmQuit);
+                    return Ok(false);
+                }
+                _ => {}
+            }
+        }
+
+        self.finish(terminal, settings)?;
+        let saved = self.save(&mut settings.lock().unwrap());
+        if !saved {
+            crate::shutdown::abandon();
+        }
+        Ok(saved)
+    }
+
+    // quits without asking, for SIGTERM or a closed terminal. settings that can't be saved are
+    // left for main to try again and report.
+    fn stop<B: Console>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        settings: &Arc<Mutex<Settings>>,
+    ) -> Result<(), anyhow::Error> {
+        tracing::info!("stopping on a signal");
+        self.finish(terminal, settings)?;
+        let mut lock = settings.lock().unwrap();
+        lock.save_session();
+        lock.to_file(config_path())
+    }
+
+    // SIGHUP with the terminal still open reads config.json again
+    fn reload(&mut self, settings: &mut Settings) {
+        self.notice = Some(match settings.reload_user_config(config_path()) {
+            Ok(()) => t("toast.config_reloaded"),
+            Err(e) => tf("toast.config_reload_failed", &[&e]),
+        });
+        crate::display::mark_dirty();
+    }
+
+    // stops what's running in the background and waits a little for changes already on their
+    // way, so quitting doesn't cut one off halfway. what isn't sent yet stays queued for next time.
+    fn finish<B: Console>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        settings: &Arc<Mutex<Settings>>,
+    ) -> Result<(), anyhow::Error> {
+        crate::shutdown::begin();
+        settings.lock().unwrap().fetch_cancel.cancel();
+        self.stop_dialog();
+
+        let deadline = Instant::now() + crate::shutdown::WAIT;
+        loop {
+            let left = crate::shutdown::settle(deadline, crate::display::SPINNER_TICK);
+            if left == 0 {
+                return Ok(());
+            }
+            if Instant::now() >= deadline {
+                tracing::warn!(left, "quitting with changes still being sent");
+                return Ok(());
             }
 
-            let last_tick = Instant::now();
+            self.notice = Some(tf("toast.finishing", &[&crate::display::spinner(), &left]));
+            // the terminal may already be gone
             let s = settings.clone();
-            terminal.draw(|f| {
+            let _ = terminal.draw(|f| {
                 self.draw(f, s).unwrap();
-            })?;
+            });
+        }
+    }
+
+    fn save(&mut self, settings: &mut Settings) -> bool {
+        settings.save_session();
+        match settings.to_file(config_path()) {
+            Ok(()) => true,
+            Err(e) => {
+                if let Dialog::SaveFailed(_) = self.dialog {
+                    self.step(Dialog::SaveFailed(e.to_string()));
+                } else {
+                    self.navigate(
+                        settings,
+                        settings.page.clone(),
+                        Dialog::SaveFailed(e.to_string()),
+                    );
+                }
+                false
+            }
+        }
+    }
 
-            let timeout = Duration::new(1, 0)
-                .checked_sub(last_tick.elapsed())
-                .unwrap_or_else(|| Duration::from_secs(0));
-            if crossterm::event::poll(timeout)? {
-                if self.read_key(terminal, settings.clone())? {
-                    return Ok(());
+    // a key Central has started turning away is asked about once, when nothing's being typed or
+    // read; the members already loaded stay up behind the question
+    fn ask_for_key(&mut self, settings: &mut Settings) {
+        let Page::Network(id) = settings.page.clone() else {
+            return;
+        };
+        if self.dialog != Dialog::None || self.help.is_some() || !self.chord.is_empty() {
+            return;
+        }
+
+        if let Some(error) = settings.unasked_rejection(&id) {
+            let page = settings.page.clone();
+            self.navigate(settings, page, Dialog::KeyRejected(id, error));
+            crate::display::mark_dirty();
+        }
+    }
+
+    // makes a change to a member, or queues it if Central can't be reached. changes are also
+    // queued behind any already waiting, so they reach Central in the order they were made.
+    #[cfg(feature = "central")]
+    fn change_member(
+        &mut self,
+        settings: &mut Settings,
+        network: &str,
+        member: &str,
+        op: Op,
+    ) -> Result<(), anyhow::Error> {
+        if settings.pending.ready().is_empty() {
+            let res = settings
+                .central_client_for_id(network.to_string())
+                .and_then(|client| op.apply(client, network, member));
+
+            match res {
+                Err(e) if client::is_unreachable(&e) => {}
+                res => return res.map_err(|e| refused(settings, network, e)),
+            }
+        }
+
+        self.notice = Some(tf("toast.queued", &[&op, &member]));
+        queue_change(settings, network, member, op)
+    }
+
+    // makes the same change to several members in the background, showing how it's going
+    #[cfg(feature = "central")]
+    fn start_batch(
+        &mut self,
+        settings: Arc<Mutex<Settings>>,
+        network: String,
+        op: Op,
+        members: Vec<String>,
+    ) {
+        let title = format!("{} {} members of {}", op, members.len(), network);
+        let s = settings.clone();
+        let batch = Batch::start(title, members.clone(), move |i| {
+            let member = &members[i];
+            let (client, queued) = {
+                let lock = s.lock().unwrap();
+                (
+                    lock.central_client_for_id(network.clone()),
+                    !lock.pending.ready().is_empty(),
+                )
+            };
+
+            // the same rules as a single change: behind anything already queued, and queued
+            // itself if Central can't be reached
+            if !queued {
+                match client.and_then(|client| op.apply(client, &network, member)) {
+                    Ok(()) => return Ok(Outcome::Done),
+                    Err(e) if client::is_unreachable(&e) => {}
+                    Err(e) => return Err(refused(&mut s.lock().unwrap(), &network, e)),
                 }
             }
+
+            queue_change(&mut s.lock().unwrap(), &network, member, op.clone())?;
+            Ok(Outcome::Queued)
+        });
+
+        let mut lock = settings.lock().unwrap();
+        let page = lock.page.clone();
+        self.navigate(&mut lock, page, Dialog::Batch(DialogState(batch)));
+    }
+
+    // opens the member list of `network` with `node_id` selected, opening its group if it's collapsed
+    fn go_to_member(&mut self, settings: &mut Settings, network: String, node_id: &str) {
+        self.navigate(settings, Page::Network(network.clone()), Dialog::None);
+
+        let members = settings.members.get(&network).cloned().unwrap_or_default();
+        let pos = match members
+            .iter()
+            .position(|m| m.node_id.as_deref() == Some(node_id))
+        {
+            Some(pos) => pos,
+            None => return,
+        };
+
+        self.collapsed.remove(&self.grouping.label(&members[pos]));
+        let row = self
+            .grouping
+            .rows(&members, &self.collapsed)
+            .iter()
+            .position(|row| *row == MemberRow::Member(pos));
+        self.member_state.select(row);
+    }
+
+    // turns the name cell of the selected member into an editor
+    fn start_rename(&mut self, settings: &mut Settings, id: String) {
+        let member = match (settings.members.get(&id), self.selected_member()) {
+            (Some(members), Some(selected)) => members[selected].clone(),
+            _ => return,
+        };
+
+        self.navigate(
+            settings,
+            Page::Network(id),
+            Dialog::RenameMember(
+                member.network_id.clone().unwrap(),
+                member.node_id.clone().unwrap(),
+            ),
+        );
+        self.set_input(member.name.unwrap_or_default());
+    }
+
+    // the form of the open dialog, if it has one
+    pub fn form(&mut self) -> Option<&mut Form> {
+        match &mut self.dialog {
+            Dialog::Join(form) | Dialog::AddMember(_, form) | Dialog::NewNetwork(form) => {
+                Some(form)
+            }
+            #[cfg(feature = "central")]
+            Dialog::SsoEdit(_, form) => Some(form),
+            _ => None,
+        }
+    }
+
+    pub fn picker(&mut self) -> Option<&mut Picker> {
+        match &mut self.dialog {
+            Dialog::Pick(_, picker) => Some(picker),
+            _ => None,
+        }
+    }
+
+    // the form, if everything in it is acceptable. otherwise what's wrong is marked in it.
+    fn valid_form(&mut self) -> Option<Form> {
+        let form = self.form()?;
+        if form.validate() {
+            Some(form.clone())
+        } else {
+            None
+        }
+    }
+
+    // starts a text dialog off with `input`, the cursor at its end
+    fn set_input(&mut self, input: String) {
+        self.cursor = input.chars().count();
+        self.inputbuffer = input;
+    }
+
+    // byte offset of the editing cursor in the input buffer
+    fn cursor_offset(&self) -> usize {
+        self.inputbuffer
+            .char_indices()
+            .nth(self.cursor)
+            .map_or(self.inputbuffer.len(), |(i, _)| i)
+    }
+
+    // (node id, new name) for each marked member, in table order
+    pub fn batch_rename_preview(&self, members: &[Member]) -> Vec<(String, String)> {
+        let marked = members
+            .iter()
+            .filter(|m| {
+                m.node_id
+                    .as_ref()
+                    .is_some_and(|node_id| self.marked.contains(node_id))
+            })
+            .collect::<Vec<&Member>>();
+
+        marked
+            .iter()
+            .enumerate()
+            .map(|(i, m)| {
+                (
+                    m.node_id.clone().unwrap(),
+                    template_rename(&self.inputbuffer, m, i + 1, marked.len()),
+                )
+            })
+            .collect()
+    }
+
+    // the member under the cursor, if it isn't on a group header
+    pub fn selected_member(&self) -> Option<usize> {
+        match self.member_rows.get(self.member_state.selected()?) {
+            Some(MemberRow::Member(i)) => Some(*i),
+            _ => None,
+        }
+    }
+
+    fn here(&self, settings: &Settings) -> Location {
+        Location {
+            page: settings.page.clone(),
+            dialog: if restorable(&self.dialog) {
+                self.dialog.clone()
+            } else {
+                Dialog::None
+            },
+        }
+    }
+
+    // tears down whatever the current dialog has running and moves to `to`. the rest of what it
+    // had goes with it.
+    fn go(&mut self, settings: &mut Settings, to: Location) {
+        self.stop_dialog();
+        if let Dialog::Batch(batch) = &self.dialog {
+            batch.cancel();
+        }
+
+        if settings.page != to.page {
+            self.member_state.select(Some(0));
+            settings.fetch_cancel.cancel();
         }
+
+        settings.page = to.page;
+        self.step(to.dialog);
     }
 
-    fn set_dialog_api_key(&mut self, settings: Arc<Mutex<Settings>>, id: String) {
-        settings.lock().unwrap().page = Page::Networks;
-        self.dialog = Dialog::APIKey(id);
-        self.editing_mode = EditingMode::Editing;
+    // moves to another step of the same dialog, which keeps whatever the dialog has running.
+    // only the input is started over.
+    fn step(&mut self, dialog: Dialog) {
+        self.dialog = dialog;
         self.inputbuffer = String::new();
+        self.cursor = 0;
+    }
+
+    // opens a page or dialog, remembering where we came from.
+    fn navigate(&mut self, settings: &mut Settings, page: Page, dialog: Dialog) {
+        let here = self.here(settings);
+        if self.back.last() != Some(&here) {
+            self.back.push(here);
+        }
+        self.forward.clear();
+
+        self.go(settings, Location { page, dialog });
     }
 
-    fn show_toast<B: Backend>(&self, f: &mut Frame<'_, B>, color: Color, mut message: String) {
+    // returns false when there's nowhere left to go back to.
+    fn go_back(&mut self, settings: &mut Settings) -> bool {
+        let to = match self.back.pop() {
+            Some(to) => to,
+            None if self.dialog != Dialog::None => Location {
+                page: settings.page.clone(),
+                dialog: Dialog::None,
+            },
+            None if settings.page != Page::Networks => Location {
+                page: Page::Networks,
+                dialog: Dialog::None,
+            },
+            None => return false,
+        };
+
+        let here = self.here(settings);
+        if here != to {
+            self.forward.push(here);
+        }
+
+        self.go(settings, to);
+        true
+    }
+
+    fn go_forward(&mut self, settings: &mut Settings) {
+        if let Some(to) = self.forward.pop() {
+            let here = self.here(settings);
+            if self.back.last() != Some(&here) {
+                self.back.push(here);
+            }
+
+            self.go(settings, to);
+        }
+    }
+
+    fn stop_dialog(&mut self) {
+        match &mut self.dialog {
+            Dialog::Ping(_, pinger) => pinger.stop(),
+            Dialog::Capture(_, capture) => capture.stop(),
+            _ => {}
+        }
+    }
+
+    fn show_toast<B: Backend>(&self, f: &mut Frame<'_, B>, color: Color, message: String) {
         let size = f.size();
-        message.truncate(size.width as usize - 10);
+        // cut by what the characters take up on screen, not by their bytes
+        let room = size.width.saturating_sub(10) as usize;
+        let mut used = 0;
+        let message = crate::privacy::redact(&message)
+            .chars()
+            .take_while(|c| {
+                used += c.width().unwrap_or(0);
+                used <= room
+            })
+            .collect::<String>();
         let span = Spans::from(vec![Span::styled(
             format!("[ {} ]", message),
             Style::default().fg(color).add_modifier(Modifier::BOLD),
         )]);
 
+        let width = (span.width() as u16).min(size.width);
         let rect = Rect::new(
-            size.width - span.width() as u16 - 2,
-            size.height - 1,
-            span.width() as u16,
-            1,
+            size.width.saturating_sub(width + 2),
+            size.height.saturating_sub(1),
+            width,
+            1.min(size.height),
         );
         f.render_widget(Clear, rect);
         f.render_widget(Paragraph::new(span), rect);
     }
 
-    fn draw<B: Backend>(
+    // images are drawn after the frame, straight to the terminal, and only again when they
+    // change. one moving or going away takes a full redraw to be rid of it in terminals that draw
+    // them into the cells.
+    fn show_image<B: Console>(&mut self, terminal: &mut Terminal<B>) -> Result<(), anyhow::Error> {
+        let Some(graphics) = crate::terminal::graphics() else {
+            return Ok(());
+        };
+        if self.image == self.shown_image {
+            return Ok(());
+        }
+
+        let moved = match (&self.shown_image, &self.image) {
+            (Some((was, _)), Some((area, _))) => was != area,
+            (Some(_), None) => true,
+            _ => false,
+        };
+        if moved {
+            crate::terminal::clear_images(terminal.backend_mut(), graphics)?;
+            terminal.clear()?;
+            self.shown_image = None;
+            crate::display::mark_dirty();
+            if self.image.is_some() {
+                // the cleared screen has to be drawn again before the image goes over it
+                return Ok(());
+            }
+        }
+
+        if let Some((area, image)) = &self.image {
+            // kitty keeps every image it's sent until it's told otherwise
+            crate::terminal::clear_images(terminal.backend_mut(), graphics)?;
+            crate::terminal::show_image(terminal.backend_mut(), graphics, *area, image)?;
+            self.shown_image = self.image.clone();
+        }
+
+        Ok(())
+    }
+
+    // what the terminal is told outside the screen: its title, and any new alerts
+    fn update_terminal<B: Console>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        settings: &mut Settings,
+    ) -> Result<(), anyhow::Error> {
+        let title = match &settings.page {
+            Page::Networks => t("title.terminal_networks"),
+            Page::Network(id) => {
+                let name = settings
+                    .get(id)
+                    .and_then(|n| n.subtype_1.name.clone())
+                    .filter(|name| !name.is_empty());
+                // an ID in the title would get around privacy mode
+                match name {
+                    Some(name) => tf("title.terminal_members", &[&name]),
+                    None if crate::privacy::enabled() => t("title.terminal_members_unnamed"),
+                    None => tf("title.terminal_members", &[id]),
+                }
+            }
+        };
+
+        self.show_image(terminal)?;
+
+        if self.terminal_title != title {
+            crate::terminal::set_title(terminal.backend_mut(), &crate::privacy::redact(&title))?;
+            self.terminal_title = title;
+        }
+
+        for alert in settings.notifications.drain(..) {
+            crate::terminal::notify(
+                terminal.backend_mut(),
+                "ztui",
+                &crate::privacy::redact(&alert),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn draw<B: Backend>(
         &mut self,
         f: &mut Frame<'_, B>,
         settings: Arc<Mutex<Settings>>,
     ) -> Result<(), anyhow::Error> {
-        let lock = settings.lock().unwrap();
+        self.image = None;
+        let mut lock = settings.lock().unwrap();
         let page = lock.page.clone();
+        if let Some(notice) = lock.notice.take() {
+            self.notice = Some(notice);
+        }
         drop(lock);
 
         match page {
             Page::Networks => {
-                crate::display::display_networks(f, self, settings.clone())?;
+                crate::display::display_networks(f, self, &mut settings.lock().unwrap())?;
+
+                if let Some(notice) = self.notice.clone() {
+                    self.show_toast(f, Color::LightYellow, notice);
+                } else if let Some(alert) = settings.lock().unwrap().alert() {
+                    self.show_toast(f, Color::LightYellow, alert);
+                }
             }
             Page::Network(id) => {
                 let lock = settings.lock().unwrap();
-                let members = lock.members.clone();
-                let members = members.get(&id);
-                let err = lock.last_error.clone();
-                drop(lock);
+                let fetch = lock.fetch(&Page::Network(id.clone()));
+                let none = RuleNames::default();
+                let names = lock.rule_names.get(&id).unwrap_or(&none);
 
-                if let Some(err) = err {
-                    self.show_toast(f, Color::LightRed, err);
-                    self.set_dialog_api_key(settings.clone(), id);
-                }
+                if let Some(members) = lock.members.get(&id) {
+                    let view = crate::display::NetworkView {
+                        id: &id,
+                        revision: lock.members_revision,
+                        names,
+                        read_only: lock.is_read_only(&id),
+                        traffic: lock.member_rates(),
+                        utilization: lock.utilization(&id),
+                    };
+                    crate::display::display_network(f, self, members, fetch, view)?;
 
-                if let Some(members) = members {
-                    crate::display::display_network(f, self, members.to_vec())?;
+                    if let Some(notice) = self.notice.clone() {
+                        self.show_toast(f, Color::LightYellow, notice);
+                    }
+                } else if let Some(failed) = fetch.failed.filter(|_| !fetch.loading) {
+                    self.show_toast(
+                        f,
+                        Color::LightRed,
+                        tf("toast.members_failed", &[&id, &failed]),
+                    )
                 } else {
                     self.show_toast(
                         f,
                         Color::LightGreen,
-                        "Loading your results, please wait...".to_string(),
+                        format!(
+                            "{} {}",
+                            crate::display::spinner(),
+                            tf("toast.loading_members", &[&id])
+                        ),
                     )
                 }
             }
         }
 
-        crate::display::display_dialogs(f, self, settings);
+        crate::display::display_dialogs(f, self, &settings.lock().unwrap());
+        if !self.chord.is_empty() {
+            crate::display::which_key(f, &settings.lock().unwrap(), &self.chord.join(" "));
+        }
+        if let Some(help) = self.help.as_mut() {
+            crate::display::dialog_help(f, help, &settings.lock().unwrap());
+        }
+        f.render_widget(crate::theme::Filter, f.size());
         Ok(())
     }
 
-    pub fn read_key<W: Write>(
+    pub fn input<B: Console>(
         &mut self,
-        terminal: &mut Terminal<CrosstermBackend<W>>,
+        terminal: &mut Terminal<B>,
         settings: Arc<Mutex<Settings>>,
+        input: Event,
     ) -> Result<bool, anyhow::Error> {
-        if let Event::Key(key) = event::read()? {
-            match self.editing_mode {
-                EditingMode::Command => {
-                    if self.command_mode_key(terminal, settings, key)? {
-                        return Ok(true);
-                    }
-                }
-                EditingMode::Editing => self.edit_mode_key(terminal, settings, key),
+        let key = match input {
+            Event::Key(key) => key,
+            Event::Resize(..) => {
+                crate::display::mark_dirty();
+                return Ok(false);
             }
+            _ => return Ok(false),
+        };
+
+        // a key nothing is bound to changes nothing, unless there was a toast for it to clear or
+        // a macro to record it in
+        self.unbound = false;
+        let shown = self.notice.take().is_some() || self.recording.is_some();
+        let res = self.dispatch_key(terminal, settings, key);
+        if shown || !self.unbound || self.notice.is_some() {
+            crate::display::mark_dirty();
         }
-        Ok(false)
+        res
     }
 
-    fn command_mode_key<W: Write>(
+    fn dispatch_key<B: Console>(
         &mut self,
-        terminal: &mut Terminal<CrosstermBackend<W>>,
+        terminal: &mut Terminal<B>,
         settings: Arc<Mutex<Settings>>,
         key: KeyEvent,
     ) -> Result<bool, anyhow::Error> {
-        let mut lock = settings.lock().unwrap();
-        match &lock.page {
-            Page::Network(id) => match key.code {
-                KeyCode::Up => {
-                    if let Some(pos) = self.member_state.selected() {
-                        if pos > 0 {
-                            self.member_state.select(Some(pos - 1));
-                        }
-                    }
-                }
-                KeyCode::Down => {
-                    let pos = self.member_state.selected().unwrap_or_default() + 1;
-                    if pos < self.member_count {
-                        self.member_state.select(Some(pos))
-                    }
-                }
-                KeyCode::Esc => {
-                    self.dialog = Dialog::None;
-                    self.editing_mode = EditingMode::Command;
-                }
-                KeyCode::Char(c) => match c {
-                    'q' => {
-                        lock.page = Page::Networks;
-                        self.member_state.select(Some(0));
-                        self.dialog = Dialog::None;
-                        self.editing_mode = EditingMode::Command;
-                    }
-                    'h' => {
-                        self.dialog = match self.dialog {
-                            Dialog::Help => Dialog::None,
-                            _ => Dialog::Help,
-                        }
+        // quits from anywhere, the same way q does from the networks page, so settings are
+        // still saved
+        if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            return Ok(true);
+        }
+
+        if self.help_key(&settings.lock().unwrap(), key) {
+            return Ok(false);
+        }
+        if self.macro_key(&mut settings.lock().unwrap(), key) {
+            return Ok(false);
+        }
+        if let Some(keys) = self.recording.as_mut() {
+            keys.push(crate::keys::name(&key));
+        }
+
+        // a failed action is reported, not a reason to exit
+        match self.handle_key(terminal, settings.clone(), key) {
+            Ok(true) => return Ok(true),
+            Ok(false) => {}
+            Err(e) => {
+                tracing::debug!(error = %e, "key failed");
+                self.failed(&settings, &e);
+            }
+        }
+
+        if let Some(key) = self.menu_key.take() {
+            if let Some(keys) = self.recording.as_mut() {
+                keys.push(crate::keys::name(&key));
+            }
+            match self.handle_key(terminal, settings.clone(), key) {
+                Ok(quit) => return Ok(quit),
+                Err(e) => self.failed(&settings, &e),
+            }
+        }
+
+        #[cfg(feature = "central")]
+        if let Some((network, op, members)) = self.queued_batch.take() {
+            self.start_batch(settings.clone(), network, op, members);
+        }
+
+        if let Some((name, times)) = self.queued_macro.take() {
+            return self.replay(terminal, settings, name, times);
+        }
+        Ok(false)
+    }
+
+    // an action that failed is said in a toast, and kept in the log dialog
+    fn failed(&mut self, settings: &Arc<Mutex<Settings>>, e: &anyhow::Error) {
+        let mut lock = settings.lock().unwrap();
+        let network = match &lock.page {
+            Page::Network(id) => Some(id.clone()),
+            Page::Networks => lock.selected_network_id(),
+        };
+        lock.record_error(network.as_deref(), "action", e);
+        self.notice = Some(e.to_string());
+    }
+
+    fn handle_key<B: Console>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        settings: Arc<Mutex<Settings>>,
+        key: KeyEvent,
+    ) -> Result<bool, anyhow::Error> {
+        tracing::trace!(key = %crate::keys::name(&key), page = ?settings.lock().unwrap().page, dialog = ?self.dialog, "key");
+        if self.dialog.takes_input() {
+            self.edit_mode_key(terminal, settings, key);
+            Ok(false)
+        } else {
+            self.command_mode_key(terminal, settings, key)
+        }
+    }
+
+    // while the help is open every key goes to it. `?` opens it from anywhere that isn't being
+    // typed into, and F1 from anywhere at all.
+    fn help_key(&mut self, settings: &Settings, key: KeyEvent) -> bool {
+        if let Some(help) = self.help.as_mut() {
+            if !help.key(key) {
+                self.help = None;
+            }
+            return true;
+        }
+
+        let opens = match key.code {
+            KeyCode::F(1) => true,
+            KeyCode::Char('?') => !self.dialog.takes_input() && self.dialog != Dialog::None,
+            _ => false,
+        };
+        if opens {
+            self.help = Some(Help::new(Topic::of(&settings.page, &self.dialog)));
+        }
+
+        opens
+    }
+
+    // `m` starts recording keys from a page, and stops it again to name the macro. `@` asks
+    // which macro to run, and how many times.
+    fn macro_key(&mut self, settings: &mut Settings, key: KeyEvent) -> bool {
+        if self.dialog != Dialog::None || !key.modifiers.difference(KeyModifiers::SHIFT).is_empty()
+        {
+            return false;
+        }
+
+        let action = registry::lookup(&settings.page, &key).map(|b| b.action);
+
+        if action == Some(Action::RunMacro) && self.recording.is_none() {
+            self.navigate(settings, settings.page.clone(), Dialog::RunMacro);
+            return true;
+        }
+
+        if action != Some(Action::RecordMacro) {
+            return false;
+        }
+
+        match self.recording.take() {
+            None => self.recording = Some(Vec::new()),
+            Some(keys) if keys.is_empty() => {
+                self.notice = Some(t("toast.nothing_recorded"));
+            }
+            Some(keys) => {
+                self.recorded = keys;
+                self.navigate(settings, settings.page.clone(), Dialog::MacroName);
+            }
+        }
+
+        true
+    }
+
+    // feeds a recorded macro back through the key handlers, stopping at the first thing that
+    // fails so a bulk change doesn't carry on past a problem
+    fn replay<B: Console>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        settings: Arc<Mutex<Settings>>,
+        name: String,
+        times: usize,
+    ) -> Result<bool, anyhow::Error> {
+        let keys = match settings.lock().unwrap().macros.get(&name) {
+            Some(keys) => keys.clone(),
+            None => {
+                self.notice = Some(tf("toast.no_macro", &[&name]));
+                return Ok(false);
+            }
+        };
+
+        for run in 1..=times {
+            for key in &keys {
+                let res = crate::keys::parse(key)
+                    .and_then(|key| self.handle_key(terminal, settings.clone(), key));
+
+                match res {
+                    Ok(false) => {}
+                    Ok(true) => return Ok(true),
+                    Err(e) => {
+                        self.notice =
+                            Some(tf("toast.macro_stopped", &[&name, &run, &times, key, &e]));
+                        return Ok(false);
                     }
-                    'r' => {
-                        if let Some(members) = &lock.members.get(id) {
-                            if let Some(selected) = self.member_state.selected() {
-                                self.dialog = Dialog::RenameMember(
-                                    members[selected].network_id.clone().unwrap(),
-                                    members[selected].node_id.clone().unwrap(),
-                                );
-                                self.editing_mode = EditingMode::Editing;
-                                self.inputbuffer = members[selected].name.clone().unwrap();
-                            }
+                }
+            }
+        }
+
+        self.notice = Some(tf("toast.macro_ran", &[&name, &times]));
+        Ok(false)
+    }
+
+    fn command_mode_key<B: Console>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        settings: Arc<Mutex<Settings>>,
+        key: KeyEvent,
+    ) -> Result<bool, anyhow::Error> {
+        let mut lock = settings.lock().unwrap();
+        let page = lock.page.clone();
+        let binding = registry::lookup(&page, &key);
+
+        if binding.is_some_and(|b| b.action == Action::Forward) {
+            self.go_forward(&mut lock);
+            return Ok(false);
+        }
+
+        if self.dialog == Dialog::None {
+            let mut chord = std::mem::take(&mut self.chord);
+            let started = !chord.is_empty();
+            if started && key.code == KeyCode::Esc {
+                return Ok(false);
+            }
+
+            chord.push(crate::keys::name(&key));
+            let keys = chord.join(" ");
+            let prefix = lock.user_config().prefix_key();
+            if keys == prefix
+                || lock
+                    .user_config()
+                    .continues(&page, lock.page_network(), &keys)
+            {
+                self.chord = chord;
+                return Ok(false);
+            }
+
+            if started {
+                if let Some(launch) = self.command_for(&lock, &page, &keys) {
+                    App::launch(terminal, launch)?;
+                    return Ok(false);
+                }
+
+                // the key after the prefix alone is handled as usual; the prefix only shows what
+                // it could be
+                if chord.len() != 2 || chord[0] != prefix {
+                    self.notice = Some(tf("toast.no_chord", &[&keys]));
+                    return Ok(false);
+                }
+            }
+        }
+
+        match self.dialog.clone() {
+            Dialog::None => self.page_key(terminal, &mut lock, page, binding, key),
+            dialog => self.dialog_key(terminal, &mut lock, dialog, binding, key),
+        }
+    }
+
+    fn page_key<B: Console>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        lock: &mut Settings,
+        page: Page,
+        binding: Option<&Binding>,
+        key: KeyEvent,
+    ) -> Result<bool, anyhow::Error> {
+        if let Some(binding) = binding {
+            if let Some(id) = lock.read_only_target().filter(|_| binding.writes) {
+                self.notice = Some(tf("toast.read_only", &[&id]));
+                return Ok(false);
+            }
+
+            return match page {
+                Page::Network(id) => self.member_action(lock, id, binding.action),
+                Page::Networks => self.network_action(terminal, lock, binding.action),
+            };
+        }
+
+        // anything else may be a user command for the selected row
+        match self.command_for(lock, &page, &crate::keys::name(&key)) {
+            Some(launch) => App::launch(terminal, launch)?,
+            None => self.unbound = true,
+        }
+
+        Ok(false)
+    }
+
+    // the user command bound to `keys` for the selected row
+    fn command_for(&self, lock: &Settings, page: &Page, keys: &str) -> Option<Launch> {
+        match page {
+            Page::Network(id) => self
+                .selected_member()
+                .and_then(|pos| lock.members.get(id)?.get(pos))
+                .and_then(|m| lock.user_config().command_for_member(keys, m, lock.get(id))),
+            Page::Networks => lock
+                .get_network_by_pos(lock.network_state.selected().unwrap_or_default())
+                .and_then(|net| lock.user_config().command_for_network(keys, net)),
+        }
+    }
+
+    // keys for an open dialog. they never reach the page underneath; every dialog closes with
+    // Esc or q, or the key that opened it.
+    #[cfg_attr(not(feature = "central"), allow(unused_variables))]
+    fn dialog_key<B: Console>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        lock: &mut Settings,
+        dialog: Dialog,
+        binding: Option<&Binding>,
+        key: KeyEvent,
+    ) -> Result<bool, anyhow::Error> {
+        // a dialog's own keys are plain ones; with Control or Alt held only a binding reaches it
+        let plain = key.modifiers.difference(KeyModifiers::SHIFT).is_empty();
+        let closes = (plain && matches!(key.code, KeyCode::Esc | KeyCode::Char('q')))
+            || binding.is_some_and(|b| Some(b.action) == dialog.opened_by());
+        if !plain && !closes {
+            return Ok(false);
+        }
+
+        match (dialog, key.code) {
+            (Dialog::Menu, code) if !closes => self.menu_dialog_key(lock, code),
+            (Dialog::Pending, code) if !closes => self.pending_key(lock, code)?,
+            (Dialog::RecentlyLeft, code) if !closes => self.left_key(lock, code)?,
+            (Dialog::NetworkFlags(id), KeyCode::Char(c @ ('n' | 'd' | 'g' | 'm'))) => {
+                let flag = match c {
+                    'n' => NetworkFlag::AllowDNS,
+                    'd' => NetworkFlag::AllowDefault,
+                    'g' => NetworkFlag::AllowGlobal,
+                    _ => NetworkFlag::AllowManaged,
+                };
+                crate::client::toggle_flag(id, flag)?;
+                refresh_networks(lock)?;
+            }
+            (Dialog::FullTunnel(id), KeyCode::Char('y')) => {
+                self.go_back(lock);
+                crate::client::toggle_flag(id.clone(), NetworkFlag::AllowDefault)?;
+                self.notice = Some(tf("toast.full_tunnel_on", &[&id]));
+            }
+            (Dialog::FullTunnel(_), KeyCode::Char('n')) => {
+                self.go_back(lock);
+            }
+            (Dialog::FlagAll(flag, on), KeyCode::Char('y') | KeyCode::Enter) => {
+                self.flag_all(lock, flag, on);
+            }
+            (Dialog::JoinSet(name), KeyCode::Char('y') | KeyCode::Enter) => {
+                self.apply_set(lock, name);
+            }
+            // q again, or y, quits; so does Ctrl-C
+            (Dialog::ConfirmQuit, KeyCode::Char('q' | 'y') | KeyCode::Enter) => return Ok(true),
+            (Dialog::ConfirmQuit, KeyCode::Char('n')) => {
+                self.go_back(lock);
+            }
+            (Dialog::SaveFailed(_), KeyCode::Char('r')) => return Ok(self.save(lock)),
+            (Dialog::SaveFailed(_), KeyCode::Char('q')) => return Ok(true),
+            (Dialog::AlreadyRunning(_), KeyCode::Char('q')) => return Ok(true),
+            (Dialog::AlreadyRunning(_), KeyCode::Enter) => {
+                self.go_back(lock);
+            }
+            (Dialog::KeyRejected(id, _), KeyCode::Enter) => self.step(Dialog::APIKey(id, None)),
+            #[cfg(feature = "central")]
+            (Dialog::Sso(id, sso), KeyCode::Char('e')) => {
+                self.step(Dialog::SsoEdit(
+                    id,
+                    DialogState(Form::new(vec![
+                        Field::yes_no("Enabled", sso.enabled),
+                        Field::text("Client ID", &sso.client_id),
+                        Field::text("Issuer", &sso.issuer),
+                    ])),
+                ));
+            }
+            (Dialog::Routes(id, _), KeyCode::Char('R')) => {
+                if let Some(network) = lock.get(&id) {
+                    self.step(Dialog::Routes(
+                        id,
+                        DialogState(crate::routes::verify(network)),
+                    ));
+                }
+            }
+            (Dialog::Identity(_), KeyCode::Char('r')) => {
+                self.step(Dialog::Identity(DialogState(crate::identity::verify())));
+            }
+            (Dialog::Identity(report), KeyCode::Char('b')) => {
+                self.step(Dialog::IdentityBackup(report));
+                self.set_input(
+                    config_path()
+                        .join("identity-backup")
+                        .to_string_lossy()
+                        .to_string(),
+                );
+            }
+            (Dialog::Doctor(_), KeyCode::Char('r')) => {
+                self.step(Dialog::Doctor(DialogState(crate::doctor::run(
+                    lock.any_api_key(),
+                ))));
+            }
+            (Dialog::Snippets(_), KeyCode::Up) => {
+                let pos = self.snippet_state.selected().unwrap_or_default();
+                self.snippet_state.select(Some(pos.saturating_sub(1)));
+            }
+            (Dialog::Snippets(_), KeyCode::Down) => {
+                let pos = self.snippet_state.selected().unwrap_or_default() + 1;
+                if pos < self.snippets.len() {
+                    self.snippet_state.select(Some(pos));
+                }
+            }
+            #[cfg(feature = "central")]
+            (Dialog::Snippets(id), KeyCode::Enter) => {
+                let snippet = self
+                    .snippet_state
+                    .selected()
+                    .and_then(|pos| self.snippets.get(pos))
+                    .cloned();
+
+                if let Some(snippet) = snippet {
+                    self.go_back(lock);
+                    self.start_rules_edit(terminal, lock, id, Some(&snippet))?;
+                }
+            }
+            #[cfg(feature = "central")]
+            (Dialog::RulesDiff(..), KeyCode::Up) => {
+                if let Dialog::RulesDiff(_, edit) = &mut self.dialog {
+                    edit.scroll = edit.scroll.saturating_sub(1);
+                }
+            }
+            #[cfg(feature = "central")]
+            (Dialog::RulesDiff(..), KeyCode::Down) => {
+                if let Dialog::RulesDiff(_, edit) = &mut self.dialog {
+                    edit.scroll += 1;
+                }
+            }
+            #[cfg(feature = "central")]
+            (Dialog::RulesDiff(id, edit), KeyCode::Enter | KeyCode::Char('y')) => {
+                let res = lock
+                    .central_client_for_id(id.clone())
+                    .and_then(|client| {
+                        crate::client::sync_apply_network_rules(
+                            client,
+                            id.clone(),
+                            edit.new.clone(),
+                        )
+                    })
+                    .map_err(|e| refused(lock, &id, e));
+
+                match res {
+                    Ok(_) => {
+                        self.go_back(lock);
+                    }
+                    Err(e) => {
+                        if let Dialog::RulesDiff(_, edit) = &mut self.dialog {
+                            edit.error = Some(e.to_string());
                         }
                     }
-                    'A' => {
-                        self.dialog = Dialog::AddMember(id.to_string());
-                        self.editing_mode = EditingMode::Editing;
-                        self.inputbuffer = String::new();
+                }
+            }
+            #[cfg(feature = "central")]
+            (Dialog::RulesDiff(id, edit), KeyCode::Char('e')) => {
+                let edit = edit.0;
+                self.edit_rules(terminal, lock, id, edit.old, edit.new)?;
+            }
+            #[cfg(feature = "central")]
+            (Dialog::RulesDiff(..), KeyCode::Char('n')) => {
+                self.go_back(lock);
+            }
+            (Dialog::Capture(id, mut capture), KeyCode::Char('w')) => {
+                if let Some(network) = lock.get(&id) {
+                    let written = lock.capture_interface(network).and_then(|interface| {
+                        let path = data_path().join("captures").join(format!(
+                            "{}-{}.pcap",
+                            interface,
+                            SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs()
+                        ));
+                        let command = lock
+                            .user_config()
+                            .capture_write_command_for_network(network, &interface, &path);
+
+                        capture.toggle_write(command, path)
+                    });
+
+                    if let Err(e) = written {
+                        capture.note(tf("dialog.capture.write_failed", &[&e]));
+                        lock.record_error(Some(&id), "capture", &e);
                     }
-                    'a' => {
-                        if let Some(members) = &lock.members.get(id) {
-                            if let Some(selected) = self.member_state.selected() {
-                                let node_id = members[selected].node_id.clone().unwrap();
-                                let client = central_client(
-                                    lock.api_key_for_id(id.to_string()).unwrap().to_string(),
-                                )?;
-                                crate::client::sync_authorize_member(
-                                    client,
-                                    id.to_string(),
-                                    node_id,
-                                )?;
-                            }
-                        }
+                    // the writer it started or stopped is kept with the dialog
+                    self.dialog = Dialog::Capture(id, capture);
+                }
+            }
+            (Dialog::Batch(batch), _) if closes && !batch.finished() => {
+                // the first Esc stops what's left; the next closes the dialog
+                batch.cancel();
+            }
+            _ if closes => {
+                self.go_back(lock);
+            }
+            _ => {}
+        }
+
+        Ok(false)
+    }
+
+    fn start_join(&mut self, lock: &mut Settings, id: &str) {
+        let form = Form::new(vec![
+            Field::text("Network ID", id).validate(form::network_id),
+            Field::yes_no("Allow managed", true),
+            Field::yes_no("Allow global", false),
+            Field::yes_no("Allow default", false),
+            Field::yes_no("Allow DNS", false),
+        ]);
+        self.navigate(lock, Page::Networks, Dialog::Join(DialogState(form)));
+    }
+
+    // allowDefault sends everything through the network, so it's only turned on for a network
+    // that pushes a default route, and over ssh only once it's been confirmed
+    fn full_tunnel(&mut self, lock: &mut Settings, network: Network) -> Result<(), anyhow::Error> {
+        let id = network.subtype_1.id.clone().unwrap_or_default();
+
+        if network.subtype_0.allow_default.unwrap_or_default() {
+            crate::client::toggle_flag(id.clone(), NetworkFlag::AllowDefault)?;
+            self.notice = Some(tf("toast.full_tunnel_off", &[&id]));
+        } else if crate::routes::default_routes(&network).is_empty() {
+            self.notice = Some(tf("toast.no_default_route", &[&id]));
+        } else if crate::terminal::remote_session() {
+            self.navigate(lock, Page::Networks, Dialog::FullTunnel(id));
+        } else {
+            crate::client::toggle_flag(id.clone(), NetworkFlag::AllowDefault)?;
+            self.notice = Some(tf("toast.full_tunnel_on", &[&id]));
+        }
+
+        Ok(())
+    }
+
+    // sets `flag` on the connected networks that don't have it set that way yet, in the background
+    fn flag_all(&mut self, settings: &mut Settings, flag: NetworkFlag, on: bool) {
+        let ids = settings
+            .connected()
+            .filter(|network| flag.get(network) != on)
+            .filter_map(|network| network.subtype_1.id.clone())
+            .collect::<Vec<String>>();
+        if ids.is_empty() {
+            self.go_back(settings);
+            self.notice = Some(tf("toast.flag_all_nothing", &[&flag.name(), &on_off(on)]));
+            return;
+        }
+
+        let title = format!("{} {} on {} networks", flag.name(), on_off(on), ids.len());
+        let batch = Batch::start(title, ids.clone(), move |i| {
+            client::set_flag(ids[i].clone(), flag, on)?;
+            Ok(Outcome::Done)
+        });
+
+        self.step(Dialog::Batch(DialogState(batch)));
+    }
+
+    // joins the networks of the join set, and sets the flags on those already joined, in the
+    // background
+    fn apply_set(&mut self, settings: &mut Settings, name: String) {
+        let set = match settings.user_config().join_sets().get(&name) {
+            Some(set) => set.clone(),
+            None => return,
+        };
+        let ids = match set.ids() {
+            Ok(ids) => ids,
+            Err(e) => {
+                self.notice = Some(e.to_string());
+                return;
+            }
+        };
+
+        let title = format!("join set {}", name);
+        let batch = Batch::start(title, ids.clone(), move |i| {
+            crate::join_set::apply(ids[i].clone(), &set)?;
+            Ok(Outcome::Done)
+        });
+
+        self.step(Dialog::Batch(DialogState(batch)));
+    }
+
+    fn menu_dialog_key(&mut self, lock: &mut Settings, code: KeyCode) {
+        let actions = registry::for_page(&lock.page)
+            .filter(|b| b.row)
+            .collect::<Vec<_>>();
+
+        match code {
+            KeyCode::Up => {
+                let pos = self.menu_state.selected().unwrap_or_default();
+                self.menu_state.select(Some(pos.saturating_sub(1)));
+            }
+            KeyCode::Down => {
+                let pos = self.menu_state.selected().unwrap_or_default() + 1;
+                if pos < actions.len() {
+                    self.menu_state.select(Some(pos));
+                }
+            }
+            KeyCode::Enter => {
+                let picked = self
+                    .menu_state
+                    .selected()
+                    .and_then(|pos| actions.get(pos))
+                    .and_then(|b| crate::keys::parse(b.keys[0]).ok());
+                self.go_back(lock);
+                self.menu_key = picked;
+            }
+            _ => {}
+        }
+    }
+
+    fn pending_key(&mut self, lock: &mut Settings, code: KeyCode) -> Result<(), anyhow::Error> {
+        let selected = self
+            .pending_state
+            .selected()
+            .and_then(|pos| lock.pending.changes().get(pos))
+            .map(|change| change.id);
+
+        match code {
+            KeyCode::Up => {
+                let pos = self.pending_state.selected().unwrap_or_default();
+                self.pending_state.select(Some(pos.saturating_sub(1)));
+            }
+            KeyCode::Down => {
+                let pos = self.pending_state.selected().unwrap_or_default() + 1;
+                if pos < lock.pending.changes().len() {
+                    self.pending_state.select(Some(pos));
+                }
+            }
+            KeyCode::Char('r') => {
+                if let Some(id) = selected {
+                    lock.pending.retry(id);
+                }
+            }
+            KeyCode::Char('d') => {
+                if let Some(id) = selected {
+                    lock.pending.discard(id);
+                }
+            }
+            KeyCode::Char('D') => lock.pending.clear(),
+            _ => {}
+        }
+
+        let count = lock.pending.changes().len();
+        if self.pending_state.selected().unwrap_or_default() >= count {
+            self.pending_state.select(Some(count.saturating_sub(1)));
+        }
+        lock.pending.to_file(config_path())
+    }
+
+    // Enter rejoins the selected network, or 1 to 9 the one listed there
+    fn left_key(&mut self, lock: &mut Settings, code: KeyCode) -> Result<(), anyhow::Error> {
+        let pos = match code {
+            KeyCode::Up => {
+                let pos = self.left_state.selected().unwrap_or_default();
+                self.left_state.select(Some(pos.saturating_sub(1)));
+                return Ok(());
+            }
+            KeyCode::Down => {
+                let pos = self.left_state.selected().unwrap_or_default() + 1;
+                if pos < lock.recently_left().len() {
+                    self.left_state.select(Some(pos));
+                }
+                return Ok(());
+            }
+            KeyCode::Enter => self.left_state.selected().unwrap_or_default(),
+            KeyCode::Char(c @ '1'..='9') => c as usize - '1' as usize,
+            _ => return Ok(()),
+        };
+
+        if let Some(left) = lock.recently_left().get(pos) {
+            let id = left.id.clone();
+            lock.rejoin(pos)?;
+            self.go_back(lock);
+            self.notice = Some(tf("toast.rejoined", &[&id]));
+        }
+
+        Ok(())
+    }
+
+    fn member_action(
+        &mut self,
+        lock: &mut Settings,
+        id: String,
+        action: Action,
+    ) -> Result<bool, anyhow::Error> {
+        let selected = self
+            .selected_member()
+            .and_then(|pos| lock.members.get(&id)?.get(pos).cloned());
+
+        match action {
+            Action::Up => {
+                if let Some(pos) = self.member_state.selected() {
+                    if pos > 0 {
+                        self.member_state.select(Some(pos - 1));
                     }
-                    'd' => {
-                        if let Some(members) = &lock.members.get(id) {
-                            if let Some(selected) = self.member_state.selected() {
-                                let node_id = members[selected].node_id.clone().unwrap();
-                                let client = central_client(
-                                    lock.api_key_for_id(id.to_string()).unwrap().to_string(),
-                                )?;
-                                crate::client::sync_deauthorize_member(
-                                    client,
-                                    id.to_string(),
-                                    node_id,
-                                )?;
-                            }
-                        }
+                }
+            }
+            Action::Down => {
+                let pos = self.member_state.selected().unwrap_or_default() + 1;
+                if pos < self.member_count {
+                    self.member_state.select(Some(pos))
+                }
+            }
+            Action::Back => {
+                self.go_back(lock);
+            }
+            Action::Activate | Action::Mark => {
+                match self
+                    .member_state
+                    .selected()
+                    .and_then(|pos| self.member_rows.get(pos))
+                {
+                    Some(MemberRow::Header { label, .. }) if !self.collapsed.remove(label) => {
+                        self.collapsed.insert(label.clone());
                     }
-                    'D' => {
-                        if let Some(members) = &lock.members.get(id) {
-                            if let Some(selected) = self.member_state.selected() {
-                                let node_id = members[selected].node_id.clone().unwrap();
-                                let client = central_client(
-                                    lock.api_key_for_id(id.to_string()).unwrap().to_string(),
-                                )?;
-                                crate::client::sync_delete_member(client, id.to_string(), node_id)?;
+                    Some(MemberRow::Member(_)) if action == Action::Mark => {
+                        if let Some(node_id) = selected.and_then(|m| m.node_id) {
+                            if !self.marked.remove(&node_id) {
+                                self.marked.insert(node_id);
                             }
                         }
                     }
-                    x => {
-                        if let Some(members) = &lock.members.get(id) {
-                            {
-                                if let Some(member) = members
-                                    .iter()
-                                    .nth(lock.network_state.selected().unwrap_or_default())
-                                {
-                                    if let Some(s) =
-                                        lock.user_config().command_for_member(x, member)
-                                    {
-                                        App::run_command(terminal, true, s)?;
-                                    }
-                                }
+                    Some(MemberRow::Member(_)) => {
+                        self.menu_state.select(Some(0));
+                        self.navigate(lock, Page::Network(id), Dialog::Menu);
+                    }
+                    _ => {}
+                }
+            }
+            Action::BatchRename if !self.marked.is_empty() => {
+                self.batch_errors.clear();
+                self.navigate(lock, Page::Network(id.clone()), Dialog::BatchRename(id));
+                self.set_input("%N".to_string());
+            }
+            #[cfg(feature = "central")]
+            Action::Sso => {
+                let sso = lock
+                    .central_client_for_id(id.clone())
+                    .and_then(|client| client::sync_get_sso(client, id.clone()))?;
+
+                self.navigate(
+                    lock,
+                    Page::Network(id.clone()),
+                    Dialog::Sso(id, DialogState(sso)),
+                );
+            }
+            #[cfg(feature = "central")]
+            Action::SsoExempt => {
+                if let Some(member) = selected {
+                    let node_id = member.node_id.clone().unwrap_or_default();
+                    let exempt = lock
+                        .central_client_for_id(id.clone())
+                        .and_then(|client| {
+                            client::sync_toggle_sso_exempt(client, id.clone(), node_id.clone())
+                        })
+                        .map_err(|e| refused(lock, &id, e))?;
+
+                    self.notice = Some(if exempt {
+                        tf("toast.sso_exempt", &[&node_id])
+                    } else {
+                        tf("toast.sso_not_exempt", &[&node_id])
+                    });
+                }
+            }
+            Action::SuggestName => {
+                if let Some(member) = selected {
+                    if !member.name.clone().unwrap_or_default().is_empty() {
+                        self.notice = Some(t("toast.already_named"));
+                    } else {
+                        match crate::names::suggest(&member) {
+                            Ok(name) => {
+                                self.start_rename(lock, id);
+                                self.set_input(name);
                             }
+                            Err(e) => self.notice = Some(e.to_string()),
                         }
                     }
-                },
-                _ => {}
+                }
+            }
+            Action::Group => {
+                let members = lock.members.get(&id).cloned().unwrap_or_default();
+                self.grouping = self.grouping.next(&members);
+                self.collapsed.clear();
+                self.member_state.select(Some(0));
+            }
+            Action::Help => {
+                self.help = Some(Help::new(Topic::of(&lock.page, &self.dialog)));
+            }
+            Action::Rename => self.start_rename(lock, id),
+            Action::GoToMember => self.navigate(lock, Page::Network(id), Dialog::GoToMember),
+            Action::Privacy => {
+                crate::privacy::toggle();
+            }
+            Action::BugReport => {
+                let path = crate::bugreport::write(Some(lock), None)?;
+                self.notice = Some(tf("toast.bug_report", &[&path.display()]));
+            }
+            Action::Changelog => {
+                self.navigate(lock, Page::Network(id.clone()), Dialog::Changelog(id));
+            }
+            Action::Legend => {
+                self.navigate(lock, Page::Network(id.clone()), Dialog::Legend(id));
+            }
+            Action::Ping => {
+                if let Some(member) = selected {
+                    let pinger = Pinger::start(
+                        member
+                            .config
+                            .clone()
+                            .unwrap()
+                            .ip_assignments
+                            .unwrap_or_default(),
+                    );
+                    self.navigate(
+                        lock,
+                        Page::Network(id),
+                        Dialog::Ping(member.node_id.clone().unwrap(), DialogState(pinger)),
+                    );
+                }
+            }
+            Action::History => {
+                if let Some(member) = selected {
+                    self.navigate(
+                        lock,
+                        Page::Network(id.clone()),
+                        Dialog::History(id, member.node_id.clone().unwrap()),
+                    );
+                }
+            }
+            Action::AuthorizeById => {
+                let form = Form::new(vec![
+                    Field::text("Node ID", "").validate(form::node_id),
+                    Field::text("Name", ""),
+                    Field::yes_no("Authorize", true),
+                ]);
+                self.navigate(
+                    lock,
+                    Page::Network(id.clone()),
+                    Dialog::AddMember(id, DialogState(form)),
+                );
+            }
+            #[cfg(feature = "central")]
+            Action::Authorize | Action::Deauthorize | Action::Delete => {
+                let op = match action {
+                    Action::Authorize => Op::Authorize,
+                    Action::Deauthorize => Op::Deauthorize,
+                    _ => Op::Delete,
+                };
+
+                // marked members all at once, in table order, or else the one under the cursor
+                if !self.marked.is_empty() {
+                    let members = lock
+                        .members
+                        .get(&id)
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|m| m.node_id.clone())
+                        .filter(|node_id| self.marked.contains(node_id))
+                        .collect();
+                    self.marked.clear();
+                    self.queued_batch = Some((id, op, members));
+                } else if let Some(node_id) = selected.and_then(|m| m.node_id) {
+                    self.change_member(lock, &id, &node_id, op)?;
+                }
+            }
+            // the network's own, as on the networks page
+            Action::Flags | Action::ShowConfig => {
+                refresh_networks(lock)?;
+                let Some(network) = lock.get(&id).cloned() else {
+                    self.notice = Some(tf("toast.not_joined", &[&id]));
+                    return Ok(false);
+                };
+                let dialog = match action {
+                    Action::Flags => Dialog::NetworkFlags(id.clone()),
+                    _ => Dialog::Config(serde_json::to_string_pretty(&network)?),
+                };
+                self.navigate(lock, Page::Network(id), dialog);
+            }
+            _ => {}
+        }
+
+        Ok(false)
+    }
+
+    #[cfg_attr(not(feature = "central"), allow(unused_variables))]
+    fn network_action<B: Console>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        lock: &mut Settings,
+        action: Action,
+    ) -> Result<bool, anyhow::Error> {
+        let pos = lock.network_state.selected().unwrap_or_default();
+        let selected = lock.get_network_by_pos(pos).cloned();
+
+        match action {
+            Action::Activate if lock.selected_network_id().is_some() => {
+                self.menu_state.select(Some(0));
+                self.navigate(lock, Page::Networks, Dialog::Menu);
+            }
+            Action::Up => {
+                lock.network_state
+                    .select(if pos > 0 { Some(pos - 1) } else { Some(0) });
+            }
+            Action::Down if pos + 1 < lock.count() => lock.network_state.select(Some(pos + 1)),
+            Action::Back => {
+                self.go_back(lock);
+            }
+            Action::Quit => {
+                // nothing left to back out of
+                return Ok(!self.go_back(lock));
+            }
+            Action::Forget => {
+                lock.remove_network(pos);
+            }
+            Action::Leave => {
+                if let Some(id) = lock.selected_network_id() {
+                    lock.remember_left(&id);
+                    crate::client::leave_network(id)?;
+                }
+            }
+            Action::RecentlyLeft => {
+                self.left_state.select(Some(0));
+                self.navigate(lock, Page::Networks, Dialog::RecentlyLeft);
+            }
+            Action::Join => {
+                if let Some(id) = lock.selected_network_id() {
+                    crate::client::join_network(id)?;
+                }
+            }
+            Action::NewNetwork => {
+                if lock.any_api_key().is_none() {
+                    self.notice = Some(t("toast.api_key_needed"));
+                } else {
+                    let form = Form::new(vec![
+                        Field::text("Name", "").validate(form::required),
+                        Field::text("Subnet", "").validate(form::subnet),
+                        Field::yes_no("Join", true),
+                    ]);
+                    self.navigate(lock, Page::Networks, Dialog::NewNetwork(DialogState(form)));
+                }
+            }
+            #[cfg(feature = "central")]
+            Action::JoinFromCentral => match central_networks(lock) {
+                Ok(items) => {
+                    self.navigate(
+                        lock,
+                        Page::Networks,
+                        Dialog::Pick(Purpose::JoinNetwork, DialogState(Picker::new(items))),
+                    );
+                }
+                Err(e) => self.notice = Some(e.to_string()),
             },
-            Page::Networks => match &self.dialog {
-                Dialog::NetworkFlags(id) => match key.code {
-                    KeyCode::Char('n') => {
-                        crate::client::toggle_flag(id.to_string(), NetworkFlag::AllowDNS)?;
-                    }
-                    KeyCode::Char('d') => {
-                        crate::client::toggle_flag(id.to_string(), NetworkFlag::AllowDefault)?;
-                    }
-                    KeyCode::Char('g') => {
-                        crate::client::toggle_flag(id.to_string(), NetworkFlag::AllowGlobal)?;
+            Action::JoinByAddress => self.start_join(lock, ""),
+            Action::JoinFromClipboard => match form::find_network_id(&crate::clipboard::read()?) {
+                Some(id) => self.start_join(lock, &id),
+                None => self.notice = Some(t("toast.no_network_id")),
+            },
+            Action::ShowConfig => {
+                // shown in a pager by the run loop, which closes it again
+                let json = serde_json::to_string_pretty(&selected)?;
+                self.navigate(lock, Page::Networks, Dialog::Config(json));
+            }
+            Action::ToggleDisconnected => {
+                let filter = match lock.filter() {
+                    ListFilter::None => ListFilter::Connected,
+                    ListFilter::Connected => ListFilter::None,
+                };
+
+                lock.set_filter(filter);
+                lock.network_state.select(Some(0))
+            }
+            Action::Help => {
+                self.help = Some(Help::new(Topic::of(&lock.page, &self.dialog)));
+            }
+            Action::ShowMembers => {
+                if let Some(id) = lock.selected_network_id() {
+                    if lock.api_key_for_id(id.clone()).is_some() {
+                        self.navigate(lock, Page::Network(id), Dialog::None);
+                    } else {
+                        self.navigate(lock, Page::Networks, Dialog::APIKey(id, None));
                     }
-                    KeyCode::Char('m') => {
-                        crate::client::toggle_flag(id.to_string(), NetworkFlag::AllowManaged)?;
+                }
+            }
+            Action::ToggleSplit => {
+                lock.split = !lock.split;
+            }
+            Action::Flags => {
+                if let Some(id) = lock.selected_network_id() {
+                    self.navigate(lock, Page::Networks, Dialog::NetworkFlags(id));
+                }
+            }
+            Action::AutoRejoin => {
+                if let Some(id) = lock.selected_network_id() {
+                    self.notice = Some(if lock.toggle_auto_rejoin(&id) {
+                        tf("toast.auto_rejoin_on", &[&id])
+                    } else {
+                        tf("toast.auto_rejoin_off", &[&id])
+                    });
+                }
+            }
+            Action::FullTunnel => {
+                if let Some(network) = selected {
+                    self.full_tunnel(lock, network)?;
+                }
+            }
+            Action::FlagAll => {
+                if lock.connected().next().is_none() {
+                    self.notice = Some(t("toast.flag_all_none"));
+                } else {
+                    let items = NetworkFlag::ALL
+                        .into_iter()
+                        .flat_map(|flag| {
+                            [true, false].map(|on| Item {
+                                value: format!("{}={}", flag.name(), on),
+                                label: format!("{} {}", flag.name(), on_off(on)),
+                            })
+                        })
+                        .collect();
+                    self.navigate(
+                        lock,
+                        Page::Networks,
+                        Dialog::Pick(Purpose::FlagAll, DialogState(Picker::new(items))),
+                    );
+                }
+            }
+            Action::ApplySet => {
+                let config = lock.user_config();
+                if config.join_sets().is_empty() {
+                    self.notice = Some(t("toast.no_join_sets"));
+                } else {
+                    let items = config
+                        .join_sets()
+                        .iter()
+                        .map(|(name, set)| Item {
+                            value: name.clone(),
+                            label: tf("picker.join_set_item", &[name, &set.networks.len()]),
+                        })
+                        .collect();
+                    self.navigate(
+                        lock,
+                        Page::Networks,
+                        Dialog::Pick(Purpose::JoinSet, DialogState(Picker::new(items))),
+                    );
+                }
+            }
+            Action::Doctor => {
+                let checks = crate::doctor::run(lock.any_api_key());
+                self.navigate(lock, Page::Networks, Dialog::Doctor(DialogState(checks)));
+            }
+            Action::GoToMember => self.navigate(lock, Page::Networks, Dialog::GoToMember),
+            Action::Privacy => {
+                crate::privacy::toggle();
+            }
+            Action::BugReport => {
+                let path = crate::bugreport::write(Some(lock), None)?;
+                self.notice = Some(tf("toast.bug_report", &[&path.display()]));
+            }
+            Action::Identity => {
+                let report = crate::identity::verify();
+                self.navigate(lock, Page::Networks, Dialog::Identity(DialogState(report)));
+            }
+            Action::Traffic => {
+                if let Some(id) = lock.selected_network_id() {
+                    self.navigate(lock, Page::Networks, Dialog::Traffic(id));
+                }
+            }
+            Action::Interface => {
+                if let Some(network) = selected {
+                    let id = network.subtype_1.id.clone().unwrap_or_default();
+                    let current = lock.interfaces_for_network(&network);
+                    let items = std::iter::once(Item {
+                        value: String::new(),
+                        label: t("picker.interface_auto"),
+                    })
+                    .chain(lock.nets.interfaces().into_iter().map(|name| Item {
+                        label: if current.contains(&name) {
+                            tf("picker.interface_current", &[&name])
+                        } else {
+                            name.clone()
+                        },
+                        value: name,
+                    }))
+                    .collect();
+                    self.navigate(
+                        lock,
+                        Page::Networks,
+                        Dialog::Pick(Purpose::Interface(id), DialogState(Picker::new(items))),
+                    );
+                }
+            }
+            Action::Routes => {
+                if let Some(network) = selected {
+                    let report = crate::routes::verify(&network);
+                    self.navigate(
+                        lock,
+                        Page::Networks,
+                        Dialog::Routes(network.subtype_1.id.clone().unwrap(), DialogState(report)),
+                    );
+                }
+            }
+            Action::Capture => {
+                if let Some(network) = selected {
+                    let id = network.subtype_1.id.clone().unwrap_or_default();
+                    match lock.capture_interface(&network).and_then(|interface| {
+                        Capture::start(
+                            lock.user_config()
+                                .capture_command_for_network(&network, &interface),
+                        )
+                    }) {
+                        Ok(capture) => {
+                            self.navigate(
+                                lock,
+                                Page::Networks,
+                                Dialog::Capture(id, DialogState(capture)),
+                            );
+                        }
+                        // said and logged, rather than taken as the key failing
+                        Err(e) => {
+                            lock.record_error(Some(&id), "capture", &e);
+                            self.notice = Some(tf("toast.capture_failed", &[&id, &e]));
+                        }
                     }
-                    KeyCode::Esc | KeyCode::Char('q') => {
-                        self.dialog = Dialog::None;
+                }
+            }
+            #[cfg(feature = "central")]
+            Action::EditRules => {
+                if let Some(network) = selected {
+                    self.start_rules_edit(
+                        terminal,
+                        lock,
+                        network.subtype_1.id.clone().unwrap(),
+                        None,
+                    )?;
+                }
+            }
+            Action::Log => {
+                self.navigate(lock, Page::Networks, Dialog::Actions);
+            }
+            Action::PendingChanges => {
+                self.pending_state.select(Some(0));
+                self.navigate(lock, Page::Networks, Dialog::Pending);
+            }
+            Action::EditRulesFromSnippet => {
+                if let Some(network) = selected {
+                    match crate::snippets::load(&config_path()) {
+                        Ok(snippets) => {
+                            self.snippets = snippets;
+                            self.snippet_state.select(Some(0));
+                            self.navigate(
+                                lock,
+                                Page::Networks,
+                                Dialog::Snippets(network.subtype_1.id.clone().unwrap()),
+                            );
+                        }
+                        Err(e) => self.notice = Some(e.to_string()),
                     }
-                    _ => {}
-                },
-                Dialog::None => match key.code {
-                    KeyCode::Up => {
-                        let pos = lock.network_state.selected().unwrap_or_default();
-                        lock.network_state
-                            .select(if pos > 0 { Some(pos - 1) } else { Some(0) });
+                }
+            }
+            _ => {}
+        }
+
+        Ok(false)
+    }
+
+    fn edit_mode_key<B: Console>(
+        &mut self,
+        _terminal: &mut Terminal<B>,
+        settings: Arc<Mutex<Settings>>,
+        key: KeyEvent,
+    ) {
+        // control characters aren't typed into anything
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            return;
+        }
+
+        if !matches!(key.code, KeyCode::Enter | KeyCode::Esc) {
+            if let Some(form) = self.form() {
+                form.key(key);
+                return;
+            }
+            if let Some(picker) = self.picker() {
+                picker.key(key);
+                return;
+            }
+        }
+
+        match key.code {
+            KeyCode::Char(x) => {
+                let offset = self.cursor_offset();
+                self.inputbuffer.insert(offset, x);
+                self.cursor += 1;
+            }
+            KeyCode::Left => {
+                self.cursor = self.cursor.saturating_sub(1);
+            }
+            KeyCode::Right => {
+                self.cursor = (self.cursor + 1).min(self.inputbuffer.chars().count());
+            }
+            KeyCode::Home => {
+                self.cursor = 0;
+            }
+            KeyCode::End => {
+                self.cursor = self.inputbuffer.chars().count();
+            }
+            KeyCode::Delete if self.cursor < self.inputbuffer.chars().count() => {
+                let offset = self.cursor_offset();
+                self.inputbuffer.remove(offset);
+            }
+            KeyCode::Esc => {
+                if let Dialog::IdentityBackup(report) = &self.dialog {
+                    self.step(Dialog::Identity(report.clone()));
+                } else {
+                    self.go_back(&mut settings.lock().unwrap());
+                }
+            }
+            KeyCode::Backspace if self.cursor > 0 => {
+                self.cursor -= 1;
+                let offset = self.cursor_offset();
+                self.inputbuffer.remove(offset);
+            }
+            KeyCode::Enter => {
+                let mut lock = settings.lock().unwrap();
+
+                let res = match self.dialog.clone() {
+                    Dialog::Join(_) => {
+                        let form = match self.valid_form() {
+                            Some(form) => form,
+                            None => return,
+                        };
+
+                        client::join_network_with(
+                            form::find_network_id(&form.text("Network ID")).unwrap_or_default(),
+                            client::JoinFlags {
+                                allow_managed: Some(form.yes("Allow managed")),
+                                allow_global: Some(form.yes("Allow global")),
+                                allow_default: Some(form.yes("Allow default")),
+                                allow_dns: Some(form.yes("Allow DNS")),
+                            },
+                        )
                     }
-                    KeyCode::Down => {
-                        let pos = lock.network_state.selected().unwrap_or_default() + 1;
-                        let count = lock.count();
-                        if pos < count {
-                            lock.network_state.select(Some(pos))
+                    Dialog::MacroName => {
+                        let name = self.inputbuffer.trim().to_string();
+                        if name.is_empty() {
+                            return;
                         }
+
+                        lock.macros.insert(name, std::mem::take(&mut self.recorded));
+                        Ok(())
                     }
-                    KeyCode::Esc => {
-                        self.dialog = Dialog::None;
-                        self.editing_mode = EditingMode::Command;
+                    // "<name>" or "<name> <times>"
+                    Dialog::RunMacro => {
+                        let mut words = self.inputbuffer.split_whitespace();
+                        match (words.next(), words.next().map(str::parse::<usize>)) {
+                            (Some(name), None) => {
+                                self.queued_macro = Some((name.to_string(), 1));
+                                Ok(())
+                            }
+                            (Some(name), Some(Ok(times))) => {
+                                self.queued_macro = Some((name.to_string(), times));
+                                Ok(())
+                            }
+                            _ => return,
+                        }
                     }
-                    KeyCode::Char(c) => match c {
-                        'q' => return Ok(true),
-                        'd' => {
-                            let pos = lock.network_state.selected().unwrap_or_default();
-                            lock.remove_network(pos);
+                    #[cfg(feature = "central")]
+                    Dialog::APIKey(id, _) => {
+                        // "<key>" for Central, or "<controller url> <key>" for a network on
+                        // another controller
+                        let (url, key) =
+                            match self.inputbuffer.split_whitespace().collect::<Vec<_>>()[..] {
+                                [url, key] => (Some(url.to_string()), key.to_string()),
+                                _ => (None, self.inputbuffer.trim().to_string()),
+                            };
+                        let baseurl = url.clone().unwrap_or_else(client::central_baseurl);
+
+                        // a bad key stays in the dialog instead of bouncing back on the next poll
+                        if let Err(e) = client::sync_verify_api_key(
+                            key.clone(),
+                            &baseurl,
+                            &lock.user_config().controller(&baseurl),
+                            id.clone(),
+                        ) {
+                            self.dialog = Dialog::APIKey(id, Some(e.to_string()));
+                            return;
                         }
-                        'l' => {
-                            let pos = lock.network_state.selected().unwrap_or_default();
-                            let id = lock.get_network_id_by_pos(pos);
-                            crate::client::leave_network(id)?;
+
+                        lock.set_api_key_for_id(id.clone(), key);
+                        lock.set_central_url_for_id(id.clone(), url);
+                        self.navigate(&mut lock, Page::Network(id), Dialog::None);
+                        return;
+                    }
+                    #[cfg(feature = "central")]
+                    Dialog::AddMember(network_id, _) => {
+                        let form = match self.valid_form() {
+                            Some(form) => form,
+                            None => return,
+                        };
+
+                        let node_id = form.text("Node ID");
+                        let name = form.text("Name");
+                        let mut res = Ok(());
+                        if form.yes("Authorize") {
+                            res =
+                                self.change_member(&mut lock, &network_id, &node_id, Op::Authorize);
                         }
-                        'j' => {
-                            let pos = lock.network_state.selected().unwrap_or_default();
-                            let id = lock.get_network_id_by_pos(pos);
-                            crate::client::join_network(id)?;
+                        if res.is_ok() && !name.is_empty() {
+                            res = self.change_member(
+                                &mut lock,
+                                &network_id,
+                                &node_id,
+                                Op::Rename(name),
+                            );
                         }
-                        'J' => {
-                            self.dialog = Dialog::Join;
-                            self.editing_mode = EditingMode::Editing;
-                            self.inputbuffer = String::new();
+                        res
+                    }
+                    Dialog::Pick(Purpose::JoinNetwork, picker) => match picker.picked() {
+                        Some(item) => crate::client::join_network(item.value.clone()),
+                        None => return,
+                    },
+                    Dialog::Pick(Purpose::Interface(network_id), picker) => {
+                        let interface = match picker.picked() {
+                            Some(item) => item.value.clone(),
+                            None => return,
+                        };
+                        self.notice = Some(if interface.is_empty() {
+                            t("toast.interface_auto")
+                        } else {
+                            tf("toast.interface_set", &[&interface, &network_id])
+                        });
+                        lock.set_interface(&network_id, Some(interface).filter(|i| !i.is_empty()));
+                        Ok(())
+                    }
+                    Dialog::Pick(Purpose::FlagAll, picker) => {
+                        let picked = picker
+                            .picked()
+                            .and_then(|item| item.value.split_once('='))
+                            .and_then(|(flag, on)| {
+                                Some((NetworkFlag::from_name(flag)?, on.parse().ok()?))
+                            });
+                        if let Some((flag, on)) = picked {
+                            self.step(Dialog::FlagAll(flag, on));
                         }
-                        'c' => {
-                            self.inputbuffer =
-                                serde_json::to_string_pretty(&lock.get_network_by_pos(
-                                    lock.network_state.selected().unwrap_or_default(),
-                                ))?;
-                            self.dialog = Dialog::Config;
+                        return;
+                    }
+                    Dialog::Pick(Purpose::JoinSet, picker) => {
+                        if let Some(item) = picker.picked() {
+                            self.step(Dialog::JoinSet(item.value.clone()));
                         }
-                        't' => {
-                            let filter = match lock.filter() {
-                                ListFilter::None => ListFilter::Connected,
-                                ListFilter::Connected => ListFilter::None,
-                            };
+                        return;
+                    }
+                    #[cfg(feature = "central")]
+                    Dialog::SsoEdit(network_id, _) => {
+                        let form = match self.valid_form() {
+                            Some(form) => form,
+                            None => return,
+                        };
 
-                            lock.set_filter(filter);
-                            lock.network_state.select(Some(0))
-                        }
-                        'h' => {
-                            self.dialog = match self.dialog {
-                                Dialog::Help => Dialog::None,
-                                _ => Dialog::Help,
+                        let sso = client::Sso {
+                            enabled: form.yes("Enabled"),
+                            client_id: form.text("Client ID"),
+                            issuer: form.text("Issuer"),
+                            ..Default::default()
+                        };
+                        lock.central_client_for_id(network_id.clone())
+                            .and_then(|client| {
+                                client::sync_set_sso(client, network_id.clone(), sso)
+                            })
+                            .map_err(|e| refused(&mut lock, &network_id, e))
+                    }
+                    #[cfg(feature = "central")]
+                    Dialog::NewNetwork(_) => {
+                        let form = match self.valid_form() {
+                            Some(form) => form,
+                            None => return,
+                        };
+
+                        let key = lock.any_api_key().unwrap_or_default();
+                        let subnet = form::parse_subnet(&form.text("Subnet")).ok().flatten();
+                        let res = client::central_client(key.clone()).and_then(|client| {
+                            client::sync_create_network(client, form.text("Name"), subnet)
+                        });
+
+                        match res {
+                            Ok(Some(id)) => {
+                                lock.set_api_key_for_id(id.clone(), key);
+                                if form.yes("Join") {
+                                    client::join_network(id)
+                                } else {
+                                    Ok(())
+                                }
                             }
+                            Ok(None) => Ok(()),
+                            Err(e) => Err(e),
                         }
-                        's' => {
-                            let id = lock.get_network_id_by_pos(
-                                lock.network_state.selected().unwrap_or_default(),
+                    }
+                    #[cfg(feature = "central")]
+                    Dialog::BatchRename(network_id) => {
+                        let members = lock.members.get(&network_id).cloned().unwrap_or_default();
+                        self.batch_errors.clear();
+                        for (node_id, name) in self.batch_rename_preview(&members) {
+                            let res = self.change_member(
+                                &mut lock,
+                                &network_id,
+                                &node_id,
+                                Op::Rename(name),
                             );
-                            let key = lock.api_key_for_id(id.clone());
-                            if let Some(_) = key {
-                                self.member_state.select(Some(0));
-                                lock.page = Page::Network(id)
-                            } else {
-                                self.dialog = Dialog::APIKey(id);
-                                self.editing_mode = EditingMode::Editing;
-                                self.inputbuffer = String::new();
+
+                            if let Err(e) = res {
+                                self.batch_errors.insert(node_id, e.to_string());
                             }
                         }
-                        'f' => {
-                            let pos = lock.network_state.selected().unwrap_or_default();
-                            let id = lock.get_network_id_by_pos(pos);
-                            self.dialog = Dialog::NetworkFlags(id);
-                        }
-                        'e' => {
-                            let pos = lock.network_state.selected().unwrap_or_default();
-                            if let Some(network) = lock.get_network_by_pos(pos) {
-                                if let Some(api_key) =
-                                    lock.api_key_for_id(network.subtype_1.id.clone().unwrap())
-                                {
-                                    let client = central_client(api_key.to_string())?;
-                                    let net = crate::client::sync_get_network(
-                                        client.clone(),
-                                        network.subtype_1.id.clone().unwrap(),
-                                    )?;
-
-                                    let mut tf = NamedTempFile::new()?;
-
-                                    tf.write_all(net.rules_source.clone().unwrap().as_bytes())?;
-                                    let path = tf.into_temp_path();
-                                    let modif = path.metadata()?.modified()?;
-
-                                    App::run_command(
-                                        terminal,
-                                        false,
-                                        format!("$EDITOR {}", path.display()),
-                                    )?;
-
-                                    if path.metadata()?.modified()? != modif {
-                                        crate::client::sync_apply_network_rules(
-                                            client,
-                                            network.subtype_1.id.clone().unwrap(),
-                                            std::fs::read_to_string(path)?,
-                                        )?;
-                                    }
-                                }
-                            }
+
+                        // leave the preview up with the failures marked so they can be retried
+                        if !self.batch_errors.is_empty() {
+                            self.marked
+                                .retain(|node_id| self.batch_errors.contains_key(node_id));
+                            return;
                         }
-                        x => {
-                            if let Some(net) = lock.get_network_by_pos(
-                                lock.network_state.selected().unwrap_or_default(),
-                            ) {
-                                if let Some(s) = lock.user_config().command_for_network(x, net) {
-                                    App::run_command(terminal, true, s)?;
+
+                        self.marked.clear();
+                        Ok(())
+                    }
+                    #[cfg(feature = "central")]
+                    Dialog::RenameMember(network_id, member_id) => {
+                        let name = self.inputbuffer.clone();
+                        self.change_member(&mut lock, &network_id, &member_id, Op::Rename(name))
+                    }
+                    Dialog::GoToMember => {
+                        let found = find_member(&mut lock, &self.inputbuffer);
+                        self.go_back(&mut lock);
+                        match found {
+                            Ok((network, node_id, also)) => {
+                                self.go_to_member(&mut lock, network, &node_id);
+                                if !also.is_empty() {
+                                    self.notice = Some(tf(
+                                        "toast.member_also_in",
+                                        &[&node_id, &also.join(", ")],
+                                    ));
                                 }
                             }
+                            Err(e) => self.notice = Some(e.to_string()),
                         }
-                    },
-                    _ => {}
-                },
-                Dialog::Help => match key.code {
-                    KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('h') => {
-                        self.dialog = Dialog::None;
+                        return;
                     }
-                    _ => {}
-                },
-                _ => {}
-            },
+                    Dialog::IdentityBackup(mut report) => {
+                        let path = match self.inputbuffer.strip_prefix("~/") {
+                            Some(rest) => directories::UserDirs::new()
+                                .map(|d| d.home_dir().join(rest))
+                                .unwrap_or_else(|| self.inputbuffer.clone().into()),
+                            None => self.inputbuffer.clone().into(),
+                        };
+
+                        report.backup =
+                            Some(crate::identity::backup(&path).map_err(|e| e.to_string()));
+
+                        self.step(Dialog::Identity(report));
+                        return;
+                    }
+                    _ => Ok(()),
+                };
+
+                self.go_back(&mut lock);
+                if let Err(e) = res {
+                    self.notice = Some(e.to_string());
+                }
+            }
+            _ => {}
         }
+    }
 
-        Ok(false)
+    // fetches the network's rules and opens them in $EDITOR, optionally with a snippet inserted
+    #[cfg(feature = "central")]
+    fn start_rules_edit<B: Console>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        settings: &mut Settings,
+        network_id: String,
+        snippet: Option<&Snippet>,
+    ) -> Result<(), anyhow::Error> {
+        if settings.api_key_for_id(network_id.clone()).is_none() {
+            return Ok(());
+        }
+
+        let client = settings.central_client_for_id(network_id.clone())?;
+        let net = crate::client::sync_get_network(client, network_id.clone())?;
+        let rules = net.rules_source.clone().unwrap_or_default();
+        let contents = snippet.map_or(rules.clone(), |s| s.insert_into(&rules));
+
+        self.edit_rules(terminal, settings, network_id, rules, contents)
     }
 
-    fn edit_mode_key<W: Write>(
+    // edits `contents` in $EDITOR; anything that differs from `old`, which is what Central has,
+    // is shown as a diff to confirm before it is pushed
+    #[cfg(feature = "central")]
+    fn edit_rules<B: Console>(
         &mut self,
-        _terminal: &mut Terminal<CrosstermBackend<W>>,
-        settings: Arc<Mutex<Settings>>,
-        key: KeyEvent,
-    ) {
-        match key.code {
-            KeyCode::Char(x) => {
-                self.inputbuffer.push(x);
-            }
-            KeyCode::Esc => {
-                self.inputbuffer = String::new();
-                self.dialog = Dialog::None;
-                self.editing_mode = EditingMode::Command;
-            }
-            KeyCode::Backspace => {
-                if self.inputbuffer.len() > 0 {
-                    self.inputbuffer
-                        .drain(self.inputbuffer.len() - 1..self.inputbuffer.len());
-                }
+        terminal: &mut Terminal<B>,
+        settings: &mut Settings,
+        network_id: String,
+        old: String,
+        contents: String,
+    ) -> Result<(), anyhow::Error> {
+        let mut tf = NamedTempFile::new()?;
+        tf.write_all(contents.as_bytes())?;
+        let path = tf.into_temp_path();
+
+        App::run_command(
+            terminal,
+            false,
+            Launch::new(format!("$EDITOR {}", path.display())),
+        )?;
+
+        let new = std::fs::read_to_string(path)?;
+        if new == old {
+            if let Dialog::RulesDiff(..) = self.dialog {
+                self.go_back(settings);
             }
-            KeyCode::Enter => {
-                match &self.dialog {
-                    Dialog::Join => {
-                        crate::client::join_network(self.inputbuffer.clone()).unwrap();
-                    }
-                    Dialog::APIKey(id) => {
-                        let mut lock = settings.lock().unwrap();
-                        lock.set_api_key_for_id(id.clone(), self.inputbuffer.clone());
-                        lock.page = Page::Network(id.clone());
-                    }
-                    Dialog::AddMember(network_id) => {
-                        let lock = settings.lock().unwrap();
-                        crate::client::sync_authorize_member(
-                            central_client(
-                                lock.api_key_for_id(network_id.to_string())
-                                    .unwrap()
-                                    .to_string(),
-                            )
-                            .unwrap(),
-                            network_id.to_string(),
-                            self.inputbuffer.clone(),
-                        )
-                        .unwrap();
-                    }
-                    Dialog::RenameMember(network_id, member_id) => {
-                        let mut lock = settings.lock().unwrap();
-                        client::sync_update_member_name(
-                            central_client(
-                                lock.api_key_for_id(network_id.to_string())
-                                    .unwrap()
-                                    .to_string(),
-                            )
-                            .unwrap(),
-                            network_id.to_string(),
-                            member_id.to_string(),
-                            self.inputbuffer.clone(),
-                        )
-                        .unwrap();
-                        lock.page = Page::Network(network_id.clone());
-                    }
-                    _ => {}
-                }
+            return Ok(());
+        }
 
-                self.inputbuffer = String::new();
-                self.dialog = Dialog::None;
-                self.editing_mode = EditingMode::Command;
+        let edit = DialogState(RulesEdit::new(old, new));
+        if let Dialog::RulesDiff(..) = self.dialog {
+            self.step(Dialog::RulesDiff(network_id, edit));
+        } else {
+            self.navigate(
+                settings,
+                Page::Networks,
+                Dialog::RulesDiff(network_id, edit),
+            );
+        }
+
+        Ok(())
+    }
+
+    // a custom command opens beside the UI when it asks to and ztui is in tmux, and otherwise
+    // takes over the terminal until it's done
+    fn launch<B: Console>(terminal: &mut Terminal<B>, launch: Launch) -> Result<(), anyhow::Error> {
+        match launch.tmux {
+            Tmux::Split | Tmux::Window if crate::terminal::in_tmux() => {
+                crate::terminal::tmux_open(&launch)
             }
-            _ => {}
+            _ => App::run_command(terminal, true, launch),
         }
     }
 
-    fn run_command<W: Write>(
-        terminal: &mut Terminal<CrosstermBackend<W>>,
+    fn run_command<B: Console>(
+        terminal: &mut Terminal<B>,
         trap: bool, // wrap the terminal for pty, signal handling
-        s: String,
+        launch: Launch,
     ) -> Result<(), anyhow::Error> {
-        let mut args: Vec<String> = vec!["-c".to_string()];
-        args.push(s);
+        // the command, and the wait for enter after it, have the terminal to themselves
+        crate::events::pause();
+        let res = App::run_paused(terminal, trap, launch);
+        crate::events::resume();
+        res
+    }
+
+    fn run_paused<B: Console>(
+        terminal: &mut Terminal<B>,
+        trap: bool,
+        launch: Launch,
+    ) -> Result<(), anyhow::Error> {
+        let mut args = launch.argv();
+        let shell = args.remove(0);
+        let cwd = launch.cwd.clone().map_or_else(std::env::current_dir, Ok)?;
 
         terminal.clear()?;
         let (sc, mut r) = mpsc::unbounded_channel();
@@ -592,8 +2712,10 @@ impl App {
                 // let mut cmd = CommandBuilder::new("/bin/sh");
                 // cmd.args(args);
 
-                let mut child = tokio::process::Command::new("/bin/sh")
+                let mut child = tokio::process::Command::new(shell)
                     .args(args)
+                    .envs(launch.env)
+                    .current_dir(cwd)
                     .stdin(Stdio::inherit())
                     .stdout(Stdio::inherit())
                     .stderr(Stdio::inherit())
@@ -618,21 +2740,175 @@ impl App {
             });
         });
 
-        loop {
-            if let Ok(_) = r.try_recv() {
-                break;
-            } else {
-                std::thread::sleep(Duration::new(0, 10))
-            }
-        }
+        let _ = r.blocking_recv();
 
         t.shutdown_background();
         drop(sc);
-        eprintln!("\nPress ENTER to continue");
-        let mut buf = [0u8; 1];
-        let _ = std::io::stdin().read(&mut buf).unwrap();
+        terminal.backend_mut().wait_for_enter()?;
         terminal.clear()?;
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use tui::style::Color;
+
+    use super::App;
+    use crate::testing::{Fixture, Screen};
+
+    const NETWORK: &str = "8056c2e21c000001";
+    #[cfg(feature = "central")]
+    const NODE: &str = "0123456789";
+
+    #[test]
+    fn join() {
+        let mut fx = Fixture::new();
+        fx.keys("J");
+        fx.type_in(NETWORK);
+        fx.keys("Enter");
+
+        let joined = fx.local.state().networks.get(NETWORK).cloned().unwrap();
+        assert_eq!(joined["allowManaged"], json!(true));
+        assert_eq!(joined["allowDefault"], json!(false));
+
+        fx.refresh();
+        assert!(fx.draw().contains(NETWORK));
+    }
+
+    // a form's fields go with it when it closes, so opening it again starts it over
+    #[test]
+    fn reopened_form() {
+        let mut fx = Fixture::new();
+        fx.keys("J");
+        fx.type_in(NETWORK);
+        assert!(fx.draw().contains(NETWORK));
+
+        fx.keys("Esc");
+        assert!(fx.app.form().is_none());
+        assert!(!fx.keys("J").contains(NETWORK));
+    }
+
+    // every key that acts on the selected network, with none listed to select
+    #[test]
+    fn no_networks() {
+        let mut fx = Fixture::new();
+        fx.draw();
+        for key in ["l", "j", "s", "f", "A", "u"] {
+            let screen = fx.keys(key);
+            assert!(screen.contains("Network ID"), "{}: {}", key, screen);
+        }
+        assert!(fx.local.state().requests.is_empty());
+    }
+
+    // toasts are cut to fit by how wide their characters are, on screens of any width
+    #[test]
+    fn toast_fits() {
+        let app = App::default();
+        for width in [0, 3, 20, 120] {
+            let mut terminal = Screen::terminal(width, 2);
+            terminal
+                .draw(|f| app.show_toast(f, Color::White, "ünïcödé 日本語 ".repeat(4)))
+                .unwrap();
+
+            let buffer = terminal.backend().0.buffer();
+            let row = buffer.content[buffer.area.width as usize..]
+                .iter()
+                .map(|c| c.symbol.as_str())
+                .collect::<String>();
+            // 日 takes two cells, the second left blank
+            if width == 20 {
+                assert_eq!(row.trim(), "[ ünïcödé 日  ]");
+            }
+        }
+    }
+
+    // a network that's still joining has no interface to capture on yet
+    #[test]
+    fn capture_without_interface() {
+        let mut fx = Fixture::new();
+        fx.local.network(
+            NETWORK,
+            json!({ "id": NETWORK, "status": "REQUESTING_CONFIGURATION" }),
+        );
+        fx.refresh();
+        fx.draw();
+
+        let screen = fx.keys("p");
+        assert!(screen.contains("no interface yet"), "{}", screen);
+    }
+
+    // a network with one member waiting to be let in, with its members page open
+    #[cfg(feature = "central")]
+    fn members() -> Fixture {
+        let mut fx = Fixture::new();
+        fx.network(
+            NETWORK,
+            json!({ "id": NETWORK, "config": { "name": "homelab" }, "rulesSource": "accept;" }),
+        );
+        fx.central.member(
+            NETWORK,
+            NODE,
+            json!({ "networkId": NETWORK, "nodeId": NODE, "config": { "authorized": false } }),
+        );
+
+        fx.refresh();
+        // the first frame is what selects the first row
+        fx.draw();
+        fx.keys("s");
+        fx.refresh();
+        assert!(fx.draw().contains(NODE));
+        fx
+    }
+
+    #[cfg(feature = "central")]
+    #[test]
+    fn authorize() {
+        let mut fx = members();
+        fx.keys("a");
+
+        let member = fx.central.state().members[NETWORK][NODE].clone();
+        assert_eq!(member["config"]["authorized"], json!(true));
+    }
+
+    #[cfg(feature = "central")]
+    #[test]
+    fn rename() {
+        let mut fx = members();
+        fx.keys("r");
+        fx.type_in("web-1");
+        fx.keys("Enter");
+
+        let member = fx.central.state().members[NETWORK][NODE].clone();
+        assert_eq!(member["name"], json!("web-1"));
+
+        fx.refresh();
+        assert!(fx.draw().contains("web-1"));
+    }
+
+    // the editor changes the rules, the change is shown as a diff, and only goes to Central once
+    // it's confirmed
+    #[cfg(feature = "central")]
+    #[test]
+    fn edit_rules() {
+        let mut fx = members();
+        std::env::set_var("EDITOR", "sed -i s/accept/drop/");
+        fx.keys("Esc e");
+
+        let screen = fx.draw();
+        assert!(screen.contains("drop;"), "{}", screen);
+        assert_eq!(
+            fx.central.state().networks[NETWORK]["rulesSource"],
+            json!("accept;")
+        );
+
+        fx.keys("y");
+        assert_eq!(
+            fx.central.state().networks[NETWORK]["rulesSource"],
+            json!("drop;")
+        );
+    }
+}
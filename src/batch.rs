@@ -0,0 +1,98 @@
+use std::sync::{Arc, Mutex};
+
+use crate::{
+    client::{self, Cancel},
+    shutdown,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Waiting,
+    Done,
+    // Central couldn't be reached; it'll be sent with the other queued changes
+    #[cfg(feature = "central")]
+    Queued,
+    Failed(String),
+    Cancelled,
+}
+
+#[derive(Debug, Clone)]
+pub struct Task {
+    pub label: String,
+    pub outcome: Outcome,
+}
+
+// runs one job per task in the background, in order, so the screen can show how far along it is.
+// cancelling gives up on the request underway, and skips the rest.
+#[derive(Debug, Clone)]
+pub struct Batch {
+    pub title: String,
+    tasks: Arc<Mutex<Vec<Task>>>,
+    cancel: Cancel,
+}
+
+impl Batch {
+    pub fn start<F>(title: String, labels: Vec<String>, run: F) -> Self
+    where
+        F: Fn(usize) -> Result<Outcome, anyhow::Error> + Send + 'static,
+    {
+        let tasks = Arc::new(Mutex::new(
+            labels
+                .into_iter()
+                .map(|label| Task {
+                    label,
+                    outcome: Outcome::Waiting,
+                })
+                .collect::<Vec<Task>>(),
+        ));
+        let cancel = Cancel::default();
+
+        let (t, c) = (tasks.clone(), cancel.clone());
+        std::thread::spawn(move || {
+            let count = t.lock().unwrap().len();
+            for i in 0..count {
+                // what hasn't started when ztui quits isn't started at all
+                let Some(_sending) = shutdown::Mutation::start() else {
+                    t.lock().unwrap()[i].outcome = Outcome::Cancelled;
+                    continue;
+                };
+                let outcome = match client::cancellable(&c, || run(i)) {
+                    Ok(outcome) => outcome,
+                    Err(e) if client::is_cancelled(&e) => Outcome::Cancelled,
+                    Err(e) => Outcome::Failed(e.to_string()),
+                };
+
+                t.lock().unwrap()[i].outcome = outcome;
+            }
+        });
+
+        Self {
+            title,
+            tasks,
+            cancel,
+        }
+    }
+
+    pub fn tasks(&self) -> Vec<Task> {
+        self.tasks.lock().unwrap().clone()
+    }
+
+    // (tasks that have run, all tasks)
+    pub fn progress(&self) -> (usize, usize) {
+        let tasks = self.tasks.lock().unwrap();
+        let done = tasks
+            .iter()
+            .filter(|t| t.outcome != Outcome::Waiting)
+            .count();
+        (done, tasks.len())
+    }
+
+    pub fn finished(&self) -> bool {
+        let (done, total) = self.progress();
+        done == total
+    }
+
+    pub fn cancel(&self) {
+        self.cancel.cancel();
+    }
+}
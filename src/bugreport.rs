@@ -0,0 +1,128 @@
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use serde::Serialize;
+
+use crate::config::{data_path, Settings};
+
+// how many failed API calls are kept for a report, and how much of the log
+const KEEP_ERRORS: usize = 20;
+const LOG_LINES: usize = 200;
+
+lazy_static::lazy_static! {
+    static ref ERRORS: Mutex<Vec<ApiError>> = Mutex::new(Vec::new());
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiError {
+    pub at: u64,
+    pub call: String,
+    pub error: String,
+}
+
+// every client call that fails is passed through here, so a report can say what went wrong
+// leading up to it
+pub fn api_error(call: &str, error: &anyhow::Error) {
+    let mut errors = ERRORS.lock().unwrap();
+    errors.push(ApiError {
+        at: crate::history::now(),
+        call: call.to_string(),
+        error: error.to_string(),
+    });
+    if errors.len() > KEEP_ERRORS {
+        errors.remove(0);
+    }
+}
+
+// what's written to a bug report: enough to see what ztui was doing without the API keys it was
+// doing it with
+#[derive(Serialize)]
+struct Report {
+    version: &'static str,
+    os: &'static str,
+    arch: &'static str,
+    at: u64,
+    args: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    panic: Option<String>,
+    settings: serde_json::Value,
+    actions: Vec<String>,
+    api_errors: Vec<ApiError>,
+    log: Vec<String>,
+}
+
+// the end of ztui.log, when tracing is on
+fn recent_log() -> Vec<String> {
+    let log = std::fs::read_to_string(crate::logging::path()).unwrap_or_default();
+    let lines = log.lines().collect::<Vec<_>>();
+    lines[lines.len().saturating_sub(LOG_LINES)..]
+        .iter()
+        .map(|line| line.to_string())
+        .collect()
+}
+
+// writes a report to the data directory and returns where. `settings` is left out when they
+// can't be had, such as when the panic was had while holding them.
+pub fn write(settings: Option<&Settings>, panic: Option<String>) -> Result<PathBuf, anyhow::Error> {
+    let mut settings = match settings {
+        Some(settings) => serde_json::to_value(settings)?,
+        None => serde_json::Value::Null,
+    };
+    crate::tape::scrub(&mut settings);
+
+    let report = Report {
+        version: env!("CARGO_PKG_VERSION"),
+        os: std::env::consts::OS,
+        arch: std::env::consts::ARCH,
+        at: crate::history::now(),
+        args: std::env::args().collect(),
+        panic,
+        settings,
+        actions: crate::actions::recent()
+            .iter()
+            .map(|action| format!("{} {}", action.at, action))
+            .collect(),
+        api_errors: ERRORS.lock().map(|e| e.clone()).unwrap_or_default(),
+        log: recent_log(),
+    };
+
+    let path = data_path().join(format!("bugreport-{}.json", report.at));
+    crate::config::write_atomic(&path, &serde_json::to_string_pretty(&report)?)?;
+    Ok(path)
+}
+
+// on a panic the terminal is put back first, so the report's path and the panic itself can be
+// read, then the report is written. a panic in the background only takes that thread, which the
+// watchdog restarts, so the report is written quietly and the UI carries on.
+pub fn install_panic_hook(settings: Arc<Mutex<Settings>>) {
+    let default = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if std::thread::current().name() != Some("main") {
+            let lock = settings.try_lock().ok();
+            let res = write(lock.as_deref(), Some(info.to_string()));
+            tracing::error!(panic = %info, report = ?res.map(|p| p.display().to_string()), "a background thread panicked");
+            return;
+        }
+
+        let _ = crossterm::terminal::disable_raw_mode();
+        let _ = crossterm::execute!(
+            std::io::stdout(),
+            crossterm::terminal::LeaveAlternateScreen,
+            crossterm::cursor::Show
+        );
+
+        default(info);
+
+        // the panic may have been had with the settings locked
+        let lock = settings.try_lock().ok();
+        match write(lock.as_deref(), Some(info.to_string())) {
+            Ok(path) => eprintln!(
+                "ztui crashed. A report was written to {}; please look it over and attach it to an issue.",
+                path.display()
+            ),
+            Err(e) => eprintln!("ztui crashed, and a report could not be written: {}", e),
+        }
+    }));
+}
@@ -0,0 +1,116 @@
+use std::{
+    collections::VecDeque,
+    io::{BufRead, BufReader, Read},
+    path::PathBuf,
+    process::{Child, Command, Stdio},
+    sync::{Arc, Mutex},
+};
+
+// how much capture output we keep around for the pane
+const MAX_LINES: usize = 500;
+
+fn spawn(command: &str) -> Result<Child, anyhow::Error> {
+    // exec so that killing the shell takes the capture process down with it
+    Ok(Command::new("/bin/sh")
+        .args(["-c", &format!("exec {}", command)])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?)
+}
+
+fn push(lines: &Mutex<VecDeque<String>>, line: String) {
+    let mut lock = lines.lock().unwrap();
+    lock.push_back(line);
+    while lock.len() > MAX_LINES {
+        lock.pop_front();
+    }
+}
+
+fn stream<R: Read + Send + 'static>(reader: R, lines: Arc<Mutex<VecDeque<String>>>) {
+    std::thread::spawn(move || {
+        for line in BufReader::new(reader).lines() {
+            match line {
+                Ok(line) => push(&lines, line),
+                Err(_) => break,
+            }
+        }
+    });
+}
+
+#[derive(Debug, Clone)]
+pub struct Capture {
+    lines: Arc<Mutex<VecDeque<String>>>,
+    child: Arc<Mutex<Child>>,
+    writer: Option<(PathBuf, Arc<Mutex<Child>>)>,
+    saved: Option<PathBuf>,
+}
+
+impl Capture {
+    pub fn start(command: String) -> Result<Self, anyhow::Error> {
+        let lines = Arc::new(Mutex::new(VecDeque::new()));
+        let mut child = spawn(&command)?;
+
+        stream(child.stdout.take().unwrap(), lines.clone());
+        stream(child.stderr.take().unwrap(), lines.clone());
+
+        Ok(Self {
+            lines,
+            child: Arc::new(Mutex::new(child)),
+            writer: None,
+            saved: None,
+        })
+    }
+
+    // a line of ztui's own among the capture's, such as why a pcap couldn't be written
+    pub fn note(&self, line: String) {
+        push(&self.lines, line);
+    }
+
+    pub fn lines(&self) -> Vec<String> {
+        self.lines.lock().unwrap().iter().cloned().collect()
+    }
+
+    pub fn writing(&self) -> Option<PathBuf> {
+        self.writer.as_ref().map(|(path, _)| path.clone())
+    }
+
+    pub fn saved(&self) -> Option<PathBuf> {
+        self.saved.clone()
+    }
+
+    // starts writing a pcap alongside the streaming capture, or finishes the one in progress.
+    pub fn toggle_write(&mut self, command: String, path: PathBuf) -> Result<(), anyhow::Error> {
+        if let Some(path) = self.writing() {
+            self.stop_writer();
+            self.saved = Some(path);
+            return Ok(());
+        }
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut child = spawn(&command)?;
+        stream(child.stderr.take().unwrap(), self.lines.clone());
+        self.writer = Some((path, Arc::new(Mutex::new(child))));
+
+        Ok(())
+    }
+
+    fn stop_writer(&mut self) {
+        if let Some((_, child)) = self.writer.take() {
+            let mut child = child.lock().unwrap();
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+
+    pub fn stop(&mut self) {
+        self.stop_writer();
+
+        let mut child = self.child.lock().unwrap();
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+}
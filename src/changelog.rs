@@ -0,0 +1,201 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::central::types::Member;
+#[cfg(feature = "central")]
+use crate::history::now;
+
+// how many changes we keep per network
+#[cfg(feature = "central")]
+const MAX_ENTRIES: usize = 1000;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Change {
+    Added,
+    Deleted,
+    Authorized,
+    Deauthorized,
+    Renamed { from: String, to: String },
+    Addresses { from: Vec<String>, to: Vec<String> },
+}
+
+impl std::fmt::Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Change::Added => f.write_str("added"),
+            Change::Deleted => f.write_str("deleted"),
+            Change::Authorized => f.write_str("authorized"),
+            Change::Deauthorized => f.write_str("deauthorized"),
+            Change::Renamed { from, to } => write!(f, "renamed from `{}` to `{}`", from, to),
+            Change::Addresses { from, to } => {
+                write!(f, "addresses {} -> {}", from.join(", "), to.join(", "))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entry {
+    pub at: u64,
+    pub member: String,
+    pub name: String,
+    pub change: Change,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct Snapshot {
+    name: String,
+    authorized: bool,
+    addresses: Vec<String>,
+}
+
+impl From<&Member> for Snapshot {
+    fn from(member: &Member) -> Self {
+        let config = member.config.as_ref();
+        let mut addresses = config
+            .and_then(|c| c.ip_assignments.clone())
+            .unwrap_or_default();
+        addresses.sort();
+
+        Self {
+            name: member.name.clone().unwrap_or_default(),
+            authorized: config.and_then(|c| c.authorized).unwrap_or_default(),
+            addresses,
+        }
+    }
+}
+
+// membership changes seen between refreshes, whoever made them. the last snapshot of each
+// network is kept with the log so changes made while ztui wasn't running still show up.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Changelog {
+    snapshots: HashMap<String, HashMap<String, Snapshot>>,
+    entries: HashMap<String, Vec<Entry>>,
+}
+
+impl Changelog {
+    pub fn from_dir(filename: PathBuf) -> Result<Self, anyhow::Error> {
+        let changelog_file = std::fs::read_to_string(filename.join("changelog.json"))?;
+        Ok(serde_json::from_str(&changelog_file)?)
+    }
+
+    #[cfg(feature = "central")]
+    pub fn save(&mut self, filename: PathBuf) -> Result<(), anyhow::Error> {
+        let mut json = serde_json::to_string(self)?;
+        while json.len() > crate::config::MAX_DATA_FILE && self.prune() {
+            json = serde_json::to_string(self)?;
+        }
+
+        Ok(std::fs::write(filename.join("changelog.json"), json)?)
+    }
+
+    // drops the older half of every network's entries. false once there's nothing to drop.
+    #[cfg(feature = "central")]
+    fn prune(&mut self) -> bool {
+        let mut pruned = false;
+        for entries in self.entries.values_mut() {
+            if !entries.is_empty() {
+                entries.drain(..entries.len().div_ceil(2));
+                pruned = true;
+            }
+        }
+        pruned
+    }
+
+    // returns what changed since the last snapshot. the first snapshot of a network is only a
+    // baseline.
+    #[cfg(feature = "central")]
+    pub fn record(&mut self, network_id: &str, members: &[Member]) -> Vec<Entry> {
+        let new = members
+            .iter()
+            .filter_map(|m| Some((m.node_id.clone()?, Snapshot::from(m))))
+            .collect::<HashMap<String, Snapshot>>();
+
+        let old = match self.snapshots.insert(network_id.to_string(), new.clone()) {
+            Some(old) => old,
+            None => return Vec::new(),
+        };
+
+        if old == new {
+            return Vec::new();
+        }
+
+        let now = now();
+        let mut recorded = Vec::new();
+        let mut push = |member: &str, name: &str, change: Change| {
+            recorded.push(Entry {
+                at: now,
+                member: member.to_string(),
+                name: name.to_string(),
+                change,
+            })
+        };
+
+        let mut ids = new.keys().collect::<Vec<&String>>();
+        ids.sort();
+
+        for id in ids {
+            let snapshot = &new[id];
+            let old = match old.get(id) {
+                Some(old) => old,
+                None => {
+                    push(id, &snapshot.name, Change::Added);
+                    continue;
+                }
+            };
+
+            if old.authorized != snapshot.authorized {
+                push(
+                    id,
+                    &snapshot.name,
+                    if snapshot.authorized {
+                        Change::Authorized
+                    } else {
+                        Change::Deauthorized
+                    },
+                );
+            }
+
+            if old.name != snapshot.name {
+                push(
+                    id,
+                    &snapshot.name,
+                    Change::Renamed {
+                        from: old.name.clone(),
+                        to: snapshot.name.clone(),
+                    },
+                );
+            }
+
+            if old.addresses != snapshot.addresses {
+                push(
+                    id,
+                    &snapshot.name,
+                    Change::Addresses {
+                        from: old.addresses.clone(),
+                        to: snapshot.addresses.clone(),
+                    },
+                );
+            }
+        }
+
+        for (id, snapshot) in &old {
+            if !new.contains_key(id) {
+                push(id, &snapshot.name, Change::Deleted);
+            }
+        }
+
+        let entries = self.entries.entry(network_id.to_string()).or_default();
+        entries.extend(recorded.iter().cloned());
+        if entries.len() > MAX_ENTRIES {
+            entries.drain(..entries.len() - MAX_ENTRIES);
+        }
+
+        recorded
+    }
+
+    pub fn entries(&self, network_id: &str) -> Vec<Entry> {
+        self.entries.get(network_id).cloned().unwrap_or_default()
+    }
+}
@@ -5,358 +5,685 @@
 // -erikh
 //
 use std::{
-    path::Path,
-    time::{Duration, Instant},
+    cell::RefCell,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, RwLock,
+    },
+    time::Duration,
 };
 
 use anyhow::anyhow;
 use http::{HeaderMap, HeaderValue};
-use tokio::sync::mpsc;
-use zerotier_central_api::types::Network as CentralNetwork;
-use zerotier_central_api::{types::Member, Client, ResponseValue};
-use zerotier_one_api::types::Network;
+use serde::{Deserialize, Serialize};
 
-use crate::app::NetworkFlag;
+#[cfg(feature = "central")]
+use crate::api::central::types::{Network as CentralNetwork, Status as CentralStatus};
+#[cfg(feature = "central")]
+use crate::api::central::{types::Member, Client};
+use crate::api::one::types::{Network, Peer, Status};
+#[cfg(feature = "central")]
+use crate::config::Controller;
+use crate::{actions::record, app::NetworkFlag};
 
 // address of Central
+#[cfg(feature = "central")]
 const CENTRAL_BASEURL: &str = "https://my.zerotier.com/api/v1";
+// address of the local ZeroTier service
+const LOCAL_BASEURL: &str = "http://127.0.0.1:9993";
+
+// the token itself, for containers and the like that are handed secrets rather than files
+const TOKEN_ENV: &str = "ZEROTIER_ONE_TOKEN";
+
+// --local-api and --authtoken, which win over the environment
+#[derive(Debug, Clone, Default)]
+pub struct Local {
+    pub baseurl: Option<String>,
+    pub authtoken: Option<PathBuf>,
+}
+
+lazy_static::lazy_static! {
+    static ref LOCAL: RwLock<Local> = RwLock::new(Local::default());
+}
+
+pub fn set_local(local: Local) {
+    *LOCAL.write().unwrap() = local;
+}
+
+// where the local service is, unless --local-api or ZEROTIER_ONE_INSTANCE says otherwise. a bare
+// address and port, like a port mapped into a container, is taken to be http.
+pub fn local_baseurl() -> String {
+    let baseurl = LOCAL
+        .read()
+        .unwrap()
+        .baseurl
+        .clone()
+        .or_else(|| std::env::var("ZEROTIER_ONE_INSTANCE").ok())
+        .unwrap_or(LOCAL_BASEURL.to_string());
+
+    if baseurl.contains("://") {
+        baseurl
+    } else {
+        format!("http://{}", baseurl)
+    }
+}
+
+// plain http is only allowed to something on this machine, such as a stand-in for Central
+#[cfg(feature = "central")]
+fn is_loopback(baseurl: &str) -> bool {
+    let url = match reqwest::Url::parse(baseurl) {
+        Ok(url) => url,
+        Err(_) => return false,
+    };
+
+    match url
+        .host_str()
+        .map(|h| h.trim_start_matches('[').trim_end_matches(']'))
+    {
+        Some("localhost") => true,
+        Some(host) => host
+            .parse::<std::net::IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback()),
+        None => false,
+    }
+}
+
+// the Central to use for networks that don't name their own controller
+#[cfg(feature = "central")]
+pub fn central_baseurl() -> String {
+    std::env::var("ZEROTIER_CENTRAL_INSTANCE").unwrap_or(CENTRAL_BASEURL.to_string())
+}
 
 // this provides the production configuration for talking to central through the openapi libraries.
-pub fn central_client(token: String) -> Result<zerotier_central_api::Client, anyhow::Error> {
+#[cfg(feature = "central")]
+pub fn central_client(token: String) -> Result<crate::api::central::Client, anyhow::Error> {
+    central_client_at(token, &central_baseurl(), &Controller::default())
+}
+
+#[cfg(feature = "central")]
+pub fn central_client_at(
+    token: String,
+    baseurl: &str,
+    tls: &Controller,
+) -> Result<crate::api::central::Client, anyhow::Error> {
     let mut headers = HeaderMap::new();
     headers.insert(
         "Authorization",
         HeaderValue::from_str(&format!("bearer {}", token))?,
     );
 
-    Ok(zerotier_central_api::Client::new_with_client(
-        &std::env::var("ZEROTIER_CENTRAL_INSTANCE").unwrap_or(CENTRAL_BASEURL.to_string()),
-        reqwest::Client::builder()
-            .https_only(true)
-            .default_headers(headers)
-            .build()?,
+    Ok(crate::api::central::Client::new_with_client(
+        baseurl.trim_end_matches('/'),
+        tls_config(
+            reqwest::Client::builder()
+                .https_only(!is_loopback(baseurl))
+                .default_headers(headers),
+            tls,
+        )?
+        .build()?,
     ))
 }
 
+#[cfg(feature = "central")]
+fn read_tls_file(path: &Path) -> Result<Vec<u8>, anyhow::Error> {
+    std::fs::read(path).map_err(|e| anyhow!("{}: {}", path.display(), e))
+}
+
+#[cfg(feature = "central")]
+fn tls_config(
+    mut builder: reqwest::ClientBuilder,
+    tls: &Controller,
+) -> Result<reqwest::ClientBuilder, anyhow::Error> {
+    #[cfg(feature = "rustls")]
+    {
+        builder = builder.use_rustls_tls();
+    }
+
+    if let Some(ca) = &tls.ca {
+        let pem = String::from_utf8_lossy(&read_tls_file(ca)?).to_string();
+        // a bundle is a run of certificates; reqwest takes them one at a time
+        let end = "-----END CERTIFICATE-----";
+        for cert in pem.split_inclusive(end).filter(|c| c.contains(end)) {
+            builder = builder.add_root_certificate(
+                reqwest::Certificate::from_pem(cert.trim().as_bytes())
+                    .map_err(|e| anyhow!("{}: {}", ca.display(), e))?,
+            );
+        }
+    }
+
+    match (&tls.cert, &tls.key) {
+        (Some(cert), Some(key)) => {
+            // rustls takes the certificate and its key as one PEM
+            #[cfg(feature = "rustls")]
+            let identity = reqwest::Identity::from_pem(
+                &[read_tls_file(cert)?, b"\n".to_vec(), read_tls_file(key)?].concat(),
+            );
+            #[cfg(not(feature = "rustls"))]
+            let identity =
+                reqwest::Identity::from_pkcs8_pem(&read_tls_file(cert)?, &read_tls_file(key)?);
+            Ok(builder.identity(identity.map_err(|e| anyhow!("{}: {}", cert.display(), e))?))
+        }
+        (None, None) => Ok(builder),
+        _ => Err(anyhow!(
+            "a client certificate needs both cert and key to be set"
+        )),
+    }
+}
+
 // determine the path of the authtoken.secret
-pub fn authtoken_path(arg: Option<&Path>) -> &Path {
+pub fn authtoken_path(arg: Option<&Path>) -> PathBuf {
     if let Some(arg) = arg {
-        return arg;
+        return arg.to_path_buf();
+    }
+
+    if let Some(path) = LOCAL.read().unwrap().authtoken.clone() {
+        return path;
+    }
+
+    if let Ok(path) = std::env::var("ZEROTIER_ONE_AUTHTOKEN") {
+        return PathBuf::from(path);
     }
 
     if cfg!(target_os = "linux") {
-        Path::new("/var/lib/zerotier-one/authtoken.secret")
+        PathBuf::from("/var/lib/zerotier-one/authtoken.secret")
     } else if cfg!(target_os = "windows") {
-        Path::new("C:/ProgramData/ZeroTier/One/authtoken.secret")
+        PathBuf::from("C:/ProgramData/ZeroTier/One/authtoken.secret")
     } else if cfg!(target_os = "macos") {
-        Path::new("/Library/Application Support/ZeroTier/One/authtoken.secret")
+        PathBuf::from("/Library/Application Support/ZeroTier/One/authtoken.secret")
     } else {
-        panic!("authtoken.secret not found; please provide the -s option to provide a custom path")
+        panic!("authtoken.secret not found; please provide the --authtoken option to provide a custom path")
     }
 }
 
-pub fn local_client_from_file(
-    authtoken_path: &Path,
-) -> Result<zerotier_one_api::Client, anyhow::Error> {
-    let authtoken = std::fs::read_to_string(authtoken_path)?;
-    local_client(authtoken)
+// whether the authtoken comes from ZEROTIER_ONE_TOKEN rather than a file
+pub fn authtoken_from_env() -> bool {
+    std::env::var_os(TOKEN_ENV).is_some_and(|t| !t.is_empty())
 }
 
-fn local_client(authtoken: String) -> Result<zerotier_one_api::Client, anyhow::Error> {
+// the authtoken, from ZEROTIER_ONE_TOKEN or else read from its file
+pub fn authtoken() -> Result<String, anyhow::Error> {
+    if authtoken_from_env() {
+        return Ok(std::env::var(TOKEN_ENV)?);
+    }
+
+    let path = authtoken_path(None);
+    std::fs::read_to_string(&path).map_err(|e| anyhow!("{}: {}", path.display(), e))
+}
+
+// the local service, with the authtoken from wherever it's kept
+pub fn local() -> Result<crate::api::one::Client, anyhow::Error> {
+    local_client(authtoken()?)
+}
+
+fn local_client(authtoken: String) -> Result<crate::api::one::Client, anyhow::Error> {
     let mut headers = HeaderMap::new();
-    headers.insert("X-ZT1-Auth", HeaderValue::from_str(&authtoken)?);
+    headers.insert("X-ZT1-Auth", HeaderValue::from_str(authtoken.trim())?);
 
-    Ok(zerotier_one_api::Client::new_with_client(
-        "http://127.0.0.1:9993",
+    Ok(crate::api::one::Client::new_with_client(
+        local_baseurl().trim_end_matches('/'),
         reqwest::Client::builder()
             .default_headers(headers)
             .build()?,
     ))
 }
 
-pub async fn get_networks(s: mpsc::UnboundedSender<Vec<Network>>) -> Result<(), anyhow::Error> {
-    let client = local_client_from_file(authtoken_path(None))?;
-    let networks = client.get_networks().await?;
+// gives up on requests from another thread. requests made inside `cancellable` are dropped, and
+// their connections with them, as soon as it's cancelled, rather than running to their timeout.
+#[derive(Debug, Clone, Default)]
+pub struct Cancel(Arc<CancelState>);
 
-    s.send(networks.to_vec())?;
-    Ok(())
+#[derive(Debug, Default)]
+struct CancelState {
+    cancelled: AtomicBool,
+    // wakes the requests underway
+    notify: tokio::sync::Notify,
 }
 
-pub fn leave_network(network_id: String) -> Result<ResponseValue<()>, zerotier_one_api::Error> {
-    let t = tokio::runtime::Builder::new_multi_thread()
-        .enable_all()
-        .build()
-        .unwrap();
-
-    let (s, mut r) = mpsc::unbounded_channel();
-
-    t.spawn(async move {
-        let client = local_client_from_file(authtoken_path(None)).unwrap();
-        s.send(client.delete_network(&network_id).await).unwrap()
-    });
+impl Cancel {
+    pub fn cancel(&self) {
+        self.0.cancelled.store(true, Ordering::SeqCst);
+        self.0.notify.notify_waiters();
+    }
 
-    let res: Result<ResponseValue<()>, zerotier_one_api::Error>;
+    pub fn is_cancelled(&self) -> bool {
+        self.0.cancelled.load(Ordering::SeqCst)
+    }
 
-    loop {
-        if let Ok(r) = r.try_recv() {
-            res = r;
-            break;
+    // finishes once it's cancelled, however long ago that was
+    async fn cancelled(&self) {
+        let notified = self.0.notify.notified();
+        tokio::pin!(notified);
+        // waiting from before the check, so a cancel between the two isn't missed
+        notified.as_mut().enable();
+        if !self.is_cancelled() {
+            notified.await;
         }
     }
+}
 
-    t.shutdown_background();
-    res
+thread_local! {
+    static CANCEL: RefCell<Option<Cancel>> = const { RefCell::new(None) };
 }
 
-pub fn join_network(network_id: String) -> Result<ResponseValue<Network>, zerotier_one_api::Error> {
-    let t = tokio::runtime::Builder::new_multi_thread()
-        .enable_all()
-        .build()
-        .unwrap();
-    let (s, mut r) = mpsc::unbounded_channel();
-
-    t.spawn(async move {
-        let client = local_client_from_file(authtoken_path(None)).unwrap();
-        s.send(
-            client
-                .update_network(
-                    &network_id,
-                    &Network {
-                        subtype_0: zerotier_one_api::types::NetworkSubtype0 {
-                            allow_default: None,
-                            allow_dns: None,
-                            allow_global: None,
-                            allow_managed: None,
-                        },
-                        subtype_1: zerotier_one_api::types::NetworkSubtype1 {
-                            allow_default: None,
-                            allow_dns: None,
-                            allow_global: None,
-                            allow_managed: None,
-                            assigned_addresses: Vec::new(),
-                            bridge: None,
-                            broadcast_enabled: None,
-                            dns: None,
-                            id: None,
-                            mac: None,
-                            mtu: None,
-                            multicast_subscriptions: Vec::new(),
-                            name: None,
-                            netconf_revision: None,
-                            port_device_name: None,
-                            port_error: None,
-                            routes: Vec::new(),
-                            status: None,
-                            type_: None,
-                        },
-                    },
-                )
-                .await,
-        )
-    });
+// runs `f` with every request it makes on this thread watching `cancel`
+pub fn cancellable<T>(cancel: &Cancel, f: impl FnOnce() -> T) -> T {
+    let prev = CANCEL.with(|c| c.replace(Some(cancel.clone())));
+    let res = f();
+    CANCEL.with(|c| *c.borrow_mut() = prev);
+    res
+}
 
-    let res: Result<ResponseValue<Network>, zerotier_one_api::Error>;
+#[derive(Debug)]
+struct Cancelled;
 
-    loop {
-        if let Ok(r) = r.try_recv() {
-            res = r;
-            break;
-        }
+impl std::fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("cancelled")
     }
+}
 
-    t.shutdown_background();
-    res
+impl std::error::Error for Cancelled {}
+
+pub fn is_cancelled(e: &anyhow::Error) -> bool {
+    e.downcast_ref::<Cancelled>().is_some()
 }
 
-pub fn sync_get_networks() -> Result<Vec<Network>, anyhow::Error> {
-    let (s, mut r) = mpsc::unbounded_channel();
+// runs a single request on its own runtime, with the same timeout as everything else in here.
+fn block_on_timeout<T, F>(f: F) -> Result<T, anyhow::Error>
+where
+    F: std::future::Future<Output = Result<T, anyhow::Error>>,
+{
+    let cancel = CANCEL.with(|c| c.borrow().clone());
+    if cancel.as_ref().is_some_and(Cancel::is_cancelled) {
+        return Err(Cancelled.into());
+    }
 
     let t = tokio::runtime::Builder::new_multi_thread()
         .enable_all()
         .build()?;
-    t.spawn(crate::client::get_networks(s));
-
-    let networks: Vec<Network>;
 
-    let timeout = Instant::now();
-
-    'outer: loop {
-        match r.try_recv() {
-            Ok(n) => {
-                networks = n;
-                break 'outer;
+    let res = t.block_on(async {
+        let cancelled = async {
+            match cancel {
+                Some(cancel) => cancel.cancelled().await,
+                None => std::future::pending().await,
             }
+        };
 
-            Err(_) => std::thread::sleep(Duration::new(0, 10)),
+        tokio::select! {
+            res = tokio::time::timeout(Duration::new(3, 0), f) => Some(res),
+            _ = cancelled => None,
         }
+    });
+    t.shutdown_background();
 
-        if timeout.elapsed() > Duration::new(3, 0) {
-            return Err(anyhow!("timeout reading from zerotier"));
-        }
+    match res {
+        Some(Ok(res)) => res,
+        Some(Err(e)) => Err(anyhow::Error::new(e).context("timeout reading from zerotier")),
+        None => Err(Cancelled.into()),
     }
+}
 
-    t.shutdown_background();
-    Ok(networks)
+pub fn leave_network(network_id: String) -> Result<(), anyhow::Error> {
+    crate::tape::exchange("leave_network", serde_json::json!(network_id), || {
+        if !record("local", "DELETE", format!("/network/{}", network_id), None) {
+            return Ok(());
+        }
+
+        block_on_timeout(async move {
+            let client = local()?;
+            client.delete_network(&network_id).await?;
+            Ok(())
+        })
+    })
 }
 
-pub fn sync_get_members(client: Client, id: String) -> Result<Vec<Member>, anyhow::Error> {
-    let (s, mut r) = mpsc::unbounded_channel();
+pub fn join_network(network_id: String) -> Result<(), anyhow::Error> {
+    join_network_with(network_id, JoinFlags::default())
+}
 
-    let t = tokio::runtime::Builder::new_multi_thread()
-        .enable_all()
-        .build()?;
-    t.spawn(async move { s.send(client.get_network_member_list(&id).await).unwrap() });
+// what the network may change on this machine once it's joined. None leaves it to ZeroTier.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct JoinFlags {
+    #[serde(default)]
+    pub allow_managed: Option<bool>,
+    #[serde(default)]
+    pub allow_global: Option<bool>,
+    #[serde(default)]
+    pub allow_default: Option<bool>,
+    #[serde(default)]
+    pub allow_dns: Option<bool>,
+}
 
-    let members: Vec<Member>;
+pub fn join_network_with(network_id: String, flags: JoinFlags) -> Result<(), anyhow::Error> {
+    crate::tape::exchange(
+        "join_network",
+        serde_json::json!({ "network": network_id, "flags": [flags.allow_managed, flags.allow_global, flags.allow_default, flags.allow_dns] }),
+        || {
+            let subtype_0 = crate::api::one::types::NetworkSubtype0 {
+                allow_default: flags.allow_default,
+                allow_dns: flags.allow_dns,
+                allow_global: flags.allow_global,
+                allow_managed: flags.allow_managed,
+            };
+
+            if !record(
+                "local",
+                "POST",
+                format!("/network/{}", network_id),
+                serde_json::to_value(&subtype_0).ok(),
+            ) {
+                return Ok(());
+            }
 
-    let timeout = Instant::now();
+            block_on_timeout(async move {
+                let client = local()?;
+                client
+                    .update_network(
+                        &network_id,
+                        &Network {
+                            subtype_0,
+                            subtype_1: crate::api::one::types::NetworkSubtype1 {
+                                allow_default: flags.allow_default,
+                                allow_dns: flags.allow_dns,
+                                allow_global: flags.allow_global,
+                                allow_managed: flags.allow_managed,
+                                assigned_addresses: Vec::new(),
+                                bridge: None,
+                                broadcast_enabled: None,
+                                dns: None,
+                                id: None,
+                                mac: None,
+                                mtu: None,
+                                multicast_subscriptions: Vec::new(),
+                                name: None,
+                                netconf_revision: None,
+                                port_device_name: None,
+                                port_error: None,
+                                routes: Vec::new(),
+                                status: None,
+                                type_: None,
+                            },
+                        },
+                    )
+                    .await?;
+                Ok(())
+            })
+        },
+    )
+}
 
-    'outer: loop {
-        match r.try_recv() {
-            Ok(m) => match m {
-                Ok(m) => {
-                    members = m.to_vec();
-                    break 'outer;
-                }
-                Err(e) => return Err(anyhow!(e)),
-            },
+pub fn sync_get_networks() -> Result<Vec<Network>, anyhow::Error> {
+    crate::tape::exchange("get_networks", serde_json::Value::Null, || {
+        block_on_timeout(async {
+            let client = local()?;
+            Ok(client.get_networks().await?.into_inner())
+        })
+    })
+}
 
-            Err(_) => std::thread::sleep(Duration::new(0, 10)),
-        }
+pub fn sync_get_status() -> Result<Status, anyhow::Error> {
+    crate::tape::exchange("get_status", serde_json::Value::Null, || {
+        block_on_timeout(async {
+            let client = local()?;
+            Ok(client.get_status().await?.into_inner())
+        })
+    })
+}
 
-        if timeout.elapsed() > Duration::new(3, 0) {
-            return Err(anyhow!("timeout reading from zerotier"));
-        }
+pub fn sync_get_peers() -> Result<Vec<Peer>, anyhow::Error> {
+    crate::tape::exchange("get_peers", serde_json::Value::Null, || {
+        block_on_timeout(async {
+            let client = local()?;
+            Ok(client.get_peers().await?.into_inner())
+        })
+    })
+}
+
+#[cfg(feature = "central")]
+pub fn sync_get_central_status(client: Client) -> Result<CentralStatus, anyhow::Error> {
+    crate::tape::exchange(
+        "get_central_status",
+        serde_json::json!(client.baseurl()),
+        || block_on_timeout(async move { Ok(client.get_status().await?.into_inner()) }),
+    )
+}
+
+const RELEASES_URL: &str = "https://api.github.com/repos/erikh/ztui/releases/latest";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    pub html_url: String,
+}
+
+// the newest release of ztui on GitHub
+pub fn latest_release() -> Result<Release, anyhow::Error> {
+    crate::tape::exchange("latest_release", serde_json::Value::Null, || {
+        block_on_timeout(async {
+            Ok(reqwest::Client::builder()
+                .user_agent(concat!("ztui/", env!("CARGO_PKG_VERSION")))
+                .build()?
+                .get(RELEASES_URL)
+                .send()
+                .await?
+                .error_for_status()?
+                .json::<Release>()
+                .await?)
+        })
+    })
+}
+
+// sends `body` as JSON to a webhook, such as the summary from `ztui watch`
+pub fn post_webhook(url: &str, body: &serde_json::Value) -> Result<(), anyhow::Error> {
+    let (url, body) = (url.to_string(), body.clone());
+    crate::tape::exchange("webhook", serde_json::json!(url), || {
+        block_on_timeout(async move {
+            reqwest::Client::builder()
+                .user_agent(concat!("ztui/", env!("CARGO_PKG_VERSION")))
+                .build()?
+                .post(&url)
+                .json(&body)
+                .send()
+                .await?
+                .error_for_status()?;
+            Ok(())
+        })
+    })
+}
+
+// whether Central turned the key away, as opposed to not answering at all. a key it knows but
+// won't let do something is forbidden instead.
+pub fn is_unauthorized(e: &anyhow::Error) -> bool {
+    let status = match e.downcast_ref::<crate::api::central::Error>() {
+        Some(e) => e.status(),
+        None => e.downcast_ref::<reqwest::Error>().and_then(|e| e.status()),
+    };
+    status.is_some_and(|s| s == reqwest::StatusCode::UNAUTHORIZED)
+}
+
+// whether a request failed for want of an answer, rather than being answered with a refusal
+#[cfg(feature = "central")]
+pub fn is_unreachable(e: &anyhow::Error) -> bool {
+    match e.downcast_ref::<crate::api::central::Error>() {
+        Some(crate::api::central::Error::CommunicationError(_)) => true,
+        Some(_) => false,
+        None => e.downcast_ref::<tokio::time::error::Elapsed>().is_some(),
     }
+}
 
-    t.shutdown_background();
-    Ok(members)
+// Central refused the request because the API key may only read the network
+pub fn is_forbidden(e: &anyhow::Error) -> bool {
+    let status = match e.downcast_ref::<crate::api::central::Error>() {
+        Some(e) => e.status(),
+        None => e.downcast_ref::<reqwest::Error>().and_then(|e| e.status()),
+    };
+    status.is_some_and(|s| s == reqwest::StatusCode::FORBIDDEN)
+}
+
+// checks a key against the network it was entered for, in words someone pasting a key can act on
+#[cfg(feature = "central")]
+pub fn sync_verify_api_key(
+    key: String,
+    baseurl: &str,
+    tls: &Controller,
+    network_id: String,
+) -> Result<(), anyhow::Error> {
+    crate::tape::exchange(
+        "verify_api_key",
+        serde_json::json!({ "url": baseurl, "network": network_id }),
+        || {
+            if key.trim().is_empty() {
+                return Err(anyhow!("no API key was entered"));
+            }
+
+            let client = central_client_at(key, baseurl, tls)?;
+            block_on_timeout(async move {
+                match client.get_network_by_id(&network_id).await {
+                    Ok(_) => Ok(()),
+                    Err(e) => Err(match e.status().map(|s| s.as_u16()) {
+                        Some(401) => anyhow!("Central does not recognize this API key"),
+                        Some(403) => anyhow!("this API key can't access network {}", network_id),
+                        Some(404) => {
+                            anyhow!("network {} was not found with this API key", network_id)
+                        }
+                        _ => anyhow!("could not verify the API key with {}: {}", baseurl, e),
+                    }),
+                }
+            })
+        },
+    )
 }
 
+#[cfg(feature = "central")]
+pub fn sync_get_members(client: Client, id: String) -> Result<Vec<Member>, anyhow::Error> {
+    crate::tape::exchange("get_members", serde_json::json!(id), || {
+        block_on_timeout(async move { Ok(client.get_network_member_list(&id).await?.into_inner()) })
+    })
+}
+
+#[cfg(feature = "central")]
 pub fn sync_update_member_name(
     client: Client,
     network_id: String,
     id: String,
     name: String,
-) -> Result<ResponseValue<Member>, anyhow::Error> {
-    let (s, mut r) = mpsc::unbounded_channel();
-
-    let t = tokio::runtime::Builder::new_multi_thread()
-        .enable_all()
-        .build()?;
-    t.spawn(async move {
-        let mut member = client.get_network_member(&network_id, &id).await.unwrap();
-        member.name = Some(name);
-        s.send(
-            client
-                .update_network_member(&network_id, &id, &member)
-                .await,
-        )
-        .unwrap();
-    });
-
-    let timeout = Instant::now();
-
-    loop {
-        if let Ok(res) = r.try_recv() {
-            t.shutdown_background();
-            return Ok(res?);
-        } else {
-            std::thread::sleep(Duration::new(0, 10));
-        }
+) -> Result<(), anyhow::Error> {
+    crate::tape::exchange(
+        "update_member_name",
+        serde_json::json!({ "network": network_id, "member": id, "name": name }),
+        || {
+            if !record(
+                "central",
+                "POST",
+                format!("/network/{}/member/{}", network_id, id),
+                Some(serde_json::json!({ "name": name })),
+            ) {
+                return Ok(());
+            }
 
-        if timeout.elapsed() > Duration::new(3, 0) {
-            t.shutdown_background();
-            return Err(anyhow!("timeout reading from zerotier"));
-        }
-    }
+            block_on_timeout(async move {
+                let mut member = client.get_network_member(&network_id, &id).await?;
+                member.name = Some(name);
+                client
+                    .update_network_member(&network_id, &id, &member)
+                    .await?;
+                Ok(())
+            })
+        },
+    )
 }
 
+#[cfg(feature = "central")]
 pub fn sync_member_auth(
     client: Client,
     network_id: String,
     id: String,
     auth: bool,
-) -> Result<ResponseValue<Member>, anyhow::Error> {
-    let (s, mut r) = mpsc::unbounded_channel();
-
-    let t = tokio::runtime::Builder::new_multi_thread()
-        .enable_all()
-        .build()?;
-    t.spawn(async move {
-        let mut member = client.get_network_member(&network_id, &id).await.unwrap();
-        member.config.as_mut().unwrap().authorized = Some(auth);
-        s.send(
-            client
-                .update_network_member(&network_id, &id, &member)
-                .await,
-        )
-        .unwrap();
-    });
-
-    let timeout = Instant::now();
+) -> Result<(), anyhow::Error> {
+    if auth {
+        crate::security::authorizing(&network_id, &id);
+    }
+    let (network, member) = (network_id.clone(), id.clone());
+
+    let res = crate::tape::exchange(
+        "member_auth",
+        serde_json::json!({ "network": network_id, "member": id, "auth": auth }),
+        || {
+            if !record(
+                "central",
+                "POST",
+                format!("/network/{}/member/{}", network_id, id),
+                Some(serde_json::json!({ "config": { "authorized": auth } })),
+            ) {
+                return Ok(());
+            }
 
-    loop {
-        if let Ok(res) = r.try_recv() {
-            t.shutdown_background();
-            return Ok(res?);
-        } else {
-            std::thread::sleep(Duration::new(0, 10));
-        }
+            block_on_timeout(async move {
+                let mut member = client.get_network_member(&network_id, &id).await?;
+                member
+                    .config
+                    .as_mut()
+                    .ok_or_else(|| anyhow!("member {} has no config", id))?
+                    .authorized = Some(auth);
+                client
+                    .update_network_member(&network_id, &id, &member)
+                    .await?;
+                Ok(())
+            })
+        },
+    );
 
-        if timeout.elapsed() > Duration::new(3, 0) {
-            t.shutdown_background();
-            return Err(anyhow!("timeout reading from zerotier"));
-        }
+    if auth && res.is_err() {
+        crate::security::authorize_failed(&network, &member);
     }
+    res
 }
 
+#[cfg(feature = "central")]
 pub fn sync_deauthorize_member(
     client: Client,
     network_id: String,
     id: String,
-) -> Result<ResponseValue<Member>, anyhow::Error> {
+) -> Result<(), anyhow::Error> {
     sync_member_auth(client, network_id, id, false)
 }
 
+#[cfg(feature = "central")]
 pub fn sync_authorize_member(
     client: Client,
     network_id: String,
     id: String,
-) -> Result<ResponseValue<Member>, anyhow::Error> {
+) -> Result<(), anyhow::Error> {
     sync_member_auth(client, network_id, id, true)
 }
 
+#[cfg(feature = "central")]
 pub fn sync_delete_member(
     client: Client,
     network_id: String,
     id: String,
-) -> Result<ResponseValue<()>, anyhow::Error> {
-    let (s, mut r) = mpsc::unbounded_channel();
-
-    let t = tokio::runtime::Builder::new_multi_thread()
-        .enable_all()
-        .build()?;
-    t.spawn(async move {
-        s.send(client.delete_network_member(&network_id, &id).await)
-            .unwrap();
-    });
-
-    let timeout = Instant::now();
-
-    loop {
-        if let Ok(res) = r.try_recv() {
-            t.shutdown_background();
-            return Ok(res?);
-        } else {
-            std::thread::sleep(Duration::new(0, 10));
-        }
+) -> Result<(), anyhow::Error> {
+    crate::tape::exchange(
+        "delete_member",
+        serde_json::json!({ "network": network_id, "member": id }),
+        || {
+            if !record(
+                "central",
+                "DELETE",
+                format!("/network/{}/member/{}", network_id, id),
+                None,
+            ) {
+                return Ok(());
+            }
 
-        if timeout.elapsed() > Duration::new(3, 0) {
-            t.shutdown_background();
-            return Err(anyhow!("timeout reading from zerotier"));
-        }
-    }
+            block_on_timeout(async move {
+                client.delete_network_member(&network_id, &id).await?;
+                Ok(())
+            })
+        },
+    )
 }
 
 macro_rules! true_or_none {
@@ -365,116 +692,325 @@ macro_rules! true_or_none {
     };
 }
 
-pub fn toggle_flag(id: String, flag: NetworkFlag) -> Result<ResponseValue<Network>, anyhow::Error> {
-    let (s, mut r) = mpsc::unbounded_channel();
+pub fn toggle_flag(id: String, flag: NetworkFlag) -> Result<(), anyhow::Error> {
+    crate::tape::exchange(
+        "toggle_flag",
+        serde_json::json!({ "network": id, "flag": format!("{:?}", flag) }),
+        || {
+            block_on_timeout(async move {
+                let local = local()?;
+                let mut network = local.get_network(&id).await?.into_inner();
+
+                match flag {
+                    NetworkFlag::AllowDNS => {
+                        true_or_none!(network.subtype_0.allow_dns);
+                    }
+                    NetworkFlag::AllowGlobal => {
+                        true_or_none!(network.subtype_0.allow_global);
+                    }
+                    NetworkFlag::AllowManaged => {
+                        true_or_none!(network.subtype_0.allow_managed);
+                    }
+                    NetworkFlag::AllowDefault => {
+                        true_or_none!(network.subtype_0.allow_default);
+                    }
+                }
 
-    let t = tokio::runtime::Builder::new_multi_thread()
-        .enable_all()
-        .build()?;
+                if !record(
+                    "local",
+                    "POST",
+                    format!("/network/{}", id),
+                    serde_json::to_value(&network.subtype_0).ok(),
+                ) {
+                    return Ok(());
+                }
 
-    t.spawn(async move {
-        let local = local_client_from_file(authtoken_path(None)).unwrap();
-        let mut network = match local.get_network(&id.clone()).await {
-            Ok(network) => network,
-            Err(e) => {
-                s.send(Err(e)).unwrap();
-                return;
-            }
-        };
+                local.update_network(&id, &network).await?;
+                Ok(())
+            })
+        },
+    )
+}
 
-        match flag {
-            NetworkFlag::AllowDNS => {
-                true_or_none!(network.subtype_0.allow_dns);
-            }
-            NetworkFlag::AllowGlobal => {
-                true_or_none!(network.subtype_0.allow_global);
-            }
-            NetworkFlag::AllowManaged => {
-                true_or_none!(network.subtype_0.allow_managed);
-            }
-            NetworkFlag::AllowDefault => {
-                true_or_none!(network.subtype_0.allow_default);
-            }
-        }
+// sets `flag` to `on`, whatever it was before
+pub fn set_flag(id: String, flag: NetworkFlag, on: bool) -> Result<(), anyhow::Error> {
+    crate::tape::exchange(
+        "set_flag",
+        serde_json::json!({ "network": id, "flag": format!("{:?}", flag), "on": on }),
+        || {
+            block_on_timeout(async move {
+                let local = local()?;
+                let mut network = local.get_network(&id).await?.into_inner();
+
+                *match flag {
+                    NetworkFlag::AllowDNS => &mut network.subtype_0.allow_dns,
+                    NetworkFlag::AllowGlobal => &mut network.subtype_0.allow_global,
+                    NetworkFlag::AllowManaged => &mut network.subtype_0.allow_managed,
+                    NetworkFlag::AllowDefault => &mut network.subtype_0.allow_default,
+                } = Some(on);
+
+                if !record(
+                    "local",
+                    "POST",
+                    format!("/network/{}", id),
+                    serde_json::to_value(&network.subtype_0).ok(),
+                ) {
+                    return Ok(());
+                }
 
-        s.send(local.update_network(&id, &network).await).unwrap();
-    });
+                local.update_network(&id, &network).await?;
+                Ok(())
+            })
+        },
+    )
+}
 
-    let timeout = Instant::now();
+// creates a network on Central, handing out addresses from `subnet` if one is given. returns
+// the new network's id, or None on a dry run.
+#[cfg(feature = "central")]
+pub fn sync_create_network(
+    client: Client,
+    name: String,
+    subnet: Option<(std::net::Ipv4Addr, u8)>,
+) -> Result<Option<String>, anyhow::Error> {
+    crate::tape::exchange(
+        "create_network",
+        serde_json::json!({ "name": name, "subnet": subnet.map(|(a, p)| format!("{}/{}", a, p)) }),
+        || {
+            let mut config = serde_json::json!({ "name": name });
+            if let Some((addr, prefix)) = subnet {
+                let mask = u32::MAX << (32 - prefix);
+                let network = u32::from(addr) & mask;
+                let broadcast = network | !mask;
+
+                config["routes"] = serde_json::json!([{
+                    "target": format!("{}/{}", std::net::Ipv4Addr::from(network), prefix)
+                }]);
+                config["ipAssignmentPools"] = serde_json::json!([{
+                    "ipRangeStart": std::net::Ipv4Addr::from(network + 1).to_string(),
+                    "ipRangeEnd": std::net::Ipv4Addr::from(broadcast - 1).to_string(),
+                }]);
+                config["v4AssignMode"] = serde_json::json!({ "zt": true });
+            }
 
-    loop {
-        if let Ok(res) = r.try_recv() {
-            t.shutdown_background();
-            return Ok(res?);
-        } else {
-            std::thread::sleep(Duration::new(0, 10));
-        }
+            let body = std::collections::HashMap::from([("config".to_string(), config)]);
+            if !record(
+                "central",
+                "POST",
+                "/network".to_string(),
+                serde_json::to_value(&body).ok(),
+            ) {
+                return Ok(None);
+            }
 
-        if timeout.elapsed() > Duration::new(3, 0) {
-            t.shutdown_background();
-            return Err(anyhow!("timeout reading from zerotier"));
-        }
-    }
+            block_on_timeout(async move {
+                let network = client.new_network(&body).await?.into_inner();
+                Ok(network.id)
+            })
+        },
+    )
+}
+
+#[cfg(feature = "central")]
+pub fn sync_get_network_list(client: Client) -> Result<Vec<CentralNetwork>, anyhow::Error> {
+    crate::tape::exchange(
+        "get_network_list",
+        serde_json::json!(client.baseurl()),
+        || block_on_timeout(async move { Ok(client.get_network_list().await?.into_inner()) }),
+    )
 }
 
+#[cfg(feature = "central")]
 pub fn sync_get_network(
     client: Client,
     network_id: String,
-) -> Result<ResponseValue<CentralNetwork>, anyhow::Error> {
-    let (s, mut r) = mpsc::unbounded_channel();
+) -> Result<CentralNetwork, anyhow::Error> {
+    crate::tape::exchange("get_network", serde_json::json!(network_id), || {
+        block_on_timeout(
+            async move { Ok(client.get_network_by_id(&network_id).await?.into_inner()) },
+        )
+    })
+}
 
-    let t = tokio::runtime::Builder::new_multi_thread()
-        .enable_all()
-        .build()?;
-    t.spawn(async move { s.send(client.get_network_by_id(&network_id).await).unwrap() });
+// a request to Central as plain JSON, for fields the generated client doesn't know about
+#[cfg(feature = "central")]
+async fn central_json(
+    client: &Client,
+    method: reqwest::Method,
+    path: &str,
+    body: Option<serde_json::Value>,
+) -> Result<serde_json::Value, anyhow::Error> {
+    let url = format!("{}{}", client.baseurl().trim_end_matches('/'), path);
+    let mut req = client.client().request(method, url);
+    if let Some(body) = body {
+        req = req.json(&body);
+    }
 
-    let timeout = Instant::now();
+    let text = req.send().await?.error_for_status()?.text().await?;
+    Ok(serde_json::from_str(&text).unwrap_or_default())
+}
 
-    loop {
-        if let Ok(res) = r.try_recv() {
-            t.shutdown_background();
-            return Ok(res?);
-        } else {
-            std::thread::sleep(Duration::new(0, 10));
-        }
+// a network's single sign-on settings, from config.ssoConfig
+#[cfg(feature = "central")]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Sso {
+    pub enabled: bool,
+    pub client_id: String,
+    pub issuer: String,
+    pub authorization_endpoint: String,
+}
 
-        if timeout.elapsed() > Duration::new(3, 0) {
-            t.shutdown_background();
-            return Err(anyhow!("timeout reading from zerotier"));
-        }
-    }
+#[cfg(feature = "central")]
+pub fn sync_get_sso(client: Client, network_id: String) -> Result<Sso, anyhow::Error> {
+    crate::tape::exchange("get_sso", serde_json::json!(network_id), || {
+        block_on_timeout(async move {
+            let net = central_json(
+                &client,
+                reqwest::Method::GET,
+                &format!("/network/{}", network_id),
+                None,
+            )
+            .await?;
+
+            let sso = &net["config"]["ssoConfig"];
+            let text = |key: &str| sso[key].as_str().unwrap_or_default().to_string();
+            Ok(Sso {
+                enabled: sso["enabled"].as_bool().unwrap_or_default(),
+                client_id: text("clientId"),
+                issuer: text("issuer"),
+                authorization_endpoint: text("authorizationEndpoint"),
+            })
+        })
+    })
 }
 
-pub fn sync_apply_network_rules(
+#[cfg(feature = "central")]
+pub fn sync_set_sso(client: Client, network_id: String, sso: Sso) -> Result<(), anyhow::Error> {
+    crate::tape::exchange(
+        "set_sso",
+        serde_json::json!({ "network": network_id, "sso": sso }),
+        || {
+            let path = format!("/network/{}", network_id);
+            let body = serde_json::json!({ "config": { "ssoConfig": {
+            "enabled": sso.enabled,
+            "clientId": sso.client_id,
+            "issuer": sso.issuer,
+        } } });
+
+            if !record("central", "POST", path.clone(), Some(body.clone())) {
+                return Ok(());
+            }
+
+            block_on_timeout(async move {
+                central_json(&client, reqwest::Method::POST, &path, Some(body)).await?;
+                Ok(())
+            })
+        },
+    )
+}
+
+// returns whether the member is exempt now
+#[cfg(feature = "central")]
+pub fn sync_toggle_sso_exempt(
     client: Client,
     network_id: String,
-    rules: String,
-) -> Result<ResponseValue<CentralNetwork>, anyhow::Error> {
-    let (s, mut r) = mpsc::unbounded_channel();
+    member_id: String,
+) -> Result<bool, anyhow::Error> {
+    crate::tape::exchange(
+        "toggle_sso_exempt",
+        serde_json::json!({ "network": network_id, "member": member_id }),
+        || {
+            let path = format!("/network/{}/member/{}", network_id, member_id);
+
+            let get = (client.clone(), path.clone());
+            let member = block_on_timeout(async move {
+                central_json(&get.0, reqwest::Method::GET, &get.1, None).await
+            })?;
+            let exempt = !member["config"]["ssoExempt"].as_bool().unwrap_or_default();
+
+            let body = serde_json::json!({ "config": { "ssoExempt": exempt } });
+            if !record("central", "POST", path.clone(), Some(body.clone())) {
+                return Ok(exempt);
+            }
 
-    let t = tokio::runtime::Builder::new_multi_thread()
-        .enable_all()
-        .build()?;
-    t.spawn(async move {
-        let mut net = client.get_network_by_id(&network_id).await.unwrap();
-        net.rules_source = Some(rules);
-        let res = client.update_network(&network_id, &net).await;
-        s.send(res).unwrap();
-    });
+            block_on_timeout(async move {
+                central_json(&client, reqwest::Method::POST, &path, Some(body)).await?;
+                Ok(exempt)
+            })
+        },
+    )
+}
 
-    let timeout = Instant::now();
+#[cfg(feature = "central")]
+pub fn sync_apply_network_rules(
+    client: Client,
+    network_id: String,
+    rules: String,
+) -> Result<(), anyhow::Error> {
+    crate::tape::exchange(
+        "apply_network_rules",
+        serde_json::json!({ "network": network_id, "rules": rules }),
+        || {
+            if !record(
+                "central",
+                "POST",
+                format!("/network/{}", network_id),
+                Some(serde_json::json!({ "rulesSource": rules })),
+            ) {
+                return Ok(());
+            }
 
-    loop {
-        if let Ok(res) = r.try_recv() {
-            t.shutdown_background();
-            return Ok(res?);
-        } else {
-            std::thread::sleep(Duration::new(0, 10));
-        }
+            block_on_timeout(async move {
+                let mut net = client.get_network_by_id(&network_id).await?.into_inner();
+                net.rules_source = Some(rules);
+                match client.update_network(&network_id, &net).await {
+                    // rules that don't compile come back as a 400 with the reason in the body
+                    Err(crate::api::central::Error::UnexpectedResponse(response)) => {
+                        let status = response.status();
+                        if status == reqwest::StatusCode::FORBIDDEN {
+                            return Err(crate::api::central::Error::<()>::UnexpectedResponse(
+                                response,
+                            )
+                            .into());
+                        }
+                        Err(anyhow!(
+                            "{}: {}",
+                            status,
+                            response.text().await.unwrap_or_default().trim()
+                        ))
+                    }
+                    res => res.map(|_| ()).map_err(anyhow::Error::from),
+                }
+            })
+        },
+    )
+}
 
-        if timeout.elapsed() > Duration::new(3, 0) {
-            t.shutdown_background();
-            return Err(anyhow!("timeout reading from zerotier"));
-        }
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{block_on_timeout, cancellable, is_cancelled, Cancel};
+
+    // a request that would never answer gives up as soon as it's cancelled, not at its timeout
+    #[test]
+    fn cancel_wakes_request() {
+        let cancel = Cancel::default();
+        let canceller = cancel.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(20));
+            canceller.cancel();
+        });
+
+        let started = Instant::now();
+        let res = cancellable(&cancel, || {
+            block_on_timeout(std::future::pending::<Result<(), anyhow::Error>>())
+        });
+        assert!(res.as_ref().is_err_and(is_cancelled), "{:?}", res);
+        assert!(started.elapsed() < Duration::from_secs(1));
+
+        // and one made after it's cancelled doesn't start
+        let res = cancellable(&cancel, || block_on_timeout(async { Ok(()) }));
+        assert!(res.as_ref().is_err_and(is_cancelled));
     }
 }
@@ -0,0 +1,41 @@
+#[cfg(feature = "clipboard")]
+use std::process::{Command, Stdio};
+
+use anyhow::anyhow;
+
+// whichever of these is installed reads the clipboard; there's no portable way without a
+// display server library
+#[cfg(feature = "clipboard")]
+const PASTE: &[&[&str]] = &[
+    &["pbpaste"],
+    &["wl-paste", "--no-newline"],
+    &["xclip", "-selection", "clipboard", "-o"],
+    &["xsel", "--clipboard", "--output"],
+    &["powershell.exe", "-NoProfile", "-Command", "Get-Clipboard"],
+];
+
+#[cfg(not(feature = "clipboard"))]
+pub fn read() -> Result<String, anyhow::Error> {
+    Err(anyhow!("ztui was built without clipboard support"))
+}
+
+#[cfg(feature = "clipboard")]
+pub fn read() -> Result<String, anyhow::Error> {
+    for command in PASTE {
+        let output = Command::new(command[0])
+            .args(&command[1..])
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output();
+
+        if let Ok(output) = output {
+            if output.status.success() {
+                return Ok(String::from_utf8_lossy(&output.stdout).to_string());
+            }
+        }
+    }
+
+    Err(anyhow!(
+        "could not read the clipboard; install wl-paste, xclip or xsel"
+    ))
+}
@@ -1,77 +1,668 @@
 use std::{
-    collections::{HashMap, HashSet},
-    path::PathBuf,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
 };
 
 use serde::{Deserialize, Serialize};
 use tui::widgets::TableState;
-use zerotier_central_api::types::Member;
-use zerotier_one_api::types::Network;
 
+use crate::api::central::types::Member;
+use crate::api::one::types::Network;
+#[cfg(feature = "central")]
+use crate::changelog::{Change, Entry};
 use crate::{
+    addresses::{Pool, Utilization},
     app::{ListFilter, Page, STATUS_DISCONNECTED},
-    nets::Nets,
+    changelog::Changelog,
+    conntrack::MemberTraffic,
+    errors::{self, Errors},
+    history::History,
+    join_set::JoinSet,
+    nets::{format_bytes, Nets},
+    pending::Pending,
+    rules::RuleNames,
 };
 
+// how often the supervisor polls, and how many polls can be missed before what's on screen is
+// called out as stale
+pub const REFRESH_INTERVAL: Duration = Duration::from_secs(3);
+const STALE_INTERVALS: u32 = 3;
+// how often networks watched for authorizations are refreshed when they aren't on screen
+pub const WATCH_INTERVAL: Duration = Duration::from_secs(30);
+
+pub fn is_stale(age: Duration) -> bool {
+    age > REFRESH_INTERVAL * STALE_INTERVALS
+}
+
+// settings are saved once they've been left alone this long after a change, and at least this
+// often while they keep changing
+pub const AUTOSAVE_DEBOUNCE: Duration = Duration::from_secs(2);
+pub const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(30);
+
+// how many copies of settings.json are kept from earlier runs, as settings.json.1 (the newest)
+// through settings.json.5
+const BACKUPS: usize = 5;
+
+// writes to a file next to `path`, flushes it to disk and renames it over `path`, so a crash or a
+// power cut mid-write leaves the old file whole
+pub fn write_atomic(path: &Path, contents: &str) -> Result<(), anyhow::Error> {
+    use std::io::Write;
+
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let mut file = tempfile::NamedTempFile::new_in(dir)?;
+    file.write_all(contents.as_bytes())?;
+    file.as_file().sync_all()?;
+    file.persist(path)?;
+
+    // the rename itself is only durable once the directory is
+    #[cfg(unix)]
+    std::fs::File::open(dir)?.sync_all()?;
+
+    Ok(())
+}
+
+fn backup_path(dir: &Path, n: usize) -> PathBuf {
+    dir.join(format!("settings.json.{}", n))
+}
+
+// keeps a copy of settings.json as it was when ztui started, shifting the older copies along. a
+// settings.json that doesn't parse isn't kept.
+pub fn rotate_backups(dir: &Path) -> Result<(), anyhow::Error> {
+    let path = dir.join("settings.json");
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(_) => return Ok(()),
+    };
+    if serde_json::from_str::<Settings>(&contents).is_err() {
+        return Ok(());
+    }
+
+    for n in (1..BACKUPS).rev() {
+        let from = backup_path(dir, n);
+        if from.exists() {
+            std::fs::rename(from, backup_path(dir, n + 1))?;
+        }
+    }
+    write_atomic(&backup_path(dir, 1), &contents)
+}
+
+lazy_static::lazy_static! {
+    static ref ROOT: std::sync::RwLock<Option<PathBuf>> = std::sync::RwLock::new(None);
+}
+
+// keeps everything under `dir` instead: settings, data and cache. replays run from a scratch
+// directory, so nothing from the recording is left behind.
+pub fn set_root(dir: PathBuf) {
+    *ROOT.write().unwrap() = Some(dir);
+}
+
+fn root() -> Option<PathBuf> {
+    ROOT.read().unwrap().clone()
+}
+
 pub fn config_path() -> PathBuf {
+    if let Some(root) = root() {
+        return root;
+    }
+
     directories::UserDirs::new()
         .expect("could not locate your home directory")
         .home_dir()
         .join(".config.zerotier")
 }
 
-fn template_network(s: Option<&String>, network: &Network) -> Option<String> {
-    if s.is_none() {
-        return None;
+// what ztui records as it runs: member history, the changelog, traffic totals, the action log and
+// saved captures. kept apart from the settings so they don't grow with it.
+pub fn data_path() -> PathBuf {
+    if let Some(root) = root() {
+        return root.join("data");
+    }
+
+    directories::ProjectDirs::from("", "", "ztui")
+        .map(|dirs| dirs.data_dir().to_path_buf())
+        .unwrap_or_else(|| config_path().join("data"))
+}
+
+// member lists last fetched from Central, shown until the next fetch. safe to delete.
+pub fn cache_path() -> PathBuf {
+    if let Some(root) = root() {
+        return root.join("cache");
+    }
+
+    directories::ProjectDirs::from("", "", "ztui")
+        .map(|dirs| dirs.cache_dir().to_path_buf())
+        .unwrap_or_else(|| config_path().join("cache"))
+}
+
+// a data file that grows past this is pruned of its oldest records as it's saved
+pub const MAX_DATA_FILE: usize = 4 * 1024 * 1024;
+
+// creates the data and cache directories, moving files older versions kept with the settings
+pub fn init_data_dirs() -> Result<(), anyhow::Error> {
+    std::fs::create_dir_all(data_path())?;
+    std::fs::create_dir_all(cache_path())?;
+
+    for name in [
+        "history.json",
+        "changelog.json",
+        "traffic.json",
+        "actions.log",
+    ] {
+        move_file(&config_path().join(name), &data_path().join(name))?;
+    }
+
+    // pcaps are moved one by one, into whatever's been saved since
+    let (old, new) = (config_path().join("captures"), data_path().join("captures"));
+    if old.is_dir() {
+        std::fs::create_dir_all(&new)?;
+        for entry in std::fs::read_dir(&old)? {
+            let entry = entry?;
+            move_file(&entry.path(), &new.join(entry.file_name()))?;
+        }
+        // anything left, such as a file of the same name, stays where it was
+        let _ = std::fs::remove_dir(&old);
+    }
+
+    Ok(())
+}
+
+fn move_file(old: &Path, new: &Path) -> Result<(), anyhow::Error> {
+    if old.exists() && !new.exists() {
+        std::fs::rename(old, new).or_else(|_| {
+            std::fs::copy(old, new)?;
+            std::fs::remove_file(old)
+        })?;
+    }
+    Ok(())
+}
+
+// fills in the % fields in one pass, so a value that happens to contain one is left as it is. the
+// longest name that fits wins, so %a4 isn't taken for %a and a 4. quoted, each value is made a
+// single shell word, so a name like `x; rm -rf ~` can't run anything.
+fn fill(s: &str, fields: &[(&str, String)], quoted: bool) -> String {
+    let mut out = String::new();
+    let mut rest = s;
+
+    while let Some(pos) = rest.find('%') {
+        out.push_str(&rest[..pos]);
+        let after = &rest[pos + 1..];
+
+        match fields
+            .iter()
+            .filter(|(name, _)| after.starts_with(name))
+            .max_by_key(|(name, _)| name.len())
+        {
+            Some((name, value)) => {
+                out.push_str(&if quoted {
+                    shell_word(value)
+                } else {
+                    value.clone()
+                });
+                rest = &after[name.len()..];
+            }
+            None => {
+                out.push('%');
+                rest = after;
+            }
+        }
     }
 
-    Some(
-        s.clone()
-            .unwrap()
-            .replace("%i", &network.subtype_1.port_device_name.clone().unwrap())
-            .replace("%n", &network.subtype_1.id.clone().unwrap())
-            .replace(
-                "%a",
-                &network
+    out.push_str(rest);
+    out
+}
+
+fn template_network(s: Option<&String>, network: &Network, quoted: bool) -> Option<String> {
+    let addresses = &network.subtype_1.assigned_addresses;
+    let first = addresses.first().map(String::as_str);
+
+    Some(fill(
+        s?,
+        &[
+            (
+                "i",
+                network
                     .subtype_1
-                    .assigned_addresses
-                    .iter()
-                    .nth(0)
-                    .expect("No assigned addresses"),
+                    .port_device_name
+                    .clone()
+                    .unwrap_or_default(),
+            ),
+            ("n", network.subtype_1.id.clone().unwrap_or_default()),
+            ("a", first.unwrap_or_default().to_string()),
+            ("a4", crate::addresses::first(addresses, false)),
+            ("a6", crate::addresses::first(addresses, true)),
+            ("cidr", crate::addresses::subnet(network, first)),
+            ("gw", crate::addresses::gateway(network)),
+        ],
+        quoted,
+    ))
+}
+
+// the member's network, if it's one of ours, gives the subnet and gateway
+fn template_member(
+    s: Option<&String>,
+    member: &Member,
+    network: Option<&Network>,
+    quoted: bool,
+) -> Option<String> {
+    let addresses = member
+        .config
+        .as_ref()
+        .and_then(|c| c.ip_assignments.clone())
+        .unwrap_or_default();
+    let first = addresses.first().map(String::as_str);
+
+    Some(fill(
+        s?,
+        &[
+            ("n", member.network_id.clone().unwrap_or_default()),
+            ("i", member.node_id.clone().unwrap_or_default()),
+            ("N", member.name.clone().unwrap_or_default()),
+            ("a", first.unwrap_or_default().to_string()),
+            ("a4", crate::addresses::first(&addresses, false)),
+            ("a6", crate::addresses::first(&addresses, true)),
+            (
+                "cidr",
+                network
+                    .map(|n| crate::addresses::subnet(n, first))
+                    .unwrap_or_default(),
+            ),
+            (
+                "gw",
+                network.map(crate::addresses::gateway).unwrap_or_default(),
             ),
+        ],
+        quoted,
+    ))
+}
+
+// the row's fields, set in the environment of every command run for it. a command can use these
+// in place of the % fields and have the shell quote them.
+fn network_vars(network: &Network) -> BTreeMap<String, String> {
+    let addresses = &network.subtype_1.assigned_addresses;
+    let first = addresses.first().map(String::as_str);
+
+    [
+        (
+            "ZT_NETWORK_ID",
+            network.subtype_1.id.clone().unwrap_or_default(),
+        ),
+        (
+            "ZT_NETWORK_NAME",
+            network.subtype_1.name.clone().unwrap_or_default(),
+        ),
+        (
+            "ZT_INTERFACE",
+            network
+                .subtype_1
+                .port_device_name
+                .clone()
+                .unwrap_or_default(),
+        ),
+        ("ZT_NETWORK_IP", first.unwrap_or_default().to_string()),
+        ("ZT_NETWORK_IPS", addresses.join(" ")),
+        ("ZT_NETWORK_IP4", crate::addresses::first(addresses, false)),
+        ("ZT_NETWORK_IP6", crate::addresses::first(addresses, true)),
+        ("ZT_CIDR", crate::addresses::subnet(network, first)),
+        ("ZT_GATEWAY", crate::addresses::gateway(network)),
+    ]
+    .into_iter()
+    .map(|(k, v)| (k.to_string(), v))
+    .collect()
+}
+
+fn member_vars(member: &Member, network: Option<&Network>) -> BTreeMap<String, String> {
+    let addresses = member
+        .config
+        .as_ref()
+        .and_then(|c| c.ip_assignments.clone())
+        .unwrap_or_default();
+    let first = addresses.first().map(String::as_str);
+
+    [
+        (
+            "ZT_NETWORK_ID",
+            member.network_id.clone().unwrap_or_default(),
+        ),
+        ("ZT_MEMBER_ID", member.node_id.clone().unwrap_or_default()),
+        ("ZT_MEMBER_NAME", member.name.clone().unwrap_or_default()),
+        ("ZT_MEMBER_IP", first.unwrap_or_default().to_string()),
+        ("ZT_MEMBER_IPS", addresses.join(" ")),
+        ("ZT_MEMBER_IP4", crate::addresses::first(&addresses, false)),
+        ("ZT_MEMBER_IP6", crate::addresses::first(&addresses, true)),
+        (
+            "ZT_CIDR",
+            network
+                .map(|n| crate::addresses::subnet(n, first))
+                .unwrap_or_default(),
+        ),
+        (
+            "ZT_GATEWAY",
+            network.map(crate::addresses::gateway).unwrap_or_default(),
+        ),
+    ]
+    .into_iter()
+    .map(|(k, v)| (k.to_string(), v))
+    .collect()
+}
+
+// names for a batch rename. %c is the member's position in the batch, zero padded to the width
+// of the batch so the names sort. the result is a name for Central, not a command, so it isn't
+// quoted.
+pub fn template_rename(s: &str, member: &Member, position: usize, total: usize) -> String {
+    let width = total.to_string().len();
+
+    fill(
+        s,
+        &[
+            ("n", member.network_id.clone().unwrap_or_default()),
+            ("i", member.node_id.clone().unwrap_or_default()),
+            ("N", member.name.clone().unwrap_or_default()),
+            (
+                "a",
+                member
+                    .config
+                    .as_ref()
+                    .and_then(|c| c.ip_assignments.as_ref())
+                    .and_then(|a| a.first())
+                    .cloned()
+                    .unwrap_or_default(),
+            ),
+            ("c", format!("{:0width$}", position, width = width)),
+        ],
+        false,
     )
 }
 
-fn template_member(s: Option<&String>, member: &Member) -> Option<String> {
-    if s.is_none() {
-        return None;
+const DEFAULT_CAPTURE_COMMAND: &str = "tcpdump -l -n -i %i %f";
+const DEFAULT_CAPTURE_WRITE_COMMAND: &str = "tcpdump -n -i %i -w %w %f";
+const DEFAULT_CAPTURE_FILTER: &str = "not broadcast and not multicast";
+
+fn quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "'\\''"))
+}
+
+// left as it is when the shell would read it as one word anyway, so filled in commands stay
+// readable; quoted otherwise
+fn shell_word(s: &str) -> String {
+    if !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_.,:/@+=".contains(c))
+    {
+        s.to_string()
+    } else {
+        quote(s)
+    }
+}
+
+fn template_capture(
+    s: &str,
+    filter: &str,
+    network: &Network,
+    interface: &str,
+    path: Option<&Path>,
+) -> String {
+    let mut fields = vec![
+        ("i", interface.to_string()),
+        ("n", network.subtype_1.id.clone().unwrap_or_default()),
+        ("f", filter.to_string()),
+    ];
+    if let Some(path) = path {
+        fields.push(("w", path.to_string_lossy().to_string()));
     }
 
-    return Some(
-        s.clone()
-            .unwrap()
-            .replace("%n", &member.network_id.clone().unwrap())
-            .replace("%i", &member.node_id.clone().unwrap())
-            .replace("%N", &member.name.clone().unwrap())
-            .replace(
-                "%a",
-                &member
-                    .config
+    fill(s, &fields, true)
+}
+
+// bytes per second; either direction may be left unset
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
+pub struct Threshold {
+    #[serde(default)]
+    pub rx: Option<u64>,
+    #[serde(default)]
+    pub tx: Option<u64>,
+}
+
+fn template_alert(s: &str, network: &Network, direction: &str, rate: f64, limit: u64) -> String {
+    fill(
+        s,
+        &[
+            (
+                "i",
+                network
+                    .subtype_1
+                    .port_device_name
                     .clone()
-                    .unwrap()
-                    .ip_assignments
-                    .unwrap()
-                    .iter()
-                    .nth(0)
-                    .expect("No assigned addresses"),
+                    .unwrap_or_default(),
             ),
-    );
+            ("n", network.subtype_1.id.clone().unwrap_or_default()),
+            ("N", network.subtype_1.name.clone().unwrap_or_default()),
+            ("d", direction.to_string()),
+            ("r", (rate as u64).to_string()),
+            ("l", limit.to_string()),
+        ],
+        true,
+    )
+}
+
+#[cfg(feature = "central")]
+fn template_authorization(s: &str, network_id: &str, entry: &Entry) -> String {
+    fill(
+        s,
+        &[
+            ("n", network_id.to_string()),
+            ("i", entry.member.clone()),
+            ("N", entry.name.clone()),
+        ],
+        true,
+    )
+}
+
+// runs a hook in the background, without waiting for it or minding how it went
+fn run_hook(command: &str) {
+    if let Ok(mut child) = std::process::Command::new("/bin/sh")
+        .args(["-c", command])
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+    {
+        std::thread::spawn(move || child.wait());
+    }
+}
+
+// TLS settings for a controller behind an internal PKI
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct Controller {
+    // PEM bundle of CAs to trust alongside the system's
+    #[serde(default)]
+    pub ca: Option<PathBuf>,
+    // PEM client certificate and PKCS#8 key, for controllers that require mTLS
+    #[serde(default)]
+    pub cert: Option<PathBuf>,
+    #[serde(default)]
+    pub key: Option<PathBuf>,
+}
+
+// where a custom command runs: in place of the UI until it's done, or beside it in tmux
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Tmux {
+    #[default]
+    Off,
+    Split,
+    Window,
+}
+
+// how a command gets the row it's run for: filled into the command with % fields, each quoted
+// for the shell; the same without the quoting, for commands written before it; or only from the
+// ZT_ variables in its environment, leaving the command as written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Mode {
+    #[default]
+    Template,
+    Raw,
+    Env,
 }
 
+// a custom command as written in config.json: just the command, or the command with options
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum CommandEntry {
+    Line(String),
+    Full {
+        command: String,
+        #[serde(default)]
+        tmux: Option<Tmux>,
+        #[serde(default)]
+        mode: Option<Mode>,
+        // in place of the config's own shell, directory and environment
+        #[serde(default)]
+        shell: Option<String>,
+        #[serde(default)]
+        cwd: Option<PathBuf>,
+        // added to the config's environment, and taking its place where both set a variable
+        #[serde(default)]
+        env: BTreeMap<String, String>,
+    },
+}
+
+impl CommandEntry {
+    fn line(&self) -> &String {
+        match self {
+            Self::Line(command) | Self::Full { command, .. } => command,
+        }
+    }
+
+    fn tmux(&self) -> Option<Tmux> {
+        match self {
+            Self::Line(_) => None,
+            Self::Full { tmux, .. } => *tmux,
+        }
+    }
+
+    fn mode(&self) -> Option<Mode> {
+        match self {
+            Self::Line(_) => None,
+            Self::Full { mode, .. } => *mode,
+        }
+    }
+}
+
+// commands for one network, taking the place of the global ones bound to the same keys there
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct Scoped {
+    #[serde(default)]
+    network_commands: HashMap<String, CommandEntry>,
+    #[serde(default)]
+    member_commands: HashMap<String, CommandEntry>,
+}
+
+pub const DEFAULT_SHELL: &str = "/bin/sh";
+
+// a custom command filled in for the selected row, ready to run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Launch {
+    pub line: String,
+    pub tmux: Tmux,
+    // the shell and any arguments it takes before -c
+    pub shell: Vec<String>,
+    pub cwd: Option<PathBuf>,
+    pub env: BTreeMap<String, String>,
+}
+
+impl Launch {
+    // a command of ztui's own, run the plain way
+    #[cfg(feature = "central")]
+    pub fn new(line: String) -> Self {
+        Self {
+            line,
+            tmux: Tmux::Off,
+            shell: vec![DEFAULT_SHELL.to_string()],
+            cwd: None,
+            env: BTreeMap::new(),
+        }
+    }
+
+    // what's run: the shell, its arguments, then -c and the line
+    pub fn argv(&self) -> Vec<String> {
+        let mut argv = self.shell.clone();
+        argv.extend(["-c".to_string(), self.line.clone()]);
+        argv
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct UserConfig {
-    network_commands: HashMap<char, String>,
-    member_commands: HashMap<char, String>,
+    // by key, or by keys separated by spaces for a chord: "g p"
+    #[serde(default)]
+    network_commands: HashMap<String, CommandEntry>,
+    #[serde(default)]
+    member_commands: HashMap<String, CommandEntry>,
+    // commands for particular networks, by network id or name
+    #[serde(default)]
+    networks: HashMap<String, Scoped>,
+    // where commands run when they don't say; only inside tmux
+    #[serde(default)]
+    tmux: Tmux,
+    // how commands that don't say get the row they're run for
+    #[serde(default)]
+    mode: Mode,
+    // what commands are run with, /bin/sh by default. arguments can follow the shell, as in
+    // "bash -l"; -c and the command come after them.
+    #[serde(default)]
+    shell: Option<String>,
+    // the directory commands start in, ztui's own by default
+    #[serde(default)]
+    cwd: Option<PathBuf>,
+    // set for every command, filled in like the command itself: "ZT_NETWORK": "%n"
+    #[serde(default)]
+    env: BTreeMap<String, String>,
+    #[serde(default)]
+    capture_command: Option<String>,
+    #[serde(default)]
+    capture_write_command: Option<String>,
+    #[serde(default)]
+    capture_filter: Option<String>,
+    #[serde(default)]
+    interfaces: HashMap<String, Vec<String>>,
+    #[serde(default)]
+    thresholds: HashMap<String, Threshold>,
+    #[serde(default)]
+    alert_command: Option<String>,
+    // networks where a member authorized by anyone but this instance is called out
+    #[serde(default)]
+    watch_authorizations: BTreeSet<String>,
+    #[serde(default)]
+    authorization_command: Option<String>,
+    // by controller URL
+    #[serde(default)]
+    controllers: HashMap<String, Controller>,
+    // which of locales/<locale>.json to show the UI in; English without one
+    #[serde(default)]
+    locale: Option<String>,
+    // the key that shows what can be pressed next, as written by crate::keys
+    #[serde(default)]
+    prefix_key: Option<String>,
+    // ask before quitting
+    #[serde(default)]
+    confirm_quit: bool,
+    // look for a newer release on GitHub at startup. off unless asked for, and the only thing
+    // ztui asks of GitHub
+    #[serde(default)]
+    check_for_updates: bool,
+    // attribute traffic to members from connection tracking, on Linux
+    #[serde(default)]
+    member_traffic: bool,
+    // networks to join together, with the flags to join them with, by name
+    #[serde(default)]
+    join_sets: BTreeMap<String, JoinSet>,
+}
+
+// the keys of a command binding, one space apart however they were written
+fn chord(keys: &str) -> String {
+    keys.split_whitespace().collect::<Vec<_>>().join(" ")
 }
 
 impl UserConfig {
@@ -80,33 +671,338 @@ impl UserConfig {
         Ok(serde_json::from_str(&config_file)?)
     }
 
-    pub fn command_for_network(&self, c: char, network: &Network) -> Option<String> {
-        template_network(self.network_commands.get(&c), network)
+    pub fn locale(&self) -> Option<String> {
+        self.locale.clone()
     }
 
-    pub fn command_for_member(&self, c: char, member: &Member) -> Option<String> {
-        template_member(self.member_commands.get(&c), member)
+    pub fn confirm_quit(&self) -> bool {
+        self.confirm_quit
     }
-}
 
-impl Default for UserConfig {
-    fn default() -> Self {
-        Self {
-            network_commands: HashMap::new(),
-            member_commands: HashMap::new(),
+    pub fn check_for_updates(&self) -> bool {
+        self.check_for_updates
+    }
+
+    pub fn member_traffic(&self) -> bool {
+        self.member_traffic
+    }
+
+    pub fn join_sets(&self) -> &BTreeMap<String, JoinSet> {
+        &self.join_sets
+    }
+
+    pub fn prefix_key(&self) -> String {
+        self.prefix_key.clone().unwrap_or_else(|| ",".to_string())
+    }
+
+    // the commands scoped to a network: by its id, else by its name
+    fn scoped(&self, page: &Page, network: Option<&Network>) -> Option<&Scoped> {
+        let id = match page {
+            Page::Network(id) => Some(id.clone()),
+            Page::Networks => network.and_then(|n| n.subtype_1.id.clone()),
+        };
+        let name = network.and_then(|n| n.subtype_1.name.clone());
+
+        id.and_then(|id| self.networks.get(&id))
+            .or_else(|| name.and_then(|name| self.networks.get(&name)))
+    }
+
+    // the commands for a page, by chord: the global ones with any scoped to `network`, the
+    // selected network or the one whose members are shown, in their place
+    fn entries(&self, page: &Page, network: Option<&Network>) -> HashMap<String, &CommandEntry> {
+        let scoped = self.scoped(page, network);
+        let (global, scoped) = match page {
+            Page::Networks => (&self.network_commands, scoped.map(|s| &s.network_commands)),
+            Page::Network(_) => (&self.member_commands, scoped.map(|s| &s.member_commands)),
+        };
+
+        global
+            .iter()
+            .chain(scoped.into_iter().flatten())
+            .map(|(keys, entry)| (chord(keys), entry))
+            .collect()
+    }
+
+    // the commands bound to keys for a page, in key order
+    pub fn commands(&self, page: &Page, network: Option<&Network>) -> Vec<(String, String)> {
+        let mut commands = self
+            .entries(page, network)
+            .into_iter()
+            .map(|(keys, entry)| {
+                let line = match entry.mode().unwrap_or(self.mode) {
+                    Mode::Template => entry.line().clone(),
+                    Mode::Raw => format!("{} [raw]", entry.line()),
+                    Mode::Env => format!("{} [env]", entry.line()),
+                };
+                (keys, line)
+            })
+            .collect::<Vec<(String, String)>>();
+        commands.sort();
+        commands
+    }
+
+    // some command on the page is bound to a chord that starts with `keys`
+    pub fn continues(&self, page: &Page, network: Option<&Network>, keys: &str) -> bool {
+        let start = format!("{} ", keys);
+        self.commands(page, network)
+            .iter()
+            .any(|(k, _)| k.starts_with(&start))
+    }
+
+    // `template` fills in the command and the values of its environment for the selected row, and
+    // `vars` are its fields for the environment
+    fn launch(
+        &self,
+        entry: &CommandEntry,
+        template: impl Fn(&String, bool) -> Option<String>,
+        mut vars: BTreeMap<String, String>,
+    ) -> Option<Launch> {
+        // values in the environment don't pass through the shell, so they're never quoted
+        let mode = entry.mode().unwrap_or(self.mode);
+        let template = |s: &String, quoted: bool| match mode {
+            Mode::Template => template(s, quoted),
+            Mode::Raw => template(s, false),
+            Mode::Env => Some(s.clone()),
+        };
+        let (shell, cwd, mut env) = (self.shell.clone(), self.cwd.clone(), self.env.clone());
+        let (shell, cwd) = match entry {
+            CommandEntry::Line(_) => (shell, cwd),
+            CommandEntry::Full {
+                shell: s,
+                cwd: c,
+                env: e,
+                ..
+            } => {
+                env.extend(e.clone());
+                (s.clone().or(shell), c.clone().or(cwd))
+            }
+        };
+
+        let shell = shell
+            .map(|s| s.split_whitespace().map(String::from).collect::<Vec<_>>())
+            .filter(|s| !s.is_empty())
+            .unwrap_or_else(|| vec![DEFAULT_SHELL.to_string()]);
+
+        for (k, v) in env {
+            vars.insert(k, template(&v, false)?);
         }
+
+        Some(Launch {
+            line: template(entry.line(), true)?,
+            tmux: entry.tmux().unwrap_or(self.tmux),
+            shell,
+            cwd,
+            env: vars,
+        })
+    }
+
+    pub fn command_for_network(&self, keys: &str, network: &Network) -> Option<Launch> {
+        let entry = *self.entries(&Page::Networks, Some(network)).get(keys)?;
+        self.launch(
+            entry,
+            |s, quoted| template_network(Some(s), network, quoted),
+            network_vars(network),
+        )
+    }
+
+    pub fn command_for_member(
+        &self,
+        keys: &str,
+        member: &Member,
+        network: Option<&Network>,
+    ) -> Option<Launch> {
+        let page = Page::Network(member.network_id.clone().unwrap_or_default());
+        let entry = *self.entries(&page, network).get(keys)?;
+        self.launch(
+            entry,
+            |s, quoted| template_member(Some(s), member, network, quoted),
+            member_vars(member, network),
+        )
+    }
+
+    pub fn interfaces_for_network(&self, network: &Network) -> Vec<String> {
+        match network
+            .subtype_1
+            .id
+            .as_ref()
+            .and_then(|id| self.interfaces.get(id))
+        {
+            Some(interfaces) => interfaces.clone(),
+            None => network.subtype_1.port_device_name.iter().cloned().collect(),
+        }
+    }
+
+    #[cfg(feature = "central")]
+    pub fn controller(&self, url: &str) -> Controller {
+        self.controllers
+            .iter()
+            .find(|(u, _)| u.trim_end_matches('/') == url.trim_end_matches('/'))
+            .map(|(_, c)| c.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn threshold_for_network(&self, id: &str) -> Option<Threshold> {
+        self.thresholds.get(id).copied()
+    }
+
+    pub fn alert_command_for_network(
+        &self,
+        network: &Network,
+        direction: &str,
+        rate: f64,
+        limit: u64,
+    ) -> Option<String> {
+        Some(template_alert(
+            self.alert_command.as_ref()?,
+            network,
+            direction,
+            rate,
+            limit,
+        ))
+    }
+
+    #[cfg(feature = "central")]
+    pub fn watches_authorizations(&self, id: &str) -> bool {
+        self.watch_authorizations.contains(id)
+    }
+
+    pub fn watched_networks(&self) -> Vec<String> {
+        self.watch_authorizations.iter().cloned().collect()
+    }
+
+    #[cfg(feature = "central")]
+    pub fn authorization_command_for(&self, network_id: &str, entry: &Entry) -> Option<String> {
+        Some(template_authorization(
+            self.authorization_command.as_ref()?,
+            network_id,
+            entry,
+        ))
+    }
+
+    fn capture_filter(&self) -> &str {
+        self.capture_filter
+            .as_deref()
+            .unwrap_or(DEFAULT_CAPTURE_FILTER)
+    }
+
+    pub fn capture_command_for_network(&self, network: &Network, interface: &str) -> String {
+        template_capture(
+            self.capture_command
+                .as_deref()
+                .unwrap_or(DEFAULT_CAPTURE_COMMAND),
+            self.capture_filter(),
+            network,
+            interface,
+            None,
+        )
+    }
+
+    pub fn capture_write_command_for_network(
+        &self,
+        network: &Network,
+        interface: &str,
+        path: &Path,
+    ) -> String {
+        template_capture(
+            self.capture_write_command
+                .as_deref()
+                .unwrap_or(DEFAULT_CAPTURE_WRITE_COMMAND),
+            self.capture_filter(),
+            network,
+            interface,
+            Some(path),
+        )
+    }
+}
+
+// where the user was when ztui last exited, so the next start can put them back there
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct Session {
+    page: Page,
+    network: Option<String>,
+}
+
+// the flags the network had, to join it again with
+fn join_flags(network: &Network) -> crate::client::JoinFlags {
+    crate::client::JoinFlags {
+        allow_managed: network.subtype_0.allow_managed,
+        allow_global: network.subtype_0.allow_global,
+        allow_default: network.subtype_0.allow_default,
+        allow_dns: network.subtype_0.allow_dns,
+    }
+}
+
+// how many left networks are remembered for rejoining
+const KEEP_LEFT: usize = 10;
+
+// a network that was left or forgotten, with what's needed to put it back as it was
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Left {
+    pub id: String,
+    pub at: u64,
+    pub network: Option<Network>,
+    api_key: Option<String>,
+    central_url: Option<String>,
+}
+
+impl Left {
+    pub fn name(&self) -> String {
+        self.network
+            .as_ref()
+            .and_then(|n| n.subtype_1.name.clone())
+            .unwrap_or_default()
     }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Settings {
     api_keys: HashMap<String, String>,
+    // controllers other than the default Central, by network id
+    #[serde(default)]
+    central_urls: HashMap<String, String>,
     savednetworks: HashMap<String, Network>,
     savednetworksidx: Vec<String>,
+    // kept in the cache directory; only read from settings written by older versions
+    #[serde(default, skip_serializing)]
     pub members: HashMap<String, Vec<Member>>,
     filter: ListFilter,
+    #[serde(default)]
+    session: Session,
+    // show the selected network's members next to the network list
+    #[serde(default)]
+    pub split: bool,
+    // recorded key sequences by name
+    #[serde(default)]
+    pub macros: BTreeMap<String, Vec<String>>,
+    // most recent first
+    #[serde(default)]
+    recently_left: Vec<Left>,
+    // networks joined again whenever the daemon drops them
+    #[serde(default)]
+    auto_rejoin: BTreeSet<String>,
+    #[serde(skip)]
+    rejoin_tried: HashMap<String, Instant>,
+    // the interface picked for a network in the UI, when its own couldn't be found
+    #[serde(default)]
+    interfaces: BTreeMap<String, String>,
+    // networks already told about an interface that couldn't be found
+    #[serde(skip)]
+    interface_hinted: BTreeSet<String>,
+    // a message from the supervisor, moved to the screen on the next draw
+    #[serde(skip)]
+    pub notice: Option<String>,
+    // networks whose API key Central has refused a change for; cleared when the key changes
+    #[serde(skip)]
+    read_only: BTreeSet<String>,
+    // networks whose API key Central has started turning away (HTTP 401), with what it said and
+    // whether the user has been asked for a new one yet; cleared when the key works again
     #[serde(skip)]
-    pub last_error: Option<String>,
+    rejected: BTreeMap<String, (String, bool)>,
+    #[serde(skip)]
+    pub split_error: Option<(String, String)>,
+    // what's gone wrong lately, shown in the log dialog
+    #[serde(skip)]
+    pub errors: Errors,
     #[serde(skip)]
     pub page: Page,
     #[serde(skip)]
@@ -115,21 +1011,127 @@ pub struct Settings {
     user_config: UserConfig,
     #[serde(skip)]
     pub nets: Nets,
+    #[serde(skip)]
+    pub member_traffic: MemberTraffic,
+    #[serde(skip)]
+    pub history: History,
+    #[serde(skip)]
+    pub changelog: Changelog,
+    #[serde(skip)]
+    pub pending: Pending,
+    #[serde(skip)]
+    pub networks_fetch: Fetch,
+    #[serde(skip)]
+    pub members_fetch: HashMap<String, Fetch>,
+    // given up on when the page it's fetching for is left
+    #[serde(skip)]
+    pub fetch_cancel: crate::client::Cancel,
+    // networks currently over their bandwidth threshold, with the message to show
+    #[serde(skip)]
+    pub alerts: BTreeMap<String, String>,
+    // the ranges each network hands addresses out from, by network id
+    #[serde(skip)]
+    pub pools: HashMap<String, Vec<Pool>>,
+    // networks whose pools were nearly full at the last refresh
+    #[serde(skip)]
+    #[cfg(feature = "central")]
+    nearly_full: BTreeSet<String>,
+    // addresses shared by more than one member, by network id, as of the last refresh
+    #[serde(skip)]
+    #[cfg(feature = "central")]
+    duplicates: HashMap<String, BTreeMap<String, Vec<String>>>,
+    // alerts just raised, for the UI to pass on to the terminal as notifications
+    #[serde(skip)]
+    pub notifications: Vec<String>,
+    // capability and tag names from each network's rules, by network id
+    #[serde(skip)]
+    pub rule_names: HashMap<String, RuleNames>,
+    // bumped whenever a network's members are replaced, so what's drawn from them is only worked
+    // out again when they change
+    #[serde(skip)]
+    pub members_revision: u64,
+}
+
+// the state of a page's background refresh
+#[derive(Debug, Clone, Default)]
+pub struct Fetch {
+    pub loading: bool,
+    // when the data last came back successfully
+    pub refreshed: Option<Instant>,
+    // why the last attempt didn't, cleared when one succeeds
+    pub failed: Option<String>,
+}
+
+impl Fetch {
+    // how old the data on screen is, if it has been fetched at all this run
+    pub fn age(&self) -> Option<Duration> {
+        self.refreshed.map(|at| at.elapsed())
+    }
+
+    pub fn is_stale(&self) -> bool {
+        self.age().is_some_and(is_stale)
+    }
+
+    // failing with nothing fresh to show; whatever is on screen was saved by an earlier run
+    pub fn is_offline(&self) -> bool {
+        self.failed.is_some() && self.refreshed.is_none()
+    }
+}
+
+// one picked in the UI first, then the config, then what ZeroTier reports
+fn interfaces_for(
+    picked: &BTreeMap<String, String>,
+    user_config: &UserConfig,
+    network: &Network,
+) -> Vec<String> {
+    match network.subtype_1.id.as_ref().and_then(|id| picked.get(id)) {
+        Some(interface) => vec![interface.clone()],
+        None => user_config.interfaces_for_network(network),
+    }
 }
 
 impl Default for Settings {
     fn default() -> Self {
         Self {
-            last_error: None,
+            rejected: BTreeMap::new(),
+            split_error: None,
+            errors: Errors::default(),
             members: HashMap::new(),
+            rule_names: HashMap::new(),
+            members_revision: 0,
             page: Page::Networks,
             api_keys: HashMap::new(),
+            central_urls: HashMap::new(),
             user_config: UserConfig::default(),
             network_state: TableState::default(),
             filter: ListFilter::None,
+            session: Session::default(),
+            split: false,
+            macros: BTreeMap::new(),
             savednetworks: HashMap::new(),
             savednetworksidx: Vec::new(),
+            recently_left: Vec::new(),
+            auto_rejoin: BTreeSet::new(),
+            rejoin_tried: HashMap::new(),
+            interfaces: BTreeMap::new(),
+            interface_hinted: BTreeSet::new(),
+            notice: None,
+            read_only: BTreeSet::new(),
             nets: Nets::new().unwrap(),
+            member_traffic: MemberTraffic::default(),
+            history: History::default(),
+            changelog: Changelog::default(),
+            pending: Pending::default(),
+            networks_fetch: Fetch::default(),
+            members_fetch: HashMap::new(),
+            fetch_cancel: crate::client::Cancel::default(),
+            alerts: BTreeMap::new(),
+            #[cfg(feature = "central")]
+            duplicates: HashMap::new(),
+            pools: HashMap::new(),
+            #[cfg(feature = "central")]
+            nearly_full: BTreeSet::new(),
+            notifications: Vec::new(),
         }
     }
 }
@@ -137,27 +1139,104 @@ impl Default for Settings {
 impl Settings {
     pub fn from_dir(filename: PathBuf) -> Result<Self, anyhow::Error> {
         let config_file = std::fs::read_to_string(filename.join("settings.json"))?;
-        let mut config: Self = serde_json::from_str(&config_file)?;
-
-        config.user_config = match UserConfig::from_dir(filename) {
-            Ok(uc) => uc,
-            Err(_) => UserConfig::default(),
+        let mut config: Self = match serde_json::from_str(&config_file) {
+            Ok(config) => config,
+            Err(e) => Self::from_backup(&filename).ok_or(e)?,
         };
 
+        config.user_config = UserConfig::from_dir(filename.clone()).unwrap_or_default();
+        config.history = History::from_dir(data_path()).unwrap_or_default();
+        config.changelog = Changelog::from_dir(data_path()).unwrap_or_default();
+        config.pending = Pending::from_dir(filename).unwrap_or_default();
+        let _ = config.nets.load_totals(data_path());
+        if let Ok(members) = std::fs::read_to_string(cache_path().join("members.json")) {
+            config.members = serde_json::from_str(&members).unwrap_or_default();
+        }
+        config.restore_session();
+
         Ok(config)
     }
 
+    // the newest backup that parses, for when settings.json doesn't
+    fn from_backup(dir: &Path) -> Option<Self> {
+        (1..=BACKUPS).find_map(|n| {
+            let path = backup_path(dir, n);
+            let mut config: Self =
+                serde_json::from_str(&std::fs::read_to_string(&path).ok()?).ok()?;
+            config.notice = Some(crate::i18n::tf(
+                "toast.settings_recovered",
+                &[&path.display()],
+            ));
+            Some(config)
+        })
+    }
+
+    fn restore_session(&mut self) {
+        if let Page::Network(id) = &self.session.page {
+            // without a key the member list can't be shown; stay on the networks page
+            if self.api_keys.contains_key(id) {
+                self.page = self.session.page.clone();
+            }
+        }
+
+        if let Some(id) = &self.session.network {
+            if let Some(pos) = self.savednetworksidx.iter().position(|x| x == id) {
+                self.network_state.select(Some(pos));
+            }
+        }
+    }
+
+    pub fn save_session(&mut self) {
+        self.session = Session {
+            page: self.page.clone(),
+            network: self.selected_network_id(),
+        };
+    }
+
     pub fn to_file(&self, filename: PathBuf) -> Result<(), anyhow::Error> {
-        Ok(std::fs::write(
-            filename.join("settings.json"),
-            serde_json::to_string_pretty(self)?,
-        )?)
+        if crate::instance::read_only() {
+            return Ok(());
+        }
+
+        self.nets.save_totals(data_path())?;
+        self.save_members()?;
+
+        write_atomic(&filename.join("settings.json"), &self.snapshot()?)
+    }
+
+    // caches the member lists of networks that still have a key
+    pub fn save_members(&self) -> Result<(), anyhow::Error> {
+        let members = self
+            .members
+            .iter()
+            .filter(|(id, _)| self.api_keys.contains_key(*id))
+            .collect::<HashMap<_, _>>();
+
+        write_atomic(
+            &cache_path().join("members.json"),
+            &serde_json::to_string(&members)?,
+        )
+    }
+
+    // settings.json as it would be written now
+    pub fn snapshot(&self) -> Result<String, anyhow::Error> {
+        Ok(serde_json::to_string_pretty(self)?)
     }
 
     pub fn user_config(&self) -> UserConfig {
         self.user_config.clone()
     }
 
+    // config.json read again, as on SIGHUP. one that doesn't parse leaves what was in use alone.
+    pub fn reload_user_config(&mut self, dir: PathBuf) -> Result<(), anyhow::Error> {
+        self.user_config = match UserConfig::from_dir(dir.clone()) {
+            Ok(config) => config,
+            Err(_) if !dir.join("config.json").exists() => UserConfig::default(),
+            Err(e) => return Err(e),
+        };
+        Ok(())
+    }
+
     pub fn set_filter(&mut self, filter: ListFilter) {
         self.filter = filter
     }
@@ -182,6 +1261,9 @@ impl Settings {
             self.savednetworks.insert(id, network.clone());
         }
 
+        // joined again, by ztui or anything else
+        self.recently_left.retain(|left| !ids.contains(&left.id));
+
         for (id, network) in self.savednetworks.iter_mut() {
             if !self.savednetworksidx.contains(id) {
                 self.savednetworksidx.push(id.clone());
@@ -192,44 +1274,377 @@ impl Settings {
                 continue;
             }
 
-            self.nets
-                .store_usage(network.subtype_1.port_device_name.clone().unwrap());
+            let interfaces = interfaces_for(&self.interfaces, &self.user_config, network);
+            if self.nets.len() > 0
+                && !interfaces
+                    .iter()
+                    .any(|i| self.nets.find_by_interface(i.clone()).is_some())
+                && self.interface_hinted.insert(id.clone())
+            {
+                self.notice = Some(crate::i18n::tf(
+                    "toast.interface_unknown",
+                    &[&id, &interfaces.join(", ")],
+                ));
+            }
+
+            self.nets.store_usage(id.clone(), &interfaces);
         }
 
+        self.check_thresholds();
+
         Ok(new)
     }
 
-    pub fn remove_network(&mut self, pos: usize) {
-        let id = self.savednetworksidx[pos].clone();
+    fn check_thresholds(&mut self) {
+        for (id, network) in &self.savednetworks {
+            let threshold = self.user_config.threshold_for_network(id);
+            let exceeded = match (threshold, self.nets.rate(id)) {
+                (Some(threshold), Some((rx, tx))) => {
+                    [("Rx", rx, threshold.rx), ("Tx", tx, threshold.tx)]
+                        .into_iter()
+                        .find_map(|(direction, rate, limit)| {
+                            let limit = limit?;
+                            (rate > limit as f64).then_some((direction, rate, limit))
+                        })
+                }
+                _ => None,
+            };
+
+            match exceeded {
+                Some((direction, rate, limit)) => {
+                    let name = network.subtype_1.name.clone().unwrap_or_else(|| id.clone());
+                    let message = format!(
+                        "{}: {} {}/s over the {}/s threshold",
+                        name,
+                        direction,
+                        format_bytes(rate as u64),
+                        format_bytes(limit)
+                    );
+
+                    // the hook only fires when a network crosses the threshold, not on every sample
+                    if !self.alerts.contains_key(id) {
+                        self.notifications.push(message.clone());
+                        if let Some(command) = self
+                            .user_config
+                            .alert_command_for_network(network, direction, rate, limit)
+                        {
+                            run_hook(&command);
+                        }
+                    }
+
+                    self.alerts.insert(id.clone(), message);
+                }
+                None => {
+                    self.alerts.remove(id);
+                }
+            }
+        }
+    }
+
+    // members that became authorized on a watched network without this instance doing it: a
+    // leaked API key, or another admin's slip
+    #[cfg(feature = "central")]
+    pub fn check_authorizations(&mut self, network_id: &str, entries: &[Entry]) {
+        if !self.user_config.watches_authorizations(network_id) {
+            return;
+        }
+
+        for entry in entries {
+            if entry.change != Change::Authorized
+                || crate::security::authorized_here(network_id, &entry.member)
+            {
+                continue;
+            }
 
-        let base = if pos > 0 { pos - 1 } else { pos };
-        let end = if pos > 0 { pos } else { pos + 1 };
+            let network = self
+                .savednetworks
+                .get(network_id)
+                .and_then(|n| n.subtype_1.name.clone())
+                .unwrap_or_else(|| network_id.to_string());
+            let message = crate::i18n::tf(
+                "toast.unexpected_authorization",
+                &[&entry.member, &entry.name, &network],
+            );
+            tracing::warn!(network = %network_id, member = %entry.member, "authorized elsewhere");
 
-        self.savednetworksidx = self.savednetworksidx.splice(base..=end, []).collect();
+            self.notice = Some(message.clone());
+            self.notifications.push(message);
+            if let Some(command) = self
+                .user_config
+                .authorization_command_for(network_id, entry)
+            {
+                run_hook(&command);
+            }
+        }
+    }
+
+    // says so when an address turns up on more than one member. each is only called out when it
+    // first does, and again if it's fixed and comes back.
+    #[cfg(feature = "central")]
+    pub fn check_duplicates(&mut self, network_id: &str, members: &[Member]) {
+        let duplicates = crate::addresses::duplicates(members);
+        let before = self.duplicates.remove(network_id).unwrap_or_default();
+
+        let new = duplicates
+            .iter()
+            .filter(|(address, _)| !before.contains_key(*address))
+            .map(|(address, ids)| {
+                crate::i18n::tf(
+                    "toast.duplicate_address",
+                    &[address, &ids.join(", "), &network_id],
+                )
+            })
+            .collect::<Vec<String>>();
+        if !new.is_empty() {
+            self.notice = Some(new.join(" | "));
+        }
+
+        self.duplicates.insert(network_id.to_string(), duplicates);
+    }
+
+    // per-member traffic by address, when it's turned on
+    pub fn member_rates(&self) -> Option<&HashMap<String, (f64, f64)>> {
+        self.user_config
+            .member_traffic()
+            .then(|| self.member_traffic.rates())
+    }
+
+    #[cfg(feature = "central")]
+    pub fn set_members(&mut self, network_id: String, members: Vec<Member>) {
+        self.members.insert(network_id, members);
+        self.members_revision += 1;
+    }
+
+    pub fn utilization(&self, network_id: &str) -> Option<Utilization> {
+        crate::addresses::utilization(self.pools.get(network_id)?, self.members.get(network_id)?)
+    }
+
+    // says so when a network's pools become nearly full, once until they aren't
+    #[cfg(feature = "central")]
+    pub fn check_utilization(&mut self, network_id: &str, members: &[Member]) {
+        let utilization = self
+            .pools
+            .get(network_id)
+            .and_then(|pools| crate::addresses::utilization(pools, members));
+
+        match utilization.filter(|u| u.nearly_full()) {
+            Some(u) => {
+                if self.nearly_full.insert(network_id.to_string()) {
+                    let message =
+                        crate::i18n::tf("toast.pool_nearly_full", &[&network_id, &u.used, &u.size]);
+                    self.notice = Some(message.clone());
+                    self.notifications.push(message);
+                }
+            }
+            None => {
+                self.nearly_full.remove(network_id);
+            }
+        }
+    }
+
+    pub fn alert(&self) -> Option<String> {
+        if self.alerts.is_empty() {
+            None
+        } else {
+            Some(
+                self.alerts
+                    .values()
+                    .cloned()
+                    .collect::<Vec<String>>()
+                    .join(" | "),
+            )
+        }
+    }
+
+    pub fn remove_network(&mut self, pos: usize) {
+        let id = self.savednetworksidx.remove(pos);
+        self.remember_left(&id);
         self.savednetworks.remove(&id);
     }
 
+    // keeps the network's bookmark and key around so it can be rejoined as it was
+    pub fn remember_left(&mut self, id: &str) {
+        self.recently_left.retain(|left| left.id != id);
+        self.recently_left.insert(
+            0,
+            Left {
+                id: id.to_string(),
+                at: crate::history::now(),
+                network: self.savednetworks.get(id).cloned(),
+                api_key: self.api_keys.get(id).cloned(),
+                central_url: self.central_urls.get(id).cloned(),
+            },
+        );
+        self.recently_left.truncate(KEEP_LEFT);
+    }
+
+    pub fn interfaces_for_network(&self, network: &Network) -> Vec<String> {
+        interfaces_for(&self.interfaces, &self.user_config, network)
+    }
+
+    // the interface picked or configured for the network, or else the one ZeroTier made for it. a
+    // network still joining doesn't have one yet.
+    pub fn capture_interface(&self, network: &Network) -> Result<String, anyhow::Error> {
+        self.interfaces_for_network(network)
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("the network has no interface yet"))
+    }
+
+    // true when none of the network's interfaces have counters, so there's no rate to show. with
+    // no counters at all it's the platform that can't read them, not the interface that's wrong.
+    pub fn interface_unknown(&self, network: &Network) -> bool {
+        self.nets.len() > 0
+            && !self
+                .interfaces_for_network(network)
+                .iter()
+                .any(|i| self.nets.find_by_interface(i.clone()).is_some())
+    }
+
+    // None goes back to the config, or to the interface ZeroTier reports
+    pub fn set_interface(&mut self, id: &str, interface: Option<String>) {
+        match interface {
+            Some(interface) => self.interfaces.insert(id.to_string(), interface),
+            None => self.interfaces.remove(id),
+        };
+        self.interface_hinted.remove(id);
+        self.nets.forget_rate(id);
+    }
+
+    pub fn auto_rejoin(&self, id: &str) -> bool {
+        self.auto_rejoin.contains(id)
+    }
+
+    // true if it's now on
+    pub fn toggle_auto_rejoin(&mut self, id: &str) -> bool {
+        if !self.auto_rejoin.remove(id) {
+            self.auto_rejoin.insert(id.to_string());
+            return true;
+        }
+        false
+    }
+
+    // networks with auto-rejoin on that the daemon no longer has, e.g. after an identity reset
+    // or a reinstall. ones left on purpose are skipped, and each is tried at most once a minute.
+    pub fn dropped_for_rejoin(&mut self) -> Vec<(String, crate::client::JoinFlags)> {
+        let mut dropped = Vec::new();
+
+        for id in &self.auto_rejoin {
+            let network = match self.savednetworks.get(id) {
+                Some(network) => network,
+                None => continue,
+            };
+            let disconnected =
+                network.subtype_1.status.as_deref() == Some(crate::app::STATUS_DISCONNECTED);
+            let left = self.recently_left.iter().any(|l| &l.id == id);
+            let tried = self
+                .rejoin_tried
+                .get(id)
+                .is_some_and(|at| at.elapsed() < Duration::from_secs(60));
+
+            if disconnected && !left && !tried {
+                dropped.push((id.clone(), join_flags(network)));
+            }
+        }
+
+        for (id, _) in &dropped {
+            self.rejoin_tried.insert(id.clone(), Instant::now());
+        }
+
+        dropped
+    }
+
+    pub fn recently_left(&self) -> &[Left] {
+        &self.recently_left
+    }
+
+    // joins again with the flags it had, and puts back its bookmark and key
+    pub fn rejoin(&mut self, pos: usize) -> Result<(), anyhow::Error> {
+        let left = match self.recently_left.get(pos) {
+            Some(left) => left.clone(),
+            None => return Ok(()),
+        };
+
+        let flags = left.network.as_ref().map(join_flags).unwrap_or_default();
+        crate::client::join_network_with(left.id.clone(), flags)?;
+
+        if let Some(network) = left.network {
+            self.savednetworks.insert(left.id.clone(), network);
+            if !self.savednetworksidx.contains(&left.id) {
+                self.savednetworksidx.push(left.id.clone());
+            }
+        }
+        if let Some(key) = left.api_key {
+            self.api_keys.insert(left.id.clone(), key);
+        }
+        self.set_central_url_for_id(left.id.clone(), left.central_url);
+
+        self.recently_left.remove(pos);
+        Ok(())
+    }
+
     pub fn get_network_by_pos(&self, pos: usize) -> Option<&Network> {
-        self.savednetworks.get(&self.get_network_id_by_pos(pos))
+        self.savednetworksidx
+            .get(pos)
+            .and_then(|id| self.savednetworks.get(id))
+    }
+
+    pub fn fetch(&self, page: &Page) -> Fetch {
+        match page {
+            Page::Networks => self.networks_fetch.clone(),
+            Page::Network(id) => self.members_fetch.get(id).cloned().unwrap_or_default(),
+        }
+    }
+
+    // whether anything on screen is waiting on a fetch
+    pub fn is_loading(&self) -> bool {
+        match &self.page {
+            Page::Networks => {
+                self.networks_fetch.loading
+                    || (self.split
+                        && self
+                            .selected_network_id()
+                            .is_some_and(|id| self.fetch(&Page::Network(id)).loading))
+            }
+            page => self.fetch(page).loading,
+        }
     }
 
-    pub fn get_network_id_by_pos(&self, pos: usize) -> String {
-        self.savednetworksidx[pos].clone()
+    pub fn selected_network_id(&self) -> Option<String> {
+        self.network_state
+            .selected()
+            .and_then(|pos| self.savednetworksidx.get(pos))
+            .cloned()
     }
 
     pub fn get(&self, id: &str) -> Option<&Network> {
         self.savednetworks.get(id)
     }
 
+    // the network the page's commands are for: the one whose members are shown, or the selected one
+    pub fn page_network(&self) -> Option<&Network> {
+        match &self.page {
+            Page::Network(id) => self.get(id),
+            Page::Networks => self.savednetworks.get(&self.selected_network_id()?),
+        }
+    }
+
     pub fn idx_iter(&self) -> impl Iterator<Item = &String> {
         self.savednetworksidx.iter()
     }
 
+    // the networks that aren't disconnected, in the order they're listed
+    pub fn connected(&self) -> impl Iterator<Item = &Network> {
+        self.idx_iter()
+            .filter_map(|id| self.get(id))
+            .filter(|n| n.subtype_1.status.as_deref() != Some(STATUS_DISCONNECTED))
+    }
+
     pub fn count(&self) -> usize {
         self.idx_iter()
             .filter(|x| {
                 if let ListFilter::Connected = self.filter() {
-                    self.get(&x).unwrap().subtype_1.status.clone().unwrap() != STATUS_DISCONNECTED
+                    self.get(x).unwrap().subtype_1.status.clone().unwrap() != STATUS_DISCONNECTED
                 } else {
                     true
                 }
@@ -241,7 +1656,180 @@ impl Settings {
         self.api_keys.get(&id)
     }
 
+    // networks there's a key for, so they can be watched without being opened first
+    pub fn networks_with_keys(&self) -> Vec<String> {
+        let mut ids = self.api_keys.keys().cloned().collect::<Vec<String>>();
+        ids.sort();
+        ids
+    }
+
+    pub fn any_api_key(&self) -> Option<String> {
+        self.api_keys.values().next().cloned()
+    }
+
+    #[cfg(feature = "central")]
     pub fn set_api_key_for_id(&mut self, id: String, api_key: String) {
+        self.read_only.remove(&id);
+        self.rejected.remove(&id);
         self.api_keys.insert(id, api_key);
     }
+
+    // keeps an error for the log dialog, with where it happened
+    pub fn record_error(
+        &mut self,
+        network: Option<&str>,
+        operation: &'static str,
+        error: &dyn std::fmt::Display,
+    ) {
+        self.errors.push(errors::Entry {
+            at: crate::history::now(),
+            page: self.page.clone(),
+            network: network.map(str::to_string),
+            operation,
+            message: error.to_string(),
+            times: 1,
+        });
+    }
+
+    #[cfg(feature = "central")]
+    pub fn reject_key(&mut self, id: &str, error: String) {
+        self.rejected
+            .entry(id.to_string())
+            .or_insert((error, false));
+    }
+
+    #[cfg(feature = "central")]
+    pub fn accept_key(&mut self, id: &str) {
+        self.rejected.remove(id);
+    }
+
+    // why `id`'s key was turned away, only the first time it's asked after that happens
+    pub fn unasked_rejection(&mut self, id: &str) -> Option<String> {
+        let (error, asked) = self.rejected.get_mut(id)?;
+        if *asked {
+            return None;
+        }
+        *asked = true;
+        Some(error.clone())
+    }
+
+    #[cfg(feature = "central")]
+    pub fn mark_read_only(&mut self, id: &str) {
+        self.read_only.insert(id.to_string());
+    }
+
+    pub fn is_read_only(&self, id: &str) -> bool {
+        self.read_only.contains(id)
+    }
+
+    // the network the page's actions would change, if it's read-only: the members' network, or the
+    // selected one on the networks page
+    pub fn read_only_target(&self) -> Option<String> {
+        match &self.page {
+            Page::Network(id) => Some(id.clone()),
+            Page::Networks => self.selected_network_id(),
+        }
+        .filter(|id| self.is_read_only(id))
+    }
+
+    #[cfg(feature = "central")]
+    pub fn central_url_for_id(&self, id: String) -> String {
+        self.central_urls
+            .get(&id)
+            .cloned()
+            .unwrap_or_else(crate::client::central_baseurl)
+    }
+
+    // None puts the network back on the default Central
+    pub fn set_central_url_for_id(&mut self, id: String, url: Option<String>) {
+        match url {
+            Some(url) => self.central_urls.insert(id, url),
+            None => self.central_urls.remove(&id),
+        };
+    }
+
+    // a client for whichever controller the network lives on, with its key
+    #[cfg(feature = "central")]
+    pub fn central_client_for_id(
+        &self,
+        id: String,
+    ) -> Result<crate::api::central::Client, anyhow::Error> {
+        let key = self
+            .api_key_for_id(id.clone())
+            .ok_or_else(|| anyhow::anyhow!("no API key saved for network {}", id))?;
+        let url = self.central_url_for_id(id);
+        crate::client::central_client_at(key.clone(), &url, &self.user_config.controller(&url))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    // a name with a field and a shell command in it
+    const NAME: &str = "50%a off; rm -rf ~";
+
+    fn network() -> Network {
+        serde_json::from_value(json!({
+            "id": "8056c2e21c000001",
+            "name": NAME,
+            "portDeviceName": "ztstandin0",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn rename_keeps_fields_in_names() {
+        let member = serde_json::from_value(json!({
+            "nodeId": "0123456789",
+            "name": NAME,
+            "config": { "ipAssignments": ["10.147.17.10"] },
+        }))
+        .unwrap();
+
+        assert_eq!(
+            template_rename("%N-%c", &member, 3, 12),
+            "50%a off; rm -rf ~-03"
+        );
+    }
+
+    #[test]
+    fn capture_quotes_the_filter() {
+        assert_eq!(
+            template_capture(
+                "tcpdump -i %i -w %w %f",
+                "port 80",
+                &network(),
+                "zt%n",
+                Some(Path::new("/tmp/a b.pcap"))
+            ),
+            "tcpdump -i 'zt%n' -w '/tmp/a b.pcap' 'port 80'"
+        );
+    }
+
+    #[test]
+    fn alert_quotes_the_name() {
+        assert_eq!(
+            template_alert("notify %N %d %r/%l %i", &network(), "rx", 2048.0, 1024),
+            "notify '50%a off; rm -rf ~' rx 2048/1024 ztstandin0"
+        );
+    }
+
+    #[cfg(feature = "central")]
+    #[test]
+    fn authorization_quotes_the_name() {
+        let entry = Entry {
+            at: 0,
+            member: "0123456789".to_string(),
+            name: NAME.to_string(),
+            change: Change::Authorized,
+        };
+
+        assert_eq!(
+            template_authorization("page %n %i %N", "8056c2e21c000001", &entry),
+            "page 8056c2e21c000001 0123456789 '50%a off; rm -rf ~'"
+        );
+    }
 }
@@ -0,0 +1,150 @@
+use std::{collections::HashMap, time::Instant};
+
+// where Linux keeps the connections it's tracking. bytes are only counted in it with
+// net.netfilter.nf_conntrack_acct=1, and reading it usually takes root.
+const PROC: &str = "/proc/net/nf_conntrack";
+
+// per-member traffic, worked out from the kernel's connection tracking: each connection says
+// which address sent how much to which, so what's moved between samples can be put down to the
+// members at either end. connections that close between samples take their last few bytes with
+// them, so this says who's busy rather than adding up to the interface's own counters.
+#[derive(Debug, Clone, Default)]
+pub struct MemberTraffic {
+    // bytes each connection had carried each way at the last sample
+    last: HashMap<String, (u64, u64)>,
+    sampled: Option<Instant>,
+    // received and sent per second, by address
+    rates: HashMap<String, (f64, f64)>,
+    pub failed: Option<String>,
+}
+
+// one way of a connection: who sent to whom, and how much
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Flow {
+    src: String,
+    dst: String,
+    bytes: u64,
+}
+
+// IPv6 addresses are written out in full, where Central shortens them
+fn address(value: &str) -> String {
+    value
+        .parse::<std::net::IpAddr>()
+        .map(|ip| ip.to_string())
+        .unwrap_or_else(|_| value.to_string())
+}
+
+// a line is the address family, protocol and state, then src=, dst=, ports and bytes= for the
+// way the connection was opened, and again for the way back
+fn parse(line: &str) -> Option<(String, Flow, Flow)> {
+    let mut fields = line.split_whitespace();
+    let proto = fields.nth(2)?;
+    let mut key = vec![proto];
+    let mut flows = vec![Flow::default()];
+
+    for field in fields {
+        let (name, value) = match field.split_once('=') {
+            Some(pair) => pair,
+            None => continue,
+        };
+        let flow = flows.last_mut()?;
+
+        match name {
+            "src" if !flow.src.is_empty() => flows.push(Flow {
+                src: address(value),
+                ..Default::default()
+            }),
+            "src" => flow.src = address(value),
+            "dst" => flow.dst = address(value),
+            "bytes" => flow.bytes = value.parse().ok()?,
+            _ => {}
+        }
+
+        // the first way's addresses and ports tell one connection from another
+        if flows.len() == 1 && !matches!(name, "packets" | "bytes") {
+            key.push(field);
+        }
+    }
+
+    let mut flows = flows.into_iter();
+    Some((key.join(" "), flows.next()?, flows.next()?))
+}
+
+impl MemberTraffic {
+    // received and sent per second, by address
+    pub fn rates(&self) -> &HashMap<String, (f64, f64)> {
+        &self.rates
+    }
+
+    pub fn sample(&mut self) -> Result<(), anyhow::Error> {
+        let res = self.read();
+        self.failed = res.as_ref().err().map(|e| e.to_string());
+        // started over once it works again, rather than measured against a sample from before
+        if res.is_err() {
+            *self = Self {
+                failed: self.failed.take(),
+                ..Default::default()
+            };
+        }
+        res
+    }
+
+    fn read(&mut self) -> Result<(), anyhow::Error> {
+        if !cfg!(target_os = "linux") {
+            return Err(anyhow::anyhow!(
+                "per-member traffic is only available on Linux"
+            ));
+        }
+
+        let table = std::fs::read_to_string(PROC).map_err(|e| {
+            anyhow::anyhow!(
+                "could not read {}: {}; it needs the nf_conntrack module and usually root",
+                PROC,
+                e
+            )
+        })?;
+
+        let now = Instant::now();
+        let first = self.sampled.is_none();
+        let mut counted = false;
+        let mut last = HashMap::new();
+        let mut moved: HashMap<String, (u64, u64)> = HashMap::new();
+
+        for (key, out, back) in table.lines().filter_map(parse) {
+            counted |= out.bytes > 0 || back.bytes > 0;
+            // a connection opened since the last sample has moved everything it's carried;
+            // on the first there's nothing yet to say what moved when
+            let (last_out, last_back) = match self.last.get(&key) {
+                Some(bytes) => *bytes,
+                None if first => (out.bytes, back.bytes),
+                None => (0, 0),
+            };
+
+            for (flow, before) in [(&out, last_out), (&back, last_back)] {
+                let bytes = flow.bytes.saturating_sub(before);
+                moved.entry(flow.dst.clone()).or_default().0 += bytes;
+                moved.entry(flow.src.clone()).or_default().1 += bytes;
+            }
+
+            last.insert(key, (out.bytes, back.bytes));
+        }
+
+        if !counted && !last.is_empty() {
+            return Err(anyhow::anyhow!(
+                "connection tracking isn't counting bytes; set net.netfilter.nf_conntrack_acct=1"
+            ));
+        }
+
+        if let Some(sampled) = self.sampled {
+            let elapsed = now.duration_since(sampled).as_secs_f64().max(1.0);
+            self.rates = moved
+                .into_iter()
+                .map(|(address, (rx, tx))| (address, (rx as f64 / elapsed, tx as f64 / elapsed)))
+                .collect();
+        }
+
+        self.last = last;
+        self.sampled = Some(now);
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Line {
+    Same(String),
+    Added(String),
+    Removed(String),
+}
+
+// a plain longest-common-subsequence line diff. rules documents are small enough that the
+// quadratic table is nothing to worry about.
+pub fn lines(old: &str, new: &str) -> Vec<Line> {
+    let old = old.lines().collect::<Vec<&str>>();
+    let new = new.lines().collect::<Vec<&str>>();
+
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut out = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            out.push(Line::Same(old[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            out.push(Line::Removed(old[i].to_string()));
+            i += 1;
+        } else {
+            out.push(Line::Added(new[j].to_string()));
+            j += 1;
+        }
+    }
+
+    out.extend(old[i..].iter().map(|l| Line::Removed(l.to_string())));
+    out.extend(new[j..].iter().map(|l| Line::Added(l.to_string())));
+    out
+}
@@ -1,5 +1,6 @@
 use std::{
-    sync::{Arc, Mutex},
+    collections::{HashMap, HashSet},
+    sync::atomic::{AtomicBool, Ordering},
     time::SystemTime,
 };
 
@@ -8,17 +9,165 @@ use tui::{
     backend::Backend,
     layout::{Constraint, Layout, Rect},
     style::{Color, Modifier, Style},
-    text::Span,
-    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table},
+    text::{Span, Spans},
+    widgets::{BarChart, Block, Borders, Cell, Clear, Gauge, Paragraph, Row, Table, Wrap},
     Frame,
 };
-use zerotier_central_api::types::Member;
-use zerotier_one_api::types::Network;
 
+use crate::api::central::types::Member;
+use crate::api::one::types::Network;
 use crate::{
-    app::{App, Dialog, ListFilter, Page, STATUS_DISCONNECTED},
-    config::Settings,
+    addresses::Utilization,
+    app::{on_off, App, Dialog, ListFilter, NetworkFlag, Page, STATUS_DISCONNECTED},
+    batch::{self, Batch},
+    capture::Capture,
+    changelog::{Change, Changelog},
+    config::{is_stale, Fetch, Settings},
+    doctor::{Check, Outcome},
+    errors::Errors,
+    form::Value,
+    grouping::{Grouping, MemberRow},
+    help::Help,
+    history::History,
+    i18n::{t, tf},
+    identity::IdentityReport,
+    join_set::JoinSet,
+    nets::{format_bytes, format_rate, Nets, Traffic},
+    pending::Pending,
+    picker::Purpose,
+    ping::Pinger,
+    privacy::redact,
+    registry,
+    routes::{RouteReport, RouteStatus},
+    rules::RuleNames,
 };
+#[cfg(feature = "central")]
+use crate::{client, diff::Line, rules::RulesEdit};
+
+// dry-run is called out on every page so nobody mistakes a rehearsal for the real thing
+fn main_title(extra: Vec<String>) -> String {
+    let mut parts = vec![t("title.main")];
+    if crate::actions::dry_run() {
+        parts.push(t("title.dry_run"));
+    }
+    if crate::privacy::enabled() {
+        parts.push(t("title.privacy"));
+    }
+    if crate::tape::replaying() {
+        parts.push(t("title.replay"));
+    }
+    if crate::instance::read_only() {
+        parts.push(t("title.not_saving"));
+    }
+    parts.extend(extra);
+    parts.push(t("title.help_hint"));
+
+    format!("[ {} ]", parts.join(" | "))
+}
+
+// how often the UI redraws while a spinner is on screen
+pub const SPINNER_TICK: std::time::Duration = std::time::Duration::from_millis(100);
+
+// set by whatever changes what's on screen: a key that did something, new data from the
+// supervisor, a resize. the UI only draws when it's set, or while something on screen moves.
+static DIRTY: AtomicBool = AtomicBool::new(true);
+
+pub fn mark_dirty() {
+    if !DIRTY.swap(true, Ordering::Relaxed) {
+        crate::events::wake();
+    }
+}
+
+pub fn take_dirty() -> bool {
+    DIRTY.swap(false, Ordering::Relaxed)
+}
+
+// the spinner frame for right now, so every spinner on screen turns together
+pub fn spinner() -> &'static str {
+    let ticks = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis()
+        / SPINNER_TICK.as_millis();
+    let frames = crate::theme::spinner_frames();
+    frames[ticks as usize % frames.len()]
+}
+
+fn recording(app: &App) -> Vec<String> {
+    match &app.recording {
+        Some(keys) => vec![tf("title.recording", &[&keys.len()])],
+        None => Vec::new(),
+    }
+}
+
+fn freshness(fetch: &Fetch) -> Option<String> {
+    if fetch.loading {
+        return Some(format!("{} {}", spinner(), t("title.refreshing")));
+    }
+
+    if fetch.is_offline() {
+        return Some(t("title.offline"));
+    }
+
+    fetch.refreshed.map(|at| match at.elapsed().as_secs() {
+        0 => t("title.updated_now"),
+        secs => tf(
+            "title.updated",
+            &[
+                &fancy_duration::FancyDuration::new(std::time::Duration::from_secs(secs))
+                    .to_string(),
+            ],
+        ),
+    })
+}
+
+// dims a table whose data has stopped refreshing and says so along its bottom border
+fn mark_stale<B: Backend>(f: &mut Frame<'_, B>, area: Rect, fetch: &Fetch) {
+    let message = match (fetch.age(), &fetch.failed) {
+        (Some(age), _) if is_stale(age) => {
+            format!("[ {} ]", tf("title.stale", &[&age.as_secs()]))
+        }
+        (None, Some(failed)) => format!("[ {} ]", tf("title.saved", &[failed])),
+        _ => return,
+    };
+
+    let span = Span::styled(
+        message,
+        Style::default()
+            .fg(Color::Black)
+            .bg(Color::LightYellow)
+            .add_modifier(Modifier::BOLD),
+    );
+
+    let width = (span.width() as u16).min(area.width.saturating_sub(4));
+    f.render_widget(
+        Paragraph::new(span),
+        Rect::new(area.x + 2, area.y + area.height - 1, width, 1),
+    );
+}
+
+fn stale_style(fetch: &Fetch) -> Style {
+    if fetch.is_stale() || fetch.is_offline() {
+        Style::default().add_modifier(Modifier::DIM)
+    } else {
+        Style::default()
+    }
+}
+
+// the input buffer with the character under the cursor highlighted
+fn input_spans<'a>(buf: &str, cursor: usize) -> Spans<'a> {
+    // masked in the shape it was typed, so the cursor stays where it was
+    let buf = &redact(buf);
+    let before = buf.chars().take(cursor).collect::<String>();
+    let at = buf.chars().nth(cursor).unwrap_or(' ').to_string();
+    let after = buf.chars().skip(cursor + 1).collect::<String>();
+
+    Spans::from(vec![
+        Span::raw(before),
+        Span::styled(at, Style::default().add_modifier(Modifier::REVERSED)),
+        Span::raw(after),
+    ])
+}
 
 fn dialog<B: Backend>(f: &mut Frame<B>, app: &mut App, margin: u16, help_text: String) {
     let w = f.size().width;
@@ -36,7 +185,7 @@ fn dialog<B: Backend>(f: &mut Frame<B>, app: &mut App, margin: u16, help_text: S
         )
         .split(f.size());
 
-    let p = Paragraph::new(app.inputbuffer.as_ref()).block(
+    let p = Paragraph::new(input_spans(&app.inputbuffer, app.cursor)).block(
         Block::default()
             .borders(Borders::ALL)
             .title(format!("] {} [", help_text)),
@@ -46,84 +195,354 @@ fn dialog<B: Backend>(f: &mut Frame<B>, app: &mut App, margin: u16, help_text: S
     f.render_widget(p, layout[1]);
 }
 
-fn dialog_api_key<B: Backend>(f: &mut Frame<B>, app: &mut App) {
-    dialog(f, app, 20, "Enter your Network API Key".to_string())
-}
+fn dialog_api_key<B: Backend>(f: &mut Frame<B>, app: &mut App, error: Option<String>) {
+    dialog(f, app, 30, t("prompt.api_key"));
 
-fn dialog_rename_member<B: Backend>(f: &mut Frame<B>, app: &mut App) {
-    dialog(f, app, 20, "Enter the new name".to_string())
-}
+    if let Some(error) = error {
+        let w = f.size().width;
+        // just under the input box drawn by dialog()
+        let layout = Layout::default()
+            .direction(tui::layout::Direction::Vertical)
+            .horizontal_margin(w / 2 - 30)
+            .constraints(
+                [
+                    Constraint::Percentage(50),
+                    Constraint::Length(3),
+                    Constraint::Length(4),
+                    Constraint::Min(0),
+                ]
+                .as_ref(),
+            )
+            .split(f.size());
 
-fn dialog_add_member<B: Backend>(f: &mut Frame<B>, app: &mut App) {
-    dialog(f, app, 20, "Enter the new node ID".to_string())
-}
+        let p = Paragraph::new(Span::styled(
+            redact(&error),
+            Style::default().fg(Color::LightRed),
+        ))
+        .wrap(Wrap { trim: true })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("] {} [", t("dialog.key_refused"))),
+        );
 
-fn dialog_join<B: Backend>(f: &mut Frame<B>, app: &mut App) {
-    dialog(f, app, 10, "Join a Network".to_string())
+        f.render_widget(Clear, layout[2]);
+        f.render_widget(p, layout[2]);
+    }
 }
 
-lazy_static::lazy_static! {
-static ref HELP_TEXT: Vec<Vec<[&'static str; 2]>> = vec![
-    vec![
-        ["Up/Down", "Navigate the List"],
-        ["<Esc>", "back out of something"],
-        ["d", "Delete a list member"],
-        ["q", "Quit"],
-        ["j", "Join a bookmarked network"],
-        ["l", "Leave a bookmarked network"],
-        ["J", "Join a network by address"],
-        ["c", "review network settings"],
-        ["t", "toggle disconnected in list"],
-        ["s", "show network members (requires API key)"],
-        ["e", "edit network rules (requires API key)"],
-    ],
-    vec![
-        ["Up/Down", "Navigate the List"],
-        ["q", "quit to networks screen"],
-        ["r", "Rename a Member"],
-        ["a", "Authorize a deauthorized member"],
-        ["A", "Authorize an arbitrary member ID"],
-        ["d", "Deauthorize an authorized member"],
-        ["D", "Delete a member"],
-    ],
-];
+// a list to pick from, narrowed by what's typed above it
+fn dialog_picker<B: Backend>(f: &mut Frame<B>, app: &mut App, title: &str) {
+    let picker = match app.picker() {
+        Some(picker) => picker,
+        None => return,
+    };
+
+    let size = f.size();
+    let rect = Rect::new(
+        size.width / 4,
+        size.height / 4,
+        size.width / 2,
+        size.height / 2,
+    );
+    let layout = Layout::default()
+        .direction(tui::layout::Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(1)].as_ref())
+        .split(rect);
+
+    let query = Paragraph::new(input_spans(&picker.query, picker.query.chars().count())).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("] {} [", tf("dialog.picker", &[&title]))),
+    );
+
+    let matches = picker.matches();
+    let count = format!("[ {} of {} ]", matches.len(), picker.items.len());
+    let rows = matches
+        .iter()
+        .map(|item| Row::new(vec![Cell::from(redact(&item.label))]))
+        .collect::<Vec<Row>>();
+
+    let list = Table::new(rows)
+        .block(Block::default().borders(Borders::ALL).title(count))
+        .widths(&[Constraint::Percentage(100)])
+        .highlight_style(Style::default().add_modifier(Modifier::BOLD))
+        .highlight_symbol("> ");
+
+    f.render_widget(Clear, rect);
+    f.render_widget(query, layout[0]);
+    f.render_stateful_widget(list, layout[1], &mut picker.state);
 }
 
-pub fn dialog_help<B: Backend>(f: &mut Frame<B>, page: Page) {
+// a dialog of several fields, each on its own line with anything wrong with it underneath
+fn dialog_form<B: Backend>(f: &mut Frame<B>, app: &mut App, title: &str) {
+    let form = match app.form() {
+        Some(form) => form.clone(),
+        None => return,
+    };
+
+    let width = form
+        .fields
+        .iter()
+        .map(|f| f.label.len())
+        .max()
+        .unwrap_or_default()
+        + 2;
+    let mut lines = Vec::new();
+    for (i, field) in form.fields.iter().enumerate() {
+        let focused = i == form.focus;
+        let label_style = if focused {
+            Style::default()
+                .fg(Color::LightCyan)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::LightCyan)
+        };
+
+        let mut spans = vec![Span::styled(
+            format!("{:width$}", format!("{}:", field.label), width = width),
+            label_style,
+        )];
+        match &field.value {
+            Value::Text(s) if focused => spans.extend(input_spans(s, field.cursor).0),
+            Value::Text(s) => spans.push(Span::raw(redact(s))),
+            Value::YesNo(yes) => {
+                let text = if *yes { "[x] yes" } else { "[ ] no" };
+                if focused {
+                    spans.push(Span::styled(
+                        text,
+                        Style::default().add_modifier(Modifier::REVERSED),
+                    ));
+                } else {
+                    spans.push(Span::raw(text));
+                }
+            }
+        }
+        lines.push(Spans::from(spans));
+
+        if let Some(error) = &field.error {
+            lines.push(Spans::from(Span::styled(
+                format!("{:width$}{}", "", error, width = width),
+                Style::default().fg(Color::LightRed),
+            )));
+        }
+    }
+
     let size = f.size();
-    let w = size.width;
-    let h = size.height;
+    let height = (lines.len() as u16 + 2).min(size.height);
+    let rect = Rect::new(
+        size.width / 4,
+        (size.height - height) / 2,
+        size.width / 2,
+        height,
+    );
 
-    let block = Block::default()
-        .borders(Borders::ALL)
-        .title(Span::from("[ Help ]"));
+    let p = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("] {} [", tf("dialog.form", &[&title]))),
+    );
+
+    f.render_widget(Clear, rect);
+    f.render_widget(p, rect);
+}
+
+fn dialog_identity_backup<B: Backend>(f: &mut Frame<B>, app: &mut App) {
+    dialog(f, app, 20, t("prompt.identity_backup"))
+}
+
+fn dialog_menu<B: Backend>(f: &mut Frame<B>, app: &mut App, settings: &Settings) {
+    let target = match &settings.page {
+        Page::Networks => settings.selected_network_id().map(|id| {
+            match settings.get(&id).and_then(|n| n.subtype_1.name.clone()) {
+                Some(name) if !name.is_empty() => format!("network {} ({})", redact(&id), name),
+                _ => format!("network {}", redact(&id)),
+            }
+        }),
+        Page::Network(id) => app
+            .selected_member()
+            .and_then(|pos| settings.members.get(id)?.get(pos))
+            .map(|member| match member.name.clone() {
+                Some(name) if !name.is_empty() => format!("member {}", name),
+                _ => format!(
+                    "member {}",
+                    redact(&member.node_id.clone().unwrap_or_default())
+                ),
+            }),
+    }
+    .unwrap_or_default();
 
-    let help_text = &HELP_TEXT[match page {
-        Page::Networks => 0,
-        Page::Network(_) => 1,
-    }];
+    let actions = registry::for_page(&settings.page)
+        .filter(|b| b.row)
+        .collect::<Vec<_>>();
+    let read_only = settings.read_only_target().is_some();
+    let size = f.size();
+    let height = (actions.len() as u16 + 2).min(size.height);
+    let rect = Rect::new(
+        size.width / 4,
+        (size.height - height) / 2,
+        size.width / 2,
+        height,
+    );
 
-    let rows = help_text
+    let rows = actions
         .iter()
-        .map(|s| {
+        .map(|binding| {
+            let help = if binding.writes && read_only {
+                Span::styled(binding.describe(), Style::default().fg(Color::DarkGray))
+            } else if binding.destructive {
+                Span::styled(binding.describe(), Style::default().fg(Color::LightRed))
+            } else {
+                Span::from(binding.describe())
+            };
+
             Row::new(vec![
-                Cell::from(s[0].to_string()),
-                Cell::from(s[1].to_string()),
+                Cell::from(Span::styled(
+                    binding.keys[0],
+                    Style::default().fg(Color::LightCyan),
+                )),
+                Cell::from(binding.name),
+                Cell::from(help),
             ])
         })
         .collect::<Vec<Row>>();
 
     let table = Table::new(rows)
-        .block(block)
-        .widths(&[Constraint::Length(10), Constraint::Percentage(100)]);
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("[ {} ]", tf("dialog.menu", &[&target]))),
+        )
+        .widths(&[
+            Constraint::Length(6),
+            Constraint::Length(20),
+            Constraint::Percentage(100),
+        ])
+        .highlight_style(Style::default().add_modifier(Modifier::BOLD))
+        .highlight_symbol("> ");
 
-    let mut rect = Rect::default();
-    rect.x = w / 4;
-    rect.y = h / 4;
-    rect.width = w / 2;
-    rect.height = h / 2;
     f.render_widget(Clear, rect);
-    f.render_widget(table, rect);
+    f.render_stateful_widget(table, rect, &mut app.menu_state);
+}
+
+// what can follow the prefix key, in columns along the bottom of the screen
+pub fn which_key<B: Backend>(f: &mut Frame<B>, settings: &Settings, chord: &str) {
+    const COLUMN: usize = 24;
+
+    let entries = crate::help::following(
+        &settings.page,
+        &settings.user_config(),
+        settings.page_network(),
+        chord,
+    );
+    let size = f.size();
+    let columns = ((size.width.saturating_sub(2)) as usize / COLUMN).max(1);
+    let rows = entries.len().div_ceil(columns);
+
+    let lines = (0..rows)
+        .map(|row| {
+            Spans::from(
+                entries
+                    .iter()
+                    .skip(row)
+                    .step_by(rows)
+                    .flat_map(|(key, name)| {
+                        let name = name
+                            .chars()
+                            .take(COLUMN.saturating_sub(key.chars().count() + 3))
+                            .collect::<String>();
+                        vec![
+                            Span::styled(key.clone(), Style::default().fg(Color::LightCyan)),
+                            Span::from(format!(
+                                " {:width$}",
+                                name,
+                                width = COLUMN.saturating_sub(key.chars().count() + 1)
+                            )),
+                        ]
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let height = (rows as u16 + 2).min(size.height);
+    let rect = Rect::new(0, size.height - height, size.width, height);
+    let p = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("[ {} ]", tf("dialog.which_key", &[&chord]))),
+    );
+
+    f.render_widget(Clear, rect);
+    f.render_widget(p, rect);
+}
+
+// every section at once, scrolled to the one for whatever the help was opened from
+pub fn dialog_help<B: Backend>(f: &mut Frame<B>, help: &mut Help, settings: &Settings) {
+    let size = f.size();
+    let rect = Rect::new(
+        size.width / 8,
+        size.height / 8,
+        size.width * 3 / 4,
+        size.height * 3 / 4,
+    );
+
+    let sections = crate::help::sections(
+        &settings.page,
+        &settings.user_config(),
+        settings.page_network(),
+    );
+    let width = sections
+        .iter()
+        .flat_map(|s| s.entries.iter())
+        .map(|(keys, _)| keys.chars().count())
+        .max()
+        .unwrap_or_default()
+        .min(24);
+
+    let mut lines = Vec::new();
+    let mut start = 0;
+    for section in sections {
+        if section.topic == help.topic {
+            start = lines.len();
+        }
+
+        let style = Style::default()
+            .fg(Color::LightCyan)
+            .add_modifier(Modifier::BOLD);
+        lines.push(Spans::from(Span::styled(
+            if section.topic == help.topic {
+                format!("> {}", section.title)
+            } else {
+                section.title
+            },
+            style,
+        )));
+        for (keys, text) in section.entries {
+            lines.push(Spans::from(vec![
+                Span::styled(
+                    format!("  {:width$}  ", keys, width = width),
+                    Style::default().fg(Color::White),
+                ),
+                Span::from(text),
+            ]));
+        }
+        lines.push(Spans::from(""));
+    }
+
+    let top = (start as i32 + help.scroll).clamp(0, lines.len().saturating_sub(1) as i32);
+    help.scroll = top - start as i32;
+
+    let p = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("[ {} ]", t("dialog.help"))),
+        )
+        .scroll((top as u16, 0));
+
+    f.render_widget(Clear, rect);
+    f.render_widget(p, rect);
 }
 
 fn dialog_flags<B: Backend>(f: &mut Frame<B>, _app: &mut App, network: Network) {
@@ -133,12 +552,12 @@ fn dialog_flags<B: Backend>(f: &mut Frame<B>, _app: &mut App, network: Network)
 
     let block = Block::default()
         .borders(Borders::ALL)
-        .title(Span::from("[ Set Flags ]"));
+        .title(Span::from(format!("[ {} ]", t("dialog.flags"))));
 
     let rows = vec![
         Row::new(vec![
             Cell::from(Span::styled(
-                format!("Allow D[n]S",),
+                "Allow D[n]S".to_string(),
                 Style::default().fg(Color::White),
             )),
             Cell::from(Span::styled(
@@ -152,7 +571,7 @@ fn dialog_flags<B: Backend>(f: &mut Frame<B>, _app: &mut App, network: Network)
         ]),
         Row::new(vec![
             Cell::from(Span::styled(
-                format!("Allow [D]efault",),
+                "Allow [D]efault".to_string(),
                 Style::default().fg(Color::White),
             )),
             Cell::from(Span::styled(
@@ -166,7 +585,7 @@ fn dialog_flags<B: Backend>(f: &mut Frame<B>, _app: &mut App, network: Network)
         ]),
         Row::new(vec![
             Cell::from(Span::styled(
-                format!("Allow [M]anaged",),
+                "Allow [M]anaged".to_string(),
                 Style::default().fg(Color::White),
             )),
             Cell::from(Span::styled(
@@ -180,7 +599,7 @@ fn dialog_flags<B: Backend>(f: &mut Frame<B>, _app: &mut App, network: Network)
         ]),
         Row::new(vec![
             Cell::from(Span::styled(
-                format!("Allow [G]lobal",),
+                "Allow [G]lobal".to_string(),
                 Style::default().fg(Color::White),
             )),
             Cell::from(Span::styled(
@@ -198,183 +617,2090 @@ fn dialog_flags<B: Backend>(f: &mut Frame<B>, _app: &mut App, network: Network)
         .block(block)
         .widths(&[Constraint::Percentage(50), Constraint::Percentage(50)]);
 
-    let mut rect = Rect::default();
-    rect.x = w / 4;
-    rect.y = h / 4;
-    rect.width = w / 2;
-    rect.height = h / 2;
+    let rect = Rect::new(w / 4, h / 4, w / 2, h / 2);
     f.render_widget(Clear, rect);
     f.render_widget(table, rect);
 }
 
-pub fn display_dialogs<B: Backend>(
-    f: &mut Frame<'_, B>,
-    app: &mut App,
-    settings: Arc<Mutex<Settings>>,
-) {
-    match app.dialog.clone() {
-        Dialog::Join => {
-            dialog_join(f, app);
-        }
-        Dialog::APIKey(_) => {
-            dialog_api_key(f, app);
-        }
-        Dialog::Help => {
-            dialog_help(f, settings.lock().unwrap().page.clone());
-        }
-        Dialog::RenameMember(_, _) => {
-            dialog_rename_member(f, app);
-        }
-        Dialog::AddMember(_) => {
-            dialog_add_member(f, app);
-        }
-        Dialog::NetworkFlags(id) => {
-            dialog_flags(f, app, settings.lock().unwrap().get(&id).unwrap().clone());
-        }
-        _ => {}
-    }
+#[cfg(feature = "central")]
+fn dialog_sso<B: Backend>(f: &mut Frame<B>, sso: client::Sso, network_id: &str) {
+    let size = f.size();
+    let unset = |s: String| if s.is_empty() { "-".to_string() } else { s };
+    let rows = vec![
+        (
+            "Enabled",
+            if sso.enabled { "yes" } else { "no" }.to_string(),
+        ),
+        ("Client ID", unset(sso.client_id)),
+        ("Issuer", unset(sso.issuer)),
+        ("Authorization endpoint", unset(sso.authorization_endpoint)),
+    ]
+    .into_iter()
+    .map(|(label, value)| {
+        Row::new(vec![
+            Cell::from(Span::styled(label, Style::default().fg(Color::White))),
+            Cell::from(Span::styled(value, Style::default().fg(Color::LightCyan))),
+        ])
+    })
+    .collect::<Vec<Row>>();
+
+    let table = Table::new(rows)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("[ {} ]", tf("dialog.sso", &[&redact(network_id)]))),
+        )
+        .widths(&[Constraint::Length(24), Constraint::Percentage(100)]);
+
+    let rect = Rect::new(size.width / 8, size.height / 3, size.width * 3 / 4, 6);
+    f.render_widget(Clear, rect);
+    f.render_widget(table, rect);
 }
 
-pub fn display_network<B: Backend>(
-    f: &mut Frame<'_, B>,
-    app: &mut App,
-    members: Vec<Member>,
-) -> Result<(), anyhow::Error> {
-    let list = Layout::default()
-        .constraints([Constraint::Min(4)])
-        .split(f.size());
+fn dialog_full_tunnel<B: Backend>(f: &mut Frame<B>, network: &Network) {
+    let size = f.size();
 
-    let titleblock = Block::default()
-        .borders(Borders::ALL)
-        .title("[ ZeroTier Terminal UI | Press h for Help ]");
+    let mut lines = vec![Spans::from(tf(
+        "dialog.full_tunnel.routes",
+        &[&redact(&network.subtype_1.id.clone().unwrap_or_default())],
+    ))];
+    for route in crate::routes::default_routes(network) {
+        lines.push(Spans::from(format!("  {}", redact(&route.to_string()))));
+    }
+    lines.push(Spans::from(""));
+    lines.push(Spans::from(Span::styled(
+        t("dialog.full_tunnel.warning"),
+        Style::default().fg(Color::LightRed),
+    )));
 
-    let rows = members
-        .iter()
-        .map(|m| {
-            let authed = m.config.clone().unwrap().authorized.unwrap_or_default();
-            let caps = m.config.clone().unwrap().capabilities.unwrap();
+    let p = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("[ {} ]", t("dialog.full_tunnel"))),
+    );
+
+    let rect = Rect::new(
+        size.width / 4,
+        size.height / 3,
+        size.width / 2,
+        size.height / 3,
+    );
+    f.render_widget(Clear, rect);
+    f.render_widget(p, rect);
+}
+
+// each connected network with the flag as it is and as it would be; the ones it's already set on
+// are left alone
+fn dialog_flag_all<B: Backend>(f: &mut Frame<B>, settings: &Settings, flag: NetworkFlag, on: bool) {
+    let size = f.size();
 
+    let rows = settings
+        .connected()
+        .map(|network| {
+            let was = flag.get(network);
             Row::new(vec![
                 Cell::from(Span::styled(
-                    m.node_id.clone().unwrap(),
+                    redact(&network.subtype_1.id.clone().unwrap_or_default()),
                     Style::default().fg(Color::Cyan),
                 )),
-                Cell::from(Span::styled(
-                    m.name.clone().unwrap(),
-                    Style::default().fg(Color::LightCyan),
-                )),
-                Cell::from(Span::styled(
-                    format!(
-                        "{}",
-                        fancy_duration::FancyDuration::new(
-                            OffsetDateTime::from(SystemTime::now())
-                                - OffsetDateTime::UNIX_EPOCH
-                                    .checked_add(Duration::new(m.last_online.unwrap() / 1000, 0))
-                                    .unwrap()
-                        )
-                        .to_string()
-                    ),
-                    Style::default().fg(Color::LightCyan),
-                )),
-                Cell::from(Span::styled(
-                    m.config
-                        .clone()
-                        .unwrap()
-                        .ip_assignments
-                        .unwrap_or_default()
-                        .join(", "),
-                    Style::default().fg(Color::LightGreen),
-                )),
-                Cell::from(Span::styled(
-                    if authed { "Auth" } else { "Unauth" },
-                    Style::default().fg(if authed {
-                        Color::LightGreen
-                    } else {
-                        Color::LightRed
-                    }),
-                )),
-                Cell::from(Span::styled(
-                    caps.iter()
-                        .map(|x| format!("{}", x))
-                        .collect::<Vec<String>>()
-                        .join(", "),
-                    Style::default().fg(Color::LightGreen),
-                )),
+                Cell::from(network.subtype_1.name.clone().unwrap_or_default()),
+                if was == on {
+                    Cell::from(Span::styled(
+                        tf("dialog.flag_all.unchanged", &[&on_off(on)]),
+                        Style::default().fg(Color::DarkGray),
+                    ))
+                } else {
+                    Cell::from(Span::styled(
+                        format!("{} -> {}", on_off(was), on_off(on)),
+                        Style::default().fg(Color::LightGreen),
+                    ))
+                },
             ])
         })
         .collect::<Vec<Row>>();
 
-    app.member_count = rows.len();
-
     let table = Table::new(rows)
-        .block(titleblock)
+        .block(Block::default().borders(Borders::ALL).title(format!(
+            "[ {} ]",
+            tf("dialog.flag_all", &[&flag.name(), &on_off(on)])
+        )))
         .header(Row::new(vec![
-            Cell::from(Span::styled("Node ID", Style::default().fg(Color::White))),
+            Cell::from(Span::styled("Network", Style::default().fg(Color::White))),
             Cell::from(Span::styled("Name", Style::default().fg(Color::White))),
-            Cell::from(Span::styled(
-                "Last Online",
-                Style::default().fg(Color::White),
-            )),
-            Cell::from(Span::styled(
-                "IP Addresses",
-                Style::default().fg(Color::White),
-            )),
-            Cell::from(Span::styled(
-                "Auth Status",
-                Style::default().fg(Color::White),
-            )),
-            Cell::from(Span::styled(
-                "Capabilities",
-                Style::default().fg(Color::White),
-            )),
+            Cell::from(Span::styled(flag.name(), Style::default().fg(Color::White))),
         ]))
         .widths(&[
-            Constraint::Length(12),
+            Constraint::Length(18),
             Constraint::Length(20),
-            Constraint::Length(25),
-            Constraint::Length(25),
-            Constraint::Length(8),
-            Constraint::Length(15),
-        ])
-        .highlight_style(Style::default().add_modifier(Modifier::BOLD))
-        .highlight_symbol("> ");
+            Constraint::Percentage(100),
+        ]);
 
-    f.render_stateful_widget(table, list[0], &mut app.member_state);
-    Ok(())
+    let rect = Rect::new(
+        size.width / 4,
+        size.height / 4,
+        size.width / 2,
+        size.height / 2,
+    );
+    f.render_widget(Clear, rect);
+    f.render_widget(table, rect);
 }
 
-pub fn display_networks<B: Backend>(
-    f: &mut Frame<'_, B>,
-    _app: &mut App,
-    settings: Arc<Mutex<Settings>>,
-) -> Result<(), anyhow::Error> {
-    let list = Layout::default()
-        .constraints([Constraint::Min(4)])
-        .split(f.size());
+// the set's flags, and each of its networks with whether it would be joined or only have them set
+fn dialog_join_set<B: Backend>(f: &mut Frame<B>, settings: &Settings, name: &str, set: &JoinSet) {
+    let size = f.size();
 
-    let titleblock = Block::default()
-        .borders(Borders::ALL)
-        .title("[ ZeroTier Terminal UI | Press h for Help ]");
+    let flags = set.describe_flags();
+    let mut lines = vec![
+        Spans::from(if flags.is_empty() {
+            t("dialog.join_set.no_flags")
+        } else {
+            tf("dialog.join_set.flags", &[&flags])
+        }),
+        Spans::from(""),
+    ];
+    match set.ids() {
+        Ok(ids) => {
+            for id in ids {
+                let network = settings.get(&id);
+                let (action, color) = match network {
+                    Some(_) => (t("dialog.join_set.joined"), Color::LightCyan),
+                    None => (t("dialog.join_set.join"), Color::LightGreen),
+                };
+                lines.push(Spans::from(vec![
+                    Span::styled(
+                        format!("{}  ", redact(&id)),
+                        Style::default().fg(Color::Cyan),
+                    ),
+                    Span::raw(format!(
+                        "{:<20}  ",
+                        network
+                            .and_then(|n| n.subtype_1.name.clone())
+                            .unwrap_or_default()
+                    )),
+                    Span::styled(action, Style::default().fg(color)),
+                ]));
+            }
+        }
+        Err(e) => lines.push(Spans::from(Span::styled(
+            redact(&e.to_string()),
+            Style::default().fg(Color::LightRed),
+        ))),
+    }
 
-    let mut lock = settings.lock().unwrap();
+    let p = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("[ {} ]", tf("dialog.join_set", &[&name]))),
+    );
 
-    let rows = lock
-        .idx_iter()
-        .filter_map(|k| {
-            let v = match lock.get(k) {
-                Some(v) => v,
-                None => return None,
-            };
+    let rect = Rect::new(
+        size.width / 4,
+        size.height / 4,
+        size.width / 2,
+        size.height / 2,
+    );
+    f.render_widget(Clear, rect);
+    f.render_widget(p, rect);
+}
 
-            if let ListFilter::Connected = lock.filter() {
-                if v.subtype_1.status.clone().unwrap() == STATUS_DISCONNECTED {
-                    return None;
-                }
-            }
+// asks before quitting, or says why settings couldn't be saved on the way out
+fn dialog_quit<B: Backend>(f: &mut Frame<B>, title: String, error: Option<String>) {
+    let size = f.size();
+    let lines = match error {
+        Some(error) => vec![Spans::from(Span::styled(
+            error,
+            Style::default().fg(Color::LightRed),
+        ))],
+        None => vec![Spans::from(t("dialog.quit.unsaved"))],
+    };
+
+    let p = Paragraph::new(lines).wrap(Wrap { trim: true }).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("[ {} ]", title)),
+    );
+
+    let rect = Rect::new(size.width / 4, size.height / 2 - 3, size.width / 2, 5);
+    f.render_widget(Clear, rect);
+    f.render_widget(p, rect);
+}
+
+fn dialog_already_running<B: Backend>(f: &mut Frame<B>, pid: Option<u32>) {
+    let size = f.size();
+    let running = match pid {
+        Some(pid) => tf("dialog.already_running.pid", &[&pid]),
+        None => t("dialog.already_running.unknown"),
+    };
+
+    let p = Paragraph::new(vec![
+        Spans::from(Span::styled(
+            running,
+            Style::default().fg(Color::LightYellow),
+        )),
+        Spans::from(t("dialog.already_running.read_only")),
+    ])
+    .wrap(Wrap { trim: true })
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("[ {} ]", t("dialog.already_running"))),
+    );
+
+    let rect = Rect::new(size.width / 4, size.height / 2 - 4, size.width / 2, 7);
+    f.render_widget(Clear, rect);
+    f.render_widget(p, rect);
+}
+
+fn dialog_key_rejected<B: Backend>(f: &mut Frame<B>, settings: &Settings, id: &str, error: &str) {
+    let size = f.size();
+    let name = settings
+        .get(id)
+        .and_then(|n| n.subtype_1.name.clone())
+        .filter(|name| !name.is_empty())
+        .unwrap_or(id.to_string());
+
+    let p = Paragraph::new(vec![
+        Spans::from(Span::styled(
+            redact(&tf("dialog.key_rejected.network", &[&name, &error])),
+            Style::default().fg(Color::LightRed),
+        )),
+        Spans::from(t("dialog.key_rejected.cached")),
+    ])
+    .wrap(Wrap { trim: true })
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("[ {} ]", t("dialog.key_rejected"))),
+    );
+
+    let rect = Rect::new(size.width / 4, size.height / 2 - 4, size.width / 2, 8);
+    f.render_widget(Clear, rect);
+    f.render_widget(p, rect);
+}
+
+fn format_rtt(rtt: Option<std::time::Duration>) -> String {
+    rtt.map_or("-".to_string(), |r| {
+        format!("{:.2}ms", r.as_secs_f64() * 1000.0)
+    })
+}
+
+fn dialog_ping<B: Backend>(f: &mut Frame<B>, pinger: &Pinger, node_id: String) {
+    let size = f.size();
+    let w = size.width;
+    let h = size.height;
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(Span::from(format!(
+            "[ {} ]",
+            tf("dialog.ping", &[&redact(&node_id)])
+        )));
+
+    let stats = pinger.stats();
+
+    let rows = stats
+        .iter()
+        .map(|s| {
+            let loss = s.loss();
+
+            Row::new(vec![
+                Cell::from(Span::styled(
+                    redact(&s.address),
+                    Style::default().fg(Color::LightGreen),
+                )),
+                Cell::from(Span::styled(
+                    s.method.map_or("-".to_string(), |m| m.to_string()),
+                    Style::default().fg(Color::Cyan),
+                )),
+                Cell::from(Span::styled(
+                    format!("{}/{}", s.received, s.sent),
+                    Style::default().fg(Color::LightCyan),
+                )),
+                Cell::from(Span::styled(
+                    format!("{:.0}%", loss),
+                    Style::default().fg(if s.sent == 0 || loss == 0.0 {
+                        Color::LightGreen
+                    } else if loss < 100.0 {
+                        Color::LightYellow
+                    } else {
+                        Color::LightRed
+                    }),
+                )),
+                Cell::from(Span::styled(
+                    format_rtt(s.last_rtt),
+                    Style::default().fg(Color::LightMagenta),
+                )),
+                Cell::from(Span::styled(
+                    format!(
+                        "{} / {} / {}",
+                        format_rtt(s.min_rtt),
+                        format_rtt(s.avg_rtt()),
+                        format_rtt(s.max_rtt)
+                    ),
+                    Style::default().fg(Color::LightMagenta),
+                )),
+            ])
+        })
+        .collect::<Vec<Row>>();
+
+    let table = Table::new(rows)
+        .block(block)
+        .header(Row::new(vec![
+            Cell::from(Span::styled("Address", Style::default().fg(Color::White))),
+            Cell::from(Span::styled("Via", Style::default().fg(Color::White))),
+            Cell::from(Span::styled("Recv", Style::default().fg(Color::White))),
+            Cell::from(Span::styled("Loss", Style::default().fg(Color::White))),
+            Cell::from(Span::styled("RTT", Style::default().fg(Color::White))),
+            Cell::from(Span::styled(
+                "Min / Avg / Max",
+                Style::default().fg(Color::White),
+            )),
+        ]))
+        .widths(&[
+            Constraint::Length(25),
+            Constraint::Length(5),
+            Constraint::Length(9),
+            Constraint::Length(5),
+            Constraint::Length(10),
+            Constraint::Percentage(100),
+        ]);
+
+    let rect = Rect::new(w / 8, h / 4, w - w / 4, h / 2);
+    f.render_widget(Clear, rect);
+    f.render_widget(table, rect);
+}
+
+fn dialog_capture<B: Backend>(f: &mut Frame<B>, capture: &Capture, interface: &str) {
+    let size = f.size();
+    let rect = Rect::new(2, 2, size.width - 4, size.height - 4);
+
+    let status = match (capture.writing(), capture.saved()) {
+        (Some(path), _) => format!("writing {} | w to stop", path.display()),
+        (None, Some(path)) => format!("saved {} | w to save again", path.display()),
+        (None, None) => "w to save a pcap".to_string(),
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(Span::from(format!(
+            "[ {} ]",
+            tf("dialog.capture", &[&interface, &status])
+        )));
+
+    let lines = capture.lines();
+    let height = rect.height.saturating_sub(2) as usize;
+    let text = lines
+        .iter()
+        .skip(lines.len().saturating_sub(height))
+        .map(|l| {
+            Spans::from(Span::styled(
+                redact(l),
+                Style::default().fg(Color::LightCyan),
+            ))
+        })
+        .collect::<Vec<Spans>>();
+
+    f.render_widget(Clear, rect);
+    f.render_widget(Paragraph::new(text).block(block), rect);
+}
+
+fn dialog_identity<B: Backend>(f: &mut Frame<B>, report: &IdentityReport) {
+    let size = f.size();
+    let w = size.width;
+    let h = size.height;
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(Span::from(format!(
+            "[ {} ]",
+            tf("dialog.identity", &[&report.problems()])
+        )));
+
+    let mut rows = vec![
+        Row::new(vec![
+            Cell::from(Span::styled("Home", Style::default().fg(Color::White))),
+            Cell::from(Span::styled(
+                report.home.display().to_string(),
+                Style::default().fg(Color::LightCyan),
+            )),
+        ]),
+        Row::new(vec![
+            Cell::from(Span::styled("Public", Style::default().fg(Color::White))),
+            Cell::from(Span::styled(
+                report
+                    .public
+                    .as_deref()
+                    .map_or("unreadable".to_string(), redact),
+                Style::default().fg(Color::LightCyan),
+            )),
+        ]),
+    ];
+
+    if let Some(backup) = &report.backup {
+        rows.push(Row::new(vec![
+            Cell::from(Span::styled("Backup", Style::default().fg(Color::White))),
+            match backup {
+                Ok(path) => Cell::from(Span::styled(
+                    format!("saved to {}", path.display()),
+                    Style::default().fg(Color::LightGreen),
+                )),
+                Err(e) => Cell::from(Span::styled(
+                    redact(e),
+                    Style::default().fg(Color::LightRed),
+                )),
+            },
+        ]));
+    }
+
+    rows.push(Row::new(vec![Cell::from(""), Cell::from("")]));
+
+    for check in &report.checks {
+        rows.push(Row::new(vec![
+            Cell::from(Span::styled(
+                check.name.clone(),
+                Style::default().fg(Color::LightCyan),
+            )),
+            match &check.error {
+                None => Cell::from(Span::styled("ok", Style::default().fg(Color::LightGreen))),
+                Some(e) => Cell::from(Span::styled(
+                    redact(e),
+                    Style::default().fg(Color::LightRed),
+                )),
+            },
+        ]));
+    }
+
+    let table = Table::new(rows)
+        .block(block)
+        .widths(&[Constraint::Percentage(40), Constraint::Percentage(60)]);
+
+    let rect = Rect::new(w / 4, h / 4, w / 2, h / 2);
+    f.render_widget(Clear, rect);
+    f.render_widget(table, rect);
+}
+
+fn dialog_doctor<B: Backend>(f: &mut Frame<B>, checks: &[Check]) {
+    let size = f.size();
+    let w = size.width;
+    let h = size.height;
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(Span::from(format!(
+            "[ {} ]",
+            tf(
+                "dialog.doctor",
+                &[&if crate::doctor::failed(checks) {
+                    t("dialog.doctor.failed")
+                } else {
+                    t("dialog.doctor.passed")
+                }]
+            )
+        )));
+
+    let rows = checks
+        .iter()
+        .map(|check| {
+            Row::new(vec![
+                Cell::from(Span::styled(
+                    check.outcome.to_string(),
+                    Style::default().fg(match check.outcome {
+                        Outcome::Pass => Color::LightGreen,
+                        Outcome::Warn => Color::LightYellow,
+                        Outcome::Fail => Color::LightRed,
+                        Outcome::Skip => Color::DarkGray,
+                    }),
+                )),
+                Cell::from(Span::styled(
+                    check.name,
+                    Style::default().fg(Color::LightCyan),
+                )),
+                Cell::from(Span::styled(
+                    redact(&check.detail),
+                    Style::default().fg(Color::White),
+                )),
+            ])
+        })
+        .collect::<Vec<Row>>();
+
+    let table = Table::new(rows).block(block).widths(&[
+        Constraint::Length(6),
+        Constraint::Length(20),
+        Constraint::Percentage(100),
+    ]);
+
+    let rect = Rect::new(w / 4, h / 4, w / 2, h / 2);
+    f.render_widget(Clear, rect);
+    f.render_widget(table, rect);
+}
+
+fn dialog_changelog<B: Backend>(f: &mut Frame<B>, changelog: &Changelog, network_id: &str) {
+    let size = f.size();
+    let rect = Rect::new(
+        size.width / 8,
+        size.height / 4,
+        size.width - size.width / 4,
+        size.height / 2,
+    );
+
+    let now = crate::history::now();
+    let mut rows = changelog
+        .entries(network_id)
+        .into_iter()
+        .rev()
+        .map(|entry| {
+            let color = match entry.change {
+                Change::Added | Change::Authorized => Color::LightGreen,
+                Change::Deleted | Change::Deauthorized => Color::LightRed,
+                _ => Color::LightYellow,
+            };
+
+            Row::new(vec![
+                Cell::from(Span::styled(
+                    format!(
+                        "{} ago",
+                        fancy_duration::FancyDuration::new(std::time::Duration::from_secs(
+                            now.saturating_sub(entry.at)
+                        ))
+                        .to_string()
+                    ),
+                    Style::default().fg(Color::LightCyan),
+                )),
+                Cell::from(Span::styled(
+                    redact(&entry.member),
+                    Style::default().fg(Color::Cyan),
+                )),
+                Cell::from(Span::styled(entry.name, Style::default().fg(Color::White))),
+                Cell::from(Span::styled(
+                    entry.change.to_string(),
+                    Style::default().fg(color),
+                )),
+            ])
+        })
+        .collect::<Vec<Row>>();
+
+    if rows.is_empty() {
+        rows.push(Row::new(vec![Cell::from(Span::styled(
+            "No changes seen yet; they are recorded whenever the member list refreshes",
+            Style::default().fg(Color::LightYellow),
+        ))]));
+    }
+
+    let table = Table::new(rows)
+        .block(Block::default().borders(Borders::ALL).title(format!(
+            "[ {} ]",
+            tf("dialog.changelog", &[&redact(network_id)])
+        )))
+        .widths(&[
+            Constraint::Length(16),
+            Constraint::Length(11),
+            Constraint::Length(20),
+            Constraint::Percentage(100),
+        ]);
+
+    f.render_widget(Clear, rect);
+    f.render_widget(table, rect);
+}
+
+fn dialog_legend<B: Backend>(f: &mut Frame<B>, names: &RuleNames) {
+    let size = f.size();
+    let w = size.width;
+    let h = size.height;
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(Span::from(format!("[ {} ]", t("dialog.legend"))));
+
+    let mut rows = names
+        .caps
+        .iter()
+        .map(|(id, name)| {
+            Row::new(vec![
+                Cell::from(Span::styled("cap", Style::default().fg(Color::White))),
+                Cell::from(Span::styled(
+                    id.to_string(),
+                    Style::default().fg(Color::Cyan),
+                )),
+                Cell::from(Span::styled(
+                    name.clone(),
+                    Style::default().fg(Color::LightGreen),
+                )),
+            ])
+        })
+        .collect::<Vec<Row>>();
+
+    for (id, tag) in &names.tags {
+        rows.push(Row::new(vec![
+            Cell::from(Span::styled("tag", Style::default().fg(Color::White))),
+            Cell::from(Span::styled(
+                id.to_string(),
+                Style::default().fg(Color::Cyan),
+            )),
+            Cell::from(Span::styled(
+                tag.name.clone(),
+                Style::default().fg(Color::LightGreen),
+            )),
+        ]));
+
+        for (value, name) in &tag.enums {
+            rows.push(Row::new(vec![
+                Cell::from(""),
+                Cell::from(Span::styled(
+                    format!("  = {}", value),
+                    Style::default().fg(Color::LightCyan),
+                )),
+                Cell::from(Span::styled(
+                    name.clone(),
+                    Style::default().fg(Color::LightCyan),
+                )),
+            ]));
+        }
+    }
+
+    if rows.is_empty() {
+        rows.push(Row::new(vec![Cell::from(Span::styled(
+            "The network's rules define no capabilities or tags",
+            Style::default().fg(Color::LightYellow),
+        ))]));
+    }
+
+    let table = Table::new(rows).block(block).widths(&[
+        Constraint::Length(4),
+        Constraint::Length(12),
+        Constraint::Percentage(100),
+    ]);
+
+    let rect = Rect::new(w / 4, h / 4, w / 2, h / 2);
+    f.render_widget(Clear, rect);
+    f.render_widget(table, rect);
+}
+
+fn dialog_batch_rename<B: Backend>(f: &mut Frame<B>, app: &mut App, members: &[Member]) {
+    let size = f.size();
+    let w = size.width;
+    let h = size.height;
+
+    let rect = Rect::new(w / 4, h / 4, w / 2, h / 2);
+    let layout = Layout::default()
+        .direction(tui::layout::Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(1)].as_ref())
+        .split(rect);
+
+    let input = Paragraph::new(input_spans(&app.inputbuffer, app.cursor)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("[ {} ]", t("dialog.batch_rename"))),
+    );
+
+    let rows = app
+        .batch_rename_preview(members)
+        .into_iter()
+        .map(|(node_id, name)| {
+            let old = members
+                .iter()
+                .find(|m| m.node_id.as_ref() == Some(&node_id))
+                .and_then(|m| m.name.clone())
+                .unwrap_or_default();
+
+            let error = app.batch_errors.get(&node_id).cloned();
+
+            Row::new(vec![
+                Cell::from(Span::styled(
+                    redact(&node_id),
+                    Style::default().fg(Color::Cyan),
+                )),
+                Cell::from(Span::styled(old, Style::default().fg(Color::LightCyan))),
+                match error {
+                    Some(error) => Cell::from(Span::styled(
+                        format!("{}: {}", name, error),
+                        Style::default().fg(Color::LightRed),
+                    )),
+                    None => Cell::from(Span::styled(name, Style::default().fg(Color::LightGreen))),
+                },
+            ])
+        })
+        .collect::<Vec<Row>>();
+
+    let table = Table::new(rows)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(if app.batch_errors.is_empty() {
+                    format!("[ {} ]", t("dialog.batch_rename.preview"))
+                } else {
+                    format!(
+                        "[ {} ]",
+                        tf("dialog.batch_rename.failed", &[&app.batch_errors.len()])
+                    )
+                }),
+        )
+        .header(Row::new(vec![
+            Cell::from(Span::styled("Node ID", Style::default().fg(Color::White))),
+            Cell::from(Span::styled("Name", Style::default().fg(Color::White))),
+            Cell::from(Span::styled("New Name", Style::default().fg(Color::White))),
+        ]))
+        .widths(&[
+            Constraint::Length(12),
+            Constraint::Length(20),
+            Constraint::Percentage(100),
+        ]);
+
+    f.render_widget(Clear, rect);
+    f.render_widget(input, layout[0]);
+    f.render_widget(table, layout[1]);
+}
+
+#[cfg(feature = "central")]
+fn dialog_rules_diff<B: Backend>(f: &mut Frame<B>, edit: &RulesEdit, network_id: &str) {
+    let size = f.size();
+    let w = size.width;
+    let h = size.height;
+
+    let rect = Rect::new(w / 8, h / 8, w - w / 4, h - h / 4);
+    let layout = Layout::default()
+        .constraints(
+            [
+                Constraint::Min(1),
+                Constraint::Length(if edit.error.is_some() { 5 } else { 0 }),
+            ]
+            .as_ref(),
+        )
+        .split(rect);
+
+    let lines = edit
+        .diff
+        .iter()
+        .map(|line| match line {
+            Line::Same(l) => Spans::from(Span::styled(
+                format!("  {}", l),
+                Style::default().fg(Color::Gray),
+            )),
+            Line::Added(l) => Spans::from(Span::styled(
+                format!("+ {}", l),
+                Style::default().fg(Color::LightGreen),
+            )),
+            Line::Removed(l) => Spans::from(Span::styled(
+                format!("- {}", l),
+                Style::default().fg(Color::LightRed),
+            )),
+        })
+        .collect::<Vec<Spans>>();
+
+    let diff = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title(format!(
+            "[ {} ]",
+            tf("dialog.rules_diff", &[&redact(network_id)])
+        )))
+        .scroll((edit.scroll, 0));
+
+    f.render_widget(Clear, rect);
+    f.render_widget(diff, layout[0]);
+
+    if let Some(error) = &edit.error {
+        f.render_widget(
+            Paragraph::new(Span::styled(
+                redact(error),
+                Style::default().fg(Color::LightRed),
+            ))
+            .wrap(Wrap { trim: true })
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!("[ {} ]", t("dialog.rules_rejected"))),
+            ),
+            layout[1],
+        );
+    }
+}
+
+fn age(now: u64, at: u64) -> String {
+    fancy_duration::FancyDuration::new(std::time::Duration::from_secs(now.saturating_sub(at)))
+        .to_string()
+}
+
+fn dialog_actions<B: Backend>(f: &mut Frame<B>, errors: &Errors) {
+    let size = f.size();
+    let mut rect = Rect::new(
+        size.width / 8,
+        size.height / 4,
+        size.width - size.width / 4,
+        size.height / 2,
+    );
+
+    // errors get the bottom half, when there are any
+    if !errors.is_empty() {
+        rect = Rect::new(rect.x, size.height / 8, rect.width, size.height * 3 / 8);
+        let below = Rect::new(rect.x, rect.y + rect.height, rect.width, rect.height);
+        dialog_errors(f, below, errors);
+    }
+
+    let now = crate::history::now();
+    let rows = crate::actions::recent()
+        .iter()
+        .rev()
+        .map(|action| {
+            Row::new(vec![
+                Cell::from(Span::styled(
+                    age(now, action.at),
+                    Style::default().fg(Color::LightCyan),
+                )),
+                Cell::from(Span::styled(
+                    redact(&action.to_string()),
+                    Style::default().fg(if action.dry_run {
+                        Color::LightYellow
+                    } else {
+                        Color::White
+                    }),
+                )),
+            ])
+        })
+        .collect::<Vec<Row>>();
+
+    let table = Table::new(rows)
+        .block(Block::default().borders(Borders::ALL).title(format!(
+            "[ {} ]",
+            tf(
+                if crate::actions::dry_run() {
+                    "dialog.log.dry_run"
+                } else {
+                    "dialog.log"
+                },
+                &[&crate::config::data_path().join("actions.log").display()]
+            )
+        )))
+        .widths(&[Constraint::Length(10), Constraint::Percentage(100)]);
+
+    f.render_widget(Clear, rect);
+    f.render_widget(table, rect);
+}
+
+fn dialog_errors<B: Backend>(f: &mut Frame<B>, rect: Rect, errors: &Errors) {
+    let now = crate::history::now();
+    let rows = errors
+        .recent()
+        .map(|error| {
+            let page = match &error.page {
+                Page::Networks => t("dialog.errors.networks"),
+                Page::Network(_) => t("dialog.errors.members"),
+            };
+            let message = match error.times {
+                1 => error.message.clone(),
+                times => format!("{} (x{})", error.message, times),
+            };
+            Row::new(vec![
+                Cell::from(Span::styled(
+                    age(now, error.at),
+                    Style::default().fg(Color::LightCyan),
+                )),
+                Cell::from(page),
+                Cell::from(redact(&error.network.clone().unwrap_or_default())),
+                Cell::from(error.operation),
+                Cell::from(Span::styled(
+                    redact(&message),
+                    Style::default().fg(Color::LightRed),
+                )),
+            ])
+        })
+        .collect::<Vec<Row>>();
+
+    let table = Table::new(rows)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("[ {} ]", t("dialog.errors"))),
+        )
+        .widths(&[
+            Constraint::Length(10),
+            Constraint::Length(8),
+            Constraint::Length(17),
+            Constraint::Length(16),
+            Constraint::Percentage(100),
+        ]);
+
+    f.render_widget(Clear, rect);
+    f.render_widget(table, rect);
+}
+
+fn dialog_batch<B: Backend>(f: &mut Frame<B>, batch: &Batch) {
+    let size = f.size();
+    let rect = Rect::new(
+        size.width / 4,
+        size.height / 4,
+        size.width / 2,
+        size.height / 2,
+    );
+    let layout = Layout::default()
+        .direction(tui::layout::Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(1)].as_ref())
+        .split(rect);
+
+    let (done, total) = batch.progress();
+    let close = if done == total {
+        "dialog.batch.close"
+    } else {
+        "dialog.batch.stop"
+    };
+    let gauge = Gauge::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("[ {} ]", redact(&tf(close, &[&batch.title])))),
+        )
+        .gauge_style(Style::default().fg(Color::LightGreen))
+        .ratio(if total == 0 {
+            1.0
+        } else {
+            done as f64 / total as f64
+        })
+        .label(format!("{} of {}", done, total));
+
+    let rows = batch
+        .tasks()
+        .into_iter()
+        .map(|task| {
+            let (outcome, color) = match task.outcome {
+                batch::Outcome::Waiting => ("waiting".to_string(), Color::White),
+                batch::Outcome::Done => ("done".to_string(), Color::LightGreen),
+                #[cfg(feature = "central")]
+                batch::Outcome::Queued => ("queued".to_string(), Color::LightYellow),
+                batch::Outcome::Failed(e) => (redact(&e), Color::LightRed),
+                batch::Outcome::Cancelled => ("cancelled".to_string(), Color::Gray),
+            };
+
+            Row::new(vec![
+                Cell::from(redact(&task.label)),
+                Cell::from(Span::styled(outcome, Style::default().fg(color))),
+            ])
+        })
+        .collect::<Vec<Row>>();
+
+    let table = Table::new(rows)
+        .block(Block::default().borders(Borders::ALL))
+        .widths(&[Constraint::Length(12), Constraint::Percentage(100)]);
+
+    f.render_widget(Clear, rect);
+    f.render_widget(gauge, layout[0]);
+    f.render_widget(table, layout[1]);
+}
+
+fn dialog_pending<B: Backend>(f: &mut Frame<B>, app: &mut App, pending: &Pending) {
+    let size = f.size();
+    let rect = Rect::new(
+        size.width / 8,
+        size.height / 4,
+        size.width - size.width / 4,
+        size.height / 2,
+    );
+
+    let now = crate::history::now();
+    let rows = pending
+        .changes()
+        .iter()
+        .map(|change| {
+            let (status, color) = match &change.error {
+                Some(error) => (format!("refused: {}", redact(error)), Color::LightRed),
+                None => ("waiting for Central".to_string(), Color::LightYellow),
+            };
+
+            Row::new(vec![
+                Cell::from(Span::styled(
+                    fancy_duration::FancyDuration::new(std::time::Duration::from_secs(
+                        now.saturating_sub(change.at),
+                    ))
+                    .to_string(),
+                    Style::default().fg(Color::LightCyan),
+                )),
+                Cell::from(Span::styled(
+                    redact(&change.network),
+                    Style::default().fg(Color::Cyan),
+                )),
+                Cell::from(Span::styled(
+                    format!("{} {}", change.op, redact(&change.member)),
+                    Style::default().fg(Color::White),
+                )),
+                Cell::from(Span::styled(status, Style::default().fg(color))),
+            ])
+        })
+        .collect::<Vec<Row>>();
+
+    let table = Table::new(rows)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("[ {} ]", t("dialog.pending"))),
+        )
+        .header(Row::new(vec![
+            Cell::from(Span::styled("Queued", Style::default().fg(Color::White))),
+            Cell::from(Span::styled("Network", Style::default().fg(Color::White))),
+            Cell::from(Span::styled("Change", Style::default().fg(Color::White))),
+            Cell::from(Span::styled("Status", Style::default().fg(Color::White))),
+        ]))
+        .widths(&[
+            Constraint::Length(10),
+            Constraint::Length(17),
+            Constraint::Length(40),
+            Constraint::Percentage(100),
+        ])
+        .highlight_style(Style::default().add_modifier(Modifier::BOLD))
+        .highlight_symbol("> ");
+
+    f.render_widget(Clear, rect);
+    f.render_stateful_widget(table, rect, &mut app.pending_state);
+}
+
+fn dialog_recently_left<B: Backend>(f: &mut Frame<B>, app: &mut App, settings: &Settings) {
+    let size = f.size();
+    let rect = Rect::new(
+        size.width / 8,
+        size.height / 4,
+        size.width - size.width / 4,
+        size.height / 2,
+    );
+
+    let now = crate::history::now();
+    let rows = settings
+        .recently_left()
+        .iter()
+        .enumerate()
+        .map(|(i, left)| {
+            Row::new(vec![
+                Cell::from(Span::styled(
+                    if i < 9 {
+                        (i + 1).to_string()
+                    } else {
+                        String::new()
+                    },
+                    Style::default().fg(Color::White),
+                )),
+                Cell::from(Span::styled(
+                    fancy_duration::FancyDuration::new(std::time::Duration::from_secs(
+                        now.saturating_sub(left.at),
+                    ))
+                    .to_string(),
+                    Style::default().fg(Color::LightCyan),
+                )),
+                Cell::from(Span::styled(
+                    redact(&left.id),
+                    Style::default().fg(Color::LightCyan),
+                )),
+                Cell::from(Span::styled(
+                    redact(&left.name()),
+                    Style::default().fg(Color::Cyan),
+                )),
+            ])
+        })
+        .collect::<Vec<Row>>();
+
+    let table = Table::new(rows)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("[ {} ]", t("dialog.recently_left"))),
+        )
+        .header(Row::new(vec![
+            Cell::from(""),
+            Cell::from(Span::styled("Left", Style::default().fg(Color::White))),
+            Cell::from(Span::styled(
+                "Network ID",
+                Style::default().fg(Color::White),
+            )),
+            Cell::from(Span::styled("Name", Style::default().fg(Color::White))),
+        ]))
+        .widths(&[
+            Constraint::Length(2),
+            Constraint::Length(10),
+            Constraint::Length(17),
+            Constraint::Percentage(100),
+        ])
+        .highlight_style(Style::default().add_modifier(Modifier::BOLD))
+        .highlight_symbol("> ");
+
+    f.render_widget(Clear, rect);
+    f.render_stateful_widget(table, rect, &mut app.left_state);
+}
+
+fn dialog_snippets<B: Backend>(f: &mut Frame<B>, app: &mut App) {
+    let size = f.size();
+    let w = size.width;
+    let h = size.height;
+
+    let rect = Rect::new(w / 4, h / 4, w / 2, h / 2);
+    let layout = Layout::default()
+        .direction(tui::layout::Direction::Horizontal)
+        .constraints([Constraint::Length(28), Constraint::Min(1)].as_ref())
+        .split(rect);
+
+    let rows = app
+        .snippets
+        .iter()
+        .map(|s| {
+            Row::new(vec![Cell::from(Span::styled(
+                s.name.clone(),
+                Style::default().fg(Color::LightCyan),
+            ))])
+        })
+        .collect::<Vec<Row>>();
+
+    let list = Table::new(rows)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("[ {} ]", t("dialog.snippets"))),
+        )
+        .widths(&[Constraint::Percentage(100)])
+        .highlight_style(Style::default().add_modifier(Modifier::BOLD))
+        .highlight_symbol("> ");
+
+    let body = app
+        .snippet_state
+        .selected()
+        .and_then(|pos| app.snippets.get(pos))
+        .map_or(String::new(), |s| redact(&s.body));
+
+    let preview = Paragraph::new(body)
+        .style(Style::default().fg(Color::White))
+        .block(Block::default().borders(Borders::ALL).title(format!(
+            "[ {} ]",
+            tf(
+                "dialog.snippets.dir",
+                &[&crate::snippets::snippets_dir(&crate::config::config_path()).display()]
+            )
+        )));
+
+    f.render_widget(Clear, rect);
+    f.render_stateful_widget(list, layout[0], &mut app.snippet_state);
+    f.render_widget(preview, layout[1]);
+}
+
+fn dialog_routes<B: Backend>(f: &mut Frame<B>, report: &RouteReport) {
+    let size = f.size();
+    let w = size.width;
+    let h = size.height;
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(Span::from(format!(
+            "[ {} ]",
+            tf("dialog.routes", &[&report.interface, &report.problems()])
+        )));
+
+    let mut rows = vec![
+        Row::new(vec![
+            Cell::from(Span::styled("Interface", Style::default().fg(Color::White))),
+            Cell::from(Span::styled(
+                match report.interface_up {
+                    Some(true) => "up",
+                    Some(false) => "down",
+                    None => "not found",
+                },
+                Style::default().fg(if report.interface_up.unwrap_or_default() {
+                    Color::LightGreen
+                } else {
+                    Color::LightRed
+                }),
+            )),
+        ]),
+        Row::new(vec![
+            Cell::from(Span::styled(
+                "Allow Managed",
+                Style::default().fg(Color::White),
+            )),
+            Cell::from(Span::styled(
+                if report.allow_managed {
+                    "true".to_string()
+                } else {
+                    "false: managed routes will not be installed".to_string()
+                },
+                Style::default().fg(if report.allow_managed {
+                    Color::LightGreen
+                } else {
+                    Color::LightRed
+                }),
+            )),
+        ]),
+    ];
+
+    if let Some(err) = &report.error {
+        rows.push(Row::new(vec![
+            Cell::from(Span::styled("Error", Style::default().fg(Color::White))),
+            Cell::from(Span::styled(
+                redact(err),
+                Style::default().fg(Color::LightRed),
+            )),
+        ]));
+    }
+
+    rows.push(Row::new(vec![Cell::from(""), Cell::from("")]));
+
+    for check in &report.checks {
+        let (status, color) = match &check.status {
+            RouteStatus::Present => ("installed".to_string(), Color::LightGreen),
+            RouteStatus::OtherInterface(iface) => (
+                format!("routed via {} instead (overlap?)", iface),
+                Color::LightYellow,
+            ),
+            RouteStatus::Missing => ("missing".to_string(), Color::LightRed),
+        };
+
+        rows.push(Row::new(vec![
+            Cell::from(Span::styled(
+                redact(&match &check.via {
+                    Some(via) => format!("{} via {}", check.target, via),
+                    None => check.target.clone(),
+                }),
+                Style::default().fg(Color::LightCyan),
+            )),
+            Cell::from(Span::styled(status, Style::default().fg(color))),
+        ]));
+    }
+
+    let table = Table::new(rows)
+        .block(block)
+        .widths(&[Constraint::Percentage(50), Constraint::Percentage(50)]);
+
+    let rect = Rect::new(w / 4, h / 4, w / 2, h / 2);
+    f.render_widget(Clear, rect);
+    f.render_widget(table, rect);
+}
+
+fn dialog_history<B: Backend>(
+    f: &mut Frame<B>,
+    history: &History,
+    network_id: &str,
+    node_id: &str,
+) {
+    let size = f.size();
+    let rect = Rect::new(
+        size.width / 8,
+        size.height / 4,
+        size.width - size.width / 4,
+        size.height / 2,
+    );
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(Span::from(format!(
+            "[ {} ]",
+            tf("dialog.history", &[&redact(node_id)])
+        )));
+
+    let inner = block.inner(rect);
+    f.render_widget(Clear, rect);
+    f.render_widget(block, rect);
+
+    let layout = Layout::default()
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(1),
+        ])
+        .split(inner);
+
+    let timeline = history
+        .timeline(network_id, node_id, 24 * 60 * 60, inner.width as u64)
+        .iter()
+        .map(|s| {
+            let (mark, color) = crate::theme::history_mark(*s);
+            Span::styled(mark, Style::default().fg(color))
+        })
+        .collect::<Vec<Span>>();
+
+    f.render_widget(
+        Paragraph::new(Span::styled(
+            "Last 24 hours (now is on the right):",
+            Style::default().fg(Color::White),
+        )),
+        layout[0],
+    );
+    f.render_widget(Paragraph::new(Spans::from(timeline)), layout[1]);
+
+    let transitions = history.transitions(network_id, node_id);
+    let now = crate::history::now();
+
+    let rows = transitions
+        .iter()
+        .enumerate()
+        .rev()
+        .map(|(i, t)| {
+            let until = transitions.get(i + 1).map_or(now, |n| n.at);
+
+            Row::new(vec![
+                Cell::from(Span::styled(
+                    format!(
+                        "{} ago",
+                        fancy_duration::FancyDuration::new(std::time::Duration::from_secs(
+                            now.saturating_sub(t.at)
+                        ))
+                        .to_string()
+                    ),
+                    Style::default().fg(Color::LightCyan),
+                )),
+                Cell::from(Span::styled(
+                    if t.online { "Online" } else { "Offline" },
+                    Style::default().fg(if t.online {
+                        Color::LightGreen
+                    } else {
+                        Color::LightRed
+                    }),
+                )),
+                Cell::from(Span::styled(
+                    format!(
+                        "for {}",
+                        fancy_duration::FancyDuration::new(std::time::Duration::from_secs(
+                            until.saturating_sub(t.at)
+                        ))
+                        .to_string()
+                    ),
+                    Style::default().fg(Color::LightCyan),
+                )),
+            ])
+        })
+        .collect::<Vec<Row>>();
+
+    let table = Table::new(rows)
+        .header(Row::new(vec![
+            Cell::from(Span::styled("Changed", Style::default().fg(Color::White))),
+            Cell::from(Span::styled("State", Style::default().fg(Color::White))),
+            Cell::from(Span::styled("Duration", Style::default().fg(Color::White))),
+        ]))
+        .widths(&[
+            Constraint::Length(25),
+            Constraint::Length(10),
+            Constraint::Percentage(100),
+        ]);
+
+    f.render_widget(table, layout[3]);
+}
+
+// returns the chart to draw as an image over the dialog, when the terminal can show one
+fn dialog_traffic<B: Backend>(
+    f: &mut Frame<B>,
+    nets: &Nets,
+    id: &str,
+    interfaces: Vec<String>,
+) -> Option<(Rect, crate::image::Image)> {
+    let size = f.size();
+    let w = size.width;
+    let h = size.height;
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(Span::from(format!(
+            "[ {} ]",
+            tf(
+                "dialog.traffic",
+                &[&interfaces.join(", "), &nets.source_name()]
+            )
+        )));
+
+    let row = |label: String, t: Traffic| {
+        Row::new(vec![
+            Cell::from(Span::styled(label, Style::default().fg(Color::LightCyan))),
+            Cell::from(Span::styled(
+                format_bytes(t.rx),
+                Style::default().fg(Color::LightMagenta),
+            )),
+            Cell::from(Span::styled(
+                format_bytes(t.tx),
+                Style::default().fg(Color::LightMagenta),
+            )),
+            Cell::from(Span::styled(
+                format_bytes(t.total()),
+                Style::default().fg(Color::Magenta),
+            )),
+        ])
+    };
+
+    let mut rows = vec![row("This session".to_string(), nets.session_totals(id))];
+    for (day, t) in nets.daily_totals(id) {
+        rows.push(row(day, t));
+    }
+
+    let table = Table::new(rows)
+        .block(block)
+        .header(Row::new(vec![
+            Cell::from(Span::styled("Day (UTC)", Style::default().fg(Color::White))),
+            Cell::from(Span::styled("Rx", Style::default().fg(Color::White))),
+            Cell::from(Span::styled("Tx", Style::default().fg(Color::White))),
+            Cell::from(Span::styled("Total", Style::default().fg(Color::White))),
+        ]))
+        .widths(&[
+            Constraint::Length(15),
+            Constraint::Length(15),
+            Constraint::Length(15),
+            Constraint::Length(15),
+        ]);
+
+    let rect = Rect::new(w / 4, h / 4, w / 2, h / 2);
+    let layout = Layout::default()
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(rect);
+    f.render_widget(Clear, rect);
+    f.render_widget(table, layout[0]);
+
+    traffic_chart(f, layout[1], nets.daily_totals(id))
+}
+
+// a bar for each day, as many as fit with room for a label under each. drawn as an image where
+// the terminal can show one, with received and sent side by side, and in text otherwise.
+fn traffic_chart<B: Backend>(
+    f: &mut Frame<B>,
+    rect: Rect,
+    mut days: Vec<(String, Traffic)>,
+) -> Option<(Rect, crate::image::Image)> {
+    const SLOT: u16 = 6;
+
+    let graphics = crate::terminal::graphics();
+    let title = match graphics {
+        Some(_) => Spans::from(vec![
+            Span::raw(format!("[ {} ", t("dialog.traffic_chart"))),
+            Span::styled("Rx", Style::default().fg(Color::LightCyan)),
+            Span::raw(" "),
+            Span::styled("Tx", Style::default().fg(Color::LightMagenta)),
+            Span::raw(" ]"),
+        ]),
+        None => Spans::from(format!("[ {} ]", t("dialog.traffic_chart_total"))),
+    };
+    let block = Block::default().borders(Borders::ALL).title(title);
+    let inner = block.inner(rect);
+    f.render_widget(block, rect);
+
+    days.truncate((inner.width / SLOT) as usize);
+    days.reverse();
+    let labels = days
+        .iter()
+        .map(|(day, _)| day.get(5..).unwrap_or(day).to_string())
+        .collect::<Vec<String>>();
+
+    if graphics.is_none() || inner.height < 2 || days.is_empty() {
+        let data = labels
+            .iter()
+            .zip(&days)
+            .map(|(label, (_, t))| (label.as_str(), t.total()))
+            .collect::<Vec<(&str, u64)>>();
+        // the values would be raw byte counts, so they're hidden in the bars
+        let chart = BarChart::default()
+            .data(&data)
+            .bar_width(SLOT - 1)
+            .bar_gap(1)
+            .bar_style(Style::default().fg(Color::Magenta))
+            .value_style(Style::default().fg(Color::Magenta).bg(Color::Magenta));
+        f.render_widget(chart, inner);
+        return None;
+    }
+
+    // the image goes above a row of labels, each centered under its day's share of the width
+    let area = Rect::new(inner.x, inner.y, inner.width, inner.height - 1);
+    let slot = inner.width / days.len() as u16;
+    let mut row = String::new();
+    for label in &labels {
+        row.push_str(&format!("{:^width$}", label, width = slot as usize));
+    }
+    f.render_widget(
+        Paragraph::new(row).style(Style::default().fg(Color::LightCyan)),
+        Rect::new(inner.x, inner.y + area.height, inner.width, 1),
+    );
+
+    Some((
+        area,
+        crate::image::traffic_chart(&days, area.width, area.height),
+    ))
+}
+
+pub fn display_dialogs<B: Backend>(f: &mut Frame<'_, B>, app: &mut App, settings: &Settings) {
+    match app.dialog.clone() {
+        Dialog::Join(_) => {
+            dialog_form(f, app, &t("form.join"));
+        }
+        Dialog::APIKey(_, error) => {
+            dialog_api_key(f, app, error);
+        }
+        Dialog::AddMember(..) => {
+            dialog_form(f, app, &t("form.add_member"));
+        }
+        Dialog::NewNetwork(_) => {
+            dialog_form(f, app, &t("form.new_network"));
+        }
+        #[cfg(feature = "central")]
+        Dialog::Sso(id, sso) => {
+            dialog_sso(f, sso.0, &id);
+        }
+        #[cfg(feature = "central")]
+        Dialog::SsoEdit(id, _) => {
+            dialog_form(f, app, &tf("form.sso", &[&redact(&id)]));
+        }
+        Dialog::Batch(batch) => {
+            dialog_batch(f, &batch);
+        }
+        Dialog::Pick(Purpose::JoinNetwork, _) => {
+            dialog_picker(f, app, &t("picker.join_network"));
+        }
+        Dialog::Pick(Purpose::Interface(id), _) => {
+            dialog_picker(f, app, &tf("picker.interface", &[&redact(&id)]));
+        }
+        Dialog::Pick(Purpose::FlagAll, _) => {
+            dialog_picker(f, app, &t("picker.flag_all"));
+        }
+        Dialog::Pick(Purpose::JoinSet, _) => {
+            dialog_picker(f, app, &t("picker.join_set"));
+        }
+        Dialog::Menu => {
+            dialog_menu(f, app, settings);
+        }
+        Dialog::MacroName => {
+            let title = tf("prompt.macro_name", &[&app.recorded.len()]);
+            dialog(f, app, 25, title);
+        }
+        Dialog::RunMacro => {
+            let names = settings.macros.keys().cloned().collect::<Vec<String>>();
+            let title = if names.is_empty() {
+                t("prompt.no_macros")
+            } else {
+                tf("prompt.run_macro", &[&names.join(", ")])
+            };
+            dialog(f, app, 30, title);
+        }
+        Dialog::Ping(node_id, pinger) => {
+            dialog_ping(f, &pinger, node_id);
+        }
+        Dialog::Capture(id, capture) => {
+            if let Some(network) = settings.get(&id) {
+                let interface = settings.capture_interface(network).unwrap_or_default();
+                dialog_capture(f, &capture, &interface);
+            }
+        }
+        Dialog::Routes(_, report) => {
+            dialog_routes(f, &report);
+        }
+        Dialog::Identity(report) => {
+            dialog_identity(f, &report);
+        }
+        Dialog::ConfirmQuit => {
+            dialog_quit(f, t("dialog.quit"), None);
+        }
+        Dialog::SaveFailed(error) => {
+            dialog_quit(f, t("dialog.save_failed"), Some(error));
+        }
+        Dialog::AlreadyRunning(pid) => {
+            dialog_already_running(f, pid);
+        }
+        Dialog::KeyRejected(id, error) => {
+            dialog_key_rejected(f, settings, &id, &error);
+        }
+        Dialog::Doctor(checks) => {
+            dialog_doctor(f, &checks);
+        }
+        Dialog::GoToMember => {
+            dialog(f, app, 25, t("prompt.go_to_member"));
+        }
+        Dialog::IdentityBackup(report) => {
+            dialog_identity(f, &report);
+            dialog_identity_backup(f, app);
+        }
+        Dialog::History(network_id, node_id) => {
+            dialog_history(f, &settings.history, &network_id, &node_id);
+        }
+        Dialog::Traffic(id) => {
+            if let Some(network) = settings.get(&id) {
+                app.image = dialog_traffic(
+                    f,
+                    &settings.nets,
+                    &id,
+                    settings.interfaces_for_network(network),
+                );
+            }
+        }
+        Dialog::Snippets(_) => {
+            dialog_snippets(f, app);
+        }
+        Dialog::Actions => {
+            dialog_actions(f, &settings.errors);
+        }
+        Dialog::Pending => {
+            dialog_pending(f, app, &settings.pending);
+        }
+        Dialog::RecentlyLeft => {
+            dialog_recently_left(f, app, settings);
+        }
+        #[cfg(feature = "central")]
+        Dialog::RulesDiff(id, edit) => {
+            dialog_rules_diff(f, &edit, &id);
+        }
+        Dialog::Changelog(id) => {
+            dialog_changelog(f, &settings.changelog, &id);
+        }
+        Dialog::Legend(id) => {
+            let names = settings.rule_names.get(&id).cloned().unwrap_or_default();
+            dialog_legend(f, &names);
+        }
+        Dialog::BatchRename(id) => {
+            let members = settings.members.get(&id).cloned().unwrap_or_default();
+            dialog_batch_rename(f, app, &members);
+        }
+        Dialog::NetworkFlags(id) => {
+            if let Some(network) = settings.get(&id) {
+                dialog_flags(f, app, network.clone());
+            }
+        }
+        Dialog::FullTunnel(id) => {
+            if let Some(network) = settings.get(&id) {
+                dialog_full_tunnel(f, network);
+            }
+        }
+        Dialog::FlagAll(flag, on) => {
+            dialog_flag_all(f, settings, flag, on);
+        }
+        Dialog::JoinSet(name) => {
+            if let Some(set) = settings.user_config().join_sets().get(&name) {
+                dialog_join_set(f, settings, &name, set);
+            }
+        }
+        _ => {}
+    }
+}
+
+// what a member's row says that only changes when its network's members are fetched again. it's
+// worked out once a fetch instead of every frame, and the table borrows it.
+#[derive(Debug, Clone)]
+pub struct MemberText {
+    node_id: String,
+    name: String,
+    addresses: Vec<String>,
+    ips: String,
+    duplicate: bool,
+    authed: bool,
+    caps: String,
+    // in milliseconds, as Central gives it
+    last_online: Option<u64>,
+}
+
+// the row text for one network's members, and which revision of them it was worked out from
+#[derive(Debug, Clone, Default)]
+pub struct MemberCache {
+    key: Option<(String, u64)>,
+    text: Vec<MemberText>,
+}
+
+impl MemberCache {
+    pub fn refresh(&mut self, id: &str, revision: u64, members: &[Member], names: &RuleNames) {
+        if self
+            .key
+            .as_ref()
+            .is_some_and(|(cached, r)| cached == id && *r == revision)
+        {
+            return;
+        }
+
+        let duplicates = crate::addresses::duplicates(members);
+        self.text = members
+            .iter()
+            .map(|m| {
+                let config = m.config.as_ref();
+                let addresses = config
+                    .and_then(|c| c.ip_assignments.clone())
+                    .unwrap_or_default();
+                let duplicate = addresses.iter().any(|a| duplicates.contains_key(a));
+
+                MemberText {
+                    node_id: m.node_id.clone().unwrap_or_default(),
+                    name: m.name.clone().unwrap_or_default(),
+                    ips: if duplicate {
+                        format!("! {}", addresses.join(", "))
+                    } else {
+                        addresses.join(", ")
+                    },
+                    addresses,
+                    duplicate,
+                    authed: config.and_then(|c| c.authorized).unwrap_or_default(),
+                    caps: config
+                        .and_then(|c| c.capabilities.as_ref())
+                        .map(|caps| {
+                            caps.iter()
+                                .map(|x| names.cap(*x))
+                                .collect::<Vec<String>>()
+                                .join(", ")
+                        })
+                        .unwrap_or_default(),
+                    last_online: m.last_online.map(|at| at as u64),
+                }
+            })
+            .collect();
+        self.key = Some((id.to_string(), revision));
+    }
+}
+
+// `editing` is the node being renamed and the editor to show in place of its name
+fn members_table<'a>(
+    text: &'a [MemberText],
+    rows: &[MemberRow],
+    title: String,
+    editing: Option<(&str, Spans<'a>)>,
+    marked: &HashSet<String>,
+    // by address, when per-member traffic is turned on
+    traffic: Option<&HashMap<String, (f64, f64)>>,
+) -> Table<'a> {
+    let titleblock = Block::default().borders(Borders::ALL).title(title);
+    let now = OffsetDateTime::from(SystemTime::now()) - OffsetDateTime::UNIX_EPOCH;
+
+    let rows = rows
+        .iter()
+        .map(|row| {
+            let m = match row {
+                MemberRow::Member(i) => &text[*i],
+                MemberRow::Header {
+                    label,
+                    count,
+                    collapsed,
+                } => {
+                    return Row::new(vec![Cell::from(Span::styled(
+                        format!(
+                            "{} {} ({})",
+                            if *collapsed { "+" } else { "-" },
+                            redact(label),
+                            count
+                        ),
+                        Style::default()
+                            .fg(Color::Yellow)
+                            .add_modifier(Modifier::BOLD),
+                    ))])
+                }
+            };
+
+            let mut cells = vec![
+                if marked.contains(&m.node_id) {
+                    Cell::from(Span::styled(
+                        format!("*{}", redact(&m.node_id)),
+                        Style::default()
+                            .fg(Color::LightMagenta)
+                            .add_modifier(Modifier::BOLD),
+                    ))
+                } else {
+                    Cell::from(Span::styled(
+                        redact(&m.node_id),
+                        Style::default().fg(Color::Cyan),
+                    ))
+                },
+                match &editing {
+                    Some((node_id, editor)) if m.node_id == *node_id => {
+                        Cell::from(editor.clone()).style(Style::default().fg(Color::White))
+                    }
+                    _ => Cell::from(Span::styled(
+                        m.name.as_str(),
+                        Style::default().fg(Color::LightCyan),
+                    )),
+                },
+                Cell::from(Span::styled(
+                    m.last_online
+                        .map(|at| {
+                            fancy_duration::FancyDuration::new(
+                                now - Duration::new((at / 1000) as i64, 0),
+                            )
+                            .to_string()
+                        })
+                        .unwrap_or_default(),
+                    Style::default().fg(Color::LightCyan),
+                )),
+                if m.duplicate {
+                    Cell::from(Span::styled(
+                        redact(&m.ips),
+                        Style::default()
+                            .fg(Color::LightRed)
+                            .add_modifier(Modifier::BOLD),
+                    ))
+                } else {
+                    Cell::from(Span::styled(
+                        redact(&m.ips),
+                        Style::default().fg(Color::LightGreen),
+                    ))
+                },
+                Cell::from(Span::styled(
+                    if m.authed { "Auth" } else { "Unauth" },
+                    Style::default().fg(if m.authed {
+                        Color::LightGreen
+                    } else {
+                        Color::LightRed
+                    }),
+                )),
+                Cell::from(Span::styled(
+                    m.caps.as_str(),
+                    Style::default().fg(Color::LightGreen),
+                )),
+            ];
+
+            if let Some(traffic) = traffic {
+                let (rx, tx) = m
+                    .addresses
+                    .iter()
+                    .filter_map(|a| traffic.get(a))
+                    .fold((0.0, 0.0), |(rx, tx), (r, t)| (rx + r, tx + t));
+                cells.insert(
+                    4,
+                    Cell::from(Span::styled(
+                        format_rate(rx, tx),
+                        Style::default().fg(Color::LightMagenta),
+                    )),
+                );
+            }
+
+            Row::new(cells)
+        })
+        .collect::<Vec<Row>>();
+
+    let mut header = vec![
+        Cell::from(Span::styled("Node ID", Style::default().fg(Color::White))),
+        Cell::from(Span::styled("Name", Style::default().fg(Color::White))),
+        Cell::from(Span::styled(
+            "Last Online",
+            Style::default().fg(Color::White),
+        )),
+        Cell::from(Span::styled(
+            "IP Addresses",
+            Style::default().fg(Color::White),
+        )),
+        Cell::from(Span::styled(
+            "Auth Status",
+            Style::default().fg(Color::White),
+        )),
+        Cell::from(Span::styled(
+            "Capabilities",
+            Style::default().fg(Color::White),
+        )),
+    ];
+    const WIDTHS: [Constraint; 6] = [
+        Constraint::Length(13),
+        Constraint::Length(20),
+        Constraint::Length(25),
+        Constraint::Length(25),
+        Constraint::Length(8),
+        Constraint::Percentage(100),
+    ];
+    const WIDTHS_WITH_TRAFFIC: [Constraint; 7] = [
+        Constraint::Length(13),
+        Constraint::Length(20),
+        Constraint::Length(25),
+        Constraint::Length(25),
+        Constraint::Length(30),
+        Constraint::Length(8),
+        Constraint::Percentage(100),
+    ];
+
+    let widths: &[Constraint] = if traffic.is_some() {
+        header.insert(
+            4,
+            Cell::from(Span::styled("Traffic", Style::default().fg(Color::White))),
+        );
+        &WIDTHS_WITH_TRAFFIC
+    } else {
+        &WIDTHS
+    };
+
+    Table::new(rows)
+        .block(titleblock)
+        .header(Row::new(header))
+        .widths(widths)
+}
+
+// what the members page shows about the network besides its members, borrowed from the settings
+pub struct NetworkView<'a> {
+    pub id: &'a str,
+    pub revision: u64,
+    pub names: &'a RuleNames,
+    pub read_only: bool,
+    // by address, when per-member traffic is turned on
+    pub traffic: Option<&'a HashMap<String, (f64, f64)>>,
+    pub utilization: Option<Utilization>,
+}
+
+pub fn display_network<B: Backend>(
+    f: &mut Frame<'_, B>,
+    app: &mut App,
+    members: &[Member],
+    fetch: Fetch,
+    view: NetworkView,
+) -> Result<(), anyhow::Error> {
+    let list = Layout::default()
+        .constraints([Constraint::Min(4)])
+        .split(f.size());
+
+    app.member_cache
+        .refresh(view.id, view.revision, members, view.names);
+    app.member_rows = app.grouping.rows(members, &app.collapsed);
+    app.member_count = app.member_rows.len();
+
+    let mut extra = recording(app);
+    if app.grouping != Grouping::None {
+        extra.push(tf("title.grouped", &[&app.grouping]));
+    }
+    if let Some(u) = view.utilization {
+        let key = if u.nearly_full() {
+            "title.addresses_nearly_full"
+        } else {
+            "title.addresses_used"
+        };
+        extra.push(tf(key, &[&u.used, &u.size]));
+    }
+    if view.read_only {
+        extra.push(t("title.read_only"));
+    }
+    extra.extend(freshness(&fetch));
+    let title = main_title(extra);
+
+    let editing = match &app.dialog {
+        Dialog::RenameMember(_, node_id) => {
+            Some((node_id.as_str(), input_spans(&app.inputbuffer, app.cursor)))
+        }
+        _ => None,
+    };
+
+    let table = members_table(
+        &app.member_cache.text,
+        &app.member_rows,
+        title,
+        editing,
+        &app.marked,
+        view.traffic,
+    )
+    .style(stale_style(&fetch))
+    .highlight_style(Style::default().add_modifier(Modifier::BOLD))
+    .highlight_symbol("> ");
+
+    f.render_stateful_widget(table, list[0], &mut app.member_state);
+    mark_stale(f, list[0], &fetch);
+    Ok(())
+}
+
+// the right hand side of the split view: members of whatever network is selected on the left
+fn display_split_members<B: Backend>(
+    f: &mut Frame<'_, B>,
+    area: Rect,
+    cache: &mut MemberCache,
+    settings: &Settings,
+) {
+    let id = match settings.selected_network_id() {
+        Some(id) => id,
+        None => return,
+    };
+
+    let message = if settings.api_key_for_id(id.clone()).is_none() {
+        "No API key for this network; press s to add one".to_string()
+    } else if let Some((_, err)) = settings.split_error.as_ref().filter(|(e, _)| *e == id) {
+        format!("Could not load members: {}", redact(err))
+    } else if let Some(members) = settings.members.get(&id) {
+        cache.refresh(
+            &id,
+            settings.members_revision,
+            members,
+            settings
+                .rule_names
+                .get(&id)
+                .unwrap_or(&RuleNames::default()),
+        );
+        f.render_widget(
+            members_table(
+                &cache.text,
+                &Grouping::None.rows(members, &HashSet::new()),
+                format!("[ {} ]", tf("title.members", &[&redact(&id)])),
+                None,
+                &HashSet::new(),
+                None,
+            ),
+            area,
+        );
+        return;
+    } else {
+        format!("{} Loading members...", spinner())
+    };
+
+    f.render_widget(
+        Paragraph::new(Span::styled(
+            message,
+            Style::default().fg(Color::LightYellow),
+        ))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("[ {} ]", tf("title.members", &[&redact(&id)]))),
+        ),
+        area,
+    );
+}
+
+// allowDefault, allowManaged, allowGlobal and allowDNS at a glance: the letter when it's on, a
+// dash when it's off, so flags that differ between machines stand out down the column. A is
+// ztui's own auto-rejoin.
+fn flag_spans(network: &Network, auto_rejoin: bool) -> Spans<'static> {
+    let flags = [
+        ("D", network.subtype_0.allow_default),
+        ("M", network.subtype_0.allow_managed),
+        ("G", network.subtype_0.allow_global),
+        ("N", network.subtype_0.allow_dns),
+        ("A", Some(auto_rejoin)),
+    ];
+
+    Spans::from(
+        flags
+            .iter()
+            .map(|(letter, on)| {
+                if on.unwrap_or_default() {
+                    Span::styled(
+                        format!("{} ", letter),
+                        Style::default().fg(Color::LightGreen),
+                    )
+                } else {
+                    Span::styled("- ", Style::default().fg(Color::DarkGray))
+                }
+            })
+            .collect::<Vec<Span>>(),
+    )
+}
+
+pub fn display_networks<B: Backend>(
+    f: &mut Frame<'_, B>,
+    app: &mut App,
+    settings: &mut Settings,
+) -> Result<(), anyhow::Error> {
+    let list = if settings.split {
+        Layout::default()
+            .direction(tui::layout::Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(f.size())
+    } else {
+        Layout::default()
+            .constraints([Constraint::Min(4)])
+            .split(f.size())
+    };
+
+    let fetch = settings.networks_fetch.clone();
+    let mut extra = recording(app);
+    extra.extend(freshness(&fetch));
+    if !settings.pending.is_empty() {
+        extra.push(format!(
+            "{} changes queued (P)",
+            settings.pending.changes().len()
+        ));
+    }
+    let titleblock = Block::default()
+        .borders(Borders::ALL)
+        .title(main_title(extra));
+
+    let rows = settings
+        .idx_iter()
+        .filter_map(|k| {
+            let v = match settings.get(k) {
+                Some(v) => v,
+                None => return None,
+            };
+
+            if let ListFilter::Connected = settings.filter() {
+                if v.subtype_1.status.as_deref() == Some(STATUS_DISCONNECTED) {
+                    return None;
+                }
+            }
 
             Some(Row::new(vec![
                 Cell::from(Span::styled(
-                    k.clone(),
+                    redact(k),
                     Style::default().fg(Color::LightCyan),
                 )),
                 Cell::from(Span::styled(
@@ -382,36 +2708,48 @@ pub fn display_networks<B: Backend>(
                     Style::default().fg(Color::Cyan),
                 )),
                 Cell::from(Span::styled(
-                    v.subtype_1.status.clone().unwrap(),
-                    Style::default().fg(match v.subtype_1.status.clone().unwrap().as_str() {
+                    v.subtype_1.status.clone().unwrap_or_default(),
+                    Style::default().fg(match v.subtype_1.status.as_deref().unwrap_or_default() {
                         "OK" => Color::LightGreen,
                         "REQUESTING_CONFIGURATION" => Color::LightYellow,
                         STATUS_DISCONNECTED => Color::LightRed,
                         _ => Color::LightRed,
                     }),
                 )),
+                Cell::from(flag_spans(v, settings.auto_rejoin(k))),
                 Cell::from(Span::styled(
-                    v.subtype_1.assigned_addresses.join(", "),
+                    redact(&v.subtype_1.assigned_addresses.join(", ")),
                     Style::default().fg(Color::LightGreen),
                 )),
+                if settings.interface_unknown(v) {
+                    Cell::from(Span::styled(
+                        t("network.rate_unknown"),
+                        Style::default().fg(Color::DarkGray),
+                    ))
+                } else {
+                    Cell::from(Span::styled(
+                        if let Some(s) = settings.nets.get_usage(k.clone()) {
+                            s
+                        } else {
+                            "".to_string()
+                        },
+                        Style::default().fg(Color::LightMagenta),
+                    ))
+                },
                 Cell::from(Span::styled(
-                    if let Some(s) = lock
-                        .nets
-                        .clone()
-                        .get_usage(v.subtype_1.port_device_name.clone().unwrap())
-                    {
-                        s
-                    } else {
-                        "".to_string()
-                    },
-                    Style::default().fg(Color::LightMagenta),
+                    format!(
+                        "{} / {}",
+                        format_bytes(settings.nets.session_totals(k).total()),
+                        format_bytes(settings.nets.today_totals(k).total())
+                    ),
+                    Style::default().fg(Color::Magenta),
                 )),
             ]))
         })
         .collect::<Vec<Row>>();
 
-    if lock.network_state.selected().is_none() && rows.len() > 0 {
-        lock.network_state.select(Some(0));
+    if settings.network_state.selected().is_none() && !rows.is_empty() {
+        settings.network_state.select(Some(0));
     }
 
     let table = Table::new(rows)
@@ -423,22 +2761,366 @@ pub fn display_networks<B: Backend>(
             )),
             Cell::from(Span::styled("Name", Style::default().fg(Color::White))),
             Cell::from(Span::styled("Status", Style::default().fg(Color::White))),
+            Cell::from(Span::styled("Flags", Style::default().fg(Color::White))),
             Cell::from(Span::styled(
                 "Assigned IPs",
                 Style::default().fg(Color::White),
             )),
             Cell::from(Span::styled("Usage", Style::default().fg(Color::White))),
+            Cell::from(Span::styled(
+                "Session / Today",
+                Style::default().fg(Color::White),
+            )),
         ]))
         .widths(&[
             Constraint::Length(16),
             Constraint::Length(20),
             Constraint::Length(15),
+            Constraint::Length(9),
             Constraint::Length(20),
             Constraint::Length(35),
+            Constraint::Length(25),
         ])
+        .style(stale_style(&fetch))
         .highlight_style(Style::default().add_modifier(Modifier::BOLD))
         .highlight_symbol("> ");
 
-    f.render_stateful_widget(table, list[0], &mut lock.network_state);
+    f.render_stateful_widget(table, list[0], &mut settings.network_state);
+    mark_stale(f, list[0], &fetch);
+
+    if settings.split {
+        display_split_members(f, list[1], &mut app.split_cache, settings);
+    }
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use serde_json::json;
+
+    use crate::{
+        app::Dialog,
+        nets::Nets,
+        testing::{Fixture, Screen},
+        traffic::Unsupported,
+    };
+    #[cfg(feature = "central")]
+    use crate::{app::DialogState, rules::RulesEdit};
+
+    const HOMELAB: &str = "8056c2e21c000001";
+
+    // two networks, one with a key and its members already fetched, on a screen small enough to
+    // compare whole
+    fn fixture() -> Fixture {
+        let mut fx = Fixture::new();
+        fx.terminal = Screen::terminal(100, 14);
+
+        let mut lock = fx.settings.lock().unwrap();
+        // no counters, so the usage column reads the same whatever interfaces this host has
+        lock.nets = Nets::with_source(Arc::new(Unsupported)).unwrap();
+
+        let homelab = json!({
+            "id": HOMELAB,
+            "name": "homelab",
+            "status": "OK",
+            "allowManaged": true,
+            "assignedAddresses": ["10.147.17.5/24"],
+            "portDeviceName": "ztstandin0",
+        });
+        let office = json!({
+            "id": "8056c2e21c000002",
+            "name": "office",
+            "status": "REQUESTING_CONFIGURATION",
+            "portDeviceName": "ztstandin1",
+        });
+        // homelab is seen first, so it's listed first
+        for networks in [vec![homelab.clone()], vec![homelab, office]] {
+            lock.update_networks(serde_json::from_value(json!(networks)).unwrap())
+                .unwrap();
+        }
+        #[cfg(feature = "central")]
+        {
+            lock.set_api_key_for_id(HOMELAB.to_string(), "key".to_string());
+            lock.set_members(
+                HOMELAB.to_string(),
+                vec![
+                    serde_json::from_value(json!({
+                        "networkId": HOMELAB,
+                        "nodeId": "0123456789",
+                        "name": "web-1",
+                        "config": { "authorized": true, "ipAssignments": ["10.147.17.10"] },
+                    }))
+                    .unwrap(),
+                    serde_json::from_value(json!({
+                        "networkId": HOMELAB,
+                        "nodeId": "9876543210",
+                        "config": { "authorized": false },
+                    }))
+                    .unwrap(),
+                ],
+            );
+        }
+        drop(lock);
+
+        // the first frame is what selects the first row
+        fx.draw();
+        fx
+    }
+
+    // compares the whole screen, a row at a time, so a failure points at the row that moved
+    fn assert_screen(screen: String, expected: &[&str]) {
+        let rows = screen.lines().map(str::trim_end).collect::<Vec<_>>();
+        assert_eq!(rows.len(), expected.len(), "{}", screen);
+        for (row, (got, want)) in rows.iter().zip(expected).enumerate() {
+            assert_eq!(got, want, "row {}:\n{}", row, screen);
+        }
+    }
+
+    // the networks page, a row per network with its status, flags and addresses
+    #[test]
+    fn networks_page() {
+        let mut fx = fixture();
+        let screen = fx.draw();
+        assert_screen(
+            screen,
+            &[
+            "┌[ ZeroTier Terminal UI | Press h for Help ]───────────────────────────────────────────────────────┐",
+            "│  Network ID       Name                 Status          Flags     Assigned IPs         Usage      │",
+            "│> 8056c2e21c000001 homelab              OK              - M - - - 10.147.17.5/24                  │",
+            "│  8056c2e21c000002 office               REQUESTING_CONF - - - - -                                 │",
+            "│                                                                                                  │",
+            "│                                                                                                  │",
+            "│                                                                                                  │",
+            "│                                                                                                  │",
+            "│                                                                                                  │",
+            "│                                                                                                  │",
+            "│                                                                                                  │",
+            "│                                                                                                  │",
+            "│                                                                                                  │",
+            "└──────────────────────────────────────────────────────────────────────────────────────────────────┘",
+            ],
+        );
+    }
+
+    // the join form, over the networks page
+    #[test]
+    fn join_form() {
+        let mut fx = fixture();
+        let screen = fx.keys("J");
+        assert_screen(
+            screen,
+            &[
+            "┌[ ZeroTier Terminal UI | Press h for Help ]───────────────────────────────────────────────────────┐",
+            "│  Network ID       Name                 Status          Flags     Assigned IPs         Usage      │",
+            "│> 8056c2e21c000001 homelab              OK              - M - - - 10.147.17.5/24                  │",
+            "│  8056c2e21c000002 offic┌] Join a network | Tab to move, Enter to save [─┐                        │",
+            "│                        │Network ID:                                     │                        │",
+            "│                        │Allow managed: [x] yes                          │                        │",
+            "│                        │Allow global:  [ ] no                           │                        │",
+            "│                        │Allow default: [ ] no                           │                        │",
+            "│                        │Allow DNS:     [ ] no                           │                        │",
+            "│                        └────────────────────────────────────────────────┘                        │",
+            "│                                                                                                  │",
+            "│                                                                                                  │",
+            "│                                                                                                  │",
+            "└──────────────────────────────────────────────────────────────────────────────────────────────────┘",
+            ],
+        );
+    }
+
+    // asked for a key for the network that doesn't have one
+    #[test]
+    fn api_key() {
+        let mut fx = fixture();
+        let screen = fx.keys("Down s");
+        assert_screen(
+            screen,
+            &[
+            "┌[ ZeroTier Terminal UI | Press h for Help ]───────────────────────────────────────────────────────┐",
+            "│  Network ID       Name                 Status          Flags     Assigned IPs         Usage      │",
+            "│  8056c2e21c000001 homelab              OK              - M - - - 10.147.17.5/24                  │",
+            "│> 8056c2e21c000002 office               REQUESTING_CONF - - - - -                                 │",
+            "│                                                                                                  │",
+            "│                                                                                                  │",
+            "│                                                                                                  │",
+            "│                   ┌] Enter your Network API Key, after the controller URL if ┐                   │",
+            "│                   │                                                          │                   │",
+            "│                   └──────────────────────────────────────────────────────────┘                   │",
+            "│                                                                                                  │",
+            "│                                                                                                  │",
+            "│                                                                                                  │",
+            "└──────────────────────────────────────────────────────────────────────────────────────────────────┘",
+            ],
+        );
+    }
+
+    // the selected network's commands
+    #[test]
+    fn network_menu() {
+        let mut fx = fixture();
+        let screen = fx.keys("Enter");
+        assert_screen(
+            screen,
+            &[
+            "┌[ ZeroTier Terminal UI |┌[ network 8056c2e21c000001 (homelab) | Enter to ┐────────────────────────┐",
+            "│  Network ID       Name │> d      forget               Delete a list memb│ IPs         Usage      │",
+            "│> 8056c2e21c000001 homel│  j      join                 Join a bookmarked │7.5/24                  │",
+            "│  8056c2e21c000002 offic│  l      leave                Leave a bookmarked│                        │",
+            "│                        │  c      config               review network set│                        │",
+            "│                        │  s      members              show network membe│                        │",
+            "│                        │  e      edit-rules           edit network rules│                        │",
+            "│                        │  E      edit-rules-snippet   edit network rules│                        │",
+            "│                        │  p      capture              capture packets on│                        │",
+            "│                        │  R      routes               verify managed rou│                        │",
+            "│                        │  u      traffic              traffic totals per│                        │",
+            "│                        │  I      interface            pick the interface│                        │",
+            "│                        │  f      flags                toggle what the ne│                        │",
+            "└────────────────────────└────────────────────────────────────────────────┘────────────────────────┘",
+            ],
+        );
+    }
+
+    // the members page, with who's authorized and who isn't
+    #[test]
+    #[cfg(feature = "central")]
+    fn members_page() {
+        let mut fx = fixture();
+        let screen = fx.keys("s");
+        assert_screen(
+            screen,
+            &[
+            "┌[ ZeroTier Terminal UI | Press h for Help ]───────────────────────────────────────────────────────┐",
+            "│  Node ID       Name                 Last Online               IP Addresses              Auth Sta │",
+            "│> 0123456789    web-1                                          10.147.17.10              Auth     │",
+            "│  9876543210                                                                             Unauth   │",
+            "│                                                                                                  │",
+            "│                                                                                                  │",
+            "│                                                                                                  │",
+            "│                                                                                                  │",
+            "│                                                                                                  │",
+            "│                                                                                                  │",
+            "│                                                                                                  │",
+            "│                                                                                                  │",
+            "│                                                                                                  │",
+            "└──────────────────────────────────────────────────────────────────────────────────────────────────┘",
+            ],
+        );
+    }
+
+    // renaming a member, typed into its row
+    #[test]
+    #[cfg(feature = "central")]
+    fn rename() {
+        let mut fx = fixture();
+        fx.keys("s");
+        fx.keys("Down r");
+        let screen = fx.type_in("db-1");
+        assert_screen(
+            screen,
+            &[
+            "┌[ ZeroTier Terminal UI | Press h for Help ]───────────────────────────────────────────────────────┐",
+            "│  Node ID       Name                 Last Online               IP Addresses              Auth Sta │",
+            "│  0123456789    web-1                                          10.147.17.10              Auth     │",
+            "│> 9876543210    db-1                                                                     Unauth   │",
+            "│                                                                                                  │",
+            "│                                                                                                  │",
+            "│                                                                                                  │",
+            "│                                                                                                  │",
+            "│                                                                                                  │",
+            "│                                                                                                  │",
+            "│                                                                                                  │",
+            "│                                                                                                  │",
+            "│                                                                                                  │",
+            "└──────────────────────────────────────────────────────────────────────────────────────────────────┘",
+            ],
+        );
+    }
+
+    // asked before quitting
+    #[test]
+    fn confirm_quit() {
+        let mut fx = fixture();
+        fx.app.dialog = Dialog::ConfirmQuit;
+        let screen = fx.draw();
+        assert_screen(
+            screen,
+            &[
+            "┌[ ZeroTier Terminal UI | Press h for Help ]───────────────────────────────────────────────────────┐",
+            "│  Network ID       Name                 Status          Flags     Assigned IPs         Usage      │",
+            "│> 8056c2e21c000001 homelab              OK              - M - - - 10.147.17.5/24                  │",
+            "│  8056c2e21c000002 office               REQUESTING_CONF - - - - -                                 │",
+            "│                        ┌[ Quit? | q or y to quit, Esc to stay ]─────────┐                        │",
+            "│                        │Anything being typed or edited right now is     │                        │",
+            "│                        │lost.                                           │                        │",
+            "│                        │                                                │                        │",
+            "│                        └────────────────────────────────────────────────┘                        │",
+            "│                                                                                                  │",
+            "│                                                                                                  │",
+            "│                                                                                                  │",
+            "│                                                                                                  │",
+            "└──────────────────────────────────────────────────────────────────────────────────────────────────┘",
+            ],
+        );
+    }
+
+    // with privacy on, no part of an ID or an address gets to the screen, however it's cut
+    #[test]
+    fn privacy() {
+        let mut fx = fixture();
+        crate::privacy::set(true);
+        #[cfg(feature = "central")]
+        {
+            fx.app.dialog = Dialog::RulesDiff(
+                HOMELAB.to_string(),
+                DialogState(RulesEdit::new(
+                    "accept;\n".to_string(),
+                    "drop;\n".to_string(),
+                )),
+            );
+        }
+        let screen = fx.draw();
+        crate::privacy::set(false);
+
+        assert!(screen.contains("PRIVACY"), "{}", screen);
+        for real in ["8056c2e2", "10.147.17"] {
+            assert!(!screen.contains(real), "{} on screen:\n{}", real, screen);
+        }
+    }
+
+    // the rules diff shown before they're pushed
+    #[test]
+    #[cfg(feature = "central")]
+    fn rules_diff() {
+        let mut fx = fixture();
+        fx.app.dialog = Dialog::RulesDiff(
+            HOMELAB.to_string(),
+            DialogState(RulesEdit::new(
+                "accept;\n".to_string(),
+                "drop;\n".to_string(),
+            )),
+        );
+        let screen = fx.draw();
+        assert_screen(
+            screen,
+            &[
+            "┌[ ZeroTier Terminal UI | Press h for Help ]───────────────────────────────────────────────────────┐",
+            "│  Network I┌[ Rules changes for 8056c2e21c000001 | y to push, e to edit again, n to d┐ Usage      │",
+            "│> 8056c2e21│- accept;                                                                │            │",
+            "│  8056c2e21│+ drop;                                                                  │            │",
+            "│           │                                                                         │            │",
+            "│           │                                                                         │            │",
+            "│           │                                                                         │            │",
+            "│           │                                                                         │            │",
+            "│           │                                                                         │            │",
+            "│           │                                                                         │            │",
+            "│           │                                                                         │            │",
+            "│           └─────────────────────────────────────────────────────────────────────────┘            │",
+            "│                                                                                                  │",
+            "└──────────────────────────────────────────────────────────────────────────────────────────────────┘",
+            ],
+        );
+    }
+}
@@ -0,0 +1,275 @@
+#[cfg(feature = "central")]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+
+#[cfg(feature = "central")]
+use crate::client::central_client;
+use crate::client::{self, authtoken_path};
+
+// how far our clock may drift from Central's before we complain. zerotier certificates and
+// network configs carry timestamps, so a badly skewed clock shows up as networks that never
+// come up.
+#[cfg(feature = "central")]
+const SKEW_WARN_MS: i64 = 30 * 1000;
+#[cfg(feature = "central")]
+const SKEW_FAIL_MS: i64 = 5 * 60 * 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    Pass,
+    Warn,
+    Fail,
+    Skip,
+}
+
+impl std::fmt::Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Outcome::Pass => "PASS",
+            Outcome::Warn => "WARN",
+            Outcome::Fail => "FAIL",
+            Outcome::Skip => "SKIP",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Check {
+    pub name: &'static str,
+    pub outcome: Outcome,
+    pub detail: String,
+}
+
+impl Check {
+    fn new(name: &'static str, outcome: Outcome, detail: impl ToString) -> Self {
+        Self {
+            name,
+            outcome,
+            detail: detail.to_string(),
+        }
+    }
+}
+
+#[cfg(feature = "central")]
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+// roots answer over a direct path only when UDP makes it out; over TCP fallback they don't
+// have one at all.
+fn udp_check(peers: &[crate::api::one::types::Peer]) -> Check {
+    let name = "UDP 9993 egress";
+    let roots = peers
+        .iter()
+        .filter(|p| matches!(p.role.as_deref(), Some("PLANET") | Some("MOON")))
+        .collect::<Vec<_>>();
+
+    if roots.is_empty() {
+        return Check::new(name, Outcome::Fail, "no root servers are known to the node");
+    }
+
+    let reachable = roots
+        .iter()
+        .filter(|p| {
+            p.paths
+                .iter()
+                .any(|path| path.active.unwrap_or_default() && !path.expired.unwrap_or_default())
+        })
+        .count();
+
+    if reachable > 0 {
+        Check::new(
+            name,
+            Outcome::Pass,
+            format!("{} of {} roots reachable directly", reachable, roots.len()),
+        )
+    } else {
+        Check::new(
+            name,
+            Outcome::Fail,
+            "no root server is reachable over UDP; check outbound UDP 9993 on your firewall",
+        )
+    }
+}
+
+#[cfg(feature = "central")]
+fn central_checks(api_key: Option<String>) -> Vec<Check> {
+    let mut checks = Vec::new();
+
+    // the status endpoint answers without a valid key, so any saved key (or none) will do
+    let central =
+        central_client(api_key.unwrap_or_default()).and_then(client::sync_get_central_status);
+
+    match central {
+        Ok(central) => {
+            checks.push(Check::new(
+                "Central reachable",
+                Outcome::Pass,
+                format!("version {}", central.version.unwrap_or_default()),
+            ));
+
+            checks.push(match central.clock {
+                Some(clock) => {
+                    let skew = now_ms() - clock;
+                    let detail = format!("{:+.1}s relative to Central", skew as f64 / 1000.0);
+                    if skew.abs() > SKEW_FAIL_MS {
+                        Check::new("clock skew", Outcome::Fail, detail)
+                    } else if skew.abs() > SKEW_WARN_MS {
+                        Check::new("clock skew", Outcome::Warn, detail)
+                    } else {
+                        Check::new("clock skew", Outcome::Pass, detail)
+                    }
+                }
+                None => Check::new(
+                    "clock skew",
+                    Outcome::Skip,
+                    "Central did not report a clock",
+                ),
+            });
+        }
+        Err(e) => {
+            checks.push(Check::new("Central reachable", Outcome::Fail, e));
+            checks.push(Check::new(
+                "clock skew",
+                Outcome::Skip,
+                "Central unreachable",
+            ));
+        }
+    }
+
+    checks
+}
+
+// without a Central client there's nothing to reach it with
+#[cfg(not(feature = "central"))]
+fn central_checks(_: Option<String>) -> Vec<Check> {
+    ["Central reachable", "clock skew"]
+        .into_iter()
+        .map(|name| Check::new(name, Outcome::Skip, "built without Central support"))
+        .collect()
+}
+
+pub fn run(api_key: Option<String>) -> Vec<Check> {
+    let mut checks = Vec::new();
+
+    let path = authtoken_path(None);
+    let authtoken = if client::authtoken_from_env() {
+        Ok(String::new())
+    } else {
+        std::fs::read_to_string(&path)
+    };
+    checks.push(match &authtoken {
+        Ok(_) if client::authtoken_from_env() => Check::new(
+            "authtoken readable",
+            Outcome::Pass,
+            "from ZEROTIER_ONE_TOKEN",
+        ),
+        Ok(_) => Check::new("authtoken readable", Outcome::Pass, path.display()),
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => Check::new(
+            "authtoken readable",
+            Outcome::Fail,
+            format!(
+                "permission denied reading {}; run as root or copy it somewhere readable",
+                path.display()
+            ),
+        ),
+        Err(e) => Check::new(
+            "authtoken readable",
+            Outcome::Fail,
+            format!("{}: {}", path.display(), e),
+        ),
+    });
+
+    match authtoken.ok().map(|_| client::sync_get_status()) {
+        Some(Ok(status)) => {
+            checks.push(Check::new(
+                "daemon reachable",
+                Outcome::Pass,
+                format!(
+                    "node {} version {}{}",
+                    status.address.clone().unwrap_or_default(),
+                    status.version.clone().unwrap_or_default(),
+                    if status.online.unwrap_or_default() {
+                        ""
+                    } else {
+                        " (offline)"
+                    }
+                ),
+            ));
+
+            checks.push(match client::sync_get_peers() {
+                Ok(peers) => udp_check(&peers),
+                Err(e) => Check::new("UDP 9993 egress", Outcome::Fail, e),
+            });
+
+            checks.push(if status.tcp_fallback_active.unwrap_or_default() {
+                Check::new(
+                    "TCP fallback",
+                    Outcome::Warn,
+                    "active: traffic is relayed over TCP, which is slow; UDP is being blocked",
+                )
+            } else {
+                Check::new("TCP fallback", Outcome::Pass, "not in use")
+            });
+        }
+        Some(Err(e)) => {
+            checks.push(Check::new(
+                "daemon reachable",
+                Outcome::Fail,
+                format!("{}; is zerotier-one running?", e),
+            ));
+            checks.push(Check::new(
+                "UDP 9993 egress",
+                Outcome::Skip,
+                "daemon unreachable",
+            ));
+            checks.push(Check::new(
+                "TCP fallback",
+                Outcome::Skip,
+                "daemon unreachable",
+            ));
+        }
+        None => {
+            for name in ["daemon reachable", "UDP 9993 egress", "TCP fallback"] {
+                checks.push(Check::new(name, Outcome::Skip, "authtoken unreadable"));
+            }
+        }
+    }
+
+    checks.extend(central_checks(api_key));
+
+    checks
+}
+
+pub fn failed(checks: &[Check]) -> bool {
+    checks.iter().any(|c| c.outcome == Outcome::Fail)
+}
+
+// what `ztui doctor` exits with: it's called out when the local service is the trouble, since
+// nothing else can be checked without it
+pub fn exit_code(checks: &[Check]) -> i32 {
+    let daemon = checks.iter().any(|c| {
+        c.outcome == Outcome::Fail && matches!(c.name, "authtoken readable" | "daemon reachable")
+    });
+
+    if daemon {
+        crate::exit::DAEMON_UNREACHABLE
+    } else if failed(checks) {
+        crate::exit::FAILED
+    } else {
+        0
+    }
+}
+
+pub const FIELDS: [&str; 3] = ["name", "outcome", "detail"];
+
+pub fn print(checks: &[Check]) {
+    for check in checks {
+        println!("[{}] {:<20} {}", check.outcome, check.name, check.detail);
+    }
+}
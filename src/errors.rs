@@ -0,0 +1,53 @@
+use std::collections::VecDeque;
+
+use crate::app::Page;
+
+// how many errors are kept for the log dialog
+const KEEP: usize = 50;
+
+// something that went wrong, what was being done when it did, and where
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub at: u64,
+    pub page: Page,
+    pub network: Option<String>,
+    pub operation: &'static str,
+    pub message: String,
+    // how many times in a row it's happened; `at` is the last
+    pub times: usize,
+}
+
+// the errors seen lately, so one coming in doesn't hide the one before it
+#[derive(Debug, Clone, Default)]
+pub struct Errors {
+    entries: VecDeque<Entry>,
+}
+
+impl Errors {
+    // the same error again, as a refresh that keeps failing has, counts against the one before it
+    // rather than pushing the others out
+    pub fn push(&mut self, entry: Entry) {
+        if let Some(last) = self.entries.back_mut().filter(|last| {
+            (&last.page, &last.network, last.operation, &last.message)
+                == (&entry.page, &entry.network, entry.operation, &entry.message)
+        }) {
+            last.at = entry.at;
+            last.times += 1;
+            return;
+        }
+
+        self.entries.push_back(entry);
+        if self.entries.len() > KEEP {
+            self.entries.pop_front();
+        }
+    }
+
+    // newest first
+    pub fn recent(&self) -> impl Iterator<Item = &Entry> {
+        self.entries.iter().rev()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
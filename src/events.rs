@@ -0,0 +1,187 @@
+use std::{
+    sync::{mpsc, Condvar, Mutex},
+    time::Duration,
+};
+
+use crossterm::event::{Event as Input, EventStream};
+use futures_util::StreamExt;
+use tokio::{
+    signal::unix::{Signal, SignalKind},
+    sync::{mpsc::UnboundedSender, Notify},
+    time::MissedTickBehavior,
+};
+
+use crate::{config::REFRESH_INTERVAL, display::SPINNER_TICK};
+
+// everything the UI waits on, from the one place that waits on all of it
+pub enum Event {
+    // a key, a resize, and whatever else the terminal sends
+    Input(Input),
+    // another thread changed something on screen
+    Data,
+    // the spinners move along, and the UI looks in on the refresh loop
+    Tick,
+    // SIGTERM: save and go
+    Terminate,
+    // SIGHUP: the terminal was closed, or config.json should be read again
+    Hangup,
+}
+
+enum Command {
+    // lets go of the terminal so something else can read from it, and says so once it has
+    Pause(mpsc::Sender<()>),
+    Resume,
+    Animate(bool),
+}
+
+lazy_static::lazy_static! {
+    static ref COMMANDS: Mutex<Option<UnboundedSender<Command>>> = Mutex::new(None);
+    static ref WAKE: Notify = Notify::new();
+    // how many times the refresh timer has gone off
+    static ref REFRESH: (Mutex<u64>, Condvar) = (Mutex::new(0), Condvar::new());
+}
+
+// how long pause waits for the terminal to be let go of
+const PAUSE_TIMEOUT: Duration = Duration::from_secs(1);
+
+// starts the thread that selects over the terminal, the timers and the other threads, and
+// returns what it finds in the order it found it
+pub fn start() -> Result<mpsc::Receiver<Event>, anyhow::Error> {
+    let (events, received) = mpsc::channel();
+    let (commands, ordered) = tokio::sync::mpsc::unbounded_channel();
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+
+    *COMMANDS.lock().unwrap() = Some(commands);
+    std::thread::Builder::new()
+        .name("events".to_string())
+        .spawn(move || runtime.block_on(select(events, ordered)))?;
+    Ok(received)
+}
+
+fn send(command: Command) -> bool {
+    COMMANDS
+        .lock()
+        .unwrap()
+        .as_ref()
+        .is_some_and(|c| c.send(command).is_ok())
+}
+
+// wakes the UI; several at once come through as one
+pub fn wake() {
+    WAKE.notify_one();
+}
+
+// whether the spinner timer should run
+pub fn animate(animating: bool) {
+    send(Command::Animate(animating));
+}
+
+// stops reading the terminal until resume, for the pager and user commands that read it
+// themselves
+pub fn pause() {
+    let (ack, acked) = mpsc::channel();
+    if send(Command::Pause(ack)) {
+        let _ = acked.recv_timeout(PAUSE_TIMEOUT);
+    }
+}
+
+pub fn resume() {
+    send(Command::Resume);
+}
+
+// waits for the refresh timer to next go off. without the events thread running it's the same as
+// sleeping for the refresh interval.
+pub fn wait_refresh() {
+    let (count, changed) = &*REFRESH;
+    let count = count.lock().unwrap();
+    let seen = *count;
+    let _ = changed.wait_timeout_while(count, REFRESH_INTERVAL, |count| *count == seen);
+}
+
+fn refresh() {
+    let (count, changed) = &*REFRESH;
+    *count.lock().unwrap() += 1;
+    changed.notify_all();
+}
+
+async fn next(input: &mut Option<EventStream>) -> Option<std::io::Result<Input>> {
+    match input {
+        Some(stream) => stream.next().await,
+        None => std::future::pending().await,
+    }
+}
+
+fn signal(kind: SignalKind) -> Option<Signal> {
+    tokio::signal::unix::signal(kind)
+        .map_err(|e| tracing::warn!(error = %e, "could not watch for a signal"))
+        .ok()
+}
+
+async fn caught(signal: &mut Option<Signal>) {
+    if let Some(signal) = signal {
+        if signal.recv().await.is_some() {
+            return;
+        }
+    }
+    std::future::pending().await
+}
+
+async fn select(
+    events: mpsc::Sender<Event>,
+    mut commands: tokio::sync::mpsc::UnboundedReceiver<Command>,
+) {
+    let mut input = Some(EventStream::new());
+    let mut terminate = signal(SignalKind::terminate());
+    let mut hangup = signal(SignalKind::hangup());
+    let mut animating = false;
+    let mut spinner = tokio::time::interval(SPINNER_TICK);
+    spinner.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    let mut refreshing = tokio::time::interval(REFRESH_INTERVAL);
+    refreshing.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        let event = tokio::select! {
+            Some(command) = commands.recv() => {
+                match command {
+                    Command::Pause(ack) => {
+                        input = None;
+                        let _ = ack.send(());
+                    }
+                    Command::Resume => input = Some(EventStream::new()),
+                    Command::Animate(on) => animating = on,
+                }
+                continue;
+            }
+            read = next(&mut input) => match read {
+                Some(Ok(read)) => Event::Input(read),
+                Some(Err(e)) => {
+                    tracing::warn!(error = %e, "reading the terminal failed");
+                    if crate::terminal::gone() {
+                        input = None;
+                    }
+                    continue;
+                }
+                // the terminal's gone; there's nothing more to read until it's given back
+                None => {
+                    input = None;
+                    continue;
+                }
+            },
+            _ = caught(&mut terminate) => Event::Terminate,
+            _ = caught(&mut hangup) => Event::Hangup,
+            _ = WAKE.notified() => Event::Data,
+            _ = spinner.tick(), if animating => Event::Tick,
+            _ = refreshing.tick() => {
+                refresh();
+                Event::Tick
+            }
+        };
+
+        // the UI has gone
+        if events.send(event).is_err() {
+            return;
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use crate::client;
+
+// what the headless subcommands exit with, so scripts can tell one failure from another. clap
+// exits with 2 when the arguments are wrong.
+pub const FAILED: i32 = 1;
+// the local service couldn't be asked: it isn't running, or its authtoken can't be read
+pub const DAEMON_UNREACHABLE: i32 = 3;
+// Central or the network's controller turned the API key away, or there's no key to give it
+pub const CENTRAL_AUTH: i32 = 4;
+// some of what was asked for was printed, and some couldn't be had
+pub const PARTIAL: i32 = 5;
+// Central knows the API key, but it may not do this: it's read-only, or can't see the network
+pub const CENTRAL_FORBIDDEN: i32 = 6;
+
+// an error with the code it should exit with. it reads the same as the error it carries.
+#[derive(Debug)]
+struct Coded {
+    code: i32,
+    error: anyhow::Error,
+}
+
+impl std::fmt::Display for Coded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+impl std::error::Error for Coded {}
+
+fn coded(code: i32, error: anyhow::Error) -> anyhow::Error {
+    anyhow::Error::new(Coded { code, error })
+}
+
+pub fn daemon(error: anyhow::Error) -> anyhow::Error {
+    coded(DAEMON_UNREACHABLE, error)
+}
+
+#[cfg(feature = "central")]
+pub fn central_auth(error: anyhow::Error) -> anyhow::Error {
+    coded(CENTRAL_AUTH, error)
+}
+
+pub fn code(e: &anyhow::Error) -> i32 {
+    match e.downcast_ref::<Coded>() {
+        Some(coded) => coded.code,
+        None if client::is_unauthorized(e) => CENTRAL_AUTH,
+        None if client::is_forbidden(e) => CENTRAL_FORBIDDEN,
+        None => FAILED,
+    }
+}
+
+// prints the error, if there is one, and exits with its code
+pub fn with(res: Result<(), anyhow::Error>) -> ! {
+    match res {
+        Ok(()) => std::process::exit(0),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(code(&e))
+        }
+    }
+}
@@ -0,0 +1,220 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Text(String),
+    YesNo(bool),
+}
+
+#[derive(Debug, Clone)]
+pub struct Field {
+    pub label: &'static str,
+    pub value: Value,
+    // characters in, for text fields
+    pub cursor: usize,
+    // why the value was refused the last time the form was submitted
+    pub error: Option<String>,
+    validate: fn(&Value) -> Result<(), String>,
+}
+
+fn anything(_: &Value) -> Result<(), String> {
+    Ok(())
+}
+
+impl Field {
+    pub fn text(label: &'static str, value: &str) -> Self {
+        Self {
+            label,
+            value: Value::Text(value.to_string()),
+            cursor: value.chars().count(),
+            error: None,
+            validate: anything,
+        }
+    }
+
+    pub fn yes_no(label: &'static str, value: bool) -> Self {
+        Self {
+            label,
+            value: Value::YesNo(value),
+            cursor: 0,
+            error: None,
+            validate: anything,
+        }
+    }
+
+    pub fn validate(mut self, validate: fn(&Value) -> Result<(), String>) -> Self {
+        self.validate = validate;
+        self
+    }
+
+    // byte offset of the cursor in a text value
+    fn offset(&self, s: &str) -> usize {
+        s.char_indices()
+            .nth(self.cursor)
+            .map_or(s.len(), |(i, _)| i)
+    }
+
+    fn key(&mut self, key: KeyEvent) {
+        let cursor = self.cursor;
+        let offset = match &self.value {
+            Value::Text(s) => self.offset(s),
+            Value::YesNo(_) => 0,
+        };
+
+        match &mut self.value {
+            Value::Text(s) => match key.code {
+                KeyCode::Char(x) => {
+                    s.insert(offset, x);
+                    self.cursor += 1;
+                }
+                KeyCode::Left => self.cursor = cursor.saturating_sub(1),
+                KeyCode::Right => self.cursor = (cursor + 1).min(s.chars().count()),
+                KeyCode::Home => self.cursor = 0,
+                KeyCode::End => self.cursor = s.chars().count(),
+                KeyCode::Delete if cursor < s.chars().count() => {
+                    s.remove(offset);
+                }
+                KeyCode::Backspace if cursor > 0 => {
+                    self.cursor -= 1;
+                    let offset = s
+                        .char_indices()
+                        .nth(self.cursor)
+                        .map_or(s.len(), |(i, _)| i);
+                    s.remove(offset);
+                }
+                _ => {}
+            },
+            Value::YesNo(b) => match key.code {
+                KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right => *b = !*b,
+                KeyCode::Char('y') => *b = true,
+                KeyCode::Char('n') => *b = false,
+                _ => {}
+            },
+        }
+    }
+}
+
+// several labeled fields in one dialog. Tab and Shift-Tab (or Up and Down) move between them,
+// and each is checked on its own when the form is submitted.
+#[derive(Debug, Clone)]
+pub struct Form {
+    pub fields: Vec<Field>,
+    pub focus: usize,
+}
+
+impl Form {
+    pub fn new(fields: Vec<Field>) -> Self {
+        Self { fields, focus: 0 }
+    }
+
+    // anything but Enter and Esc, which submit and close the dialog the form is in
+    pub fn key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Tab | KeyCode::Down => {
+                self.focus = (self.focus + 1) % self.fields.len();
+            }
+            KeyCode::BackTab | KeyCode::Up => {
+                self.focus = (self.focus + self.fields.len() - 1) % self.fields.len();
+            }
+            _ => {
+                if let Some(field) = self.fields.get_mut(self.focus) {
+                    field.key(key)
+                }
+            }
+        }
+    }
+
+    // checks every field, moving to the first that's refused. true if they're all fine.
+    pub fn validate(&mut self) -> bool {
+        for field in &mut self.fields {
+            field.error = (field.validate)(&field.value).err();
+        }
+
+        match self.fields.iter().position(|f| f.error.is_some()) {
+            Some(pos) => {
+                self.focus = pos;
+                false
+            }
+            None => true,
+        }
+    }
+
+    pub fn text(&self, label: &str) -> String {
+        match self
+            .fields
+            .iter()
+            .find(|f| f.label == label)
+            .map(|f| &f.value)
+        {
+            Some(Value::Text(s)) => s.trim().to_string(),
+            _ => String::new(),
+        }
+    }
+
+    pub fn yes(&self, label: &str) -> bool {
+        matches!(
+            self.fields
+                .iter()
+                .find(|f| f.label == label)
+                .map(|f| &f.value),
+            Some(Value::YesNo(true))
+        )
+    }
+}
+
+pub fn node_id(value: &Value) -> Result<(), String> {
+    match value {
+        Value::Text(s)
+            if s.trim().len() == 10 && s.trim().chars().all(|c| c.is_ascii_hexdigit()) =>
+        {
+            Ok(())
+        }
+        _ => Err("a node ID is 10 hex digits".to_string()),
+    }
+}
+
+// the first 16 hex digit network ID in `s`, which may be the bare ID, a Central network or
+// invite URL, or a `zerotier-cli join` line
+pub fn find_network_id(s: &str) -> Option<String> {
+    s.split(|c: char| !c.is_ascii_alphanumeric())
+        .find(|word| word.len() == 16 && word.chars().all(|c| c.is_ascii_hexdigit()))
+        .map(str::to_lowercase)
+}
+
+pub fn network_id(value: &Value) -> Result<(), String> {
+    match value {
+        Value::Text(s) if find_network_id(s).is_some() => Ok(()),
+        _ => Err("a network ID is 16 hex digits, or a URL with one in it".to_string()),
+    }
+}
+
+pub fn required(value: &Value) -> Result<(), String> {
+    match value {
+        Value::Text(s) if s.trim().is_empty() => Err("required".to_string()),
+        _ => Ok(()),
+    }
+}
+
+// an IPv4 subnet like 10.147.17.0/24, or nothing
+pub fn parse_subnet(s: &str) -> Result<Option<(std::net::Ipv4Addr, u8)>, String> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Ok(None);
+    }
+
+    let refused = || format!("`{}` is not a subnet like 10.147.17.0/24", s);
+    let (addr, prefix) = s.split_once('/').ok_or_else(refused)?;
+    let addr = addr.parse().map_err(|_| refused())?;
+    match prefix.parse() {
+        Ok(prefix) if (8..=30).contains(&prefix) => Ok(Some((addr, prefix))),
+        Ok(_) => Err("the prefix must be between /8 and /30".to_string()),
+        Err(_) => Err(refused()),
+    }
+}
+
+pub fn subnet(value: &Value) -> Result<(), String> {
+    match value {
+        Value::Text(s) => parse_subnet(s).map(|_| ()),
+        Value::YesNo(_) => Ok(()),
+    }
+}
@@ -0,0 +1,131 @@
+use std::collections::{BTreeMap, HashSet};
+
+use crate::api::central::types::{Member, MemberConfigTagsItemItem};
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Grouping {
+    #[default]
+    None,
+    Auth,
+    Tag(i64),
+}
+
+impl std::fmt::Display for Grouping {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Grouping::None => f.write_str("none"),
+            Grouping::Auth => f.write_str("auth status"),
+            Grouping::Tag(id) => write!(f, "tag {}", id),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MemberRow {
+    Header {
+        label: String,
+        count: usize,
+        collapsed: bool,
+    },
+    // index into the member list the rows were built from
+    Member(usize),
+}
+
+fn tag_value(member: &Member, tag: i64) -> Option<i64> {
+    member
+        .config
+        .as_ref()?
+        .tags
+        .as_ref()?
+        .iter()
+        .find_map(|pair| match pair.as_slice() {
+            [MemberConfigTagsItemItem::Variant0(id), MemberConfigTagsItemItem::Variant0(value)]
+                if *id == tag =>
+            {
+                Some(*value)
+            }
+            _ => None,
+        })
+}
+
+// tag ids in use by any member, in order
+pub fn tags(members: &[Member]) -> Vec<i64> {
+    let mut tags = members
+        .iter()
+        .filter_map(|m| m.config.as_ref()?.tags.as_ref())
+        .flatten()
+        .filter_map(|pair| match pair.first() {
+            Some(MemberConfigTagsItemItem::Variant0(id)) => Some(*id),
+            _ => None,
+        })
+        .collect::<Vec<i64>>();
+
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
+impl Grouping {
+    // none, then auth state, then each tag the members carry
+    pub fn next(&self, members: &[Member]) -> Self {
+        let tags = tags(members);
+
+        match self {
+            Grouping::None => Grouping::Auth,
+            Grouping::Auth => tags.first().map_or(Grouping::None, |t| Grouping::Tag(*t)),
+            Grouping::Tag(current) => tags
+                .iter()
+                .find(|t| *t > current)
+                .map_or(Grouping::None, |t| Grouping::Tag(*t)),
+        }
+    }
+
+    pub fn label(&self, member: &Member) -> String {
+        match self {
+            Grouping::None => String::new(),
+            Grouping::Auth => {
+                if member
+                    .config
+                    .as_ref()
+                    .and_then(|c| c.authorized)
+                    .unwrap_or_default()
+                {
+                    "Authorized".to_string()
+                } else {
+                    "Unauthorized".to_string()
+                }
+            }
+            Grouping::Tag(tag) => match tag_value(member, *tag) {
+                Some(value) => format!("tag {} = {}", tag, value),
+                None => format!("tag {} unset", tag),
+            },
+        }
+    }
+
+    pub fn rows(&self, members: &[Member], collapsed: &HashSet<String>) -> Vec<MemberRow> {
+        if *self == Grouping::None {
+            return (0..members.len()).map(MemberRow::Member).collect();
+        }
+
+        let mut groups: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+        for (i, member) in members.iter().enumerate() {
+            groups.entry(self.label(member)).or_default().push(i);
+        }
+
+        let mut rows = Vec::new();
+        for (label, indexes) in groups {
+            let is_collapsed = collapsed.contains(&label);
+            rows.push(MemberRow::Header {
+                count: indexes.len(),
+                collapsed: is_collapsed,
+                label,
+            });
+
+            if !is_collapsed {
+                rows.extend(indexes.into_iter().map(MemberRow::Member));
+            }
+        }
+
+        rows
+    }
+}
@@ -0,0 +1,369 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::api::one::types::Network;
+use crate::{
+    app::{Dialog, Page},
+    config::UserConfig,
+    i18n::{lookup, t},
+    registry,
+};
+
+// a section of the help: one for each page, and one for each dialog with keys of its own
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Topic {
+    Networks,
+    Members,
+    Commands,
+    Menu,
+    Editing,
+    Form,
+    Picker,
+    Flags,
+    FullTunnel,
+    FlagAll,
+    JoinSet,
+    RulesDiff,
+    Snippets,
+    Capture,
+    Identity,
+    Doctor,
+    Routes,
+    Pending,
+    RecentlyLeft,
+    Sso,
+    Batch,
+    Quit,
+    KeyRejected,
+}
+
+use Topic::*;
+
+impl Topic {
+    // the section about whatever is on screen
+    pub fn of(page: &Page, dialog: &Dialog) -> Self {
+        match dialog {
+            Dialog::Menu => Menu,
+            Dialog::Join(_) | Dialog::AddMember(..) | Dialog::NewNetwork(_) => Form,
+            #[cfg(feature = "central")]
+            Dialog::SsoEdit(..) => Form,
+            Dialog::Pick(..) => Picker,
+            dialog if dialog.takes_input() => Editing,
+            Dialog::NetworkFlags(_) => Flags,
+            Dialog::FullTunnel(_) => FullTunnel,
+            Dialog::FlagAll(_, _) => FlagAll,
+            Dialog::JoinSet(_) => JoinSet,
+            #[cfg(feature = "central")]
+            Dialog::RulesDiff(..) => RulesDiff,
+            Dialog::Snippets(_) => Snippets,
+            Dialog::Capture(..) => Capture,
+            Dialog::Identity(_) => Identity,
+            Dialog::Doctor(_) => Doctor,
+            Dialog::Routes(..) => Routes,
+            Dialog::Pending => Pending,
+            Dialog::RecentlyLeft => RecentlyLeft,
+            #[cfg(feature = "central")]
+            Dialog::Sso(..) => Sso,
+            Dialog::Batch(_) => Batch,
+            Dialog::ConfirmQuit | Dialog::SaveFailed(_) | Dialog::AlreadyRunning(_) => Quit,
+            Dialog::KeyRejected(_, _) => KeyRejected,
+            _ => match page {
+                Page::Networks => Networks,
+                Page::Network(_) => Members,
+            },
+        }
+    }
+
+    // as used in message keys: help.section.<key>, and help.<key>.<keys> for each entry
+    fn key(&self) -> &'static str {
+        match self {
+            Networks => "networks",
+            Members => "members",
+            Commands => "commands",
+            Menu => "menu",
+            Editing => "editing",
+            Form => "form",
+            Picker => "picker",
+            Flags => "flags",
+            FullTunnel => "full_tunnel",
+            FlagAll => "flag_all",
+            JoinSet => "join_set",
+            RulesDiff => "rules_diff",
+            Snippets => "snippets",
+            Capture => "capture",
+            Identity => "identity",
+            Doctor => "doctor",
+            Routes => "routes",
+            Pending => "pending",
+            RecentlyLeft => "recently_left",
+            Sso => "sso",
+            Batch => "batch",
+            Quit => "quit",
+            KeyRejected => "key_rejected",
+        }
+    }
+}
+
+// the keys of each dialog, in the order they're listed. the pages' keys are in crate::registry.
+const DIALOG_KEYS: &[(Topic, &[(&str, &str)])] = &[
+    (
+        Menu,
+        &[
+            ("Up / Down", "pick an action for the selected row"),
+            ("Enter", "run it"),
+            ("Esc / q", "close"),
+        ],
+    ),
+    (
+        Editing,
+        &[
+            ("Left / Right", "move the cursor"),
+            ("Home / End", "to the start or end"),
+            ("Backspace / Delete", "delete before or under the cursor"),
+            ("Enter", "accept"),
+            ("Esc", "cancel"),
+        ],
+    ),
+    (
+        Form,
+        &[
+            ("Tab / Down", "next field"),
+            ("BackTab / Up", "previous field"),
+            ("Space / Left / Right", "flip a yes or no field"),
+            ("y / n", "answer a yes or no field"),
+            ("Enter", "check the fields and save"),
+            ("Esc", "cancel"),
+        ],
+    ),
+    (
+        Picker,
+        &[
+            ("letters", "narrow the list; they must appear in order"),
+            ("Up / Down", "move through what's left"),
+            ("Backspace", "take back a letter"),
+            ("Enter", "pick"),
+            ("Esc", "cancel"),
+        ],
+    ),
+    (
+        Flags,
+        &[
+            ("n", "allow DNS"),
+            ("d", "allow a default route"),
+            ("m", "allow managed addresses and routes"),
+            ("g", "allow global addresses"),
+            ("f / Esc", "close"),
+        ],
+    ),
+    (
+        FullTunnel,
+        &[
+            ("y", "turn the full tunnel on"),
+            ("n / Esc", "leave it off"),
+        ],
+    ),
+    (
+        FlagAll,
+        &[
+            ("y / Enter", "change the networks marked as changing"),
+            ("Esc", "leave them as they are"),
+        ],
+    ),
+    (
+        JoinSet,
+        &[
+            ("y / Enter", "join the networks and set their flags"),
+            ("Esc", "leave them as they are"),
+        ],
+    ),
+    (
+        RulesDiff,
+        &[
+            ("Up / Down", "scroll"),
+            ("y / Enter", "push the rules to Central"),
+            ("e", "edit again"),
+            ("n", "discard"),
+        ],
+    ),
+    (
+        Snippets,
+        &[
+            ("Up / Down", "pick a snippet"),
+            ("Enter", "edit the rules starting from it"),
+            ("E / Esc", "close"),
+        ],
+    ),
+    (
+        Capture,
+        &[
+            ("w", "start or stop writing a pcap"),
+            ("p / Esc", "stop and close"),
+        ],
+    ),
+    (
+        Identity,
+        &[
+            ("b", "back up the identity to a directory"),
+            ("r", "check again"),
+            ("i / Esc", "close"),
+        ],
+    ),
+    (
+        Doctor,
+        &[("r", "run the checks again"), ("D / Esc", "close")],
+    ),
+    (Routes, &[("R", "check again"), ("Esc", "close")]),
+    (
+        Pending,
+        &[
+            ("Up / Down", "pick a change"),
+            ("r", "retry it"),
+            ("d", "discard it"),
+            ("D", "discard all of them"),
+            ("P / Esc", "close"),
+        ],
+    ),
+    (
+        RecentlyLeft,
+        &[
+            ("Up / Down", "pick a network"),
+            ("Enter", "rejoin it, with its flags, name and API key"),
+            ("1 - 9", "rejoin the network listed there"),
+            ("U / Esc", "close"),
+        ],
+    ),
+    (Sso, &[("e", "edit the SSO settings"), ("S / Esc", "close")]),
+    (
+        Batch,
+        &[("Esc", "stop what's left, then close once it has stopped")],
+    ),
+    (
+        Quit,
+        &[
+            ("q / y / C-c", "quit"),
+            ("r", "try saving settings again, if they couldn't be saved"),
+            (
+                "Enter",
+                "carry on without saving, if another ztui is running",
+            ),
+            ("Esc", "stay"),
+        ],
+    ),
+    (
+        KeyRejected,
+        &[
+            ("Enter", "give the network a new API key"),
+            ("Esc / q", "keep looking at the members already loaded"),
+        ],
+    ),
+];
+
+pub struct Section {
+    pub topic: Topic,
+    pub title: String,
+    // (keys, what they do)
+    pub entries: Vec<(String, String)>,
+}
+
+fn section(topic: Topic, entries: Vec<(String, String)>) -> Section {
+    Section {
+        topic,
+        title: t(&format!("help.section.{}", topic.key())),
+        entries,
+    }
+}
+
+// the page's own keys first, then commands from the config, then every dialog
+pub fn sections(page: &Page, config: &UserConfig, network: Option<&Network>) -> Vec<Section> {
+    let topic = match page {
+        Page::Networks => Networks,
+        Page::Network(_) => Members,
+    };
+
+    let mut sections = vec![section(
+        topic,
+        registry::for_page(page)
+            .map(|b| (b.keys.join(" / "), b.describe()))
+            .collect(),
+    )];
+
+    let commands = config.commands(page, network);
+    if !commands.is_empty() {
+        sections.push(section(Commands, commands));
+    }
+
+    for (topic, keys) in DIALOG_KEYS {
+        sections.push(section(
+            *topic,
+            keys.iter()
+                .map(|(k, help)| {
+                    (
+                        k.to_string(),
+                        lookup(&format!("help.{}.{}", topic.key(), k))
+                            .unwrap_or_else(|| help.to_string()),
+                    )
+                })
+                .collect(),
+        ));
+    }
+
+    sections
+}
+
+// what can be pressed after `chord`: the rest of each command bound to a chord starting with it.
+// after the prefix key alone that's everything on the page, by first key and short name, then
+// every command.
+pub fn following(
+    page: &Page,
+    config: &UserConfig,
+    network: Option<&Network>,
+    chord: &str,
+) -> Vec<(String, String)> {
+    let start = format!("{} ", chord);
+    let commands = config.commands(page, network).into_iter();
+
+    if chord != config.prefix_key() {
+        return commands
+            .filter_map(|(keys, command)| Some((keys.strip_prefix(&start)?.to_string(), command)))
+            .collect();
+    }
+
+    registry::for_page(page)
+        .map(|b| (b.keys[0].to_string(), b.name.to_string()))
+        .chain(commands.map(|(keys, command)| {
+            (
+                keys.strip_prefix(&start)
+                    .map(str::to_string)
+                    .unwrap_or(keys),
+                command,
+            )
+        }))
+        .collect()
+}
+
+// the help, drawn over whatever it was opened from so that closing it goes straight back
+#[derive(Debug, Clone)]
+pub struct Help {
+    pub topic: Topic,
+    // lines scrolled past the start of the topic's section
+    pub scroll: i32,
+}
+
+impl Help {
+    pub fn new(topic: Topic) -> Self {
+        Self { topic, scroll: 0 }
+    }
+
+    // false when the key closes the help
+    pub fn key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Up => self.scroll -= 1,
+            KeyCode::Down => self.scroll += 1,
+            KeyCode::PageUp => self.scroll -= 10,
+            KeyCode::PageDown => self.scroll += 10,
+            KeyCode::Esc | KeyCode::F(1) | KeyCode::Char('q' | 'h' | '?') => return false,
+            _ => {}
+        }
+
+        true
+    }
+}
@@ -0,0 +1,141 @@
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::central::types::Member;
+
+// central considers a member online if it has been heard from in roughly this long
+pub const ONLINE_THRESHOLD: u64 = 5 * 60;
+// how many transitions we keep per member
+#[cfg(feature = "central")]
+const MAX_TRANSITIONS: usize = 1000;
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+// whether central has heard from the member recently, as of `now`
+pub fn is_online(member: &Member, now: u64) -> Option<bool> {
+    let last_online = member.last_online? as u64 / 1000;
+    Some(now.saturating_sub(last_online) < ONLINE_THRESHOLD)
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Transition {
+    pub at: u64,
+    pub online: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct History {
+    members: HashMap<String, HashMap<String, Vec<Transition>>>,
+}
+
+impl History {
+    pub fn from_dir(filename: PathBuf) -> Result<Self, anyhow::Error> {
+        let history_file = std::fs::read_to_string(filename.join("history.json"))?;
+        Ok(serde_json::from_str(&history_file)?)
+    }
+
+    #[cfg(feature = "central")]
+    pub fn save(&mut self, filename: PathBuf) -> Result<(), anyhow::Error> {
+        let mut json = serde_json::to_string(self)?;
+        while json.len() > crate::config::MAX_DATA_FILE && self.prune() {
+            json = serde_json::to_string(self)?;
+        }
+
+        Ok(std::fs::write(filename.join("history.json"), json)?)
+    }
+
+    // drops the older half of every member's transitions. false once there's nothing to drop.
+    #[cfg(feature = "central")]
+    fn prune(&mut self) -> bool {
+        let mut pruned = false;
+        for transitions in self.members.values_mut().flat_map(|m| m.values_mut()) {
+            if !transitions.is_empty() {
+                transitions.drain(..transitions.len().div_ceil(2));
+                pruned = true;
+            }
+        }
+        pruned
+    }
+
+    // returns true if any member changed state since the last sample
+    #[cfg(feature = "central")]
+    pub fn record(&mut self, network_id: &str, members: &[Member]) -> bool {
+        let now = now();
+        let network = self.members.entry(network_id.to_string()).or_default();
+        let mut changed = false;
+
+        for member in members {
+            let (node_id, last_online) = match (&member.node_id, member.last_online) {
+                (Some(node_id), Some(last_online)) => (node_id, last_online as u64 / 1000),
+                _ => continue,
+            };
+
+            let online = is_online(member, now).unwrap_or_default();
+            let transitions = network.entry(node_id.clone()).or_default();
+
+            if transitions.last().map(|t| t.online) == Some(online) {
+                continue;
+            }
+
+            // when a member drops off, the last time central heard from it is a better
+            // estimate of when that happened than the time we noticed.
+            let at = if online || transitions.is_empty() {
+                now
+            } else {
+                last_online.max(transitions.last().map_or(0, |t| t.at))
+            };
+
+            transitions.push(Transition { at, online });
+            if transitions.len() > MAX_TRANSITIONS {
+                transitions.remove(0);
+            }
+
+            changed = true;
+        }
+
+        changed
+    }
+
+    pub fn transitions(&self, network_id: &str, node_id: &str) -> Vec<Transition> {
+        self.members
+            .get(network_id)
+            .and_then(|n| n.get(node_id))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    // state of the member at each of `slots` points spread evenly across the last `span` seconds.
+    // None means we have no samples that far back.
+    pub fn timeline(
+        &self,
+        network_id: &str,
+        node_id: &str,
+        span: u64,
+        slots: u64,
+    ) -> Vec<Option<bool>> {
+        let transitions = self.transitions(network_id, node_id);
+        let now = now();
+        let start = now.saturating_sub(span);
+
+        (0..slots)
+            .map(|i| {
+                let t = start + span * (i + 1) / slots;
+                transitions
+                    .iter()
+                    .rev()
+                    .find(|tr| tr.at <= t)
+                    .map(|tr| tr.online)
+            })
+            .collect()
+    }
+}
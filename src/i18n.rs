@@ -0,0 +1,366 @@
+use std::{collections::HashMap, fmt::Display, path::Path, sync::RwLock};
+
+// every string ztui shows, in English, by key. `{}` is filled in with the arguments in order.
+// a translation is a JSON object of the same keys in <config dir>/locales/<locale>.json; any key
+// it leaves out is shown in English. help and menu entries are `help.<page>.<name>`, where page
+// is networks, members or all, and name is the binding's name in crate::registry. the keys of
+// each dialog are `help.<section>.<keys>`, as listed in crate::help.
+const ENGLISH: &[(&str, &str)] = &[
+    ("title.main", "ZeroTier Terminal UI"),
+    ("title.dry_run", "DRY RUN: changes are only logged"),
+    ("title.privacy", "PRIVACY: IDs and addresses are masked (z)"),
+    ("title.replay", "REPLAY of a recording; nothing is changed"),
+    (
+        "title.not_saving",
+        "NOT SAVING: another ztui has the settings",
+    ),
+    ("title.terminal_networks", "ztui: networks"),
+    ("dialog.traffic_chart", "Per day, oldest first:"),
+    ("dialog.traffic_chart_total", "Total per day, oldest first"),
+    ("title.terminal_members", "ztui: {} members"),
+    ("title.terminal_members_unnamed", "ztui: members"),
+    ("title.help_hint", "Press h for Help"),
+    ("title.recording", "REC {} keys (m to stop)"),
+    ("title.refreshing", "Refreshing"),
+    ("title.offline", "OFFLINE"),
+    ("title.updated_now", "Updated just now"),
+    ("title.updated", "Updated {} ago"),
+    ("title.grouped", "Grouped by {}"),
+    ("title.members", "Members of {}"),
+    ("title.addresses_used", "{}/{} addresses used"),
+    ("title.addresses_nearly_full", "{}/{} addresses used: NEARLY FULL"),
+    ("title.read_only", "READ ONLY: the API key can't change members"),
+    ("title.stale", "Data is {}s old; refreshes are failing"),
+    ("title.saved", "Showing saved data; {}"),
+    (
+        "prompt.api_key",
+        "Enter your Network API Key, after the controller URL if not Central",
+    ),
+    (
+        "prompt.identity_backup",
+        "Back up the identity to directory",
+    ),
+    ("prompt.go_to_member", "Go to member (node ID or its start)"),
+    ("prompt.macro_name", "Name this macro of {} keys"),
+    ("prompt.run_macro", "Run macro [times]: {}"),
+    ("prompt.no_macros", "No macros recorded yet"),
+    ("dialog.key_refused", "Key refused"),
+    ("dialog.picker", "{} | type to narrow, Enter to pick"),
+    ("dialog.form", "{} | Tab to move, Enter to save"),
+    ("dialog.menu", "{} | Enter to run, Esc to close"),
+    ("dialog.which_key", "{} ... | Esc to cancel"),
+    ("dialog.help", "Help | Up/Down to scroll, Esc to close"),
+    ("help.section.networks", "Networks"),
+    ("help.section.members", "Members"),
+    ("help.section.commands", "Commands from config.json"),
+    ("help.section.menu", "Action menu"),
+    ("help.section.editing", "Typing into a prompt"),
+    ("help.section.form", "Forms"),
+    ("help.section.picker", "Picking from a list"),
+    ("help.section.flags", "Network flags"),
+    ("help.section.full_tunnel", "Confirming a full tunnel"),
+    ("help.section.flag_all", "A flag on every connected network"),
+    ("help.section.join_set", "Applying a join set"),
+    ("help.section.rules_diff", "Reviewing rules changes"),
+    ("help.section.snippets", "Rules snippets"),
+    ("help.section.capture", "Packet capture"),
+    ("help.section.identity", "Identity"),
+    ("help.section.doctor", "Diagnostics"),
+    ("help.section.routes", "Managed routes"),
+    ("help.section.pending", "Queued changes"),
+    ("help.section.recently_left", "Recently left networks"),
+    ("help.section.sso", "Network SSO"),
+    ("help.section.batch", "Changes to marked members"),
+    ("help.section.quit", "Quitting"),
+    ("help.section.key_rejected", "A rejected API key"),
+    ("dialog.flags", "Set Flags"),
+    (
+        "dialog.full_tunnel",
+        "Full tunnel | y to turn it on, n to leave it off",
+    ),
+    (
+        "dialog.full_tunnel.routes",
+        "All traffic will go through {} by its default route:",
+    ),
+    (
+        "dialog.full_tunnel.warning",
+        "This is an ssh session. If the network can't reach back to where you're connecting from, this session will hang and the change can't be undone from here.",
+    ),
+    (
+        "dialog.flag_all",
+        "{} {} on every connected network | y to apply, Esc to cancel",
+    ),
+    ("dialog.flag_all.unchanged", "already {}"),
+    (
+        "dialog.join_set",
+        "Join set {} | y to apply, Esc to cancel",
+    ),
+    ("dialog.join_set.flags", "Flags: {}"),
+    ("dialog.join_set.no_flags", "Flags: left to ZeroTier"),
+    ("dialog.join_set.join", "join"),
+    ("dialog.join_set.joined", "joined; flags set"),
+    ("dialog.quit", "Quit? | q or y to quit, Esc to stay"),
+    (
+        "dialog.quit.unsaved",
+        "Anything being typed or edited right now is lost.",
+    ),
+    (
+        "dialog.save_failed",
+        "Settings could not be saved | r to retry, q to quit anyway, Esc to stay",
+    ),
+    (
+        "dialog.already_running",
+        "Already running | Enter to carry on without saving, q to quit",
+    ),
+    ("dialog.already_running.pid", "ztui is already running, as PID {}."),
+    (
+        "dialog.already_running.unknown",
+        "Another ztui is already running.",
+    ),
+    (
+        "dialog.already_running.read_only",
+        "Whichever quit last would undo what the other saved, so nothing changed here is saved: not API keys, names, or anything else. Everything else works as usual.",
+    ),
+    (
+        "dialog.key_rejected",
+        "API key rejected | Enter for a new one, Esc to keep browsing",
+    ),
+    (
+        "dialog.key_rejected.network",
+        "Central turned away the API key for {}: {}",
+    ),
+    (
+        "dialog.key_rejected.cached",
+        "It may have been revoked or have expired. The members shown are from the last time it worked, and stay up until it does again or a new key is given. This isn't asked again for the same key.",
+    ),
+    ("dialog.ping", "Ping {} | p to close"),
+    ("dialog.capture", "Capture {} | {} | p to close"),
+    ("dialog.capture.write_failed", "[ztui] could not write a pcap: {}"),
+    (
+        "dialog.identity",
+        "Identity | {} problem(s) | b to back up, r to re-check",
+    ),
+    (
+        "dialog.doctor",
+        "Diagnostics | {} | r to re-run, D to close",
+    ),
+    ("dialog.doctor.failed", "problems found"),
+    ("dialog.doctor.passed", "all good"),
+    ("dialog.changelog", "Changelog {} | C to close"),
+    ("dialog.legend", "Capabilities and Tags | L to close"),
+    (
+        "dialog.batch_rename",
+        "Rename template | %i node, %N name, %a address, %c counter | Enter to apply",
+    ),
+    ("dialog.batch_rename.preview", "Preview | Esc to cancel"),
+    (
+        "dialog.batch_rename.failed",
+        "{} renames failed | Enter to retry, Esc to cancel",
+    ),
+    (
+        "dialog.rules_diff",
+        "Rules changes for {} | y to push, e to edit again, n to discard",
+    ),
+    ("dialog.rules_rejected", "Central rejected the rules"),
+    ("dialog.log", "Action log | {} | L to close"),
+    (
+        "dialog.log.dry_run",
+        "Action log (dry run) | {} | L to close",
+    ),
+    ("dialog.errors", "Errors, newest first"),
+    ("dialog.errors.networks", "networks"),
+    ("dialog.errors.members", "members"),
+    ("dialog.batch.close", "{} | Esc to close"),
+    ("dialog.batch.stop", "{} | Esc to stop"),
+    (
+        "dialog.pending",
+        "Queued changes | r to retry, d to discard, D to discard all, P to close",
+    ),
+    (
+        "dialog.recently_left",
+        "Recently left | Enter or 1-9 to rejoin, U to close",
+    ),
+    ("dialog.sso", "SSO for {} | e to edit, S to close"),
+    ("dialog.snippets", "Snippets | Enter to insert"),
+    ("dialog.snippets.dir", "{} | E to close"),
+    (
+        "dialog.routes",
+        "Routes on {} | {} problem(s) | R to re-check",
+    ),
+    ("dialog.history", "History {} | H to close"),
+    ("dialog.traffic", "Traffic on {} via {} | u to close"),
+    ("form.join", "Join a network"),
+    ("form.add_member", "Add a member"),
+    ("form.new_network", "Create a network"),
+    ("form.sso", "SSO for {}"),
+    ("picker.join_network", "Join a network from Central"),
+    ("picker.interface", "Count traffic on {} from"),
+    (
+        "picker.interface_auto",
+        "The interface ZeroTier reports, or the config's",
+    ),
+    ("picker.interface_current", "{} (current)"),
+    ("picker.flag_all", "Set on every connected network"),
+    ("picker.join_set", "Apply a join set"),
+    ("picker.join_set_item", "{} ({} networks)"),
+    ("network.rate_unknown", "n/a (I to pick an interface)"),
+    (
+        "toast.interface_unknown",
+        "Can't measure traffic on {}: no interface named {} was found. Press I to pick one",
+    ),
+    (
+        "toast.unexpected_authorization",
+        "{} ({}) was authorized on {}, and not from here",
+    ),
+    (
+        "toast.duplicate_address",
+        "{} is assigned to more than one member ({}) of {}",
+    ),
+    (
+        "toast.pool_nearly_full",
+        "The address pools of {} are nearly full: {} of {} used",
+    ),
+    ("toast.member_traffic_failed", "Per-member traffic: {}"),
+    ("toast.capture_failed", "Could not capture on {}: {}"),
+    ("toast.interface_set", "Counting traffic on {} for {}"),
+    (
+        "toast.interface_auto",
+        "Counting traffic on the interfaces from the config, or the one ZeroTier reports",
+    ),
+    ("toast.loading_members", "Loading members of {}"),
+    ("toast.members_failed", "Could not load members of {}: {}"),
+    (
+        "toast.queued",
+        "Central is unreachable; queued {} of {} (P to review)",
+    ),
+    ("toast.full_tunnel_on", "Full tunnel on for {}"),
+    ("toast.full_tunnel_off", "Full tunnel off for {}"),
+    ("toast.flag_all_none", "No networks are connected"),
+    ("toast.no_join_sets", "No join sets in config.json"),
+    (
+        "toast.flag_all_nothing",
+        "{} is already {} on every connected network",
+    ),
+    (
+        "toast.no_default_route",
+        "{} pushes no default route; add 0.0.0.0/0 via a gateway on Central first",
+    ),
+    ("toast.no_network_id", "No network ID on the clipboard"),
+    ("toast.rejoined", "Rejoined {}"),
+    (
+        "toast.not_joined",
+        "{} isn't joined on this node, so it has no local settings",
+    ),
+    (
+        "toast.auto_rejoined",
+        "{} was dropped by ZeroTier and has been joined again",
+    ),
+    ("toast.auto_rejoin_failed", "Could not rejoin {}: {}"),
+    ("toast.auto_rejoin_on", "{} will be joined again whenever it's dropped"),
+    ("toast.auto_rejoin_off", "{} will no longer be joined again automatically"),
+    ("toast.sso_exempt", "{} is exempt from SSO"),
+    ("toast.sso_not_exempt", "{} is no longer exempt from SSO"),
+    (
+        "toast.read_only",
+        "The API key for {} can only read it; set another with s to make changes",
+    ),
+        ("toast.not_a_node_id", "`{}` is not a node ID"),
+    (
+        "toast.no_such_member",
+        "No member starting with {} in any network with an API key",
+    ),
+    ("toast.member_ambiguous", "{} could be any of {}"),
+    ("toast.member_also_in", "{} is also a member of {}"),
+    ("toast.no_chord", "Nothing is bound to `{}`"),
+    (
+        "toast.settings_recovered",
+        "settings.json was damaged; settings were recovered from {}",
+    ),
+    ("toast.nothing_recorded", "Nothing was recorded"),
+    (
+        "toast.supervisor_died",
+        "Refreshing stopped unexpectedly and was restarted; a bug report was written",
+    ),
+    (
+        "toast.supervisor_stalled",
+        "Refreshing was stuck for {}s and was restarted",
+    ),
+    ("toast.supervisor_failed", "Refreshing could not be restarted: {}"),
+    ("toast.config_reloaded", "config.json was read again"),
+    (
+        "toast.config_reload_failed",
+        "config.json could not be read, the last one is still in use: {}",
+    ),
+    (
+        "toast.finishing",
+        "{} Waiting for {} change(s) to finish before quitting",
+    ),
+    (
+        "toast.update_available",
+        "ztui {} is out (this is {}): {}",
+    ),
+    (
+        "toast.bug_report",
+        "A bug report was written to {}; look it over before attaching it to an issue",
+    ),
+    ("toast.no_macro", "No macro named `{}`"),
+    (
+        "toast.macro_stopped",
+        "Macro `{}` stopped on run {} of {} at `{}`: {}",
+    ),
+    ("toast.macro_ran", "Ran macro `{}` {} times"),
+    (
+        "toast.already_named",
+        "member is already named; use r to rename",
+    ),
+    (
+        "toast.api_key_needed",
+        "an API key is needed; add one with s on any network",
+    ),
+];
+
+lazy_static::lazy_static! {
+    static ref CATALOG: RwLock<HashMap<String, String>> = RwLock::new(HashMap::new());
+}
+
+// switches to the translation for `locale`. "en", or a locale with no file, leaves English.
+pub fn load(dir: &Path, locale: &str) -> Result<(), anyhow::Error> {
+    let path = dir.join("locales").join(format!("{}.json", locale));
+    if locale == "en" || !path.exists() {
+        return Ok(());
+    }
+
+    let catalog: HashMap<String, String> =
+        serde_json::from_str(&std::fs::read_to_string(&path)?)
+            .map_err(|e| anyhow::anyhow!("{}: {}", path.display(), e))?;
+    *CATALOG.write().unwrap() = catalog;
+    Ok(())
+}
+
+// the translation for `key`, if the locale has one
+pub fn lookup(key: &str) -> Option<String> {
+    CATALOG.read().unwrap().get(key).cloned()
+}
+
+pub fn t(key: &str) -> String {
+    lookup(key)
+        .or_else(|| {
+            ENGLISH
+                .iter()
+                .find(|(k, _)| *k == key)
+                .map(|(_, s)| s.to_string())
+        })
+        .unwrap_or_else(|| key.to_string())
+}
+
+// t(key) with each `{}` replaced by the next argument
+pub fn tf(key: &str, args: &[&dyn Display]) -> String {
+    let mut out = String::new();
+    for (i, part) in t(key).split("{}").enumerate() {
+        if let Some(arg) = i.checked_sub(1).and_then(|i| args.get(i)) {
+            out += &arg.to_string();
+        }
+        out += part;
+    }
+    out
+}
@@ -0,0 +1,199 @@
+use std::{
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use anyhow::anyhow;
+
+use crate::client::authtoken_path;
+
+pub const PUBLIC_FILE: &str = "identity.public";
+pub const SECRET_FILE: &str = "identity.secret";
+
+// the identity lives next to the authtoken in the zerotier home directory
+pub fn home_dir() -> PathBuf {
+    authtoken_path(None)
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default()
+}
+
+// we only ever read from the zerotier home directory; make the usual failure obvious.
+fn read(path: &Path) -> Result<String, anyhow::Error> {
+    match std::fs::read_to_string(path) {
+        Ok(s) => Ok(s.trim().to_string()),
+        Err(e) if e.kind() == ErrorKind::PermissionDenied => Err(anyhow!(
+            "permission denied reading {}; run ztui as a user that can read the ZeroTier home directory",
+            path.display()
+        )),
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            Err(anyhow!("{} does not exist", path.display()))
+        }
+        Err(e) => Err(anyhow!("could not read {}: {}", path.display(), e)),
+    }
+}
+
+fn is_hex(s: &str, len: usize) -> bool {
+    s.len() == len && s.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+// identities are "address:0:public" with a 10 digit address and a 64 byte (c25519 + ed25519)
+// public key. the secret file appends the 64 byte private key.
+fn parse(s: &str, secret: bool) -> Result<(String, String), anyhow::Error> {
+    let parts = s.split(':').collect::<Vec<&str>>();
+    let expected = if secret { 4 } else { 3 };
+
+    if parts.len() != expected {
+        return Err(anyhow!(
+            "expected {} fields, found {}",
+            expected,
+            parts.len()
+        ));
+    }
+
+    if !is_hex(parts[0], 10) {
+        return Err(anyhow!("malformed address {}", parts[0]));
+    }
+
+    if parts[1] != "0" {
+        return Err(anyhow!("unknown identity type {}", parts[1]));
+    }
+
+    if !is_hex(parts[2], 128) {
+        return Err(anyhow!("malformed public key"));
+    }
+
+    if secret && !is_hex(parts[3], 128) {
+        return Err(anyhow!("malformed private key"));
+    }
+
+    Ok((parts[0].to_string(), parts[2].to_string()))
+}
+
+#[derive(Debug, Clone)]
+pub struct IdentityCheck {
+    pub name: String,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct IdentityReport {
+    pub home: PathBuf,
+    pub public: Option<String>,
+    pub checks: Vec<IdentityCheck>,
+    pub backup: Option<Result<PathBuf, String>>,
+}
+
+impl IdentityReport {
+    pub fn problems(&self) -> usize {
+        self.checks.iter().filter(|c| c.error.is_some()).count()
+    }
+}
+
+fn check<T>(checks: &mut Vec<IdentityCheck>, name: &str, res: &Result<T, anyhow::Error>) {
+    checks.push(IdentityCheck {
+        name: name.to_string(),
+        error: res.as_ref().err().map(|e| e.to_string()),
+    });
+}
+
+pub fn verify() -> IdentityReport {
+    let home = home_dir();
+    let mut checks = Vec::new();
+
+    let public = read(&home.join(PUBLIC_FILE));
+    let parsed_public = match &public {
+        Ok(p) => parse(p, false),
+        Err(e) => Err(anyhow!(e.to_string())),
+    };
+    check(
+        &mut checks,
+        "identity.public is well-formed",
+        &parsed_public,
+    );
+
+    let parsed_secret = read(&home.join(SECRET_FILE)).and_then(|s| parse(&s, true));
+    check(
+        &mut checks,
+        "identity.secret is well-formed",
+        &parsed_secret,
+    );
+
+    if let (Ok(public), Ok(secret)) = (&parsed_public, &parsed_secret) {
+        check(
+            &mut checks,
+            "identity.secret matches identity.public",
+            &if public == secret {
+                Ok(())
+            } else {
+                Err(anyhow!("the files belong to different identities"))
+            },
+        );
+    }
+
+    if let Ok(public) = &public {
+        check(
+            &mut checks,
+            "the running node uses this identity",
+            &crate::client::sync_get_status().and_then(|status| match status.public_identity {
+                Some(running) if running == *public => Ok(()),
+                Some(running) => Err(anyhow!(
+                    "the node reports {}",
+                    running.split(':').next().unwrap_or_default()
+                )),
+                None => Err(anyhow!("the node did not report its identity")),
+            }),
+        );
+    }
+
+    IdentityReport {
+        home,
+        public: public.ok(),
+        checks,
+        backup: None,
+    }
+}
+
+// copies the identity pair into `dir`, which is created if needed. the secret is only ever
+// readable by the user doing the backup, even when it's written over an older backup that wasn't.
+pub fn backup(dir: &Path) -> Result<PathBuf, anyhow::Error> {
+    let home = home_dir();
+    let public = read(&home.join(PUBLIC_FILE))?;
+    let secret = read(&home.join(SECRET_FILE))?;
+
+    parse(&public, false)?;
+    parse(&secret, true)?;
+
+    std::fs::create_dir_all(dir)?;
+
+    let secret_path = dir.join(SECRET_FILE);
+    #[cfg(unix)]
+    {
+        use std::{
+            fs::Permissions,
+            io::Write,
+            os::unix::fs::{OpenOptionsExt, PermissionsExt},
+        };
+
+        let mut file = std::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(0o600)
+            .open(&secret_path)?;
+        // the mode only applies to a file that's created; one already there keeps its own
+        file.set_permissions(Permissions::from_mode(0o600))?;
+        file.write_all(format!("{}\n", secret).as_bytes())?;
+    }
+    #[cfg(not(unix))]
+    std::fs::write(&secret_path, format!("{}\n", secret))?;
+
+    std::fs::write(dir.join(PUBLIC_FILE), format!("{}\n", public))?;
+
+    // read it back so a bad disk or a full filesystem doesn't go unnoticed
+    if read(&secret_path)? != secret {
+        return Err(anyhow!("backup of {} did not verify", SECRET_FILE));
+    }
+
+    Ok(dir.to_path_buf())
+}
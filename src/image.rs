@@ -0,0 +1,110 @@
+use std::io::Write;
+
+use crate::nets::Traffic;
+
+// pixels per cell an image is drawn at. terminals scale images to the cells they're placed in, so
+// this only needs to be fine enough to look smooth.
+pub const CELL_WIDTH: u32 = 8;
+pub const CELL_HEIGHT: u32 = 16;
+
+// the same colors as the traffic table, LightCyan and LightMagenta
+const RX: [u8; 4] = [0x55, 0xff, 0xff, 0xff];
+const TX: [u8; 4] = [0xff, 0x55, 0xff, 0xff];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    // rows of RGBA, top first
+    pub rgba: Vec<u8>,
+}
+
+impl Image {
+    // transparent, so the terminal's background shows through
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            rgba: vec![0; (width * height * 4) as usize],
+        }
+    }
+
+    fn fill(&mut self, x: u32, y: u32, width: u32, height: u32, color: [u8; 4]) {
+        for row in y..(y + height).min(self.height) {
+            for col in x..(x + width).min(self.width) {
+                let i = ((row * self.width + col) * 4) as usize;
+                self.rgba[i..i + 4].copy_from_slice(&color);
+            }
+        }
+    }
+
+    // as a PNG, for the protocols that want a file rather than pixels
+    pub fn png(&self) -> Result<Vec<u8>, anyhow::Error> {
+        let mut raw = Vec::with_capacity(self.rgba.len() + self.height as usize);
+        for row in self.rgba.chunks((self.width * 4) as usize) {
+            // no filter on each scanline
+            raw.push(0);
+            raw.extend_from_slice(row);
+        }
+
+        let mut zlib = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::fast());
+        zlib.write_all(&raw)?;
+
+        let mut header = Vec::new();
+        header.extend_from_slice(&self.width.to_be_bytes());
+        header.extend_from_slice(&self.height.to_be_bytes());
+        // 8 bits a channel, RGBA, then the standard compression, filtering and no interlacing
+        header.extend_from_slice(&[8, 6, 0, 0, 0]);
+
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        chunk(&mut png, b"IHDR", &header);
+        chunk(&mut png, b"IDAT", &zlib.finish()?);
+        chunk(&mut png, b"IEND", &[]);
+        Ok(png)
+    }
+}
+
+fn chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    let mut crc = crc32fast::Hasher::new();
+    crc.update(kind);
+    crc.update(data);
+
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    png.extend_from_slice(&crc.finalize().to_be_bytes());
+}
+
+// a pair of bars for each day, received then sent, scaled to the busiest. days are oldest first
+// and each gets an equal share of the width, so labels can be put under them in cells.
+pub fn traffic_chart(days: &[(String, Traffic)], cols: u16, rows: u16) -> Image {
+    let mut image = Image::new(cols as u32 * CELL_WIDTH, rows as u32 * CELL_HEIGHT);
+    if days.is_empty() {
+        return image;
+    }
+
+    let max = days
+        .iter()
+        .map(|(_, t)| t.rx.max(t.tx))
+        .max()
+        .unwrap_or_default()
+        .max(1);
+    let slot = image.width / days.len() as u32;
+    let bar = (slot / 3).max(1);
+
+    for (i, (_, traffic)) in days.iter().enumerate() {
+        let x = i as u32 * slot + (slot - bar * 2) / 2;
+        for (n, (bytes, color)) in [(traffic.rx, RX), (traffic.tx, TX)].into_iter().enumerate() {
+            let height = (bytes as f64 / max as f64 * image.height as f64).round() as u32;
+            image.fill(
+                x + n as u32 * bar,
+                image.height - height,
+                bar,
+                height,
+                color,
+            );
+        }
+    }
+
+    image
+}
@@ -0,0 +1,54 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::{Read, Write},
+    os::unix::io::AsRawFd,
+    path::Path,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use nix::fcntl::{flock, FlockArg};
+
+// set when another ztui has the config directory. settings.json, the pending changes and the
+// caches beside them are then left alone, so what this one changes can't overwrite what that one
+// saves on the way out.
+static READ_ONLY: AtomicBool = AtomicBool::new(false);
+
+pub fn read_only() -> bool {
+    READ_ONLY.load(Ordering::Relaxed)
+}
+
+// held for as long as ztui runs. the kernel lets go of it when ztui exits, however it exits, so a
+// crash never leaves it stuck.
+pub struct Lock {
+    _file: File,
+}
+
+pub enum Acquired {
+    Held(Lock),
+    // another ztui has it, with its PID when it could be read
+    Running(Option<u32>),
+}
+
+pub fn acquire(dir: &Path) -> Result<Acquired, anyhow::Error> {
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(dir.join("ztui.lock"))?;
+
+    match flock(file.as_raw_fd(), FlockArg::LockExclusiveNonblock) {
+        Ok(()) => {
+            file.set_len(0)?;
+            write!(file, "{}", std::process::id())?;
+            Ok(Acquired::Held(Lock { _file: file }))
+        }
+        Err(nix::errno::Errno::EWOULDBLOCK) => {
+            let mut pid = String::new();
+            file.read_to_string(&mut pid)?;
+            READ_ONLY.store(true, Ordering::Relaxed);
+            Ok(Acquired::Running(pid.trim().parse().ok()))
+        }
+        Err(e) => Err(e.into()),
+    }
+}
@@ -0,0 +1,93 @@
+use std::collections::HashSet;
+
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::{self, JoinFlags},
+    exit,
+    form::find_network_id,
+};
+
+// networks a machine should be on, and what they may change on it, kept under a name in
+// config.json so a new machine can be brought up with one step
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JoinSet {
+    pub networks: Vec<String>,
+    #[serde(flatten)]
+    pub flags: JoinFlags,
+}
+
+impl JoinSet {
+    // the networks by ID. they can be written as anything with an ID in it, as a Central URL is.
+    pub fn ids(&self) -> Result<Vec<String>, anyhow::Error> {
+        self.networks
+            .iter()
+            .map(|n| find_network_id(n).ok_or_else(|| anyhow!("{} isn't a network ID", n)))
+            .collect()
+    }
+
+    // the flags that are set, as the local service names them
+    pub fn describe_flags(&self) -> String {
+        [
+            ("allowManaged", self.flags.allow_managed),
+            ("allowGlobal", self.flags.allow_global),
+            ("allowDefault", self.flags.allow_default),
+            ("allowDNS", self.flags.allow_dns),
+        ]
+        .iter()
+        .filter_map(|(name, on)| Some(format!("{} {}", name, crate::app::on_off((*on)?))))
+        .collect::<Vec<_>>()
+        .join(", ")
+    }
+}
+
+// joining a network the node is already on changes only its flags
+pub fn apply(id: String, set: &JoinSet) -> Result<(), anyhow::Error> {
+    client::join_network_with(id, set.flags)
+}
+
+// how applying a set went for one of its networks, for ztui apply-set
+#[derive(Debug, Clone, Serialize)]
+pub struct Record {
+    pub network: String,
+    // join, or flags for a network that was already joined
+    pub action: &'static str,
+    pub outcome: &'static str,
+    pub error: Option<String>,
+}
+
+pub const FIELDS: [&str; 4] = ["network", "action", "outcome", "error"];
+
+// joins every network of the set, and sets the flags on those already joined. one failing doesn't
+// stop the rest.
+pub fn run(set: &JoinSet) -> Result<Vec<Record>, anyhow::Error> {
+    let ids = set.ids()?;
+    let joined = client::sync_get_networks()
+        .map_err(exit::daemon)?
+        .into_iter()
+        .filter_map(|n| n.subtype_1.id)
+        .collect::<HashSet<_>>();
+
+    Ok(ids
+        .into_iter()
+        .map(|id| {
+            let action = if joined.contains(&id) {
+                "flags"
+            } else {
+                "join"
+            };
+            let res = apply(id.clone(), set);
+            Record {
+                network: id,
+                action,
+                outcome: if res.is_ok() { "done" } else { "failed" },
+                error: res.err().map(|e| e.to_string()),
+            }
+        })
+        .collect())
+}
+
+pub fn failed(records: &[Record]) -> bool {
+    records.iter().any(|r| r.error.is_some())
+}
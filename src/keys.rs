@@ -0,0 +1,75 @@
+use anyhow::anyhow;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+const NAMED: &[(&str, KeyCode)] = &[
+    ("Enter", KeyCode::Enter),
+    ("Esc", KeyCode::Esc),
+    ("Space", KeyCode::Char(' ')),
+    ("Tab", KeyCode::Tab),
+    ("BackTab", KeyCode::BackTab),
+    ("Backspace", KeyCode::Backspace),
+    ("Delete", KeyCode::Delete),
+    ("Up", KeyCode::Up),
+    ("Down", KeyCode::Down),
+    ("Left", KeyCode::Left),
+    ("Right", KeyCode::Right),
+    ("Home", KeyCode::Home),
+    ("End", KeyCode::End),
+    ("PageUp", KeyCode::PageUp),
+    ("PageDown", KeyCode::PageDown),
+];
+
+// a key as it's written in settings and shown on screen: `a`, `Enter`, `F2`, `C-o`
+pub fn name(key: &KeyEvent) -> String {
+    let base = match key.code {
+        KeyCode::F(n) => format!("F{}", n),
+        code => match NAMED.iter().find(|(_, c)| *c == code) {
+            Some((name, _)) => name.to_string(),
+            None => match code {
+                KeyCode::Char(c) => c.to_string(),
+                code => format!("{:?}", code),
+            },
+        },
+    };
+
+    let mut prefix = String::new();
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        prefix.push_str("C-");
+    }
+    if key.modifiers.contains(KeyModifiers::ALT) {
+        prefix.push_str("A-");
+    }
+
+    prefix + &base
+}
+
+pub fn parse(s: &str) -> Result<KeyEvent, anyhow::Error> {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut rest = s;
+
+    loop {
+        if let Some(r) = rest.strip_prefix("C-").filter(|r| !r.is_empty()) {
+            modifiers |= KeyModifiers::CONTROL;
+            rest = r;
+        } else if let Some(r) = rest.strip_prefix("A-").filter(|r| !r.is_empty()) {
+            modifiers |= KeyModifiers::ALT;
+            rest = r;
+        } else {
+            break;
+        }
+    }
+
+    let code = if let Some((_, code)) = NAMED.iter().find(|(name, _)| *name == rest) {
+        *code
+    } else if let Some(n) = rest.strip_prefix('F').and_then(|n| n.parse().ok()) {
+        KeyCode::F(n)
+    } else {
+        let mut chars = rest.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => return Err(anyhow!("unknown key `{}`", s)),
+        }
+    };
+
+    Ok(KeyEvent::new(code, modifiers))
+}
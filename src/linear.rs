@@ -0,0 +1,151 @@
+use std::{collections::BTreeMap, io::Write, sync::mpsc, time::Duration};
+
+use crate::api::central::types::Member;
+use crate::api::one::types::Network;
+use crate::{
+    client,
+    config::Settings,
+    history::{is_online, now},
+    watch::shutdown_signal,
+};
+
+fn name(network: &Network) -> String {
+    match network.subtype_1.name.clone() {
+        Some(name) if !name.is_empty() => name,
+        _ => network.subtype_1.id.clone().unwrap_or_default(),
+    }
+}
+
+// one sentence about a network, e.g. "Network homelab: OK, 3 of 4 members online"
+fn summary(network: &Network, members: Option<Result<Vec<Member>, anyhow::Error>>) -> String {
+    let mut line = format!(
+        "Network {}: {}",
+        name(network),
+        network
+            .subtype_1
+            .status
+            .clone()
+            .unwrap_or_else(|| "unknown status".to_string())
+    );
+
+    match members {
+        Some(Ok(members)) => {
+            let now = now();
+            let online = members
+                .iter()
+                .filter(|m| is_online(m, now).unwrap_or_default())
+                .count();
+            let waiting = members
+                .iter()
+                .filter(|m| {
+                    !m.config
+                        .as_ref()
+                        .and_then(|c| c.authorized)
+                        .unwrap_or_default()
+                })
+                .count();
+
+            line += &format!(", {} of {} members online", online, members.len());
+            if waiting > 0 {
+                line += &format!(", {} waiting for authorization", waiting);
+            }
+        }
+        Some(Err(e)) => line += &format!(", members could not be read: {}", e),
+        None => {}
+    }
+
+    line
+}
+
+#[cfg(feature = "central")]
+fn members(settings: &Settings, id: &str) -> Option<Result<Vec<Member>, anyhow::Error>> {
+    settings.api_key_for_id(id.to_string()).map(|_| {
+        settings
+            .central_client_for_id(id.to_string())
+            .and_then(|client| client::sync_get_members(client, id.to_string()))
+    })
+}
+
+// members are only said to be online or waiting when there's a Central client to ask
+#[cfg(not(feature = "central"))]
+fn members(_: &Settings, _: &str) -> Option<Result<Vec<Member>, anyhow::Error>> {
+    None
+}
+
+// (name, line) for each network, by network id
+fn poll(settings: &Settings) -> Result<BTreeMap<String, (String, String)>, anyhow::Error> {
+    let mut lines = BTreeMap::new();
+
+    for network in client::sync_get_networks()? {
+        let id = match network.subtype_1.id.clone() {
+            Some(id) => id,
+            None => continue,
+        };
+
+        let members = members(settings, &id);
+        lines.insert(id, (name(&network), summary(&network, members)));
+    }
+
+    Ok(lines)
+}
+
+// instead of a screen that's redrawn in place, a line of plain labeled text whenever something
+// changes. nothing moves around and nothing is said twice, which suits screen readers and
+// braille displays.
+pub fn run(settings: Settings, interval: Duration) -> Result<(), anyhow::Error> {
+    let shutdown = shutdown_signal();
+    let mut last: BTreeMap<String, (String, String)> = BTreeMap::new();
+    let mut last_error = None;
+    let mut first = true;
+
+    loop {
+        let mut out = Vec::new();
+
+        match poll(&settings) {
+            Ok(lines) => {
+                if last_error.take().is_some() {
+                    out.push("ZeroTier is reachable again".to_string());
+                }
+
+                for (id, (_, line)) in &lines {
+                    if last.get(id).map(|(_, l)| l) != Some(line) {
+                        out.push(line.clone());
+                    }
+                }
+                for (id, (name, _)) in &last {
+                    if !lines.contains_key(id) {
+                        out.push(format!("Network {}: left", name));
+                    }
+                }
+
+                if first && lines.is_empty() {
+                    out.push("No networks joined".to_string());
+                }
+                first = false;
+                last = lines;
+            }
+            Err(e) => {
+                let e = format!("Could not reach ZeroTier: {}", e);
+                if last_error.as_ref() != Some(&e) {
+                    out.push(e.clone());
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        let mut stdout = std::io::stdout().lock();
+        for line in out {
+            writeln!(stdout, "{}", line)?;
+        }
+        stdout.flush()?;
+        drop(stdout);
+
+        match shutdown.recv_timeout(interval) {
+            Ok(()) => break,
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => std::thread::sleep(interval),
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,233 @@
+use std::{
+    fmt::Write as _,
+    fs::File,
+    io::Write,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+};
+
+use tracing::{
+    field::{Field, Visit},
+    level_filters::LevelFilter,
+    span, Event, Metadata, Subscriber,
+};
+
+use crate::config::data_path;
+
+// `ZTUI_LOG` turns on tracing to ztui.log in the data directory, so a freeze or a refresh that
+// keeps failing can be looked into afterwards. it takes the same form as RUST_LOG does elsewhere:
+// a default level and per-module ones, `ZTUI_LOG=info,client=debug,app=trace`.
+pub const ENV: &str = "ZTUI_LOG";
+pub const FILE: &str = "ztui.log";
+// when ztui.log reaches this it's moved to ztui.log.1, and so on up to ztui.log.KEEP
+const ROTATE_AT: u64 = 1024 * 1024;
+const KEEP: usize = 3;
+
+struct Filter {
+    default: LevelFilter,
+    // the most specific module wins, so these are kept longest first
+    modules: Vec<(String, LevelFilter)>,
+}
+
+impl Filter {
+    fn parse(spec: &str) -> Result<Self, anyhow::Error> {
+        let mut filter = Self {
+            default: LevelFilter::OFF,
+            modules: Vec::new(),
+        };
+
+        for directive in spec.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            match directive.split_once('=') {
+                Some((module, level)) => filter.modules.push((
+                    module.trim().trim_start_matches("ztui::").to_string(),
+                    level_of(level)?,
+                )),
+                // a bare module turns everything in it on, a bare level sets the default
+                None => match level_of(directive) {
+                    Ok(level) => filter.default = level,
+                    Err(_) => filter.modules.push((
+                        directive.trim_start_matches("ztui::").to_string(),
+                        LevelFilter::TRACE,
+                    )),
+                },
+            }
+        }
+
+        filter
+            .modules
+            .sort_by_key(|(module, _)| std::cmp::Reverse(module.len()));
+        Ok(filter)
+    }
+
+    fn level(&self, target: &str) -> LevelFilter {
+        let target = target.trim_start_matches("ztui::");
+        self.modules
+            .iter()
+            .find(|(module, _)| {
+                target == module
+                    || target
+                        .strip_prefix(module.as_str())
+                        .is_some_and(|rest| rest.starts_with("::"))
+            })
+            .map(|(_, level)| *level)
+            .unwrap_or(self.default)
+    }
+
+    fn max(&self) -> LevelFilter {
+        self.modules
+            .iter()
+            .map(|(_, level)| *level)
+            .fold(self.default, std::cmp::max)
+    }
+}
+
+fn level_of(s: &str) -> Result<LevelFilter, anyhow::Error> {
+    s.trim()
+        .parse()
+        .map_err(|_| anyhow::anyhow!("{}: {} is not a log level", ENV, s.trim()))
+}
+
+struct Log {
+    path: PathBuf,
+    file: File,
+    written: u64,
+}
+
+impl Log {
+    fn open(path: PathBuf) -> Result<Self, anyhow::Error> {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)?;
+        let written = file.metadata()?.len();
+        Ok(Self {
+            path,
+            file,
+            written,
+        })
+    }
+
+    fn write(&mut self, line: &str) {
+        if self.written + line.len() as u64 > ROTATE_AT {
+            self.rotate();
+        }
+
+        // a log that can't be written shouldn't stop ztui
+        if self.file.write_all(line.as_bytes()).is_ok() {
+            self.written += line.len() as u64;
+        }
+    }
+
+    fn rotate(&mut self) {
+        let numbered = |n: usize| {
+            let mut path = self.path.clone().into_os_string();
+            path.push(format!(".{}", n));
+            PathBuf::from(path)
+        };
+
+        for n in (1..KEEP).rev() {
+            let _ = std::fs::rename(numbered(n), numbered(n + 1));
+        }
+        let _ = std::fs::rename(&self.path, numbered(1));
+
+        if let Ok(log) = Self::open(self.path.clone()) {
+            *self = log;
+        }
+    }
+}
+
+// writes each event as a line: when, level, module, message and any fields. spans aren't used,
+// so they're only given IDs.
+struct FileSubscriber {
+    filter: Filter,
+    log: Mutex<Log>,
+    next_span: AtomicU64,
+}
+
+impl Subscriber for FileSubscriber {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        *metadata.level() <= self.filter.level(metadata.target())
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        Some(self.filter.max())
+    }
+
+    fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+        span::Id::from_u64(self.next_span.fetch_add(1, Ordering::Relaxed))
+    }
+
+    fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+    fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let metadata = event.metadata();
+        let mut line = Line::default();
+        event.record(&mut line);
+
+        let line = format!(
+            "{} {:>5} {}: {}{}\n",
+            time::OffsetDateTime::now_utc(),
+            metadata.level(),
+            metadata.target().trim_start_matches("ztui::"),
+            line.message,
+            line.fields
+        );
+        self.log.lock().unwrap().write(&line);
+    }
+
+    fn enter(&self, _: &span::Id) {}
+
+    fn exit(&self, _: &span::Id) {}
+}
+
+#[derive(Default)]
+struct Line {
+    message: String,
+    fields: String,
+}
+
+impl Visit for Line {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+pub fn path() -> PathBuf {
+    data_path().join(FILE)
+}
+
+// starts tracing if ZTUI_LOG asks for it. without it nothing is installed and the tracing calls
+// throughout cost next to nothing.
+pub fn init() -> Result<(), anyhow::Error> {
+    let spec = match std::env::var(ENV) {
+        Ok(spec) if !spec.trim().is_empty() => spec,
+        _ => return Ok(()),
+    };
+
+    let subscriber = FileSubscriber {
+        filter: Filter::parse(&spec)?,
+        log: Mutex::new(Log::open(path())?),
+        next_span: AtomicU64::new(1),
+    };
+    tracing::subscriber::set_global_default(subscriber)?;
+    tracing::info!(version = env!("CARGO_PKG_VERSION"), filter = %spec, "tracing started");
+
+    Ok(())
+}
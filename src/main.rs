@@ -4,76 +4,749 @@ use std::{
 };
 
 use app::Page;
-use client::central_client;
+use clap::{Arg, ArgAction, Command, ValueHint};
 use tui::widgets::TableState;
 
+#[cfg(feature = "central")]
+use crate::{client::Cancel, config::data_path};
 use crate::{
-    config::{config_path, Settings},
+    config::{
+        config_path, write_atomic, Settings, AUTOSAVE_DEBOUNCE, AUTOSAVE_INTERVAL,
+        REFRESH_INTERVAL, WATCH_INTERVAL,
+    },
     terminal::deinit_terminal,
 };
 
+mod actions;
+mod addresses;
+mod api;
 mod app;
+mod batch;
+mod bugreport;
+mod capture;
+mod changelog;
 mod client;
+mod clipboard;
 mod config;
+mod conntrack;
+#[cfg(feature = "central")]
+mod diff;
 mod display;
+mod doctor;
+mod errors;
+mod events;
+mod exit;
+mod form;
+mod grouping;
+mod help;
+mod history;
+mod i18n;
+mod identity;
+mod image;
+mod instance;
+mod join_set;
+mod keys;
+mod linear;
+mod logging;
+mod names;
 mod nets;
+mod output;
+mod pending;
+mod picker;
+mod ping;
+mod privacy;
+mod registry;
+mod routes;
+mod rules;
+mod script;
+#[cfg(feature = "central")]
+mod security;
+mod shutdown;
+mod snippets;
+mod systemd;
+mod tape;
 mod terminal;
+#[cfg(test)]
+mod testing;
+mod theme;
+mod traffic;
+mod update;
+mod watch;
+mod watchdog;
+
+// every argument and subcommand
+fn cli() -> Command {
+    Command::new("ztui")
+        .version(env!("CARGO_PKG_VERSION"))
+        .about(env!("CARGO_PKG_DESCRIPTION"))
+        .arg(
+            Arg::new("no-color")
+                .long("no-color")
+                .action(ArgAction::SetTrue)
+                .global(true)
+                .help("Draw without colors; also done when NO_COLOR is set"),
+        )
+        .arg(
+            Arg::new("ascii")
+                .long("ascii")
+                .action(ArgAction::SetTrue)
+                .global(true)
+                .help("Draw with plain ASCII only, for terminals without box drawing characters"),
+        )
+        .arg(
+            Arg::new("privacy")
+                .long("privacy")
+                .action(ArgAction::SetTrue)
+                .help("Start with network IDs, node IDs and addresses masked, for screenshots; z toggles it"),
+        )
+        .arg(
+            Arg::new("linear")
+                .long("linear")
+                .action(ArgAction::SetTrue)
+                .help("Instead of the full screen UI, print a plain line about each network whenever it changes, for screen readers"),
+        )
+        .arg(
+            Arg::new("record")
+                .long("record")
+                .value_name("FILE")
+                .value_hint(ValueHint::FilePath)
+                .conflicts_with("replay")
+                .help("Record every request made of ZeroTier and Central, and the answers, to FILE with secrets scrubbed, for a bug report"),
+        )
+        .arg(
+            Arg::new("replay")
+                .long("replay")
+                .value_name("FILE")
+                .value_hint(ValueHint::FilePath)
+                .help("Run from a recording made with --record instead of ZeroTier and Central; nothing is changed or saved"),
+        )
+        .arg(
+            Arg::new("local-api")
+                .long("local-api")
+                .value_name("URL")
+                .global(true)
+                .help("Where the ZeroTier service answers, such as a port mapped into a container; also ZEROTIER_ONE_INSTANCE"),
+        )
+        .arg(
+            Arg::new("authtoken")
+                .long("authtoken")
+                .value_name("FILE")
+                .value_hint(ValueHint::FilePath)
+                .global(true)
+                .help("The ZeroTier service's authtoken.secret; also ZEROTIER_ONE_AUTHTOKEN, or ZEROTIER_ONE_TOKEN for the token itself"),
+        )
+        .arg(
+            Arg::new("dry-run")
+                .long("dry-run")
+                .action(ArgAction::SetTrue)
+                .global(true)
+                .help(
+                    "Log the changes that would be made to the action log instead of making them",
+                ),
+        )
+        .subcommand(
+            Command::new("doctor")
+                .about("Check the prerequisites for ZeroTier connectivity")
+                .arg(output_arg()),
+        )
+        .subcommand(
+            Command::new("run")
+                .about("Run a script of membership, naming and rules changes")
+                .arg(
+                    Arg::new("script")
+                        .required(true)
+                        .value_hint(ValueHint::FilePath)
+                        .help("The script to run, one change a line"),
+                )
+                .arg(output_arg()),
+        )
+        .subcommand(
+            Command::new("status")
+                .about("Print the local node's address, version and whether it's online")
+                .arg(output_arg()),
+        )
+        .subcommand(
+            Command::new("list")
+                .about("Print the networks the local node has joined")
+                .arg(output_arg()),
+        )
+        .subcommand(
+            Command::new("members")
+                .about("Print a network's members, given its ID or name; it needs an API key saved for it")
+                .arg(
+                    Arg::new("network")
+                        .required(true)
+                        .help("The network's ID, or its name"),
+                )
+                .arg(output_arg()),
+        )
+        .subcommand(
+            Command::new("export")
+                .about("Print the members of every network there's an API key for")
+                .arg(output_arg()),
+        )
+        .subcommand(
+            Command::new("apply-set")
+                .about("Join the networks of a join set from config.json, with its flags; those already joined just get the flags")
+                .arg(
+                    Arg::new("name")
+                        .required(true)
+                        .help("The join set's name"),
+                )
+                .arg(output_arg()),
+        )
+        .subcommand(
+            Command::new("watch")
+                .about("Poll without the UI and print membership changes as JSON lines")
+                .arg(
+                    Arg::new("notify-on")
+                        .long("notify-on")
+                        .value_delimiter(',')
+                        .help("Events to report, comma separated: joined, left, authorized, unauthorized, online, offline, renamed, status (default: all)"),
+                )
+                .arg(
+                    Arg::new("interval")
+                        .long("interval")
+                        .value_parser(clap::value_parser!(u64))
+                        .default_value("30")
+                        .help("Seconds between polls"),
+                )
+                .arg(
+                    Arg::new("hook")
+                        .long("hook")
+                        .help("Run this shell command for each event with its JSON on stdin, instead of printing it"),
+                )
+                .arg(
+                    Arg::new("summary-webhook")
+                        .long("summary-webhook")
+                        .value_name("URL")
+                        .help("POST a JSON summary of disconnected networks, members long offline and members waiting for authorization to URL, after the first poll and then on a schedule"),
+                )
+                .arg(
+                    Arg::new("summary-every")
+                        .long("summary-every")
+                        .value_name("HOURS")
+                        .value_parser(clap::value_parser!(u64))
+                        .default_value("24")
+                        .help("Hours between summaries"),
+                )
+                .arg(
+                    Arg::new("offline-hours")
+                        .long("offline-hours")
+                        .value_name("HOURS")
+                        .value_parser(clap::value_parser!(u64))
+                        .default_value("24")
+                        .help("Authorized members offline at least this long are listed in the summary"),
+                ),
+        )
+}
 
 fn main() -> Result<(), anyhow::Error> {
-    client::local_client_from_file(client::authtoken_path(None)).expect(
-        "must be able to read the authtoken.secret file in the zerotier configuration directory",
-    );
+    let matches = cli().get_matches();
+
+    actions::set_dry_run(matches.get_flag("dry-run"));
+    theme::set(matches.get_flag("no-color"), matches.get_flag("ascii"));
+    privacy::set(matches.get_flag("privacy"));
+    client::set_local(client::Local {
+        baseurl: matches.get_one::<String>("local-api").cloned(),
+        authtoken: matches.get_one::<String>("authtoken").map(Into::into),
+    });
+
+    // kept until ztui exits; the replay's settings and data live in it
+    let _scratch = match matches.get_one::<String>("replay") {
+        Some(path) => {
+            let recording = tape::replay(std::path::Path::new(path))?;
+            let scratch = tempfile::tempdir()?;
+            config::set_root(scratch.path().to_path_buf());
+            std::fs::write(
+                config_path().join("settings.json"),
+                serde_json::to_string(&recording.header.settings)?,
+            )?;
+            Some(scratch)
+        }
+        None => None,
+    };
+
+    if let Some(locale) = config::UserConfig::from_dir(config_path())
+        .ok()
+        .and_then(|c| c.locale())
+    {
+        if let Err(e) = i18n::load(&config_path(), &locale) {
+            eprintln!(
+                "Could not load the {} translation, using English: {}",
+                locale, e
+            );
+        }
+    }
+
+    if let Some(("doctor", args)) = matches.subcommand() {
+        let settings = Settings::from_dir(config_path()).unwrap_or_default();
+        let checks = doctor::run(settings.any_api_key());
+        match output_format(args)? {
+            output::Format::Table => doctor::print(&checks),
+            format => output::print(format, &doctor::FIELDS, &checks)?,
+        }
+        std::process::exit(doctor::exit_code(&checks));
+    }
+
+    if let Some(("run", args)) = matches.subcommand() {
+        let path = std::path::PathBuf::from(args.get_one::<String>("script").unwrap());
+        let script = std::fs::read_to_string(&path)
+            .map_err(|e| anyhow::anyhow!("{}: {}", path.display(), e))?;
+        let steps = script::parse(&script, path.parent().unwrap_or(std::path::Path::new(".")))?;
+
+        let settings = Settings::from_dir(config_path()).unwrap_or_default();
+        let outcomes = script::run(&settings, &steps);
+        match output_format(args)? {
+            output::Format::Table => script::print(&steps, &outcomes),
+            format => output::print(format, &script::FIELDS, &script::records(&steps, &outcomes))?,
+        }
+        std::process::exit(if script::failed(&outcomes) {
+            exit::FAILED
+        } else {
+            0
+        });
+    }
+
+    match matches.subcommand() {
+        Some(("status", args)) => exit::with(output::status().and_then(|status| {
+            output::print(output_format(args)?, &output::STATUS_FIELDS, &[status])
+        })),
+        Some(("list", args)) => exit::with(output::networks().and_then(|networks| {
+            output::print(output_format(args)?, &output::NETWORK_FIELDS, &networks)
+        })),
+        Some(("members", args)) => {
+            let settings = Settings::from_dir(config_path()).unwrap_or_default();
+            let id = output::network_id(args.get_one::<String>("network").unwrap());
+            exit::with(output::members(&settings, &id).and_then(|members| {
+                output::print(output_format(args)?, &output::MEMBER_FIELDS, &members)
+            }))
+        }
+        Some(("export", args)) => {
+            let settings = Settings::from_dir(config_path()).unwrap_or_default();
+            let (members, mut failed) = output::export(&settings);
+            // with nothing to show, it's whatever went wrong first
+            if members.is_empty() && !failed.is_empty() {
+                exit::with(Err(failed.remove(0).1));
+            }
+            if let Err(e) = output::print(output_format(args)?, &output::MEMBER_FIELDS, &members) {
+                exit::with(Err(e));
+            }
+            for (id, e) in &failed {
+                eprintln!("Error: {}: {}", id, e);
+            }
+            std::process::exit(if failed.is_empty() { 0 } else { exit::PARTIAL });
+        }
+        Some(("apply-set", args)) => {
+            let name = args.get_one::<String>("name").unwrap();
+            let config = config::UserConfig::from_dir(config_path()).unwrap_or_default();
+            let set = match config.join_sets().get(name) {
+                Some(set) => set,
+                None => exit::with(Err(anyhow::anyhow!(
+                    "there's no join set named {} in config.json",
+                    name
+                ))),
+            };
+            let records = match join_set::run(set) {
+                Ok(records) => records,
+                Err(e) => exit::with(Err(e)),
+            };
+            if let Err(e) = output::print(output_format(args)?, &join_set::FIELDS, &records) {
+                exit::with(Err(e));
+            }
+            std::process::exit(if join_set::failed(&records) {
+                exit::FAILED
+            } else {
+                0
+            });
+        }
+        _ => {}
+    }
+
+    if let Some(("watch", args)) = matches.subcommand() {
+        let kinds = match args.get_many::<String>("notify-on") {
+            Some(kinds) => kinds
+                .map(|k| k.parse())
+                .collect::<Result<Vec<watch::Kind>, _>>()?,
+            None => watch::KINDS.to_vec(),
+        };
+
+        return watch::run(
+            Settings::from_dir(config_path()).unwrap_or_default(),
+            watch::Options {
+                kinds,
+                interval: Duration::from_secs(*args.get_one::<u64>("interval").unwrap()),
+                hook: args.get_one::<String>("hook").cloned(),
+                summary: args.get_one::<String>("summary-webhook").map(|webhook| {
+                    let hours =
+                        |name| Duration::from_secs(*args.get_one::<u64>(name).unwrap() * 3600);
+                    watch::SummaryOptions {
+                        webhook: webhook.clone(),
+                        every: hours("summary-every"),
+                        offline_after: hours("offline-hours"),
+                    }
+                }),
+            },
+        );
+    }
+
+    if let Err(e) =
+        client::local()
+            .map(|_| ())
+            .or_else(|e| if tape::replaying() { Ok(()) } else { Err(e) })
+    {
+        eprintln!(
+            "must be able to read the authtoken.secret file in the zerotier configuration directory: {}\n",
+            e
+        );
+        doctor::print(&doctor::run(None));
+        std::process::exit(exit::DAEMON_UNREACHABLE);
+    }
+
+    if matches.get_flag("linear") {
+        return linear::run(
+            Settings::from_dir(config_path()).unwrap_or_default(),
+            REFRESH_INTERVAL,
+        );
+    }
 
     let mut terminal = terminal::init_terminal()?;
 
     let mut app = app::App::default();
     std::fs::create_dir_all(config_path())?;
-    let settings = Arc::new(Mutex::new(match Settings::from_dir(config_path()) {
-        Ok(c) => c,
-        Err(_) => Settings::default(),
-    }));
+    config::init_data_dirs()?;
+    logging::init()?;
+    // kept until ztui exits. with another ztui running, this one carries on without saving if the
+    // user wants it to.
+    let mut _instance = None;
+    match instance::acquire(&config_path()) {
+        Ok(instance::Acquired::Held(lock)) => _instance = Some(lock),
+        Ok(instance::Acquired::Running(pid)) => app.dialog = app::Dialog::AlreadyRunning(pid),
+        Err(e) => tracing::warn!(error = %e, "could not lock the config directory"),
+    }
+    if !instance::read_only() {
+        if let Err(e) = config::rotate_backups(&config_path()) {
+            eprintln!("could not back up settings.json: {}", e);
+        }
+    }
+    let settings = Arc::new(Mutex::new(
+        Settings::from_dir(config_path()).unwrap_or_default(),
+    ));
+    if let Some(path) = matches.get_one::<String>("record") {
+        let value = serde_json::to_value(&*settings.lock().unwrap())?;
+        tape::record(std::path::Path::new(path), value)?;
+    }
+    bugreport::install_panic_hook(settings.clone());
 
     terminal.clear()?;
     eprintln!("Polling ZeroTier for network information...");
 
+    watchdog::start(start_supervisors, settings.clone())?;
     let s = settings.clone();
-    std::thread::spawn(move || start_supervisors(s));
+    std::thread::spawn(move || autosave(s));
+    let s = settings.clone();
+    std::thread::spawn(move || update::check(s));
+    // settings are saved by the app on the way out, where a failure can still be shown
     let res = app.run(&mut terminal, settings.clone());
+    // a closed terminal can't be put back, but settings are still saved
+    let deinit = deinit_terminal(terminal);
 
-    settings.lock().unwrap().to_file(config_path())?;
-    deinit_terminal(terminal)?;
+    if res.is_err() {
+        let mut lock = settings.lock().unwrap();
+        lock.save_session();
+        lock.to_file(config_path())?;
+    }
 
+    deinit?;
     res
 }
 
-fn start_supervisors(settings: Arc<Mutex<Settings>>) {
+fn output_arg() -> Arg {
+    Arg::new("output")
+        .long("output")
+        .short('o')
+        .value_parser(output::FORMATS)
+        .default_value("table")
+        .help("Print lined up for reading, or as json or csv for scripts; the field names are the same in each")
+}
+
+fn output_format(args: &clap::ArgMatches) -> Result<output::Format, anyhow::Error> {
+    args.get_one::<String>("output").unwrap().parse()
+}
+
+// saves settings.json whenever it changes, so a crash loses at most the last few seconds. a change
+// is written once it has settled for AUTOSAVE_DEBOUNCE, or after AUTOSAVE_INTERVAL if it never does.
+fn autosave(settings: Arc<Mutex<Settings>>) {
+    let snapshot = |settings: &Arc<Mutex<Settings>>| settings.lock().unwrap().snapshot().ok();
+
+    let mut saved = snapshot(&settings).unwrap_or_default();
+    let mut last = saved.clone();
+    let mut since = std::time::Instant::now();
+
     loop {
+        std::thread::sleep(AUTOSAVE_DEBOUNCE);
+
+        let json = match snapshot(&settings) {
+            Some(json) => json,
+            None => continue,
+        };
+        if json == saved {
+            since = std::time::Instant::now();
+        } else if (json == last || since.elapsed() >= AUTOSAVE_INTERVAL)
+            && !instance::read_only()
+            && !shutdown::stopping()
+            && write_atomic(&config_path().join("settings.json"), &json).is_ok()
+        {
+            saved = json.clone();
+            since = std::time::Instant::now();
+        }
+        last = json;
+    }
+}
+
+// requests are made without holding the settings lock, so the UI keeps drawing (and animating
+// its spinners) while they're out
+#[cfg(feature = "central")]
+fn fetch_members(settings: &Arc<Mutex<Settings>>, id: String) -> Result<(), anyhow::Error> {
+    let (client, cancel) = {
         let mut lock = settings.lock().unwrap();
-        match lock.page.clone() {
-            Page::Networks => {
-                let networks = crate::client::sync_get_networks().unwrap();
-                lock.nets.refresh().unwrap();
-                if lock.update_networks(networks).unwrap() {
-                    lock.network_state = TableState::default();
-                };
+        if lock.api_key_for_id(id.clone()).is_none() {
+            return Ok(());
+        }
+
+        lock.members_fetch.entry(id.clone()).or_default().loading = true;
+        lock.fetch_cancel = Cancel::default();
+        display::mark_dirty();
+        (
+            lock.central_client_for_id(id.clone()),
+            lock.fetch_cancel.clone(),
+        )
+    };
+
+    let res = crate::client::cancellable(&cancel, || {
+        let client = client?;
+        let members = crate::client::sync_get_members(client.clone(), id.clone())?;
+        // names and pools are a nicety; members still show with bare ids if the network can't be
+        // had
+        let network = crate::client::sync_get_network(client, id.clone()).ok();
+        Ok((members, network))
+    });
+
+    let mut lock = settings.lock().unwrap();
+    let fetch = lock.members_fetch.entry(id.clone()).or_default();
+    fetch.loading = false;
+    // left before it finished; not a failure, and nothing to show
+    if res.as_ref().is_err_and(crate::client::is_cancelled) {
+        return Ok(());
+    }
+    fetch.failed = res.as_ref().err().map(|e| e.to_string());
+
+    match &res {
+        Err(e) => {
+            lock.record_error(Some(&id), "refresh members", e);
+            if crate::client::is_unauthorized(e) {
+                lock.reject_key(&id, e.to_string());
+            } else if crate::client::is_forbidden(e) {
+                lock.mark_read_only(&id);
             }
-            Page::Network(id) => {
-                if let Some(key) = lock.api_key_for_id(id.clone()) {
-                    let client = central_client(key.to_string()).unwrap();
-                    match crate::client::sync_get_members(client, id.clone()) {
-                        Ok(members) => {
-                            lock.members.insert(id.clone(), members);
+        }
+        Ok(_) => lock.accept_key(&id),
+    }
+    let (members, network) = res?;
+    lock.members_fetch.entry(id.clone()).or_default().refreshed = Some(std::time::Instant::now());
+
+    if let Some(network) = network {
+        if let Some(source) = network.rules_source {
+            lock.rule_names
+                .insert(id.clone(), crate::rules::RuleNames::parse(&source));
+        }
+        let pools = network
+            .config
+            .and_then(|c| c.ip_assignment_pools)
+            .unwrap_or_default();
+        lock.pools.insert(
+            id.clone(),
+            pools
+                .iter()
+                .filter_map(crate::addresses::Pool::parse)
+                .collect(),
+        );
+    }
+    let mut changed = !lock.members.contains_key(&id);
+    if lock.history.record(&id, &members) {
+        let _ = lock.history.save(data_path());
+        changed = true;
+    }
+    let changes = lock.changelog.record(&id, &members);
+    if !changes.is_empty() {
+        let _ = lock.changelog.save(data_path());
+        lock.check_authorizations(&id, &changes);
+        changed = true;
+    }
+    lock.check_duplicates(&id, &members);
+    lock.check_utilization(&id, &members);
+    lock.set_members(id, members);
+    if changed {
+        let _ = lock.save_members();
+    }
+
+    Ok(())
+}
+
+// members only come from Central; without it the saved ones are all there is
+#[cfg(not(feature = "central"))]
+fn fetch_members(_: &Arc<Mutex<Settings>>, _: String) -> Result<(), anyhow::Error> {
+    Ok(())
+}
+
+// sends changes queued while Central was unreachable, in order, stopping as soon as it stops
+// answering again
+#[cfg(feature = "central")]
+fn replay_pending(settings: &Arc<Mutex<Settings>>) {
+    let ready = settings.lock().unwrap().pending.ready();
+
+    for change in ready {
+        // left queued for next time once ztui is quitting
+        let Some(_sending) = shutdown::Mutation::start() else {
+            return;
+        };
+        let client = settings
+            .lock()
+            .unwrap()
+            .central_client_for_id(change.network.clone());
+        let res =
+            client.and_then(|client| change.op.apply(client, &change.network, &change.member));
+
+        if res.as_ref().is_err_and(crate::client::is_unreachable) {
+            return;
+        }
+
+        let mut lock = settings.lock().unwrap();
+        if res.as_ref().is_err_and(crate::client::is_forbidden) {
+            lock.mark_read_only(&change.network);
+        }
+        lock.pending
+            .finish(change.id, res.map_err(|e| e.to_string()));
+        let _ = lock.pending.to_file(config_path());
+    }
+}
+
+// the refresh loop, run and restarted by the watchdog. it stops once another has taken its place.
+fn start_supervisors(settings: Arc<Mutex<Settings>>, generation: u64) {
+    let mut watched: Option<std::time::Instant> = None;
+
+    while watchdog::beat(generation) {
+        // nothing new is started on the way out
+        if shutdown::stopping() {
+            events::wait_refresh();
+            continue;
+        }
+
+        #[cfg(feature = "central")]
+        replay_pending(&settings);
+
+        let mut lock = settings.lock().unwrap();
+        let page = lock.page.clone();
+        let split = lock.split.then(|| lock.selected_network_id()).flatten();
+        tracing::trace!(?page, ?split, "refreshing");
+
+        match page {
+            Page::Networks => {
+                lock.networks_fetch.loading = true;
+                drop(lock);
+                display::mark_dirty();
+                let networks = crate::client::sync_get_networks();
+
+                let mut lock = settings.lock().unwrap();
+                lock.networks_fetch.loading = false;
+                // a failed poll leaves the last list up, or the saved one if the daemon hasn't
+                // answered yet; the display marks it either way
+                let res = networks.and_then(|networks| {
+                    lock.nets.refresh()?;
+                    lock.update_networks(networks)
+                });
+                match res {
+                    Ok(reset) => {
+                        if reset {
+                            lock.network_state = TableState::default();
                         }
+                        lock.networks_fetch.refreshed = Some(std::time::Instant::now());
+                        lock.networks_fetch.failed = None;
+                    }
+                    Err(e) => {
+                        tracing::warn!(error = %e, "refreshing networks failed");
+                        lock.record_error(None, "refresh networks", &e);
+                        lock.networks_fetch.failed = Some(e.to_string());
+                    }
+                }
+                let dropped = lock.dropped_for_rejoin();
+                drop(lock);
+
+                for (id, flags) in dropped {
+                    let res = crate::client::join_network_with(id.clone(), flags);
+                    let mut lock = settings.lock().unwrap();
+                    lock.notice = Some(match res {
+                        Ok(()) => i18n::tf("toast.auto_rejoined", &[&id]),
                         Err(e) => {
-                            lock.last_error = Some(e.to_string());
+                            lock.record_error(Some(&id), "rejoin", &e);
+                            i18n::tf("toast.auto_rejoin_failed", &[&id, &e])
+                        }
+                    });
+                }
+
+                if let Some(id) = split {
+                    // shown in the pane rather than bouncing to the API key dialog
+                    let err = fetch_members(&settings, id.clone())
+                        .err()
+                        .map(|e| (id, e.to_string()));
+                    settings.lock().unwrap().split_error = err;
+                }
+            }
+            Page::Network(id) => {
+                drop(lock);
+                // the saved members stay up, marked offline; a refused key is asked about once
+                // over them
+                if let Err(e) = fetch_members(&settings, id.clone()) {
+                    tracing::warn!(network = %id, error = %e, "refreshing members failed");
+                }
+
+                let mut lock = settings.lock().unwrap();
+                if lock.user_config().member_traffic() {
+                    let failing = lock.member_traffic.failed.is_some();
+                    if let Err(e) = lock.member_traffic.sample() {
+                        // said once, and again only after it's worked in between
+                        if !failing {
+                            lock.notice = Some(i18n::tf("toast.member_traffic_failed", &[&e]));
+                            lock.record_error(Some(&id), "member traffic", &e);
                         }
                     }
                 }
             }
         }
 
-        drop(lock);
+        // networks watched for authorizations are looked at now and then even when they aren't
+        // on screen; the one that is was just refreshed above
+        if watched.is_none_or(|at| at.elapsed() >= WATCH_INTERVAL) {
+            watched = Some(std::time::Instant::now());
+            let (networks, page, split) = {
+                let lock = settings.lock().unwrap();
+                (
+                    lock.user_config().watched_networks(),
+                    lock.page.clone(),
+                    lock.split.then(|| lock.selected_network_id()).flatten(),
+                )
+            };
+            for id in networks {
+                if page == Page::Network(id.clone()) || split.as_ref() == Some(&id) {
+                    continue;
+                }
+                if let Err(e) = fetch_members(&settings, id.clone()) {
+                    tracing::warn!(network = %id, error = %e, "watching for authorizations failed");
+                }
+            }
+        }
 
-        std::thread::sleep(Duration::new(3, 0));
+        display::mark_dirty();
+        events::wait_refresh();
     }
 }
@@ -0,0 +1,85 @@
+use std::{net::IpAddr, sync::mpsc, time::Duration};
+
+use anyhow::anyhow;
+
+use crate::api::central::types::Member;
+
+// lookups go to whatever resolver the system has, which can take its time when nothing answers
+const LOOKUP_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[cfg(unix)]
+fn reverse(ip: IpAddr) -> Result<String, anyhow::Error> {
+    let addr = socket2::SockAddr::from(std::net::SocketAddr::new(ip, 0));
+    let mut host = [0 as libc::c_char; libc::NI_MAXHOST as usize];
+
+    let res = unsafe {
+        libc::getnameinfo(
+            addr.as_ptr(),
+            addr.len(),
+            host.as_mut_ptr(),
+            host.len() as libc::socklen_t,
+            std::ptr::null_mut(),
+            0,
+            libc::NI_NAMEREQD,
+        )
+    };
+
+    if res != 0 {
+        let msg = unsafe { std::ffi::CStr::from_ptr(libc::gai_strerror(res)) };
+        return Err(anyhow!("{}: {}", ip, msg.to_string_lossy()));
+    }
+
+    let host = unsafe { std::ffi::CStr::from_ptr(host.as_ptr()) };
+    Ok(host.to_string_lossy().to_string())
+}
+
+#[cfg(not(unix))]
+fn reverse(ip: IpAddr) -> Result<String, anyhow::Error> {
+    Err(anyhow!(
+        "{}: reverse lookups are not supported on this platform",
+        ip
+    ))
+}
+
+// the short hostname of the first managed address that has a PTR record
+pub fn suggest(member: &Member) -> Result<String, anyhow::Error> {
+    let ips = member
+        .config
+        .as_ref()
+        .and_then(|c| c.ip_assignments.clone())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|ip| ip.parse::<IpAddr>().ok())
+        .collect::<Vec<IpAddr>>();
+
+    if ips.is_empty() {
+        return Err(anyhow!("member has no managed addresses to look up"));
+    }
+
+    let (s, r) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut errors = Vec::new();
+        for ip in ips {
+            match reverse(ip) {
+                Ok(host) => {
+                    let _ = s.send(Ok(host));
+                    return;
+                }
+                Err(e) => errors.push(e.to_string()),
+            }
+        }
+
+        let _ = s.send(Err(anyhow!("no name found: {}", errors.join(", "))));
+    });
+
+    let host = r
+        .recv_timeout(LOOKUP_TIMEOUT)
+        .map_err(|_| anyhow!("reverse lookup timed out"))??;
+
+    Ok(host
+        .trim_end_matches('.')
+        .split('.')
+        .next()
+        .unwrap_or_default()
+        .to_string())
+}
@@ -1,41 +1,220 @@
-use std::{collections::HashMap, time::Instant};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    path::PathBuf,
+    sync::Arc,
+    time::Instant,
+};
 
-use sys_metrics::network::IoNet;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::traffic::{default_source, Counters, TrafficSource};
+
+// how many days of totals we keep per interface
+const MAX_DAYS: usize = 90;
+
+pub fn format_bytes(bytes: u64) -> String {
+    byte_unit::Byte::from_bytes(bytes as u128)
+        .get_appropriate_unit(true)
+        .to_string()
+}
+
+pub fn format_rate(rx_bytes: f64, tx_bytes: f64) -> String {
+    format!(
+        "Rx: {}/s | Tx: {}/s",
+        byte_unit::Byte::from_bytes(rx_bytes as u128).get_appropriate_unit(true),
+        byte_unit::Byte::from_bytes(tx_bytes as u128).get_appropriate_unit(true),
+    )
+}
+
+fn today() -> String {
+    OffsetDateTime::now_utc().date().to_string()
+}
+
+// bytes moved between two samples of an OS counter. some platforms still use 32-bit counters
+// which wrap; anything else going backwards means the interface was recreated and its counters
+// started over.
+fn counter_delta(last: u64, current: u64) -> u64 {
+    if current >= last {
+        current - last
+    } else if last > u32::MAX as u64 / 2 && last <= u32::MAX as u64 {
+        u32::MAX as u64 - last + current + 1
+    } else {
+        current
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+pub struct Traffic {
+    pub rx: u64,
+    pub tx: u64,
+}
+
+impl Traffic {
+    fn add(&mut self, rx: u64, tx: u64) {
+        self.rx += rx;
+        self.tx += tx;
+    }
+
+    pub fn total(&self) -> u64 {
+        self.rx + self.tx
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+struct NetworkTotals {
+    // last raw OS counters seen for each interface backing the network
+    counters: HashMap<String, (u64, u64)>,
+    days: BTreeMap<String, Traffic>,
+}
 
 #[derive(Clone, Debug)]
 pub struct Nets {
-    nets: Vec<IoNet>,
+    source: Arc<dyn TrafficSource>,
+    nets: Vec<Counters>,
     last_usage: HashMap<String, Vec<(u128, u128, Instant)>>,
+    totals: HashMap<String, NetworkTotals>,
+    session: HashMap<String, Traffic>,
+    seen: HashSet<String>,
 }
 
 impl Default for Nets {
     fn default() -> Self {
+        let source = default_source();
         Self {
-            nets: sys_metrics::network::get_ionets().unwrap(),
+            nets: source.counters().unwrap_or_default(),
+            source,
             last_usage: HashMap::new(),
+            totals: HashMap::new(),
+            session: HashMap::new(),
+            seen: HashSet::new(),
         }
     }
 }
 
 impl Nets {
     pub fn new() -> Result<Self, anyhow::Error> {
+        Self::with_source(default_source())
+    }
+
+    pub fn with_source(source: Arc<dyn TrafficSource>) -> Result<Self, anyhow::Error> {
         Ok(Self {
             last_usage: HashMap::new(),
-            nets: sys_metrics::network::get_ionets()?,
+            nets: source.counters()?,
+            source,
+            totals: HashMap::new(),
+            session: HashMap::new(),
+            seen: HashSet::new(),
         })
     }
 
-    #[allow(unused)]
+    pub fn load_totals(&mut self, filename: PathBuf) -> Result<(), anyhow::Error> {
+        let traffic_file = std::fs::read_to_string(filename.join("traffic.json"))?;
+        self.totals = serde_json::from_str(&traffic_file)?;
+        Ok(())
+    }
+
+    pub fn save_totals(&self, filename: PathBuf) -> Result<(), anyhow::Error> {
+        Ok(std::fs::write(
+            filename.join("traffic.json"),
+            serde_json::to_string(&self.totals)?,
+        )?)
+    }
+
+    fn accumulate(&mut self, network_id: &str, nets: &[Counters]) {
+        let totals = self.totals.entry(network_id.to_string()).or_default();
+        let first = !self.seen.contains(network_id);
+        let mut traffic = Traffic::default();
+
+        for net in nets {
+            // without a previous reading there's nothing to measure against yet. the first
+            // sample of a session against a persisted reading picks up whatever happened while
+            // we weren't running, which counts toward the day but not the session.
+            if let Some((last_rx, last_tx)) = totals.counters.get(&net.interface) {
+                traffic.add(
+                    counter_delta(*last_rx, net.rx_bytes),
+                    counter_delta(*last_tx, net.tx_bytes),
+                );
+            }
+
+            totals
+                .counters
+                .insert(net.interface.clone(), (net.rx_bytes, net.tx_bytes));
+        }
+
+        totals
+            .days
+            .entry(today())
+            .or_default()
+            .add(traffic.rx, traffic.tx);
+
+        while totals.days.len() > MAX_DAYS {
+            let oldest = totals.days.keys().next().cloned().unwrap();
+            totals.days.remove(&oldest);
+        }
+
+        if first {
+            self.seen.insert(network_id.to_string());
+        } else {
+            self.session
+                .entry(network_id.to_string())
+                .or_default()
+                .add(traffic.rx, traffic.tx);
+        }
+    }
+
+    pub fn session_totals(&self, network_id: &str) -> Traffic {
+        self.session.get(network_id).copied().unwrap_or_default()
+    }
+
+    pub fn today_totals(&self, network_id: &str) -> Traffic {
+        self.totals
+            .get(network_id)
+            .and_then(|t| t.days.get(&today()))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    // newest first
+    pub fn daily_totals(&self, network_id: &str) -> Vec<(String, Traffic)> {
+        self.totals
+            .get(network_id)
+            .map(|t| t.days.iter().rev().map(|(k, v)| (k.clone(), *v)).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn source_name(&self) -> &'static str {
+        self.source.name()
+    }
+
     pub fn len(&self) -> usize {
         self.nets.len()
     }
 
     pub fn refresh(&mut self) -> Result<(), anyhow::Error> {
-        self.nets = sys_metrics::network::get_ionets()?;
+        self.nets = self.source.counters()?;
         Ok(())
     }
 
-    pub fn find_by_interface(&self, interface: String) -> Option<IoNet> {
+    // the interfaces counters were found for, by name
+    pub fn interfaces(&self) -> Vec<String> {
+        let mut names = self
+            .nets
+            .iter()
+            .map(|n| n.interface.clone())
+            .collect::<Vec<String>>();
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    // the rate is worked out from the sum of a network's interfaces, so when they change the
+    // samples taken before can't be compared with the ones after
+    pub fn forget_rate(&mut self, network_id: &str) {
+        self.last_usage.remove(network_id);
+    }
+
+    pub fn find_by_interface(&self, interface: String) -> Option<Counters> {
         for net in &self.nets {
             if interface == net.interface {
                 return Some(net.clone());
@@ -45,62 +224,66 @@ impl Nets {
         None
     }
 
-    pub fn store_usage(&mut self, interface: String) {
-        if let Some(net) = self.find_by_interface(interface.clone()) {
-            if let Some(v) = self.last_usage.get_mut(&interface) {
-                v.push((net.rx_bytes as u128, net.tx_bytes as u128, Instant::now()));
-                if v.len() > 2 {
-                    let v2 = v
-                        .iter()
-                        .skip(v.len() - 3)
-                        .map(|k| *k)
-                        .collect::<Vec<(u128, u128, Instant)>>();
-                    self.last_usage.insert(net.interface.clone(), v2);
-                }
-            } else {
-                self.last_usage.insert(
-                    net.interface.clone(),
-                    vec![(net.rx_bytes as u128, net.tx_bytes as u128, Instant::now())],
-                );
+    // a network can be backed by more than one OS interface (macOS feth pairs, for example);
+    // their counters are summed.
+    pub fn store_usage(&mut self, network_id: String, interfaces: &[String]) {
+        let nets = interfaces
+            .iter()
+            .filter_map(|i| self.find_by_interface(i.clone()))
+            .collect::<Vec<Counters>>();
+
+        if nets.is_empty() {
+            return;
+        }
+
+        self.accumulate(&network_id, &nets);
+
+        let rx = nets.iter().map(|n| n.rx_bytes as u128).sum::<u128>();
+        let tx = nets.iter().map(|n| n.tx_bytes as u128).sum::<u128>();
+
+        if let Some(v) = self.last_usage.get_mut(&network_id) {
+            v.push((rx, tx, Instant::now()));
+            if v.len() > 2 {
+                let v2 = v
+                    .iter()
+                    .skip(v.len() - 3)
+                    .copied()
+                    .collect::<Vec<(u128, u128, Instant)>>();
+                self.last_usage.insert(network_id, v2);
             }
+        } else {
+            self.last_usage
+                .insert(network_id, vec![(rx, tx, Instant::now())]);
         }
     }
 
-    pub fn get_usage(&mut self, interface: String) -> Option<String> {
-        if let Some(s) = self.last_usage.get_mut(&interface) {
-            if s.len() < 2 {
-                return None;
-            } else {
-                let len = s.len();
-                let mut i = s.iter();
-                let first = i.nth(len - 2).unwrap();
-                let mut i = s.iter();
-                let second = i.nth(len - 1).unwrap();
-
-                let elapsed = second.2.duration_since(first.2).as_millis() as f64 / 1000 as f64;
-                let mut rx_bytes: f64 = second.0 as f64 - first.0 as f64;
-                let mut tx_bytes: f64 = second.1 as f64 - first.1 as f64;
-
-                if elapsed > 1.0 {
-                    rx_bytes /= elapsed;
-                    tx_bytes /= elapsed;
-                } else {
-                    rx_bytes *= 1.0 + (1.0 - elapsed);
-                    tx_bytes *= 1.0 + (1.0 - elapsed);
-                }
-
-                Some(format!(
-                    "Rx: {}/s | Tx: {}/s",
-                    byte_unit::Byte::from_bytes(rx_bytes as u128)
-                        .get_appropriate_unit(true)
-                        .to_string(),
-                    byte_unit::Byte::from_bytes(tx_bytes as u128)
-                        .get_appropriate_unit(true)
-                        .to_string(),
-                ))
-            }
+    // bytes per second over the last two samples
+    pub fn rate(&self, network_id: &str) -> Option<(f64, f64)> {
+        let s = self.last_usage.get(network_id)?;
+        if s.len() < 2 {
+            return None;
+        }
+
+        let first = s[s.len() - 2];
+        let second = s[s.len() - 1];
+
+        let elapsed = second.2.duration_since(first.2).as_millis() as f64 / 1000_f64;
+        let mut rx_bytes: f64 = second.0 as f64 - first.0 as f64;
+        let mut tx_bytes: f64 = second.1 as f64 - first.1 as f64;
+
+        if elapsed > 1.0 {
+            rx_bytes /= elapsed;
+            tx_bytes /= elapsed;
         } else {
-            None
+            rx_bytes *= 1.0 + (1.0 - elapsed);
+            tx_bytes *= 1.0 + (1.0 - elapsed);
         }
+
+        Some((rx_bytes, tx_bytes))
+    }
+
+    pub fn get_usage(&self, network_id: String) -> Option<String> {
+        let (rx_bytes, tx_bytes) = self.rate(&network_id)?;
+        Some(format_rate(rx_bytes, tx_bytes))
     }
 }
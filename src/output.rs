@@ -0,0 +1,271 @@
+use std::{collections::HashMap, str::FromStr};
+
+use serde::Serialize;
+
+#[cfg(feature = "central")]
+use crate::{api::central::types::Member, history::is_online};
+use crate::{api::one::types::Network, client, config::Settings, exit};
+
+// how the headless subcommands print what they find: lined up for people, or as JSON or CSV for
+// scripts. the field names are the same in all three and are kept stable.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Table,
+    Json,
+    Csv,
+}
+
+pub const FORMATS: [&str; 3] = ["table", "json", "csv"];
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(Format::Table),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(anyhow::anyhow!(
+                "unknown output {}, expected one of {}",
+                s,
+                FORMATS.join(", ")
+            )),
+        }
+    }
+}
+
+// a cell of a table or CSV line; lists are separated by spaces and what's missing is left empty
+fn cell(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => String::new(),
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Array(values) => values.iter().map(cell).collect::<Vec<_>>().join(" "),
+        value => value.to_string(),
+    }
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+// prints `records` with `fields` as the columns, in that order. JSON is an array of objects
+// with every field, whether or not it has a value.
+pub fn print<T: Serialize>(
+    format: Format,
+    fields: &[&str],
+    records: &[T],
+) -> Result<(), anyhow::Error> {
+    let records = records
+        .iter()
+        .map(serde_json::to_value)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if format == Format::Json {
+        println!("{}", serde_json::to_string_pretty(&records)?);
+        return Ok(());
+    }
+
+    let rows = records
+        .iter()
+        .map(|record| {
+            fields
+                .iter()
+                .map(|field| cell(&record[field]))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    if format == Format::Csv {
+        for row in std::iter::once(fields.iter().map(|f| f.to_string()).collect()).chain(rows) {
+            println!(
+                "{}",
+                row.iter()
+                    .map(|s| csv_field(s))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            );
+        }
+        return Ok(());
+    }
+
+    let headers = fields.iter().map(|f| f.to_uppercase()).collect::<Vec<_>>();
+    let widths = headers
+        .iter()
+        .enumerate()
+        .map(|(i, header)| {
+            rows.iter()
+                .map(|row| row[i].chars().count())
+                .chain([header.len()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>();
+
+    for row in std::iter::once(headers).chain(rows) {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(s, width)| format!("{:<width$}", s, width = width))
+            .collect::<Vec<_>>()
+            .join("  ");
+        println!("{}", line.trim_end());
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NodeStatus {
+    pub address: Option<String>,
+    pub online: Option<bool>,
+    pub version: Option<String>,
+    pub tcp_fallback: Option<bool>,
+    pub networks: usize,
+}
+
+pub const STATUS_FIELDS: [&str; 5] = ["address", "online", "version", "tcp_fallback", "networks"];
+
+pub fn status() -> Result<NodeStatus, anyhow::Error> {
+    let status = client::sync_get_status().map_err(exit::daemon)?;
+    Ok(NodeStatus {
+        address: status.address,
+        online: status.online,
+        version: status.version,
+        tcp_fallback: status.tcp_fallback_active,
+        networks: client::sync_get_networks().map_err(exit::daemon)?.len(),
+    })
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JoinedNetwork {
+    pub id: Option<String>,
+    pub name: Option<String>,
+    pub status: Option<String>,
+    #[serde(rename = "type")]
+    pub type_: Option<String>,
+    pub addresses: Vec<String>,
+    pub interface: Option<String>,
+    pub mac: Option<String>,
+}
+
+pub const NETWORK_FIELDS: [&str; 7] = [
+    "id",
+    "name",
+    "status",
+    "type",
+    "addresses",
+    "interface",
+    "mac",
+];
+
+impl From<Network> for JoinedNetwork {
+    fn from(network: Network) -> Self {
+        let network = network.subtype_1;
+        Self {
+            id: network.id,
+            name: network.name,
+            status: network.status,
+            type_: network.type_,
+            addresses: network.assigned_addresses,
+            interface: network.port_device_name,
+            mac: network.mac,
+        }
+    }
+}
+
+// the networks the local node has joined
+pub fn networks() -> Result<Vec<JoinedNetwork>, anyhow::Error> {
+    let mut networks = client::sync_get_networks()
+        .map_err(exit::daemon)?
+        .into_iter()
+        .map(JoinedNetwork::from)
+        .collect::<Vec<_>>();
+    networks.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(networks)
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NetworkMember {
+    pub network: String,
+    pub id: Option<String>,
+    pub name: Option<String>,
+    pub authorized: bool,
+    pub online: Option<bool>,
+    // seconds since the epoch
+    pub last_online: Option<u64>,
+    pub addresses: Vec<String>,
+}
+
+pub const MEMBER_FIELDS: [&str; 7] = [
+    "network",
+    "id",
+    "name",
+    "authorized",
+    "online",
+    "last_online",
+    "addresses",
+];
+
+#[cfg(feature = "central")]
+fn member(network: &str, member: Member, now: u64) -> NetworkMember {
+    let config = member.config.as_ref();
+    NetworkMember {
+        network: network.to_string(),
+        online: is_online(&member, now),
+        last_online: member.last_online.map(|at| at as u64 / 1000),
+        id: member.node_id,
+        name: member.name.filter(|n| !n.is_empty()),
+        authorized: config.and_then(|c| c.authorized).unwrap_or_default(),
+        addresses: config
+            .and_then(|c| c.ip_assignments.clone())
+            .unwrap_or_default(),
+    }
+}
+
+// networks can be given by ID or by the name the local node knows them by
+pub fn network_id(network: &str) -> String {
+    let names = client::sync_get_networks()
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|n| Some((n.subtype_1.name?, n.subtype_1.id?)))
+        .collect::<HashMap<_, _>>();
+    names.get(network).cloned().unwrap_or(network.to_string())
+}
+
+// a network's members, from Central or its own controller
+#[cfg(feature = "central")]
+pub fn members(settings: &Settings, id: &str) -> Result<Vec<NetworkMember>, anyhow::Error> {
+    let client = settings
+        .central_client_for_id(id.to_string())
+        .map_err(exit::central_auth)?;
+    let now = crate::history::now();
+    let mut members = client::sync_get_members(client, id.to_string())?
+        .into_iter()
+        .map(|m| member(id, m, now))
+        .collect::<Vec<_>>();
+    members.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(members)
+}
+
+#[cfg(not(feature = "central"))]
+pub fn members(_: &Settings, _: &str) -> Result<Vec<NetworkMember>, anyhow::Error> {
+    Err(anyhow::anyhow!("ztui was built without Central support"))
+}
+
+// the members of every network there's an API key for, and the networks they couldn't be had for
+pub fn export(settings: &Settings) -> (Vec<NetworkMember>, Vec<(String, anyhow::Error)>) {
+    let mut all = Vec::new();
+    let mut failed = Vec::new();
+    for id in settings.networks_with_keys() {
+        match members(settings, &id) {
+            Ok(members) => all.extend(members),
+            Err(e) => failed.push((id, e)),
+        }
+    }
+    (all, failed)
+}
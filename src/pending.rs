@@ -0,0 +1,139 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "central")]
+use crate::history::now;
+#[cfg(feature = "central")]
+use crate::{api::central::Client, client};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Op {
+    Authorize,
+    Deauthorize,
+    Rename(String),
+    Delete,
+}
+
+impl std::fmt::Display for Op {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Op::Authorize => f.write_str("authorize"),
+            Op::Deauthorize => f.write_str("deauthorize"),
+            Op::Rename(name) => write!(f, "rename to `{}`", name),
+            Op::Delete => f.write_str("delete"),
+        }
+    }
+}
+
+#[cfg(feature = "central")]
+impl Op {
+    pub fn apply(&self, client: Client, network: &str, member: &str) -> Result<(), anyhow::Error> {
+        let (network, member) = (network.to_string(), member.to_string());
+
+        match self {
+            Op::Authorize => client::sync_authorize_member(client, network, member),
+            Op::Deauthorize => client::sync_deauthorize_member(client, network, member),
+            Op::Rename(name) => {
+                client::sync_update_member_name(client, network, member, name.clone())
+            }
+            Op::Delete => client::sync_delete_member(client, network, member),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Change {
+    pub id: u64,
+    pub at: u64,
+    pub network: String,
+    pub member: String,
+    pub op: Op,
+    // why Central refused it when it was replayed. refused changes wait to be retried or
+    // discarded rather than being sent again on every refresh.
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+// member changes made while Central couldn't be reached, kept in order until they can be sent
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Pending {
+    next: u64,
+    changes: Vec<Change>,
+}
+
+impl Pending {
+    pub fn from_dir(filename: PathBuf) -> Result<Self, anyhow::Error> {
+        let pending_file = std::fs::read_to_string(filename.join("pending.json"))?;
+        Ok(serde_json::from_str(&pending_file)?)
+    }
+
+    pub fn to_file(&self, filename: PathBuf) -> Result<(), anyhow::Error> {
+        if crate::instance::read_only() {
+            return Ok(());
+        }
+
+        Ok(std::fs::write(
+            filename.join("pending.json"),
+            serde_json::to_string(self)?,
+        )?)
+    }
+
+    #[cfg(feature = "central")]
+    pub fn push(&mut self, network: String, member: String, op: Op) {
+        self.next += 1;
+        self.changes.push(Change {
+            id: self.next,
+            at: now(),
+            network,
+            member,
+            op,
+            error: None,
+        });
+    }
+
+    pub fn changes(&self) -> &[Change] {
+        &self.changes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    // the changes to send next, oldest first
+    #[cfg(feature = "central")]
+    pub fn ready(&self) -> Vec<Change> {
+        self.changes
+            .iter()
+            .filter(|c| c.error.is_none())
+            .cloned()
+            .collect()
+    }
+
+    // a sent change is done with; a refused one stays, with the reason
+    #[cfg(feature = "central")]
+    pub fn finish(&mut self, id: u64, res: Result<(), String>) {
+        match res {
+            Ok(()) => self.discard(id),
+            Err(e) => {
+                if let Some(change) = self.changes.iter_mut().find(|c| c.id == id) {
+                    change.error = Some(e);
+                }
+            }
+        }
+    }
+
+    pub fn retry(&mut self, id: u64) {
+        if let Some(change) = self.changes.iter_mut().find(|c| c.id == id) {
+            change.error = None;
+        }
+    }
+
+    pub fn discard(&mut self, id: u64) {
+        self.changes.retain(|c| c.id != id);
+    }
+
+    pub fn clear(&mut self) {
+        self.changes.clear();
+    }
+}
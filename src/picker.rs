@@ -0,0 +1,100 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use serde::{Deserialize, Serialize};
+use tui::widgets::TableState;
+
+// what a picked item is for
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Purpose {
+    JoinNetwork,
+    // the interface a network's traffic is counted on, by network id
+    Interface(String),
+    // a flag, and whether it's to be on, for every connected network
+    FlagAll,
+    // a join set from config.json
+    JoinSet,
+}
+
+#[derive(Debug, Clone)]
+pub struct Item {
+    pub value: String,
+    pub label: String,
+}
+
+// a list narrowed down by typing, like fzf: the letters typed have to appear in order, and the
+// closer together they are the higher the item is listed
+#[derive(Debug, Clone)]
+pub struct Picker {
+    pub items: Vec<Item>,
+    pub query: String,
+    pub state: TableState,
+}
+
+// how spread out the query is across `label`, or None if it isn't there at all
+fn score(query: &str, label: &str) -> Option<usize> {
+    let label = label.to_lowercase().chars().collect::<Vec<char>>();
+    let mut pos = 0;
+    let mut start = None;
+
+    for q in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = label[pos..].iter().position(|&c| c == q)? + pos;
+        start.get_or_insert(found);
+        pos = found + 1;
+    }
+
+    Some(pos - start.unwrap_or_default())
+}
+
+impl Picker {
+    pub fn new(items: Vec<Item>) -> Self {
+        let mut state = TableState::default();
+        state.select(Some(0));
+
+        Self {
+            items,
+            query: String::new(),
+            state,
+        }
+    }
+
+    // best match first
+    pub fn matches(&self) -> Vec<&Item> {
+        let mut scored = self
+            .items
+            .iter()
+            .filter_map(|item| Some((score(&self.query, &item.label)?, item)))
+            .collect::<Vec<(usize, &Item)>>();
+
+        // stable, so equally good matches stay in the order they were given
+        scored.sort_by_key(|(score, _)| *score);
+        scored.into_iter().map(|(_, item)| item).collect()
+    }
+
+    pub fn picked(&self) -> Option<&Item> {
+        self.matches().get(self.state.selected()?).copied()
+    }
+
+    // anything but Enter and Esc, which pick and close the dialog the picker is in
+    pub fn key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Up => {
+                let pos = self.state.selected().unwrap_or_default();
+                self.state.select(Some(pos.saturating_sub(1)));
+            }
+            KeyCode::Down => {
+                let pos = self.state.selected().unwrap_or_default() + 1;
+                if pos < self.matches().len() {
+                    self.state.select(Some(pos));
+                }
+            }
+            KeyCode::Char(c) => {
+                self.query.push(c);
+                self.state.select(Some(0));
+            }
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.state.select(Some(0));
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,269 @@
+use std::{
+    io::ErrorKind,
+    net::{IpAddr, SocketAddr, TcpStream, UdpSocket},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use socket2::{Domain, Protocol, Socket, Type};
+
+const PING_INTERVAL: Duration = Duration::from_secs(1);
+const PING_TIMEOUT: Duration = Duration::from_secs(1);
+// port used when we can't open an ICMP socket. a refused connection still means the host answered.
+const TCP_PING_PORT: u16 = 22;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PingMethod {
+    Icmp,
+    Tcp,
+}
+
+impl std::fmt::Display for PingMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            PingMethod::Icmp => "ICMP",
+            PingMethod::Tcp => "TCP",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PingStats {
+    pub address: String,
+    pub method: Option<PingMethod>,
+    pub sent: u64,
+    pub received: u64,
+    pub last_rtt: Option<Duration>,
+    pub min_rtt: Option<Duration>,
+    pub max_rtt: Option<Duration>,
+    total_rtt: Duration,
+}
+
+impl PingStats {
+    fn new(address: String) -> Self {
+        Self {
+            address,
+            ..Default::default()
+        }
+    }
+
+    fn record(&mut self, rtt: Option<Duration>) {
+        self.sent += 1;
+        self.last_rtt = rtt;
+
+        if let Some(rtt) = rtt {
+            self.received += 1;
+            self.total_rtt += rtt;
+            self.min_rtt = Some(self.min_rtt.map_or(rtt, |m| m.min(rtt)));
+            self.max_rtt = Some(self.max_rtt.map_or(rtt, |m| m.max(rtt)));
+        }
+    }
+
+    pub fn loss(&self) -> f64 {
+        if self.sent == 0 {
+            return 0.0;
+        }
+
+        (self.sent - self.received) as f64 / self.sent as f64 * 100.0
+    }
+
+    pub fn avg_rtt(&self) -> Option<Duration> {
+        if self.received == 0 {
+            return None;
+        }
+
+        Some(self.total_rtt / self.received as u32)
+    }
+}
+
+enum Prober {
+    Icmp(UdpSocket),
+    Tcp,
+}
+
+impl Prober {
+    // unprivileged ICMP sockets are available on macOS and most linux distributions
+    // (net.ipv4.ping_group_range); everything else falls back to TCP connect.
+    fn new(addr: IpAddr) -> Self {
+        let (domain, protocol) = match addr {
+            IpAddr::V4(_) => (Domain::IPV4, Protocol::ICMPV4),
+            IpAddr::V6(_) => (Domain::IPV6, Protocol::ICMPV6),
+        };
+
+        match Socket::new(domain, Type::DGRAM, Some(protocol)) {
+            Ok(socket) => {
+                let socket: UdpSocket = socket.into();
+                if socket.set_read_timeout(Some(PING_TIMEOUT)).is_ok() {
+                    Prober::Icmp(socket)
+                } else {
+                    Prober::Tcp
+                }
+            }
+            Err(_) => Prober::Tcp,
+        }
+    }
+
+    fn method(&self) -> PingMethod {
+        match self {
+            Prober::Icmp(_) => PingMethod::Icmp,
+            Prober::Tcp => PingMethod::Tcp,
+        }
+    }
+
+    fn probe(&self, addr: IpAddr, seq: u16) -> Option<Duration> {
+        match self {
+            Prober::Icmp(socket) => icmp_probe(socket, addr, seq),
+            Prober::Tcp => tcp_probe(addr),
+        }
+    }
+}
+
+fn checksum(packet: &[u8]) -> u16 {
+    let mut sum: u32 = 0;
+
+    for chunk in packet.chunks(2) {
+        let word = if chunk.len() == 2 {
+            u16::from_be_bytes([chunk[0], chunk[1]])
+        } else {
+            u16::from_be_bytes([chunk[0], 0])
+        };
+        sum += word as u32;
+    }
+
+    while sum >> 16 != 0 {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+
+    !(sum as u16)
+}
+
+fn icmp_probe(socket: &UdpSocket, addr: IpAddr, seq: u16) -> Option<Duration> {
+    let (request, reply) = match addr {
+        IpAddr::V4(_) => (8, 0),
+        IpAddr::V6(_) => (128, 129),
+    };
+
+    let mut packet = vec![request, 0, 0, 0];
+    packet.extend_from_slice(&(std::process::id() as u16).to_be_bytes());
+    packet.extend_from_slice(&seq.to_be_bytes());
+    packet.extend_from_slice(b"ztui-ping");
+
+    // the kernel fills in the checksum for ICMPv6
+    if addr.is_ipv4() {
+        let sum = checksum(&packet);
+        packet[2..4].copy_from_slice(&sum.to_be_bytes());
+    }
+
+    let start = Instant::now();
+    socket.send_to(&packet, SocketAddr::new(addr, 0)).ok()?;
+
+    let mut buf = [0u8; 1500];
+
+    while start.elapsed() < PING_TIMEOUT {
+        let (len, from) = match socket.recv_from(&mut buf) {
+            Ok(res) => res,
+            Err(_) => return None,
+        };
+
+        if from.ip() != addr {
+            continue;
+        }
+
+        // macOS hands us the IPv4 header on datagram ICMP sockets, linux does not.
+        let mut data = &buf[..len];
+        if addr.is_ipv4() && !data.is_empty() && data[0] >> 4 == 4 {
+            let header_len = (data[0] & 0x0f) as usize * 4;
+            if data.len() < header_len {
+                continue;
+            }
+            data = &data[header_len..];
+        }
+
+        if data.len() >= 8 && data[0] == reply && u16::from_be_bytes([data[6], data[7]]) == seq {
+            return Some(start.elapsed());
+        }
+    }
+
+    None
+}
+
+fn tcp_probe(addr: IpAddr) -> Option<Duration> {
+    let start = Instant::now();
+
+    match TcpStream::connect_timeout(&SocketAddr::new(addr, TCP_PING_PORT), PING_TIMEOUT) {
+        Ok(_) => Some(start.elapsed()),
+        Err(e) if e.kind() == ErrorKind::ConnectionRefused => Some(start.elapsed()),
+        Err(_) => None,
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Pinger {
+    stats: Arc<Mutex<Vec<PingStats>>>,
+    stop: Arc<AtomicBool>,
+}
+
+impl Pinger {
+    pub fn start(addresses: Vec<String>) -> Self {
+        let stats = Arc::new(Mutex::new(
+            addresses
+                .iter()
+                .map(|a| PingStats::new(a.clone()))
+                .collect::<Vec<PingStats>>(),
+        ));
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let pinger = Self {
+            stats: stats.clone(),
+            stop: stop.clone(),
+        };
+
+        std::thread::spawn(move || {
+            // member IPs come out of central with their prefix length on some networks
+            let targets = addresses
+                .iter()
+                .map(|a| {
+                    a.split('/')
+                        .next()
+                        .unwrap_or_default()
+                        .parse::<IpAddr>()
+                        .ok()
+                        .map(|ip| (ip, Prober::new(ip)))
+                })
+                .collect::<Vec<Option<(IpAddr, Prober)>>>();
+
+            let mut seq: u16 = 0;
+
+            while !stop.load(Ordering::Relaxed) {
+                let tick = Instant::now();
+                seq = seq.wrapping_add(1);
+
+                for (i, target) in targets.iter().enumerate() {
+                    if let Some((ip, prober)) = target {
+                        let rtt = prober.probe(*ip, seq);
+                        let mut lock = stats.lock().unwrap();
+                        lock[i].method = Some(prober.method());
+                        lock[i].record(rtt);
+                    }
+                }
+
+                if let Some(remaining) = PING_INTERVAL.checked_sub(tick.elapsed()) {
+                    std::thread::sleep(remaining);
+                }
+            }
+        });
+
+        pinger
+    }
+
+    pub fn stats(&self) -> Vec<PingStats> {
+        self.stats.lock().unwrap().clone()
+    }
+
+    pub fn stop(&self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,129 @@
+use std::{
+    collections::hash_map::RandomState,
+    hash::BuildHasher,
+    net::{Ipv4Addr, Ipv6Addr},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+static PRIVACY: AtomicBool = AtomicBool::new(false);
+
+lazy_static::lazy_static! {
+    // pseudonyms are the same for the same ID for as long as ztui runs, and different each run
+    static ref SALT: RandomState = RandomState::new();
+}
+
+pub fn set(privacy: bool) {
+    PRIVACY.store(privacy, Ordering::SeqCst);
+}
+
+pub fn enabled() -> bool {
+    PRIVACY.load(Ordering::SeqCst)
+}
+
+pub fn toggle() -> bool {
+    !PRIVACY.fetch_xor(true, Ordering::SeqCst)
+}
+
+#[derive(PartialEq)]
+enum Kind {
+    Id,
+    Ipv4,
+    Ipv6,
+}
+
+fn kind(token: &str) -> Option<Kind> {
+    let hex = token.chars().all(|c| c.is_ascii_hexdigit());
+    if hex && (token.len() == 16 || token.len() == 10) {
+        Some(Kind::Id)
+    } else if token.contains('.') && token.parse::<Ipv4Addr>().is_ok() {
+        Some(Kind::Ipv4)
+    } else if token.contains(':') && token.parse::<Ipv6Addr>().is_ok() {
+        Some(Kind::Ipv6)
+    } else {
+        None
+    }
+}
+
+// the same shape as `token`, so nothing on screen moves: hex digits for hex digits, decimal for
+// decimal, separators kept
+fn pseudonym(token: &str, kind: Kind) -> String {
+    let mut state = SALT.hash_one(token) | 1;
+
+    token
+        .chars()
+        .map(|c| {
+            if !c.is_ascii_alphanumeric() {
+                return c;
+            }
+
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let radix = if kind == Kind::Ipv4 { 10 } else { 16 };
+            std::char::from_digit((state % radix) as u32, radix as u32).unwrap()
+        })
+        .collect()
+}
+
+// a token that isn't one thing can still hold them: an address and its port, an identity's
+// `id:0:key`, a file named for a network
+fn parts(token: &str) -> String {
+    token
+        .split(':')
+        .map(|part| match kind(part) {
+            Some(kind) => pseudonym(part, kind),
+            None => part
+                .split('.')
+                .map(|part| match kind(part) {
+                    Some(kind) => pseudonym(part, kind),
+                    None => part.to_string(),
+                })
+                .collect::<Vec<String>>()
+                .join("."),
+        })
+        .collect::<Vec<String>>()
+        .join(":")
+}
+
+// the text with any network IDs, node IDs and addresses in it masked while privacy mode is on.
+// called where they're formatted, before anything on the way to the screen or the terminal can cut
+// them short or send them on.
+pub fn redact(text: &str) -> String {
+    if !enabled() {
+        return text.to_string();
+    }
+
+    let chars = text.chars().collect::<Vec<char>>();
+    let token_char = |c: &char| c.is_ascii_alphanumeric() || matches!(c, '.' | ':');
+    let mut out = String::with_capacity(text.len());
+    let mut start = 0;
+    while start < chars.len() {
+        if !token_char(&chars[start]) {
+            out.push(chars[start]);
+            start += 1;
+            continue;
+        }
+
+        let mut end = start;
+        while end < chars.len() && token_char(&chars[end]) {
+            end += 1;
+        }
+
+        // a sentence can end on an address, and a label can end in a colon
+        let mut token = chars[start..end].iter().collect::<String>();
+        let mut rest = String::new();
+        if kind(&token).is_none() && token.ends_with(['.', ':']) {
+            rest.extend(token.pop());
+        }
+
+        match kind(&token) {
+            Some(kind) => out.push_str(&pseudonym(&token, kind)),
+            None => out.push_str(&parts(&token)),
+        }
+        out.push_str(&rest);
+
+        start = end;
+    }
+
+    out
+}
@@ -0,0 +1,543 @@
+use crossterm::event::KeyEvent;
+
+use crate::app::Page;
+
+// everything that can be done from a page with a key. what each one does lives in the app; the
+// keys, help and menu all come from the bindings below.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Up,
+    Down,
+    Activate,
+    Back,
+    Forward,
+    GoToMember,
+    Privacy,
+    BugReport,
+    Quit,
+    Help,
+    RecordMacro,
+    RunMacro,
+    // networks
+    Forget,
+    Join,
+    Leave,
+    JoinByAddress,
+    JoinFromCentral,
+    JoinFromClipboard,
+    RecentlyLeft,
+    NewNetwork,
+    ShowConfig,
+    ToggleDisconnected,
+    ShowMembers,
+    EditRules,
+    EditRulesFromSnippet,
+    Capture,
+    Routes,
+    Traffic,
+    Interface,
+    Flags,
+    FullTunnel,
+    FlagAll,
+    ApplySet,
+    AutoRejoin,
+    ToggleSplit,
+    Identity,
+    Doctor,
+    Log,
+    PendingChanges,
+    // members
+    Rename,
+    Mark,
+    BatchRename,
+    SuggestName,
+    Legend,
+    Changelog,
+    Authorize,
+    AuthorizeById,
+    Deauthorize,
+    Delete,
+    Ping,
+    History,
+    Group,
+    Sso,
+    SsoExempt,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    Networks,
+    Members,
+    Both,
+}
+
+impl Scope {
+    fn includes(&self, page: &Page) -> bool {
+        matches!(
+            (self, page),
+            (Scope::Both, _)
+                | (Scope::Networks, Page::Networks)
+                | (Scope::Members, Page::Network(_))
+        )
+    }
+}
+
+pub struct Binding {
+    // key names, as written by crate::keys
+    pub keys: &'static [&'static str],
+    pub action: Action,
+    pub name: &'static str,
+    pub help: &'static str,
+    pub scope: Scope,
+    // changes something that can't simply be changed back
+    pub destructive: bool,
+    // acts on the selected row, so it's offered in the action menu
+    pub row: bool,
+    // changes the network on Central, so it can't be used with a read-only API key
+    pub writes: bool,
+}
+
+impl Binding {
+    // the help, in the configured locale
+    pub fn describe(&self) -> String {
+        let page = match self.scope {
+            Networks => "networks",
+            Members => "members",
+            Both => "all",
+        };
+        crate::i18n::lookup(&format!("help.{}.{}", page, self.name))
+            .unwrap_or_else(|| self.help.to_string())
+    }
+}
+
+const fn bind(
+    keys: &'static [&'static str],
+    action: Action,
+    name: &'static str,
+    help: &'static str,
+    scope: Scope,
+) -> Binding {
+    Binding {
+        keys,
+        action,
+        name,
+        help,
+        scope,
+        destructive: false,
+        row: false,
+        writes: false,
+    }
+}
+
+const fn row(mut binding: Binding) -> Binding {
+    binding.row = true;
+    binding
+}
+
+const fn destructive(mut binding: Binding) -> Binding {
+    binding.destructive = true;
+    binding
+}
+
+const fn writes(mut binding: Binding) -> Binding {
+    binding.writes = true;
+    binding
+}
+
+use Action::*;
+use Scope::*;
+
+// in the order they're listed in the help
+pub const BINDINGS: &[Binding] = &[
+    bind(&["Up"], Up, "up", "move up the list", Both),
+    bind(&["Down"], Down, "down", "move down the list", Both),
+    bind(
+        &["Enter"],
+        Activate,
+        "menu",
+        "menu of everything that can be done to the network",
+        Networks,
+    ),
+    bind(&["Esc"], Back, "back", "back out of something", Networks),
+    bind(
+        &["q", "Esc"],
+        Back,
+        "back",
+        "quit to networks screen",
+        Members,
+    ),
+    bind(
+        &["C-o"],
+        Forward,
+        "forward",
+        "go forward again after backing out",
+        Both,
+    ),
+    bind(
+        &["G"],
+        GoToMember,
+        "go-to-member",
+        "go to a member by node ID, or its start, in any network with an API key",
+        Both,
+    ),
+    bind(
+        &["z"],
+        Privacy,
+        "privacy",
+        "mask network IDs, node IDs and addresses, for screenshots and screen sharing",
+        Both,
+    ),
+    bind(
+        &["C-b"],
+        BugReport,
+        "bug-report",
+        "write a bug report with scrubbed settings, recent errors and the log",
+        Both,
+    ),
+    row(destructive(bind(
+        &["d"],
+        Forget,
+        "forget",
+        "Delete a list member",
+        Networks,
+    ))),
+    bind(&["q"], Quit, "quit", "Quit", Networks),
+    row(bind(
+        &["j"],
+        Join,
+        "join",
+        "Join a bookmarked network",
+        Networks,
+    )),
+    row(destructive(bind(
+        &["l"],
+        Leave,
+        "leave",
+        "Leave a bookmarked network",
+        Networks,
+    ))),
+    bind(
+        &["J"],
+        JoinByAddress,
+        "join-address",
+        "Join a network by address",
+        Networks,
+    ),
+    bind(
+        &["C"],
+        JoinFromCentral,
+        "join-central",
+        "Join a network picked from those on Central",
+        Networks,
+    ),
+    bind(
+        &["V"],
+        JoinFromClipboard,
+        "join-clipboard",
+        "Join the network whose ID or invite URL is on the clipboard",
+        Networks,
+    ),
+    bind(
+        &["U"],
+        RecentlyLeft,
+        "recently-left",
+        "Networks recently left or forgotten, to rejoin as they were",
+        Networks,
+    ),
+    bind(
+        &["n"],
+        NewNetwork,
+        "new-network",
+        "create a network on Central, with a subnet",
+        Networks,
+    ),
+    row(bind(
+        &["c"],
+        ShowConfig,
+        "config",
+        "review network settings",
+        Networks,
+    )),
+    bind(
+        &["t"],
+        ToggleDisconnected,
+        "toggle-disconnected",
+        "toggle disconnected in list",
+        Networks,
+    ),
+    row(bind(
+        &["s"],
+        ShowMembers,
+        "members",
+        "show network members (requires API key)",
+        Networks,
+    )),
+    writes(row(bind(
+        &["e"],
+        EditRules,
+        "edit-rules",
+        "edit network rules (requires API key)",
+        Networks,
+    ))),
+    writes(row(bind(
+        &["E"],
+        EditRulesFromSnippet,
+        "edit-rules-snippet",
+        "edit network rules starting from a snippet",
+        Networks,
+    ))),
+    row(bind(
+        &["p"],
+        Capture,
+        "capture",
+        "capture packets on the network interface",
+        Networks,
+    )),
+    row(bind(
+        &["R"],
+        Routes,
+        "routes",
+        "verify managed routes are installed",
+        Networks,
+    )),
+    row(bind(
+        &["u"],
+        Traffic,
+        "traffic",
+        "traffic totals per day",
+        Networks,
+    )),
+    row(bind(
+        &["I"],
+        Interface,
+        "interface",
+        "pick the interface traffic is counted on",
+        Networks,
+    )),
+    row(bind(
+        &["f"],
+        Flags,
+        "flags",
+        "toggle what the network may change (DNS, routes)",
+        Networks,
+    )),
+    row(bind(
+        &["T"],
+        FullTunnel,
+        "full-tunnel",
+        "send all traffic through the network (allowDefault), or stop",
+        Networks,
+    )),
+    bind(
+        &["F"],
+        FlagAll,
+        "flag-all",
+        "set a flag the same way on every connected network, after a preview",
+        Networks,
+    ),
+    bind(
+        &["B"],
+        ApplySet,
+        "apply-set",
+        "join a set of networks from config.json with its flags, after a preview",
+        Networks,
+    ),
+    row(bind(
+        &["A"],
+        AutoRejoin,
+        "auto-rejoin",
+        "toggle joining the network again whenever ZeroTier drops it",
+        Networks,
+    )),
+    bind(
+        &["v"],
+        ToggleSplit,
+        "split",
+        "toggle members of the selected network alongside",
+        Networks,
+    ),
+    bind(
+        &["i"],
+        Identity,
+        "identity",
+        "node identity: show, verify and back up",
+        Networks,
+    ),
+    bind(
+        &["D"],
+        Doctor,
+        "doctor",
+        "diagnose connectivity problems",
+        Networks,
+    ),
+    bind(
+        &["L"],
+        Log,
+        "action-log",
+        "log of changes made (or, with --dry-run, not made)",
+        Networks,
+    ),
+    bind(
+        &["P"],
+        PendingChanges,
+        "pending",
+        "member changes queued while Central was unreachable",
+        Networks,
+    ),
+    writes(row(bind(
+        &["r", "F2"],
+        Rename,
+        "rename",
+        "Rename a Member in place",
+        Members,
+    ))),
+    row(bind(
+        &["Space"],
+        Mark,
+        "mark",
+        "Mark or unmark the member under the cursor",
+        Members,
+    )),
+    writes(bind(
+        &["R"],
+        BatchRename,
+        "batch-rename",
+        "Rename marked members from a template, with a preview",
+        Members,
+    )),
+    writes(row(bind(
+        &["N"],
+        SuggestName,
+        "suggest-name",
+        "Suggest a name for an unnamed member from reverse DNS",
+        Members,
+    ))),
+    bind(
+        &["L"],
+        Legend,
+        "legend",
+        "Legend of capability and tag names from the rules",
+        Members,
+    ),
+    bind(
+        &["C"],
+        Changelog,
+        "changelog",
+        "Changelog of members added, removed, authorized and renamed",
+        Members,
+    ),
+    writes(row(bind(
+        &["a"],
+        Authorize,
+        "authorize",
+        "Authorize a deauthorized member, or all marked members",
+        Members,
+    ))),
+    writes(bind(
+        &["A"],
+        AuthorizeById,
+        "authorize-id",
+        "Add a member by ID, with a name, authorized or not",
+        Members,
+    )),
+    writes(row(destructive(bind(
+        &["d"],
+        Deauthorize,
+        "deauthorize",
+        "Deauthorize an authorized member, or all marked members",
+        Members,
+    )))),
+    writes(row(destructive(bind(
+        &["D"],
+        Delete,
+        "delete",
+        "Delete a member, or all marked members",
+        Members,
+    )))),
+    row(bind(
+        &["p"],
+        Ping,
+        "ping",
+        "Ping the selected member",
+        Members,
+    )),
+    row(bind(
+        &["H"],
+        History,
+        "history",
+        "Online history of the selected member",
+        Members,
+    )),
+    bind(
+        &["f"],
+        Flags,
+        "flags",
+        "Toggle what this network may change (DNS, routes)",
+        Members,
+    ),
+    bind(
+        &["c"],
+        ShowConfig,
+        "config",
+        "Review this network's settings",
+        Members,
+    ),
+    bind(
+        &["S"],
+        Sso,
+        "sso",
+        "Show and edit the network's SSO (OIDC) settings",
+        Members,
+    ),
+    writes(row(bind(
+        &["x"],
+        SsoExempt,
+        "sso-exempt",
+        "Exempt the member from SSO, or stop exempting it",
+        Members,
+    ))),
+    bind(
+        &["g"],
+        Group,
+        "group",
+        "Group by nothing, auth status, or each tag in turn",
+        Members,
+    ),
+    bind(
+        &["Enter"],
+        Activate,
+        "menu",
+        "Collapse or expand the group, or a menu for the member",
+        Members,
+    ),
+    bind(
+        &["h", "?"],
+        Help,
+        "help",
+        "this help; ? or F1 in a dialog shows its keys",
+        Both,
+    ),
+    bind(
+        &["m"],
+        RecordMacro,
+        "record-macro",
+        "record a macro of keys, then m again to name it",
+        Both,
+    ),
+    bind(
+        &["@"],
+        RunMacro,
+        "run-macro",
+        "run a macro, optionally a number of times",
+        Both,
+    ),
+];
+
+pub fn for_page(page: &Page) -> impl Iterator<Item = &'static Binding> + '_ {
+    BINDINGS.iter().filter(|b| b.scope.includes(page))
+}
+
+pub fn lookup(page: &Page, key: &KeyEvent) -> Option<&'static Binding> {
+    let name = crate::keys::name(key);
+    for_page(page).find(|b| b.keys.contains(&name.as_str()))
+}
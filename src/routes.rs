@@ -0,0 +1,319 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use crate::api::one::types::Network;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OsRoute {
+    pub target: (IpAddr, u8),
+    pub interface: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RouteStatus {
+    Present,
+    OtherInterface(String),
+    Missing,
+}
+
+#[derive(Debug, Clone)]
+pub struct RouteCheck {
+    pub target: String,
+    pub via: Option<String>,
+    pub status: RouteStatus,
+}
+
+#[derive(Debug, Clone)]
+pub struct RouteReport {
+    pub interface: String,
+    pub interface_up: Option<bool>,
+    pub allow_managed: bool,
+    pub checks: Vec<RouteCheck>,
+    pub error: Option<String>,
+}
+
+impl RouteReport {
+    pub fn problems(&self) -> usize {
+        self.checks
+            .iter()
+            .filter(|c| c.status != RouteStatus::Present)
+            .count()
+    }
+}
+
+fn mask(addr: IpAddr, prefix: u8) -> IpAddr {
+    match addr {
+        IpAddr::V4(a) => {
+            let bits = u32::from(a);
+            let mask = if prefix == 0 {
+                0
+            } else {
+                u32::MAX << (32 - prefix.min(32) as u32)
+            };
+            IpAddr::V4(Ipv4Addr::from(bits & mask))
+        }
+        IpAddr::V6(a) => {
+            let bits = u128::from(a);
+            let mask = if prefix == 0 {
+                0
+            } else {
+                u128::MAX << (128 - prefix.min(128) as u32)
+            };
+            IpAddr::V6(Ipv6Addr::from(bits & mask))
+        }
+    }
+}
+
+pub fn parse_cidr(s: &str) -> Option<(IpAddr, u8)> {
+    let (addr, prefix) = match s.split_once('/') {
+        Some((addr, prefix)) => (addr, Some(prefix.parse::<u8>().ok()?)),
+        None => (s, None),
+    };
+
+    let addr = addr.parse::<IpAddr>().ok()?;
+    let prefix = prefix.unwrap_or(if addr.is_ipv4() { 32 } else { 128 });
+
+    Some((mask(addr, prefix), prefix))
+}
+
+// the default routes (0.0.0.0/0 or ::/0) the network pushes, which allowDefault sends all
+// traffic through, as "target via gateway"
+pub fn default_routes(network: &Network) -> Vec<String> {
+    network
+        .subtype_1
+        .routes
+        .iter()
+        .filter(|r| {
+            r.target
+                .as_deref()
+                .and_then(parse_cidr)
+                .is_some_and(|(_, prefix)| prefix == 0)
+        })
+        .map(|r| {
+            format!(
+                "{} via {}",
+                r.target.clone().unwrap_or_default(),
+                r.via.clone().unwrap_or_else(|| "the network".to_string())
+            )
+        })
+        .collect()
+}
+
+// /proc/net/route stores addresses as host-order hex
+fn proc_ipv4(hex: &str) -> Option<Ipv4Addr> {
+    Some(Ipv4Addr::from(
+        u32::from_str_radix(hex, 16).ok()?.to_le_bytes(),
+    ))
+}
+
+fn proc_ipv6(hex: &str) -> Option<Ipv6Addr> {
+    Some(Ipv6Addr::from(u128::from_str_radix(hex, 16).ok()?))
+}
+
+fn linux_routes() -> Result<Vec<OsRoute>, anyhow::Error> {
+    let mut routes = Vec::new();
+
+    for line in std::fs::read_to_string("/proc/net/route")?.lines().skip(1) {
+        let fields = line.split_whitespace().collect::<Vec<&str>>();
+        if fields.len() < 8 {
+            continue;
+        }
+
+        if let (Some(dest), Some(mask)) = (proc_ipv4(fields[1]), proc_ipv4(fields[7])) {
+            let prefix = u32::from(mask).count_ones() as u8;
+            routes.push(OsRoute {
+                target: (IpAddr::V4(dest), prefix),
+                interface: fields[0].to_string(),
+            });
+        }
+    }
+
+    // IPv6 may be disabled entirely, in which case this file does not exist.
+    if let Ok(content) = std::fs::read_to_string("/proc/net/ipv6_route") {
+        for line in content.lines() {
+            let fields = line.split_whitespace().collect::<Vec<&str>>();
+            if fields.len() < 10 {
+                continue;
+            }
+
+            if let (Some(dest), Ok(prefix)) =
+                (proc_ipv6(fields[0]), u8::from_str_radix(fields[1], 16))
+            {
+                routes.push(OsRoute {
+                    target: (IpAddr::V6(dest), prefix),
+                    interface: fields[9].to_string(),
+                });
+            }
+        }
+    }
+
+    Ok(routes)
+}
+
+// BSD netstat abbreviates IPv4 destinations: "10.147.17/24", "10.147.17.5", "default"
+fn parse_netstat_destination(s: &str) -> Option<(IpAddr, u8)> {
+    let s = match s.split_once('%') {
+        Some((addr, rest)) => match rest.split_once('/') {
+            Some((_, prefix)) => format!("{}/{}", addr, prefix),
+            None => addr.to_string(),
+        },
+        None => s.to_string(),
+    };
+
+    if s.contains(':') {
+        return parse_cidr(&s);
+    }
+
+    let (addr, prefix) = match s.split_once('/') {
+        Some((addr, prefix)) => (addr.to_string(), Some(prefix.parse::<u8>().ok()?)),
+        None => (s.clone(), None),
+    };
+
+    let octets = addr.split('.').count();
+    let mut padded = addr.clone();
+    for _ in octets..4 {
+        padded.push_str(".0");
+    }
+
+    let prefix = prefix.unwrap_or(if octets == 4 { 32 } else { octets as u8 * 8 });
+    parse_cidr(&format!("{}/{}", padded, prefix))
+}
+
+fn netstat_routes() -> Result<Vec<OsRoute>, anyhow::Error> {
+    let output = std::process::Command::new("netstat").arg("-rn").output()?;
+    let mut routes = Vec::new();
+
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let fields = line.split_whitespace().collect::<Vec<&str>>();
+        if fields.len() < 4 {
+            continue;
+        }
+
+        let target = if fields[0] == "default" {
+            if fields[1].contains(':') {
+                Some((IpAddr::V6(Ipv6Addr::UNSPECIFIED), 0))
+            } else {
+                Some((IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0))
+            }
+        } else {
+            parse_netstat_destination(fields[0])
+        };
+
+        if let Some(target) = target {
+            routes.push(OsRoute {
+                target,
+                interface: fields[3].to_string(),
+            });
+        }
+    }
+
+    Ok(routes)
+}
+
+pub fn os_routes() -> Result<Vec<OsRoute>, anyhow::Error> {
+    if cfg!(target_os = "linux") {
+        linux_routes()
+    } else {
+        netstat_routes()
+    }
+}
+
+pub fn interface_up(interface: &str) -> Option<bool> {
+    if cfg!(target_os = "linux") {
+        let flags = std::fs::read_to_string(format!("/sys/class/net/{}/flags", interface)).ok()?;
+        let flags = u32::from_str_radix(flags.trim().trim_start_matches("0x"), 16).ok()?;
+        // IFF_UP
+        Some(flags & 0x1 != 0)
+    } else {
+        let output = std::process::Command::new("ifconfig")
+            .arg(interface)
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return Some(false);
+        }
+
+        Some(
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .next()
+                .is_some_and(|l| l.contains("<UP")),
+        )
+    }
+}
+
+fn check_route(target: (IpAddr, u8), interface: &str, os: &[OsRoute]) -> RouteStatus {
+    let matching = os
+        .iter()
+        .filter(|r| r.target == target)
+        .collect::<Vec<&OsRoute>>();
+
+    if matching.iter().any(|r| r.interface == interface) {
+        return RouteStatus::Present;
+    }
+
+    // zerotier installs the default route as two halves so it wins over the system default
+    if target.1 == 0 {
+        let halves = match target.0 {
+            IpAddr::V4(_) => [
+                (IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)), 1),
+                (IpAddr::V4(Ipv4Addr::new(128, 0, 0, 0)), 1),
+            ],
+            IpAddr::V6(_) => [
+                (IpAddr::V6(Ipv6Addr::UNSPECIFIED), 1),
+                (IpAddr::V6(Ipv6Addr::new(0x8000, 0, 0, 0, 0, 0, 0, 0)), 1),
+            ],
+        };
+
+        if halves.iter().all(|h| {
+            os.iter()
+                .any(|r| r.target == *h && r.interface == interface)
+        }) {
+            return RouteStatus::Present;
+        }
+    }
+
+    match matching.first() {
+        Some(r) => RouteStatus::OtherInterface(r.interface.clone()),
+        None => RouteStatus::Missing,
+    }
+}
+
+pub fn verify(network: &Network) -> RouteReport {
+    let interface = network
+        .subtype_1
+        .port_device_name
+        .clone()
+        .unwrap_or_default();
+
+    let mut report = RouteReport {
+        interface_up: interface_up(&interface),
+        allow_managed: network.subtype_0.allow_managed.unwrap_or_default(),
+        interface,
+        checks: Vec::new(),
+        error: None,
+    };
+
+    let os = match os_routes() {
+        Ok(os) => os,
+        Err(e) => {
+            report.error = Some(format!("could not read the routing table: {}", e));
+            Vec::new()
+        }
+    };
+
+    for route in &network.subtype_1.routes {
+        let target = route.target.clone().unwrap_or_default();
+        let status = match parse_cidr(&target) {
+            Some(t) => check_route(t, &report.interface, &os),
+            None => RouteStatus::Missing,
+        };
+
+        report.checks.push(RouteCheck {
+            target,
+            via: route.via.clone(),
+            status,
+        });
+    }
+
+    report
+}
@@ -0,0 +1,134 @@
+use std::collections::BTreeMap;
+
+#[cfg(feature = "central")]
+use crate::diff::{lines, Line};
+
+#[derive(Debug, Clone, Default)]
+pub struct TagName {
+    pub name: String,
+    pub enums: BTreeMap<i64, String>,
+}
+
+// names for the numeric capability and tag ids a network's rules define. Central only hands
+// back the ids for members, so the names have to come from the rules source.
+#[derive(Debug, Clone, Default)]
+pub struct RuleNames {
+    pub caps: BTreeMap<i64, String>,
+    pub tags: BTreeMap<i64, TagName>,
+}
+
+#[cfg(feature = "central")]
+enum Block {
+    None,
+    Cap(String),
+    Tag(String),
+}
+
+#[cfg(feature = "central")]
+fn number(token: Option<&str>) -> Option<i64> {
+    token?.trim_end_matches(';').parse().ok()
+}
+
+impl RuleNames {
+    // picks out `cap <name> id <n>` and `tag <name> id <n> enum <n> <name>` definitions; the
+    // rules themselves are left to Central to make sense of.
+    #[cfg(feature = "central")]
+    pub fn parse(source: &str) -> Self {
+        let mut names = Self::default();
+        let mut block = Block::None;
+        let mut tag_id = None;
+
+        let mut tokens = source
+            .lines()
+            .map(|l| l.split('#').next().unwrap_or_default())
+            .flat_map(|l| l.split_whitespace());
+
+        while let Some(token) = tokens.next() {
+            match token {
+                "cap" => {
+                    if let Some(name) = tokens.next() {
+                        block = Block::Cap(name.to_string());
+                    }
+                }
+                "tag" => {
+                    if let Some(name) = tokens.next() {
+                        block = Block::Tag(name.to_string());
+                        tag_id = None;
+                    }
+                }
+                "id" => {
+                    let id = match number(tokens.next()) {
+                        Some(id) => id,
+                        None => continue,
+                    };
+
+                    match &block {
+                        Block::Cap(name) => {
+                            names.caps.insert(id, name.clone());
+                        }
+                        Block::Tag(name) => {
+                            names.tags.insert(
+                                id,
+                                TagName {
+                                    name: name.clone(),
+                                    enums: BTreeMap::new(),
+                                },
+                            );
+                            tag_id = Some(id);
+                        }
+                        Block::None => {}
+                    }
+                }
+                "enum" => {
+                    if let (Some(value), Some(name), Some(tag)) = (
+                        number(tokens.next()),
+                        tokens.next(),
+                        tag_id.and_then(|id| names.tags.get_mut(&id)),
+                    ) {
+                        tag.enums
+                            .insert(value, name.trim_end_matches(';').to_string());
+                    }
+                }
+                ";" => {
+                    block = Block::None;
+                    tag_id = None;
+                }
+                _ => {}
+            }
+        }
+
+        names
+    }
+
+    pub fn cap(&self, id: i64) -> String {
+        self.caps
+            .get(&id)
+            .cloned()
+            .unwrap_or_else(|| id.to_string())
+    }
+}
+
+#[cfg(feature = "central")]
+// an edit to a network's rules waiting on confirmation
+#[derive(Debug, Clone)]
+pub struct RulesEdit {
+    pub old: String,
+    pub new: String,
+    pub diff: Vec<Line>,
+    // why Central refused the last push, usually a compile error
+    pub error: Option<String>,
+    pub scroll: u16,
+}
+
+#[cfg(feature = "central")]
+impl RulesEdit {
+    pub fn new(old: String, new: String) -> Self {
+        Self {
+            diff: lines(&old, &new),
+            old,
+            new,
+            error: None,
+            scroll: 0,
+        }
+    }
+}
@@ -0,0 +1,252 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::anyhow;
+use serde::Serialize;
+
+use crate::{client, config::Settings};
+
+// a script is one operation per line, with `#` comments:
+//
+//     network 8056c2e21c000001
+//     authorize 0123456789 web-1
+//     name 9876543210 db-1
+//     deauthorize 1111111111
+//     delete 2222222222
+//     rules onboarding.rules
+//     join 8056c2e21c000001
+//     leave 8056c2e21c000001
+//
+// member operations and rules apply to the network most recently named with `network`.
+#[derive(Debug, Clone)]
+pub enum Op {
+    Join(String),
+    Leave(String),
+    Authorize(String, Option<String>),
+    Deauthorize(String),
+    Name(String, String),
+    Delete(String),
+    Rules(PathBuf),
+}
+
+impl std::fmt::Display for Op {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Op::Join(id) => write!(f, "join {}", id),
+            Op::Leave(id) => write!(f, "leave {}", id),
+            Op::Authorize(node, None) => write!(f, "authorize {}", node),
+            Op::Authorize(node, Some(name)) => write!(f, "authorize {} as {}", node, name),
+            Op::Deauthorize(node) => write!(f, "deauthorize {}", node),
+            Op::Name(node, name) => write!(f, "name {} {}", node, name),
+            Op::Delete(node) => write!(f, "delete {}", node),
+            Op::Rules(path) => write!(f, "rules from {}", path.display()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Step {
+    pub line: usize,
+    pub network: Option<String>,
+    pub op: Op,
+}
+
+#[derive(Debug)]
+pub enum Outcome {
+    Done,
+    Failed(String),
+    NotRun,
+}
+
+// the whole script is checked before anything runs, so a typo on the last line doesn't leave
+// the first half applied.
+pub fn parse(script: &str, dir: &Path) -> Result<Vec<Step>, anyhow::Error> {
+    let mut steps = Vec::new();
+    let mut network = None;
+    let mut errors = Vec::new();
+
+    for (i, line) in script.lines().enumerate() {
+        let line_no = i + 1;
+        let words = line
+            .split('#')
+            .next()
+            .unwrap_or_default()
+            .split_whitespace()
+            .collect::<Vec<&str>>();
+
+        let op = match words.as_slice() {
+            [] => continue,
+            ["network", id] => {
+                network = Some(id.to_string());
+                continue;
+            }
+            ["join", id] => Op::Join(id.to_string()),
+            ["leave", id] => Op::Leave(id.to_string()),
+            ["authorize", node] => Op::Authorize(node.to_string(), None),
+            ["authorize", node, name @ ..] => Op::Authorize(node.to_string(), Some(name.join(" "))),
+            ["deauthorize", node] => Op::Deauthorize(node.to_string()),
+            ["name", node, name @ ..] if !name.is_empty() => {
+                Op::Name(node.to_string(), name.join(" "))
+            }
+            ["delete", node] => Op::Delete(node.to_string()),
+            ["rules", path] => Op::Rules(dir.join(path)),
+            _ => {
+                errors.push(format!(
+                    "line {}: cannot understand `{}`",
+                    line_no,
+                    line.trim()
+                ));
+                continue;
+            }
+        };
+
+        let needs_network = !matches!(op, Op::Join(_) | Op::Leave(_));
+        if needs_network && network.is_none() {
+            errors.push(format!(
+                "line {}: `{}` needs a `network` line before it",
+                line_no, op
+            ));
+            continue;
+        }
+
+        if let Op::Rules(path) = &op {
+            if !path.is_file() {
+                errors.push(format!(
+                    "line {}: rules file {} does not exist",
+                    line_no,
+                    path.display()
+                ));
+                continue;
+            }
+        }
+
+        steps.push(Step {
+            line: line_no,
+            network: network.clone(),
+            op,
+        });
+    }
+
+    if !errors.is_empty() {
+        return Err(anyhow!(errors.join("\n")));
+    }
+
+    Ok(steps)
+}
+
+fn apply(settings: &Settings, step: &Step) -> Result<(), anyhow::Error> {
+    match &step.op {
+        Op::Join(id) => client::join_network(id.clone()),
+        Op::Leave(id) => client::leave_network(id.clone()),
+        op => apply_central(settings, step.network.clone().unwrap_or_default(), op),
+    }
+}
+
+#[cfg(feature = "central")]
+fn apply_central(settings: &Settings, network: String, op: &Op) -> Result<(), anyhow::Error> {
+    let c = settings.central_client_for_id(network.clone())?;
+
+    match op {
+        Op::Authorize(node, name) => {
+            client::sync_authorize_member(c.clone(), network.clone(), node.clone())?;
+            match name {
+                Some(name) => {
+                    client::sync_update_member_name(c, network, node.clone(), name.clone())
+                }
+                None => Ok(()),
+            }
+        }
+        Op::Deauthorize(node) => client::sync_deauthorize_member(c, network, node.clone()),
+        Op::Name(node, name) => {
+            client::sync_update_member_name(c, network, node.clone(), name.clone())
+        }
+        Op::Delete(node) => client::sync_delete_member(c, network, node.clone()),
+        Op::Rules(path) => {
+            client::sync_apply_network_rules(c, network, std::fs::read_to_string(path)?)
+        }
+        Op::Join(_) | Op::Leave(_) => unreachable!("joins and leaves go to the local service"),
+    }
+}
+
+// member operations and rules have nowhere to go without a Central client
+#[cfg(not(feature = "central"))]
+fn apply_central(_: &Settings, _: String, _: &Op) -> Result<(), anyhow::Error> {
+    Err(anyhow!("ztui was built without Central support"))
+}
+
+// runs the steps in order, stopping at the first failure. Central has no transactions, so the
+// report says exactly which steps were applied and which were never attempted.
+pub fn run(settings: &Settings, steps: &[Step]) -> Vec<Outcome> {
+    let mut failed = false;
+
+    steps
+        .iter()
+        .map(|step| {
+            if failed {
+                return Outcome::NotRun;
+            }
+
+            match apply(settings, step) {
+                Ok(()) => Outcome::Done,
+                Err(e) => {
+                    failed = true;
+                    Outcome::Failed(e.to_string())
+                }
+            }
+        })
+        .collect()
+}
+
+pub fn print(steps: &[Step], outcomes: &[Outcome]) {
+    for (step, outcome) in steps.iter().zip(outcomes) {
+        let target = step
+            .network
+            .as_ref()
+            .filter(|_| !matches!(step.op, Op::Join(_) | Op::Leave(_)))
+            .map_or(String::new(), |n| format!(" on {}", n));
+
+        match outcome {
+            Outcome::Done => println!("[DONE]    line {}: {}{}", step.line, step.op, target),
+            Outcome::Failed(e) => {
+                println!("[FAILED]  line {}: {}{}: {}", step.line, step.op, target, e)
+            }
+            Outcome::NotRun => println!("[NOT RUN] line {}: {}{}", step.line, step.op, target),
+        }
+    }
+}
+
+// a step and how it went, for --output json and csv
+#[derive(Debug, Clone, Serialize)]
+pub struct Record {
+    pub line: usize,
+    pub network: Option<String>,
+    pub op: String,
+    pub outcome: &'static str,
+    pub error: Option<String>,
+}
+
+pub const FIELDS: [&str; 5] = ["line", "network", "op", "outcome", "error"];
+
+pub fn records(steps: &[Step], outcomes: &[Outcome]) -> Vec<Record> {
+    steps
+        .iter()
+        .zip(outcomes)
+        .map(|(step, outcome)| Record {
+            line: step.line,
+            network: step.network.clone(),
+            op: step.op.to_string(),
+            outcome: match outcome {
+                Outcome::Done => "done",
+                Outcome::Failed(_) => "failed",
+                Outcome::NotRun => "not run",
+            },
+            error: match outcome {
+                Outcome::Failed(e) => Some(e.clone()),
+                _ => None,
+            },
+        })
+        .collect()
+}
+
+pub fn failed(outcomes: &[Outcome]) -> bool {
+    outcomes.iter().any(|o| matches!(o, Outcome::Failed(_)))
+}
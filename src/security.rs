@@ -0,0 +1,28 @@
+use std::{collections::HashSet, sync::Mutex};
+
+lazy_static::lazy_static! {
+    // members this instance has asked Central to authorize, by network and node id, until a
+    // refresh shows them authorized
+    static ref OURS: Mutex<HashSet<(String, String)>> = Mutex::new(HashSet::new());
+}
+
+// noted before the request goes out, so a refresh that races it doesn't take it for someone
+// else's; taken back if the request fails
+pub fn authorizing(network: &str, member: &str) {
+    OURS.lock()
+        .unwrap()
+        .insert((network.to_string(), member.to_string()));
+}
+
+pub fn authorize_failed(network: &str, member: &str) {
+    OURS.lock()
+        .unwrap()
+        .remove(&(network.to_string(), member.to_string()));
+}
+
+// true once for each authorization made here
+pub fn authorized_here(network: &str, member: &str) -> bool {
+    OURS.lock()
+        .unwrap()
+        .remove(&(network.to_string(), member.to_string()))
+}
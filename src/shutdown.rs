@@ -0,0 +1,69 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Condvar, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+// how long quitting waits for changes already on their way to Central or the local service
+pub const WAIT: Duration = Duration::from_secs(5);
+
+// set once ztui is on its way out. the refresh loop and the batches stop taking on anything new,
+// and autosave leaves the last save to the UI.
+static STOPPING: AtomicBool = AtomicBool::new(false);
+
+lazy_static::lazy_static! {
+    // how many changes are being sent right now
+    static ref IN_FLIGHT: (Mutex<usize>, Condvar) = (Mutex::new(0), Condvar::new());
+}
+
+pub fn begin() {
+    // under the lock, so a change can't slip in between the two
+    let _count = IN_FLIGHT.0.lock().unwrap();
+    STOPPING.store(true, Ordering::SeqCst);
+}
+
+// quitting didn't happen after all: settings couldn't be saved, and the user stayed to see why
+pub fn abandon() {
+    STOPPING.store(false, Ordering::SeqCst);
+}
+
+pub fn stopping() -> bool {
+    STOPPING.load(Ordering::SeqCst)
+}
+
+// held while a change is being sent, so quitting can wait for it
+pub struct Mutation;
+
+impl Mutation {
+    // None once quitting has begun; the change is left for next time
+    pub fn start() -> Option<Self> {
+        let mut count = IN_FLIGHT.0.lock().unwrap();
+        if stopping() {
+            return None;
+        }
+        *count += 1;
+        Some(Self)
+    }
+}
+
+impl Drop for Mutation {
+    fn drop(&mut self) {
+        let (count, changed) = &*IN_FLIGHT;
+        *count.lock().unwrap() -= 1;
+        changed.notify_all();
+    }
+}
+
+// waits until no changes are being sent, or `deadline` passes, or `tick` goes by without either.
+// returns how many are still being sent.
+pub fn settle(deadline: Instant, tick: Duration) -> usize {
+    let (count, changed) = &*IN_FLIGHT;
+    let count = count.lock().unwrap();
+    let wait = deadline.saturating_duration_since(Instant::now()).min(tick);
+    let (count, _) = changed
+        .wait_timeout_while(count, wait, |count| *count > 0)
+        .unwrap();
+    *count
+}
@@ -0,0 +1,95 @@
+use std::path::{Path, PathBuf};
+
+// installed into the snippets directory the first time it is used. after that the files are the
+// user's to edit, delete, or add to; anything ending in .rules is offered.
+const DEFAULTS: &[(&str, &str)] = &[
+    (
+        "drop-non-ip",
+        "# drop anything that isn't IPv4, IPv6 or ARP
+drop
+  not ethertype ipv4
+  and not ethertype arp
+  and not ethertype ipv6
+;
+",
+    ),
+    (
+        "isolate-clients",
+        "# members tagged as clients can't reach each other
+tag role
+  id 1
+  enum 0 server
+  enum 1 client
+;
+
+drop
+  tseq role 1
+  and treq role 1
+;
+",
+    ),
+    (
+        "clients-to-servers-only",
+        "# members tagged as clients may only send to servers
+tag role
+  id 1
+  enum 0 server
+  enum 1 client
+;
+
+drop
+  tseq role 1
+  and not treq role 0
+;
+",
+    ),
+];
+
+#[derive(Debug, Clone)]
+pub struct Snippet {
+    pub name: String,
+    pub body: String,
+}
+
+impl Snippet {
+    // snippets go ahead of the existing rules, so their drops apply before any trailing accept
+    #[cfg(feature = "central")]
+    pub fn insert_into(&self, rules: &str) -> String {
+        format!("# snippet: {}\n{}\n{}", self.name, self.body, rules)
+    }
+}
+
+pub fn snippets_dir(config: &Path) -> PathBuf {
+    config.join("snippets")
+}
+
+pub fn load(config: &Path) -> Result<Vec<Snippet>, anyhow::Error> {
+    let dir = snippets_dir(config);
+
+    if !dir.exists() {
+        std::fs::create_dir_all(&dir)?;
+        for (name, body) in DEFAULTS {
+            std::fs::write(dir.join(format!("{}.rules", name)), body)?;
+        }
+    }
+
+    let mut snippets = Vec::new();
+    for entry in std::fs::read_dir(&dir)? {
+        let path = entry?.path();
+        if path.extension().is_none_or(|ext| ext != "rules") {
+            continue;
+        }
+
+        snippets.push(Snippet {
+            name: path
+                .file_stem()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string(),
+            body: std::fs::read_to_string(&path)?,
+        });
+    }
+
+    snippets.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(snippets)
+}
@@ -0,0 +1,88 @@
+// just enough of systemd's protocols to run `ztui watch` as a Type=notify unit that logs
+// structured entries to the journal. everything here quietly does nothing outside systemd.
+#[cfg(target_os = "linux")]
+mod imp {
+    use std::os::{
+        linux::net::SocketAddrExt,
+        unix::net::{SocketAddr, UnixDatagram},
+    };
+
+    const JOURNAL_SOCKET: &str = "/run/systemd/journal/socket";
+
+    pub fn notify(state: &str) {
+        let path = match std::env::var("NOTIFY_SOCKET") {
+            Ok(path) => path,
+            Err(_) => return,
+        };
+
+        let addr = match path.strip_prefix('@') {
+            Some(name) => SocketAddr::from_abstract_name(name.as_bytes()),
+            None => SocketAddr::from_pathname(&path),
+        };
+
+        if let (Ok(addr), Ok(socket)) = (addr, UnixDatagram::unbound()) {
+            let _ = socket.send_to_addr(state.as_bytes(), &addr);
+        }
+    }
+
+    #[derive(Debug)]
+    pub struct Journal(UnixDatagram);
+
+    impl Journal {
+        // only when our output is going to the journal anyway, so running watch by hand keeps
+        // printing to the terminal
+        pub fn connect() -> Option<Self> {
+            std::env::var_os("JOURNAL_STREAM")?;
+            let socket = UnixDatagram::unbound().ok()?;
+            socket.connect(JOURNAL_SOCKET).ok()?;
+            Some(Self(socket))
+        }
+
+        pub fn send(&self, priority: u8, message: &str, fields: &[(&str, String)]) {
+            let mut buf = Vec::new();
+            let all = [
+                ("MESSAGE", message.to_string()),
+                ("PRIORITY", priority.to_string()),
+                ("SYSLOG_IDENTIFIER", "ztui".to_string()),
+            ];
+
+            for (key, value) in all.iter().chain(fields) {
+                buf.extend_from_slice(key.as_bytes());
+                if value.contains('\n') {
+                    // multi-line values are sent length-prefixed instead of after an `=`
+                    buf.push(b'\n');
+                    buf.extend_from_slice(&(value.len() as u64).to_le_bytes());
+                } else {
+                    buf.push(b'=');
+                }
+                buf.extend_from_slice(value.as_bytes());
+                buf.push(b'\n');
+            }
+
+            let _ = self.0.send(&buf);
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod imp {
+    pub fn notify(_state: &str) {}
+
+    #[derive(Debug)]
+    pub struct Journal;
+
+    impl Journal {
+        pub fn connect() -> Option<Self> {
+            None
+        }
+
+        pub fn send(&self, _priority: u8, _message: &str, _fields: &[(&str, String)]) {}
+    }
+}
+
+pub use imp::{notify, Journal};
+
+// syslog priorities, as the journal wants them
+pub const WARNING: u8 = 4;
+pub const NOTICE: u8 = 5;
+pub const INFO: u8 = 6;
@@ -0,0 +1,256 @@
+use std::{collections::HashMap, fs::File, io::Write, path::Path, sync::Mutex};
+
+use anyhow::anyhow;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+// `--record` writes every request ztui makes of ZeroTier and Central to a file, with what came
+// back, and `--replay` runs the UI from that file instead, so a problem can be sent in and seen
+// again without either one. secrets are scrubbed before anything is written.
+//
+// the file is JSON lines: the header first, then an exchange a line, appended as they happen.
+#[derive(Debug, Clone, Default)]
+pub struct Recording {
+    pub header: Header,
+    pub exchanges: Vec<Exchange>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Header {
+    pub version: String,
+    // settings.json as it was when recording started, with the API keys scrubbed
+    pub settings: Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Exchange {
+    pub at: u64,
+    pub call: String,
+    pub request: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ok: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+enum Mode {
+    Off,
+    Record(File),
+    // how many times each call has been answered so far
+    Replay(Recording, HashMap<(String, String), usize>),
+}
+
+lazy_static::lazy_static! {
+    static ref MODE: Mutex<Mode> = Mutex::new(Mode::Off);
+}
+
+const SECRETS: &[&str] = &[
+    "token", "secret", "password", "private", "apikey", "api_key",
+];
+pub const SCRUBBED: &str = "<scrubbed>";
+
+// blanks every string under a key that looks like it holds a secret
+pub fn scrub(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                let key = key.to_lowercase();
+                if SECRETS.iter().any(|s| key.contains(s)) {
+                    blank(value);
+                } else {
+                    scrub(value);
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(scrub),
+        _ => {}
+    }
+}
+
+fn blank(value: &mut Value) {
+    match value {
+        Value::String(s) => *s = SCRUBBED.to_string(),
+        Value::Object(map) => map.values_mut().for_each(blank),
+        Value::Array(values) => values.iter_mut().for_each(blank),
+        _ => {}
+    }
+}
+
+pub fn record(path: &Path, mut settings: Value) -> Result<(), anyhow::Error> {
+    scrub(&mut settings);
+    let header = Header {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        settings,
+    };
+    let mut file = File::create(path)?;
+    append(&mut file, &header)?;
+
+    *MODE.lock().unwrap() = Mode::Record(file);
+    Ok(())
+}
+
+// starts answering requests from the recording at `path`, returning it for its settings
+pub fn replay(path: &Path) -> Result<Recording, anyhow::Error> {
+    let recording =
+        load(&std::fs::read_to_string(path)?).map_err(|e| anyhow!("{}: {}", path.display(), e))?;
+
+    *MODE.lock().unwrap() = Mode::Replay(recording.clone(), HashMap::new());
+    Ok(recording)
+}
+
+fn load(text: &str) -> Result<Recording, anyhow::Error> {
+    let lines = text
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .collect::<Vec<_>>();
+    let (header, lines) = lines
+        .split_first()
+        .ok_or_else(|| anyhow!("the recording is empty"))?;
+    let mut recording = Recording {
+        header: serde_json::from_str(header)?,
+        exchanges: Vec::new(),
+    };
+    for (n, line) in lines.iter().enumerate() {
+        match serde_json::from_str(line) {
+            Ok(exchange) => recording.exchanges.push(exchange),
+            // ztui stopped partway through its last line: everything before it still replays
+            Err(_) if n + 1 == lines.len() => break,
+            Err(e) => return Err(anyhow!("line {}: {}", n + 2, e)),
+        }
+    }
+
+    Ok(recording)
+}
+
+pub fn replaying() -> bool {
+    matches!(*MODE.lock().unwrap(), Mode::Replay(..))
+}
+
+fn append<T: Serialize>(file: &mut File, line: &T) -> Result<(), anyhow::Error> {
+    let mut line = serde_json::to_string(line)?;
+    line.push('\n');
+    // one write a line, so a crash loses at most the line it was writing
+    Ok(file.write_all(line.as_bytes())?)
+}
+
+// makes a request through `f`, unless it's being replayed. `request` is whatever tells this call
+// apart from others of the same kind: a network ID, a member ID.
+pub fn exchange<T, F>(call: &'static str, request: Value, f: F) -> Result<T, anyhow::Error>
+where
+    T: Serialize + DeserializeOwned,
+    F: FnOnce() -> Result<T, anyhow::Error>,
+{
+    tracing::debug!(target: "ztui::client", %request, "{}", call);
+    if let Mode::Replay(recording, answered) = &mut *MODE.lock().unwrap() {
+        return answer(recording, answered, call, &request);
+    }
+
+    let started = std::time::Instant::now();
+    let res = f();
+    let elapsed = started.elapsed();
+    match &res {
+        Ok(_) => tracing::debug!(target: "ztui::client", ?elapsed, "{} answered", call),
+        Err(e) => {
+            tracing::warn!(target: "ztui::client", ?elapsed, error = %e, "{} failed", call);
+            crate::bugreport::api_error(call, e);
+        }
+    }
+    if res.as_ref().is_err_and(crate::client::is_cancelled) {
+        return res;
+    }
+
+    if let Mode::Record(file) = &mut *MODE.lock().unwrap() {
+        let mut exchange = Exchange {
+            at: crate::history::now(),
+            call: call.to_string(),
+            request,
+            ok: None,
+            error: None,
+        };
+        match &res {
+            Ok(value) => {
+                let mut value = serde_json::to_value(value).unwrap_or_default();
+                scrub(&mut value);
+                exchange.ok = Some(value);
+            }
+            Err(e) => exchange.error = Some(e.to_string()),
+        }
+
+        // a recording that can't be written shouldn't stop ztui
+        let _ = append(file, &exchange);
+    }
+
+    res
+}
+
+// the next answer recorded for the same call. once they run out the last one is given again, so
+// the UI keeps showing how things were when the recording stopped.
+fn answer<T: DeserializeOwned>(
+    recording: &Recording,
+    answered: &mut HashMap<(String, String), usize>,
+    call: &str,
+    request: &Value,
+) -> Result<T, anyhow::Error> {
+    let matching = recording
+        .exchanges
+        .iter()
+        .filter(|e| e.call == call && e.request == *request)
+        .collect::<Vec<_>>();
+
+    let count = answered
+        .entry((call.to_string(), request.to_string()))
+        .or_default();
+    let exchange = matching
+        .get(*count)
+        .or(matching.last())
+        .ok_or_else(|| anyhow!("{} {} is not in the recording", call, request))?;
+    *count += 1;
+
+    match (&exchange.ok, &exchange.error) {
+        (_, Some(error)) => Err(anyhow!("{}", error)),
+        (Some(value), None) => Ok(serde_json::from_value(value.clone())?),
+        (None, None) => Ok(serde_json::from_value(Value::Null)?),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{load, Exchange, Header};
+
+    // a header, two exchanges and the start of a third, as a recording cut short would end
+    #[test]
+    fn load_lines() {
+        let header = Header {
+            version: "1".to_string(),
+            settings: json!({}),
+        };
+        let exchange = |call: &str| Exchange {
+            at: 0,
+            call: call.to_string(),
+            request: json!(null),
+            ok: Some(json!([])),
+            error: None,
+        };
+        let text = [
+            serde_json::to_string(&header).unwrap(),
+            serde_json::to_string(&exchange("get_networks")).unwrap(),
+            serde_json::to_string(&exchange("get_status")).unwrap(),
+            r#"{"at":0,"call":"get_pe"#.to_string(),
+        ]
+        .join("\n");
+
+        let recording = load(&text).unwrap();
+        assert_eq!(recording.header.version, "1");
+        let calls = recording
+            .exchanges
+            .iter()
+            .map(|e| e.call.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(calls, ["get_networks", "get_status"]);
+
+        let torn = text.replacen("get_status", "get_st\"", 1);
+        assert!(load(&torn).is_err());
+    }
+}
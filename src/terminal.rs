@@ -1,18 +1,49 @@
-use std::io::Write;
+use std::io::{Read, Write};
 
 use crossterm::{
     execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    terminal::{
+        disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen, SetTitle,
+    },
 };
-use tui::{backend::CrosstermBackend, Terminal};
+use tui::{
+    backend::{Backend, CrosstermBackend},
+    Terminal,
+};
+
+// what ztui needs from a terminal besides drawing on it: handing it over to a command, and
+// waiting for the command's output to be read before taking it back
+pub trait Console: Backend + Write {
+    fn set_raw(&mut self, raw: bool) -> std::io::Result<()>;
+    fn wait_for_enter(&mut self) -> std::io::Result<()>;
+}
+
+impl<W: Write> Console for CrosstermBackend<W> {
+    fn set_raw(&mut self, raw: bool) -> std::io::Result<()> {
+        if raw {
+            enable_raw_mode()
+        } else {
+            disable_raw_mode()
+        }
+    }
+
+    fn wait_for_enter(&mut self) -> std::io::Result<()> {
+        eprintln!("\nPress ENTER to continue");
+        let mut buf = [0u8; 1];
+        let _ = std::io::stdin().read(&mut buf)?;
+        Ok(())
+    }
+}
 
 pub fn init_terminal() -> std::io::Result<Terminal<CrosstermBackend<impl Write>>> {
     enable_raw_mode()?;
     let mut stdout = std::io::stdout();
     execute!(stdout, EnterAlternateScreen)?;
+    // xterm and most since keep a stack of titles, so the one ztui replaces can be put back
+    write!(stdout, "\x1b[22;0t")?;
     let backend = CrosstermBackend::new(stdout);
 
-    Ok(Terminal::new(backend)?)
+    Terminal::new(backend)
 }
 
 pub fn deinit_terminal(
@@ -20,18 +51,216 @@ pub fn deinit_terminal(
 ) -> std::io::Result<()> {
     disable_raw_mode()?;
     execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    write!(terminal.backend_mut(), "\x1b[23;0t")?;
+    Write::flush(terminal.backend_mut())?;
     terminal.show_cursor()?;
     Ok(())
 }
 
+// whether the terminal ztui runs in has gone away, as it does when its window is closed
+pub fn gone() -> bool {
+    std::fs::File::open("/dev/tty").is_err()
+}
+
+pub fn set_title(w: &mut impl Write, title: &str) -> std::io::Result<()> {
+    execute!(w, SetTitle(title))
+}
+
+// desktop notifications through escape sequences. OSC 9 is iTerm2's and has been taken up by
+// others; OSC 777 is urxvt's and is what VTE-based terminals and foot understand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Notifier {
+    Osc9,
+    Osc777,
+}
+
+// there's no way to ask a terminal which it understands, so it's guessed from what it says it is.
+// sending one a terminal doesn't know can print garbage, so anything else gets neither.
+fn notifier() -> Option<Notifier> {
+    let env = |name| std::env::var(name).unwrap_or_default();
+    let term = env("TERM");
+
+    if matches!(
+        env("TERM_PROGRAM").as_str(),
+        "iTerm.app" | "WezTerm" | "ghostty"
+    ) || std::env::var_os("WT_SESSION").is_some()
+        || term == "xterm-kitty"
+    {
+        Some(Notifier::Osc9)
+    } else if std::env::var_os("VTE_VERSION").is_some()
+        || term.starts_with("foot")
+        || term.starts_with("rxvt-unicode")
+    {
+        Some(Notifier::Osc777)
+    } else {
+        None
+    }
+}
+
+// raises a desktop notification where the terminal can, and otherwise does nothing; the alert is
+// on screen either way
+pub fn notify(w: &mut impl Write, title: &str, body: &str) -> std::io::Result<()> {
+    // the sequences end at a BEL or ESC, and 777 splits its fields on semicolons
+    let clean = |s: &str| s.replace(['\x07', '\x1b', ';'], " ");
+    match notifier() {
+        Some(Notifier::Osc9) => write!(w, "\x1b]9;{}: {}\x07", clean(title), clean(body))?,
+        Some(Notifier::Osc777) => {
+            write!(w, "\x1b]777;notify;{};{}\x07", clean(title), clean(body))?
+        }
+        None => return Ok(()),
+    }
+    w.flush()
+}
+
+// the image protocols a terminal may understand. kitty's takes pixels and is drawn over the text
+// until it's deleted; iTerm2's takes a file and is drawn into the cells like text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Graphics {
+    Kitty,
+    Iterm2,
+}
+
+// guessed from what the terminal says it is, as with notifications. tmux doesn't pass images
+// through without being set up to, and --ascii asks for plain text, so neither gets any.
+pub fn graphics() -> Option<Graphics> {
+    let env = |name| std::env::var(name).unwrap_or_default();
+    if std::env::var_os("TMUX").is_some() || crate::theme::ascii() {
+        return None;
+    }
+
+    if env("TERM") == "xterm-kitty"
+        || std::env::var_os("KITTY_WINDOW_ID").is_some()
+        || env("TERM_PROGRAM") == "ghostty"
+    {
+        Some(Graphics::Kitty)
+    } else if matches!(env("TERM_PROGRAM").as_str(), "iTerm.app" | "WezTerm") {
+        Some(Graphics::Iterm2)
+    } else {
+        None
+    }
+}
+
+// draws `image` scaled to fill `area`, leaving the cursor where it was
+pub fn show_image(
+    w: &mut impl Write,
+    graphics: Graphics,
+    area: tui::layout::Rect,
+    image: &crate::image::Image,
+) -> Result<(), anyhow::Error> {
+    use base64::Engine;
+
+    execute!(
+        w,
+        crossterm::cursor::SavePosition,
+        crossterm::cursor::MoveTo(area.x, area.y)
+    )?;
+
+    match graphics {
+        Graphics::Kitty => {
+            let data = base64::engine::general_purpose::STANDARD.encode(&image.rgba);
+            // sent in pieces of at most 4096, each saying whether more follow
+            let chunks = data.as_bytes().chunks(4096).collect::<Vec<_>>();
+            for (i, chunk) in chunks.iter().enumerate() {
+                let more = (i + 1 < chunks.len()) as u8;
+                if i == 0 {
+                    write!(
+                        w,
+                        "\x1b_Ga=T,f=32,s={},v={},c={},r={},C=1,q=2,m={};",
+                        image.width, image.height, area.width, area.height, more
+                    )?;
+                } else {
+                    write!(w, "\x1b_Gm={};", more)?;
+                }
+                w.write_all(chunk)?;
+                write!(w, "\x1b\\")?;
+            }
+        }
+        Graphics::Iterm2 => {
+            let png = image.png()?;
+            write!(
+                w,
+                "\x1b]1337;File=inline=1;size={};width={};height={};preserveAspectRatio=0:{}\x07",
+                png.len(),
+                area.width,
+                area.height,
+                base64::engine::general_purpose::STANDARD.encode(&png)
+            )?;
+        }
+    }
+
+    execute!(w, crossterm::cursor::RestorePosition)?;
+    Ok(())
+}
+
+// takes down images drawn by show_image. iTerm2's go when the cells under them are drawn over,
+// which the caller has to see to.
+pub fn clear_images(w: &mut impl Write, graphics: Graphics) -> std::io::Result<()> {
+    if graphics == Graphics::Kitty {
+        write!(w, "\x1b_Ga=d,d=A,q=2\x1b\\")?;
+        w.flush()?;
+    }
+    Ok(())
+}
+
+pub fn in_tmux() -> bool {
+    std::env::var_os("TMUX").is_some()
+}
+
+// runs a command in a new tmux pane beside ztui, or a new window, which stays open until it's read
+pub fn tmux_open(launch: &crate::config::Launch) -> Result<(), anyhow::Error> {
+    let mut args = match launch.tmux {
+        crate::config::Tmux::Window => vec!["new-window".to_string()],
+        _ => vec!["split-window".to_string(), "-h".to_string()],
+    };
+    // in ztui's directory unless the command says otherwise, as it would be run without tmux
+    let cwd = launch.cwd.clone().map_or_else(std::env::current_dir, Ok)?;
+    args.extend(["-c".to_string(), cwd.to_string_lossy().to_string()]);
+    for (name, value) in &launch.env {
+        args.extend(["-e".to_string(), format!("{}={}", name, value)]);
+    }
+    // the configured shell and the command are handed to a wrapper as arguments, so they need no
+    // quoting here
+    args.extend([
+        "--".to_string(),
+        "/bin/sh".to_string(),
+        "-c".to_string(),
+        "\"$@\"; printf '\\nPress ENTER to close'; read _".to_string(),
+        "sh".to_string(),
+    ]);
+    args.extend(launch.argv());
+
+    let status = std::process::Command::new("tmux")
+        .args(&args)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .map_err(|e| anyhow::anyhow!("could not run tmux: {}", e))?;
+    if !status.success() {
+        return Err(anyhow::anyhow!(
+            "tmux could not open the command: {}",
+            status
+        ));
+    }
+
+    Ok(())
+}
+
+// logged in over ssh, where a change to routing can take away the connection ztui is shown on
+pub fn remote_session() -> bool {
+    ["SSH_CONNECTION", "SSH_CLIENT", "SSH_TTY"]
+        .iter()
+        .any(|v| std::env::var_os(v).is_some())
+}
+
 #[macro_export]
 macro_rules! temp_mute_terminal {
     ($terminal:expr, $code:block) => {
-        disable_raw_mode()?;
+        $crate::terminal::Console::set_raw($terminal.backend_mut(), false)?;
         execute!($terminal.backend_mut(), LeaveAlternateScreen)?;
         $terminal.show_cursor()?;
         $code();
-        enable_raw_mode()?;
+        $crate::terminal::Console::set_raw($terminal.backend_mut(), true)?;
         execute!($terminal.backend_mut(), EnterAlternateScreen)?;
         $terminal.hide_cursor()?;
         $terminal.clear()?;
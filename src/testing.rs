@@ -0,0 +1,363 @@
+use std::{
+    collections::BTreeMap,
+    convert::Infallible,
+    io::Write,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use hyper::{
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, Server, StatusCode,
+};
+use serde_json::{json, Value};
+use tui::{
+    backend::{Backend, TestBackend},
+    buffer::Cell,
+    layout::Rect,
+    Terminal,
+};
+
+use crate::{app::App, config::Settings, terminal::Console};
+
+lazy_static::lazy_static! {
+    static ref SERIAL: Mutex<()> = Mutex::new(());
+}
+
+// settings, data and the clients' addresses are all process-wide, so tests that use them take
+// turns. a test that failed while holding it doesn't hold up the rest.
+pub fn serial() -> MutexGuard<'static, ()> {
+    SERIAL.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+// what a stand-in has been told and what it answers with. networks and members are kept as the
+// JSON they were last sent, so both the local service and Central can be played by one.
+#[derive(Debug, Default)]
+pub struct State {
+    pub networks: BTreeMap<String, Value>,
+    pub members: BTreeMap<String, BTreeMap<String, Value>>,
+    // "METHOD /path" for every request, in the order they came in
+    pub requests: Vec<String>,
+}
+
+// the local service's API or Central's, answered in-process on a loopback port
+pub struct StandIn {
+    pub url: String,
+    state: Arc<Mutex<State>>,
+    // the server runs for as long as this does
+    _runtime: tokio::runtime::Runtime,
+}
+
+impl StandIn {
+    pub fn start() -> Self {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()
+            .unwrap();
+        let state = Arc::new(Mutex::new(State::default()));
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let s = state.clone();
+        let make = make_service_fn(move |_| {
+            let s = s.clone();
+            async move { Ok::<_, Infallible>(service_fn(move |req| handle(s.clone(), req))) }
+        });
+
+        {
+            let _entered = runtime.enter();
+            runtime.spawn(Server::from_tcp(listener).unwrap().serve(make));
+        }
+
+        Self {
+            url,
+            state,
+            _runtime: runtime,
+        }
+    }
+
+    pub fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap()
+    }
+
+    pub fn network(&self, id: &str, network: Value) -> &Self {
+        self.state().networks.insert(id.to_string(), network);
+        self
+    }
+
+    #[cfg(feature = "central")]
+    pub fn member(&self, network: &str, node_id: &str, member: Value) -> &Self {
+        self.state()
+            .members
+            .entry(network.to_string())
+            .or_default()
+            .insert(node_id.to_string(), member);
+        self
+    }
+}
+
+// objects are merged key by key, the way Central applies a partial update; anything else is
+// replaced
+fn merge(into: &mut Value, from: Value) {
+    match (into, from) {
+        (Value::Object(into), Value::Object(from)) => {
+            for (k, v) in from {
+                merge(into.entry(k).or_insert(Value::Null), v);
+            }
+        }
+        (into, from) => *into = from,
+    }
+}
+
+async fn handle(
+    state: Arc<Mutex<State>>,
+    req: Request<Body>,
+) -> Result<Response<Body>, Infallible> {
+    let method = req.method().clone();
+    let path = req.uri().path().to_string();
+    let body = hyper::body::to_bytes(req.into_body())
+        .await
+        .unwrap_or_default();
+    let body = serde_json::from_slice(&body).unwrap_or(Value::Null);
+
+    let mut state = state.lock().unwrap();
+    state.requests.push(format!("{} {}", method, path));
+
+    let segments = path.trim_matches('/').split('/').collect::<Vec<_>>();
+    let answer = match (&method, segments.as_slice()) {
+        (&Method::GET, ["status"]) => Some(json!({
+            "address": "1122334455",
+            "online": true,
+            "version": "1.12.2",
+        })),
+        (&Method::GET, ["peer"]) => Some(json!([])),
+        (&Method::GET, ["network"]) => {
+            Some(Value::Array(state.networks.values().cloned().collect()))
+        }
+        (&Method::GET, ["network", id]) => state.networks.get(*id).cloned(),
+        (&Method::POST, ["network", id]) => {
+            let network = state
+                .networks
+                .entry(id.to_string())
+                .or_insert_with(|| json!({ "id": id }));
+            merge(network, body);
+            Some(network.clone())
+        }
+        (&Method::DELETE, ["network", id]) => state.networks.remove(*id).map(|_| json!({})),
+        (&Method::GET, ["network", id, "member"]) => Some(Value::Array(
+            state
+                .members
+                .get(*id)
+                .into_iter()
+                .flat_map(|m| m.values().cloned())
+                .collect(),
+        )),
+        (&Method::GET, ["network", id, "member", node_id]) => state
+            .members
+            .get(*id)
+            .and_then(|m| m.get(*node_id))
+            .cloned(),
+        (&Method::POST, ["network", id, "member", node_id]) => {
+            let member = state
+                .members
+                .entry(id.to_string())
+                .or_default()
+                .entry(node_id.to_string())
+                .or_insert_with(|| json!({ "networkId": id, "nodeId": node_id }));
+            merge(member, body);
+            Some(member.clone())
+        }
+        _ => None,
+    };
+
+    Ok(match answer {
+        Some(answer) => Response::builder()
+            .header("content-type", "application/json")
+            .body(Body::from(answer.to_string()))
+            .unwrap(),
+        None => Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::empty())
+            .unwrap(),
+    })
+}
+
+// a TestBackend that also swallows the escape sequences ztui writes around tui, for titles,
+// notifications and images
+pub struct Screen(pub TestBackend);
+
+impl Screen {
+    pub fn terminal(width: u16, height: u16) -> Terminal<Screen> {
+        Terminal::new(Screen(TestBackend::new(width, height))).unwrap()
+    }
+}
+
+impl Write for Screen {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+// there's no tty behind it to leave raw mode on, or anyone to press Enter
+impl Console for Screen {
+    fn set_raw(&mut self, _: bool) -> std::io::Result<()> {
+        Ok(())
+    }
+
+    fn wait_for_enter(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Backend for Screen {
+    fn draw<'a, I>(&mut self, content: I) -> Result<(), std::io::Error>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        self.0.draw(content)
+    }
+
+    fn hide_cursor(&mut self) -> Result<(), std::io::Error> {
+        self.0.hide_cursor()
+    }
+
+    fn show_cursor(&mut self) -> Result<(), std::io::Error> {
+        self.0.show_cursor()
+    }
+
+    fn get_cursor(&mut self) -> Result<(u16, u16), std::io::Error> {
+        self.0.get_cursor()
+    }
+
+    fn set_cursor(&mut self, x: u16, y: u16) -> Result<(), std::io::Error> {
+        self.0.set_cursor(x, y)
+    }
+
+    fn clear(&mut self) -> Result<(), std::io::Error> {
+        self.0.clear()
+    }
+
+    fn size(&self) -> Result<Rect, std::io::Error> {
+        self.0.size()
+    }
+
+    fn flush(&mut self) -> Result<(), std::io::Error> {
+        Backend::flush(&mut self.0)
+    }
+}
+
+// ztui running against stand-ins for the local service and Central, with its settings and data
+// in a scratch directory, drawn on a screen of its own
+pub struct Fixture {
+    pub local: StandIn,
+    #[cfg(feature = "central")]
+    pub central: StandIn,
+    pub settings: Arc<Mutex<Settings>>,
+    pub app: App,
+    pub terminal: Terminal<Screen>,
+    _dir: tempfile::TempDir,
+    _serial: MutexGuard<'static, ()>,
+}
+
+impl Fixture {
+    pub fn new() -> Self {
+        let serial = serial();
+        let dir = tempfile::tempdir().unwrap();
+        crate::config::set_root(dir.path().to_path_buf());
+        // privacy mode is process-wide too, and a test that turned it on may not have lived to
+        // turn it off
+        crate::privacy::set(false);
+
+        let local = StandIn::start();
+        let authtoken = dir.path().join("authtoken.secret");
+        std::fs::write(&authtoken, "authtoken").unwrap();
+        crate::client::set_local(crate::client::Local {
+            baseurl: Some(local.url.clone()),
+            authtoken: Some(authtoken),
+        });
+
+        Self {
+            local,
+            #[cfg(feature = "central")]
+            central: StandIn::start(),
+            settings: Arc::new(Mutex::new(Settings::default())),
+            app: App::default(),
+            terminal: Screen::terminal(120, 30),
+            _dir: dir,
+            _serial: serial,
+        }
+    }
+
+    // a network this node has joined, that Central has a key saved for
+    #[cfg(feature = "central")]
+    pub fn network(&self, id: &str, central: Value) {
+        self.local.network(id, json!({ "id": id, "status": "OK" }));
+        self.central.network(id, central);
+
+        let mut lock = self.settings.lock().unwrap();
+        lock.set_api_key_for_id(id.to_string(), "key".to_string());
+        lock.set_central_url_for_id(id.to_string(), Some(self.central.url.clone()));
+    }
+
+    // what the refresh loop reads: the local service's networks, and the members of the network
+    // that's open
+    pub fn refresh(&mut self) {
+        let networks = crate::client::sync_get_networks().unwrap();
+        let mut lock = self.settings.lock().unwrap();
+        lock.update_networks(networks).unwrap();
+
+        #[cfg(feature = "central")]
+        if let crate::app::Page::Network(id) = lock.page.clone() {
+            let client = lock.central_client_for_id(id.clone()).unwrap();
+            let members = crate::client::sync_get_members(client, id.clone()).unwrap();
+            lock.set_members(id, members);
+        }
+    }
+
+    // each key as it's written in settings, `a` or `Enter`, separated by spaces
+    pub fn keys(&mut self, keys: &str) -> String {
+        for key in keys.split_whitespace() {
+            self.key(crate::keys::parse(key).unwrap());
+        }
+        self.draw()
+    }
+
+    // text typed into whatever has the focus
+    pub fn type_in(&mut self, text: &str) -> String {
+        for c in text.chars() {
+            self.key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+        self.draw()
+    }
+
+    fn key(&mut self, key: KeyEvent) {
+        let quit = self
+            .app
+            .input(&mut self.terminal, self.settings.clone(), Event::Key(key))
+            .unwrap();
+        assert!(!quit, "{} quit", crate::keys::name(&key));
+    }
+
+    // draws a frame the way the run loop does, and gives back what's on screen, a line per row
+    pub fn draw(&mut self) -> String {
+        let settings = self.settings.clone();
+        let app = &mut self.app;
+        self.terminal
+            .draw(|f| app.draw(f, settings).unwrap())
+            .unwrap();
+
+        let buffer = self.terminal.backend().0.buffer();
+        buffer
+            .content
+            .chunks(buffer.area.width as usize)
+            .map(|row| row.iter().map(|c| c.symbol.as_str()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
@@ -0,0 +1,91 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use tui::{buffer::Buffer, layout::Rect, style::Color, widgets::Widget};
+
+static COLOR: AtomicBool = AtomicBool::new(true);
+static ASCII: AtomicBool = AtomicBool::new(false);
+
+const SPINNER: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+const ASCII_SPINNER: &[&str] = &["|", "/", "-", "\\"];
+
+// colors are dropped with --no-color, or when NO_COLOR is set to anything (https://no-color.org)
+pub fn set(no_color: bool, ascii: bool) {
+    let no_color = no_color || std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+    COLOR.store(!no_color, Ordering::SeqCst);
+    ASCII.store(ascii, Ordering::SeqCst);
+}
+
+pub fn color() -> bool {
+    COLOR.load(Ordering::SeqCst)
+}
+
+pub fn ascii() -> bool {
+    ASCII.load(Ordering::SeqCst)
+}
+
+pub fn spinner_frames() -> &'static [&'static str] {
+    if ascii() {
+        ASCII_SPINNER
+    } else {
+        SPINNER
+    }
+}
+
+// a cell of an online history: up, down, or not known. without color up and down need
+// different marks to be told apart.
+pub fn history_mark(online: Option<bool>) -> (&'static str, Color) {
+    match (online, color(), ascii()) {
+        (Some(true), _, false) => ("█", Color::LightGreen),
+        (Some(true), _, true) => ("#", Color::LightGreen),
+        (Some(false), true, false) => ("█", Color::LightRed),
+        (Some(false), true, true) => ("#", Color::LightRed),
+        (Some(false), false, _) => ("_", Color::LightRed),
+        (None, _, false) => ("░", Color::DarkGray),
+        (None, _, true) => (".", Color::DarkGray),
+    }
+}
+
+fn to_ascii(c: char) -> Option<char> {
+    match c {
+        '─' | '━' | '═' | '╌' | '╍' | '┄' | '┅' | '┈' | '┉' => Some('-'),
+        '│' | '┃' | '║' | '╎' | '╏' | '┆' | '┇' | '┊' | '┋' => Some('|'),
+        '\u{2500}'..='\u{257f}' => Some('+'),
+        '░' => Some('.'),
+        '\u{2580}'..='\u{259f}' => Some('#'),
+        _ => None,
+    }
+}
+
+// drawn over the whole screen after everything else, so every style and symbol decision passes
+// through here on its way out: colors are dropped without color, and box drawing becomes plain
+// ASCII in ascii mode. emphasis (bold, reversed) is kept either way, so the selection and the
+// cursor still show.
+pub struct Filter;
+
+impl Widget for Filter {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let (color, ascii) = (color(), ascii());
+        if color && !ascii {
+            return;
+        }
+
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                let cell = buf.get_mut(x, y);
+                if !color {
+                    cell.fg = Color::Reset;
+                    cell.bg = Color::Reset;
+                }
+
+                if ascii {
+                    let mut chars = cell.symbol.chars();
+                    if let (Some(c), None) = (chars.next(), chars.next()) {
+                        if let Some(a) = to_ascii(c) {
+                            cell.set_char(a);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use std::sync::Arc;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Counters {
+    pub interface: String,
+    pub rx_bytes: u64,
+    pub tx_bytes: u64,
+}
+
+// where per-interface byte counters come from. sys_metrics only covers linux and macOS, so each
+// platform also has a native backend and the rest of the program only sees this trait. built
+// without the traffic feature there are none, and every network shows no traffic.
+pub trait TrafficSource: std::fmt::Debug + Send + Sync {
+    fn name(&self) -> &'static str;
+    fn counters(&self) -> Result<Vec<Counters>, anyhow::Error>;
+}
+
+#[cfg(all(feature = "traffic", any(target_os = "linux", target_os = "macos")))]
+#[derive(Debug)]
+pub struct SysMetrics;
+
+#[cfg(all(feature = "traffic", any(target_os = "linux", target_os = "macos")))]
+impl TrafficSource for SysMetrics {
+    fn name(&self) -> &'static str {
+        "sys_metrics"
+    }
+
+    fn counters(&self) -> Result<Vec<Counters>, anyhow::Error> {
+        Ok(sys_metrics::network::get_ionets()?
+            .into_iter()
+            .map(|n| Counters {
+                interface: n.interface,
+                rx_bytes: n.rx_bytes,
+                tx_bytes: n.tx_bytes,
+            })
+            .collect())
+    }
+}
+
+#[cfg(all(feature = "traffic", target_os = "linux"))]
+#[derive(Debug)]
+pub struct ProcNetDev;
+
+#[cfg(all(feature = "traffic", target_os = "linux"))]
+impl TrafficSource for ProcNetDev {
+    fn name(&self) -> &'static str {
+        "/proc/net/dev"
+    }
+
+    fn counters(&self) -> Result<Vec<Counters>, anyhow::Error> {
+        let mut counters = Vec::new();
+
+        // two header lines, then "iface: rx_bytes rx_packets ... (8 rx fields) tx_bytes ..."
+        for line in std::fs::read_to_string("/proc/net/dev")?.lines().skip(2) {
+            if let Some((interface, fields)) = line.split_once(':') {
+                let fields = fields.split_whitespace().collect::<Vec<&str>>();
+                if fields.len() < 9 {
+                    continue;
+                }
+
+                counters.push(Counters {
+                    interface: interface.trim().to_string(),
+                    rx_bytes: fields[0].parse()?,
+                    tx_bytes: fields[8].parse()?,
+                });
+            }
+        }
+
+        Ok(counters)
+    }
+}
+
+#[cfg(all(feature = "traffic", target_os = "macos"))]
+#[derive(Debug)]
+pub struct Sysctl;
+
+#[cfg(all(feature = "traffic", target_os = "macos"))]
+impl TrafficSource for Sysctl {
+    fn name(&self) -> &'static str {
+        "sysctl"
+    }
+
+    fn counters(&self) -> Result<Vec<Counters>, anyhow::Error> {
+        let mut mib = [libc::CTL_NET, libc::PF_ROUTE, 0, 0, libc::NET_RT_IFLIST2, 0];
+        let mut len: libc::size_t = 0;
+        let mut counters = Vec::new();
+
+        unsafe {
+            if libc::sysctl(
+                mib.as_mut_ptr(),
+                mib.len() as libc::c_uint,
+                std::ptr::null_mut(),
+                &mut len,
+                std::ptr::null_mut(),
+                0,
+            ) != 0
+            {
+                return Err(std::io::Error::last_os_error().into());
+            }
+
+            let mut buf = vec![0u8; len];
+            if libc::sysctl(
+                mib.as_mut_ptr(),
+                mib.len() as libc::c_uint,
+                buf.as_mut_ptr() as *mut libc::c_void,
+                &mut len,
+                std::ptr::null_mut(),
+                0,
+            ) != 0
+            {
+                return Err(std::io::Error::last_os_error().into());
+            }
+
+            let mut offset = 0;
+            while offset + std::mem::size_of::<libc::if_msghdr>() <= len {
+                let ptr = buf.as_ptr().add(offset);
+                let hdr = std::ptr::read_unaligned(ptr as *const libc::if_msghdr);
+                if hdr.ifm_msglen == 0 {
+                    break;
+                }
+
+                if hdr.ifm_type as libc::c_int == libc::RTM_IFINFO2
+                    && offset + std::mem::size_of::<libc::if_msghdr2>() <= len
+                {
+                    let msg = std::ptr::read_unaligned(ptr as *const libc::if_msghdr2);
+                    let mut name = [0 as libc::c_char; libc::IF_NAMESIZE];
+
+                    if !libc::if_indextoname(msg.ifm_index as libc::c_uint, name.as_mut_ptr())
+                        .is_null()
+                    {
+                        counters.push(Counters {
+                            interface: std::ffi::CStr::from_ptr(name.as_ptr())
+                                .to_string_lossy()
+                                .into_owned(),
+                            rx_bytes: { msg.ifm_data.ifi_ibytes },
+                            tx_bytes: { msg.ifm_data.ifi_obytes },
+                        });
+                    }
+                }
+
+                offset += hdr.ifm_msglen as usize;
+            }
+        }
+
+        Ok(counters)
+    }
+}
+
+#[derive(Debug)]
+pub struct Unsupported;
+
+impl TrafficSource for Unsupported {
+    fn name(&self) -> &'static str {
+        "unsupported"
+    }
+
+    fn counters(&self) -> Result<Vec<Counters>, anyhow::Error> {
+        Ok(Vec::new())
+    }
+}
+
+fn candidates() -> Vec<Arc<dyn TrafficSource>> {
+    vec![
+        #[cfg(all(feature = "traffic", any(target_os = "linux", target_os = "macos")))]
+        Arc::new(SysMetrics),
+        #[cfg(all(feature = "traffic", target_os = "linux"))]
+        Arc::new(ProcNetDev),
+        #[cfg(all(feature = "traffic", target_os = "macos"))]
+        Arc::new(Sysctl),
+    ]
+}
+
+// the first backend that can actually read counters on this machine
+pub fn default_source() -> Arc<dyn TrafficSource> {
+    candidates()
+        .into_iter()
+        .find(|s| s.counters().is_ok())
+        .unwrap_or_else(|| Arc::new(Unsupported))
+}
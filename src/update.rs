@@ -0,0 +1,44 @@
+use std::sync::{Arc, Mutex};
+
+use crate::{config::Settings, i18n::tf};
+
+// "v1.2.3" or "1.2.3" as numbers, so 0.10.0 comes after 0.9.0. anything after a dash, like a
+// prerelease, is ignored.
+fn version(s: &str) -> Vec<u64> {
+    s.trim()
+        .trim_start_matches('v')
+        .split('-')
+        .next()
+        .unwrap_or_default()
+        .split('.')
+        .map(|part| part.parse().unwrap_or_default())
+        .collect()
+}
+
+pub fn newer(latest: &str, running: &str) -> bool {
+    version(latest) > version(running)
+}
+
+// asks GitHub for the latest release once, if config.json says to, and says so when it's newer
+// than this one. failing to find out isn't worth interrupting anyone over.
+pub fn check(settings: Arc<Mutex<Settings>>) {
+    if !settings.lock().unwrap().user_config().check_for_updates() {
+        return;
+    }
+
+    match crate::client::latest_release() {
+        Ok(release) if newer(&release.tag_name, env!("CARGO_PKG_VERSION")) => {
+            settings.lock().unwrap().notice = Some(tf(
+                "toast.update_available",
+                &[
+                    &release.tag_name,
+                    &env!("CARGO_PKG_VERSION"),
+                    &release.html_url,
+                ],
+            ));
+            crate::display::mark_dirty();
+        }
+        Ok(_) => {}
+        Err(e) => tracing::debug!(error = %e, "checking for updates failed"),
+    }
+}
@@ -0,0 +1,506 @@
+use std::{
+    collections::HashMap,
+    io::Write,
+    process::{Command, Stdio},
+    str::FromStr,
+    sync::mpsc,
+    time::Duration,
+};
+
+use anyhow::anyhow;
+use serde::Serialize;
+
+#[cfg(feature = "central")]
+use crate::{api::central::types::Member, history::is_online};
+use crate::{
+    client,
+    config::Settings,
+    history::now,
+    systemd::{self, Journal},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Kind {
+    Joined,
+    Left,
+    Authorized,
+    Unauthorized,
+    Online,
+    Offline,
+    Renamed,
+    // the local node's view of a network changed, e.g. OK to ACCESS_DENIED
+    Status,
+}
+
+pub const KINDS: &[Kind] = &[
+    Kind::Joined,
+    Kind::Left,
+    Kind::Authorized,
+    Kind::Unauthorized,
+    Kind::Online,
+    Kind::Offline,
+    Kind::Renamed,
+    Kind::Status,
+];
+
+impl FromStr for Kind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        KINDS
+            .iter()
+            .find(|k| {
+                serde_json::to_value(k)
+                    .ok()
+                    .is_some_and(|v| v.as_str() == Some(s))
+            })
+            .copied()
+            .ok_or_else(|| anyhow!("unknown event `{}`", s))
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Event {
+    pub at: u64,
+    pub kind: Kind,
+    pub network: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub member: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct MemberState {
+    name: String,
+    authorized: bool,
+    online: Option<bool>,
+    // seconds since the epoch
+    last_online: Option<u64>,
+}
+
+#[cfg(feature = "central")]
+fn member_state(member: &Member, now: u64) -> MemberState {
+    MemberState {
+        name: member.name.clone().unwrap_or_default(),
+        authorized: member
+            .config
+            .as_ref()
+            .and_then(|c| c.authorized)
+            .unwrap_or_default(),
+        online: is_online(member, now),
+        last_online: member.last_online.map(|at| at as u64 / 1000),
+    }
+}
+
+// how things stand as of the last poll, sent to the summary webhook
+#[derive(Debug, Clone, Serialize)]
+pub struct Summary {
+    pub at: u64,
+    pub networks: usize,
+    pub disconnected: Vec<Disconnected>,
+    pub offline: Vec<Offline>,
+    pub unauthorized: Vec<Unauthorized>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Disconnected {
+    pub network: String,
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Offline {
+    pub network: String,
+    pub member: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub hours: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Unauthorized {
+    pub network: String,
+    pub member: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+// the last thing we saw, so each poll can be compared against it
+#[derive(Debug, Default)]
+pub struct Watcher {
+    statuses: HashMap<String, String>,
+    members: HashMap<String, HashMap<String, MemberState>>,
+}
+
+impl Watcher {
+    fn event(
+        kind: Kind,
+        network: &str,
+        member: Option<(&str, &str)>,
+        detail: impl ToString,
+    ) -> Event {
+        Event {
+            at: now(),
+            kind,
+            network: network.to_string(),
+            member: member.map(|(id, _)| id.to_string()),
+            name: member
+                .map(|(_, name)| name.to_string())
+                .filter(|n| !n.is_empty()),
+            detail: detail.to_string(),
+        }
+    }
+
+    fn statuses(&mut self, networks: &[crate::api::one::types::Network]) -> Vec<Event> {
+        let mut events = Vec::new();
+
+        for network in networks {
+            let (id, status) = match (&network.subtype_1.id, &network.subtype_1.status) {
+                (Some(id), Some(status)) => (id, status),
+                _ => continue,
+            };
+
+            if let Some(old) = self.statuses.insert(id.clone(), status.clone()) {
+                if old != *status {
+                    events.push(Self::event(
+                        Kind::Status,
+                        id,
+                        None,
+                        format!("{} -> {}", old, status),
+                    ));
+                }
+            }
+        }
+
+        events
+    }
+
+    // the first sample of a network is only remembered; events are for what changes after
+    #[cfg(feature = "central")]
+    fn members(&mut self, network: &str, members: &[Member]) -> Vec<Event> {
+        let now = now();
+        let new = members
+            .iter()
+            .filter_map(|m| Some((m.node_id.clone()?, member_state(m, now))))
+            .collect::<HashMap<String, MemberState>>();
+
+        let old = match self.members.insert(network.to_string(), new.clone()) {
+            Some(old) => old,
+            None => return Vec::new(),
+        };
+
+        let mut events = Vec::new();
+
+        for (id, state) in &new {
+            let member = Some((id.as_str(), state.name.as_str()));
+
+            let old = match old.get(id) {
+                Some(old) => old,
+                None => {
+                    events.push(Self::event(Kind::Joined, network, member, "new member"));
+                    if !state.authorized {
+                        events.push(Self::event(
+                            Kind::Unauthorized,
+                            network,
+                            member,
+                            "waiting for authorization",
+                        ));
+                    }
+                    continue;
+                }
+            };
+
+            if old.authorized != state.authorized {
+                events.push(if state.authorized {
+                    Self::event(Kind::Authorized, network, member, "authorized")
+                } else {
+                    Self::event(Kind::Unauthorized, network, member, "deauthorized")
+                });
+            }
+
+            match (old.online, state.online) {
+                (Some(false), Some(true)) => {
+                    events.push(Self::event(Kind::Online, network, member, "came online"))
+                }
+                (Some(true), Some(false)) => {
+                    events.push(Self::event(Kind::Offline, network, member, "went offline"))
+                }
+                _ => {}
+            }
+
+            if old.name != state.name {
+                events.push(Self::event(
+                    Kind::Renamed,
+                    network,
+                    member,
+                    format!("was `{}`", old.name),
+                ));
+            }
+        }
+
+        for (id, state) in &old {
+            if !new.contains_key(id) {
+                events.push(Self::event(
+                    Kind::Left,
+                    network,
+                    Some((id.as_str(), state.name.as_str())),
+                    "removed",
+                ));
+            }
+        }
+
+        events
+    }
+
+    pub fn summary(&self, offline_after: Duration) -> Summary {
+        let now = now();
+        let name = |state: &MemberState| Some(state.name.clone()).filter(|n| !n.is_empty());
+
+        let mut summary = Summary {
+            at: now,
+            networks: self.statuses.len(),
+            disconnected: self
+                .statuses
+                .iter()
+                .filter(|(_, status)| status.as_str() != "OK")
+                .map(|(network, status)| Disconnected {
+                    network: network.clone(),
+                    status: status.clone(),
+                })
+                .collect(),
+            offline: Vec::new(),
+            unauthorized: Vec::new(),
+        };
+
+        for (network, members) in &self.members {
+            for (member, state) in members {
+                if !state.authorized {
+                    summary.unauthorized.push(Unauthorized {
+                        network: network.clone(),
+                        member: member.clone(),
+                        name: name(state),
+                    });
+                    continue;
+                }
+
+                let offline = now.saturating_sub(state.last_online.unwrap_or_default());
+                if state.online == Some(false) && offline >= offline_after.as_secs() {
+                    summary.offline.push(Offline {
+                        network: network.clone(),
+                        member: member.clone(),
+                        name: name(state),
+                        hours: offline / 3600,
+                    });
+                }
+            }
+        }
+
+        summary
+            .disconnected
+            .sort_by(|a, b| a.network.cmp(&b.network));
+        summary
+            .offline
+            .sort_by(|a, b| (&a.network, &a.member).cmp(&(&b.network, &b.member)));
+        summary
+            .unauthorized
+            .sort_by(|a, b| (&a.network, &a.member).cmp(&(&b.network, &b.member)));
+        summary
+    }
+
+    // one round of the same polling the TUI does, over every network there's a key for
+    pub fn poll(&mut self, settings: &Settings) -> Result<Vec<Event>, anyhow::Error> {
+        let mut events = self.statuses(&client::sync_get_networks()?);
+        events.extend(self.member_events(settings)?);
+        Ok(events)
+    }
+
+    #[cfg(feature = "central")]
+    fn member_events(&mut self, settings: &Settings) -> Result<Vec<Event>, anyhow::Error> {
+        let mut events = Vec::new();
+
+        for id in settings.networks_with_keys() {
+            let members =
+                client::sync_get_members(settings.central_client_for_id(id.clone())?, id.clone())?;
+            events.extend(self.members(&id, &members));
+        }
+
+        Ok(events)
+    }
+
+    // members only come from Central, so there's nothing to watch them with
+    #[cfg(not(feature = "central"))]
+    fn member_events(&mut self, _: &Settings) -> Result<Vec<Event>, anyhow::Error> {
+        Ok(Vec::new())
+    }
+}
+
+pub struct Options {
+    pub kinds: Vec<Kind>,
+    pub interval: Duration,
+    // run for each event with its JSON on stdin, instead of printing it
+    pub hook: Option<String>,
+    pub summary: Option<SummaryOptions>,
+}
+
+// a summary posted to `webhook` after the first poll and every `every` after that
+pub struct SummaryOptions {
+    pub webhook: String,
+    pub every: Duration,
+    // authorized members offline longer than this are listed
+    pub offline_after: Duration,
+}
+
+fn emit(
+    event: &Event,
+    hook: &Option<String>,
+    journal: &Option<Journal>,
+) -> Result<(), anyhow::Error> {
+    let line = serde_json::to_string(event)?;
+
+    match (hook, journal) {
+        (Some(hook), _) => {
+            let mut child = Command::new("/bin/sh")
+                .args(["-c", hook])
+                .stdin(Stdio::piped())
+                .spawn()?;
+            if let Some(mut stdin) = child.stdin.take() {
+                writeln!(stdin, "{}", line)?;
+            }
+            child.wait()?;
+        }
+        (None, Some(journal)) => {
+            let mut fields = vec![
+                (
+                    "ZTUI_EVENT",
+                    serde_json::to_value(event.kind)?
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                ),
+                ("ZTUI_NETWORK", event.network.clone()),
+                ("ZTUI_JSON", line),
+            ];
+            fields.extend(event.member.clone().map(|m| ("ZTUI_MEMBER", m)));
+            fields.extend(event.name.clone().map(|n| ("ZTUI_MEMBER_NAME", n)));
+
+            journal.send(
+                systemd::NOTICE,
+                &format!(
+                    "{} {}: {}",
+                    event.network,
+                    event.member.as_deref().unwrap_or("network"),
+                    event.detail
+                ),
+                &fields,
+            );
+        }
+        (None, None) => {
+            let mut stdout = std::io::stdout().lock();
+            writeln!(stdout, "{}", line)?;
+            stdout.flush()?;
+        }
+    }
+
+    Ok(())
+}
+
+fn log(journal: &Option<Journal>, priority: u8, message: String) {
+    match journal {
+        Some(journal) => journal.send(priority, &message, &[]),
+        None => eprintln!("{}", message),
+    }
+}
+
+// SIGTERM from systemd, or ^C by hand. the receiver hangs up if signals can't be watched, in
+// which case we just sleep between polls.
+pub fn shutdown_signal() -> mpsc::Receiver<()> {
+    let (s, r) = mpsc::channel();
+
+    std::thread::spawn(move || {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+
+        rt.block_on(async {
+            #[cfg(unix)]
+            {
+                let mut term =
+                    tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())?;
+                tokio::select! {
+                    _ = term.recv() => {}
+                    _ = tokio::signal::ctrl_c() => {}
+                }
+            }
+            #[cfg(not(unix))]
+            tokio::signal::ctrl_c().await?;
+
+            Ok::<(), std::io::Error>(())
+        })?;
+
+        let _ = s.send(());
+        Ok::<(), std::io::Error>(())
+    });
+
+    r
+}
+
+pub fn run(settings: Settings, options: Options) -> Result<(), anyhow::Error> {
+    let mut watcher = Watcher::default();
+    let journal = Journal::connect();
+    let shutdown = shutdown_signal();
+    let mut ready = false;
+    let mut summarized: Option<std::time::Instant> = None;
+
+    loop {
+        match watcher.poll(&settings) {
+            Ok(events) => {
+                if let Some(options) = &options.summary {
+                    if summarized.is_none_or(|at| at.elapsed() >= options.every) {
+                        summarized = Some(std::time::Instant::now());
+                        let summary = serde_json::to_value(watcher.summary(options.offline_after))?;
+                        if let Err(e) = client::post_webhook(&options.webhook, &summary) {
+                            log(
+                                &journal,
+                                systemd::WARNING,
+                                format!("could not send the summary: {}", e),
+                            );
+                        }
+                    }
+                }
+
+                if !ready {
+                    let status =
+                        format!("watching {} networks", settings.networks_with_keys().len());
+                    systemd::notify(&format!("READY=1\nSTATUS={}", status));
+                    log(&journal, systemd::INFO, status);
+                    ready = true;
+                }
+
+                for event in events.iter().filter(|e| options.kinds.contains(&e.kind)) {
+                    if let Err(e) = emit(event, &options.hook, &journal) {
+                        log(
+                            &journal,
+                            systemd::WARNING,
+                            format!("could not deliver event: {}", e),
+                        );
+                    }
+                }
+            }
+            // a missed poll is picked up by the next one
+            Err(e) => log(&journal, systemd::WARNING, format!("poll failed: {}", e)),
+        }
+
+        match shutdown.recv_timeout(options.interval) {
+            Ok(()) => break,
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => std::thread::sleep(options.interval),
+        }
+    }
+
+    systemd::notify("STOPPING=1");
+    log(&journal, systemd::INFO, "stopping".to_string());
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    thread::JoinHandle,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{config::Settings, i18n::tf};
+
+// a refresh that hasn't come round in this long is taken to be stuck. every request gives up
+// after a few seconds, so this is well past anything a slow network accounts for.
+pub const STALL: Duration = Duration::from_secs(60);
+
+// the refresh loop that should be running. one that's been replaced finds it isn't, and stops
+// when it next comes round.
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+// when the running loop last came round, in milliseconds
+static BEAT: AtomicU64 = AtomicU64::new(0);
+
+type Worker = fn(Arc<Mutex<Settings>>, u64);
+
+struct Running {
+    handle: JoinHandle<()>,
+    worker: Worker,
+    settings: Arc<Mutex<Settings>>,
+}
+
+lazy_static::lazy_static! {
+    static ref RUNNING: Mutex<Option<Running>> = Mutex::new(None);
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+// called by the refresh loop each time round; false once it's been replaced
+pub fn beat(generation: u64) -> bool {
+    if generation != GENERATION.load(Ordering::SeqCst) {
+        return false;
+    }
+
+    BEAT.store(now(), Ordering::SeqCst);
+    true
+}
+
+fn spawn(worker: Worker, settings: Arc<Mutex<Settings>>) -> Result<JoinHandle<()>, anyhow::Error> {
+    let generation = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    BEAT.store(now(), Ordering::SeqCst);
+
+    let s = settings.clone();
+    Ok(std::thread::Builder::new()
+        .name("supervisor".to_string())
+        .spawn(move || worker(s, generation))?)
+}
+
+// starts the refresh loop, to be looked after by check
+pub fn start(worker: Worker, settings: Arc<Mutex<Settings>>) -> Result<(), anyhow::Error> {
+    let handle = spawn(worker, settings.clone())?;
+    *RUNNING.lock().unwrap() = Some(Running {
+        handle,
+        worker,
+        settings,
+    });
+    Ok(())
+}
+
+// called from the UI loop. a refresh loop that died, or that's stuck in a request, is replaced
+// with a new one and the reason is returned to be shown; the stuck one is cancelled and left to
+// stop on its own.
+pub fn check() -> Option<String> {
+    let mut running = RUNNING.lock().unwrap();
+    let current = running.as_mut()?;
+
+    let stalled = Duration::from_millis(now().saturating_sub(BEAT.load(Ordering::SeqCst)));
+    let reason = if current.handle.is_finished() {
+        tf("toast.supervisor_died", &[])
+    } else if stalled > STALL {
+        tf("toast.supervisor_stalled", &[&stalled.as_secs()])
+    } else {
+        return None;
+    };
+
+    tracing::warn!(%reason, "restarting the refresh loop");
+    // the lock is poisoned if the loop died holding it; the settings themselves are still whole
+    match current.settings.lock() {
+        Ok(lock) => lock.fetch_cancel.cancel(),
+        Err(poisoned) => poisoned.into_inner().fetch_cancel.cancel(),
+    }
+    current.settings.clear_poison();
+
+    match spawn(current.worker, current.settings.clone()) {
+        Ok(handle) => {
+            current.handle = handle;
+            Some(reason)
+        }
+        Err(e) => Some(tf("toast.supervisor_failed", &[&e])),
+    }
+}